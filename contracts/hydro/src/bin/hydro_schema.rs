@@ -5,14 +5,24 @@ use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use hydro::msg::{ExecuteMsg, InstantiateMsg};
 use hydro::query::{
-    AllUserLockupsResponse, AllUserLockupsWithTrancheInfosResponse, ConstantsResponse,
-    CurrentRoundResponse, ExpiredUserLockupsResponse, ICQManagersResponse,
-    LiquidityDeploymentResponse, ProposalResponse, QueryMsg, RegisteredValidatorQueriesResponse,
-    RoundEndResponse, RoundProposalsResponse, RoundTotalVotingPowerResponse,
-    RoundTrancheLiquidityDeploymentsResponse, SpecificUserLockupsResponse,
-    SpecificUserLockupsWithTrancheInfosResponse, TopNProposalsResponse, TotalLockedTokensResponse,
-    TranchesResponse, UserVotesResponse, UserVotingPowerResponse, ValidatorPowerRatioResponse,
-    WhitelistAdminsResponse, WhitelistResponse,
+    AdminActionsResponse, AllNotificationPreferencesResponse, AllUserLockupsResponse,
+    AllUserLockupsWithTrancheInfosResponse, AutoRefreshLocksResponse, CapabilitiesResponse,
+    ConstantsResponse, CurrentRoundResponse, DelegationsForUserResponse, DenomValidatorResponse,
+    ExpiredUserLockupsResponse, ExportRawStateResponse, HashOnlyDescriptionTranchesResponse,
+    ICQManagersResponse, InvalidatedProposalsResponse, LiquidityDeploymentResponse,
+    LockAutoMergeResponse, LockOperatorsResponse, NotificationPreferencesResponse,
+    PendingUnlocksResponse, ProposalResponse, ProposalSlotsResponse, ProposalTemplateResponse,
+    ProposalTemplatesResponse, ProposalTextLimitsResponse, ProposalVotersResponse,
+    ProtocolStatsResponse, QueryMsg, RecomputeRoundTotalsDryRunResponse,
+    RegisteredValidatorQueriesResponse, RelayedVoteNonceResponse, RoundCutoffsResponse,
+    RoundDataPruningResponse, RoundEndResponse, RoundProposalsResponse, RoundResultsResponse,
+    RoundTotalVotingPowerResponse, RoundTrancheLiquidityDeploymentsResponse,
+    SpecificUserLockupsResponse, SpecificUserLockupsWithTrancheInfosResponse,
+    TopNProposalsResponse, TotalLockedTokensResponse, TranchePowerScalingResponse,
+    TrancheTributeWeightBonusResponse, TranchesResponse, TributeContractResponse,
+    UserVotesResponse, UserVotingPowerResponse, ValidatorLocksResponse,
+    ValidatorPowerRatioResponse, ValidatorSlashesResponse, VotePowerCapResponse,
+    VotingExecutorResponse, WhitelistAdminsResponse, WhitelistResponse,
 };
 
 fn main() {
@@ -26,7 +36,17 @@ fn main() {
     export_schema(&schema_for!(QueryMsg), &out_dir);
 
     export_schema(&schema_for!(ConstantsResponse), &out_dir);
+    export_schema(&schema_for!(CapabilitiesResponse), &out_dir);
     export_schema(&schema_for!(TranchesResponse), &out_dir);
+    export_schema(&schema_for!(TranchePowerScalingResponse), &out_dir);
+    export_schema(&schema_for!(TrancheTributeWeightBonusResponse), &out_dir);
+    export_schema(&schema_for!(VotePowerCapResponse), &out_dir);
+    export_schema(&schema_for!(HashOnlyDescriptionTranchesResponse), &out_dir);
+    export_schema(&schema_for!(TributeContractResponse), &out_dir);
+    export_schema(&schema_for!(InvalidatedProposalsResponse), &out_dir);
+    export_schema(&schema_for!(ProposalSlotsResponse), &out_dir);
+    export_schema(&schema_for!(ProposalTextLimitsResponse), &out_dir);
+    export_schema(&schema_for!(ProposalVotersResponse), &out_dir);
     export_schema(&schema_for!(RoundProposalsResponse), &out_dir);
     export_schema(&schema_for!(AllUserLockupsResponse), &out_dir);
     export_schema(
@@ -41,8 +61,24 @@ fn main() {
     export_schema(&schema_for!(ExpiredUserLockupsResponse), &out_dir);
     export_schema(&schema_for!(UserVotingPowerResponse), &out_dir);
     export_schema(&schema_for!(UserVotesResponse), &out_dir);
+    export_schema(&schema_for!(NotificationPreferencesResponse), &out_dir);
+    export_schema(&schema_for!(AllNotificationPreferencesResponse), &out_dir);
+    export_schema(&schema_for!(ProposalTemplateResponse), &out_dir);
+    export_schema(&schema_for!(ProposalTemplatesResponse), &out_dir);
+    export_schema(&schema_for!(ExportRawStateResponse), &out_dir);
+    export_schema(&schema_for!(RoundDataPruningResponse), &out_dir);
+    export_schema(&schema_for!(RoundResultsResponse), &out_dir);
+    export_schema(&schema_for!(AdminActionsResponse), &out_dir);
+    export_schema(&schema_for!(AutoRefreshLocksResponse), &out_dir);
+    export_schema(&schema_for!(PendingUnlocksResponse), &out_dir);
+    export_schema(&schema_for!(DelegationsForUserResponse), &out_dir);
+    export_schema(&schema_for!(LockOperatorsResponse), &out_dir);
+    export_schema(&schema_for!(LockAutoMergeResponse), &out_dir);
+    export_schema(&schema_for!(VotingExecutorResponse), &out_dir);
+    export_schema(&schema_for!(RelayedVoteNonceResponse), &out_dir);
     export_schema(&schema_for!(CurrentRoundResponse), &out_dir);
     export_schema(&schema_for!(RoundEndResponse), &out_dir);
+    export_schema(&schema_for!(RoundCutoffsResponse), &out_dir);
     export_schema(&schema_for!(RoundTotalVotingPowerResponse), &out_dir);
     export_schema(&schema_for!(ProposalResponse), &out_dir);
     export_schema(&schema_for!(TopNProposalsResponse), &out_dir);
@@ -57,4 +93,9 @@ fn main() {
     export_schema(&schema_for!(ICQManagersResponse), &out_dir);
     export_schema(&schema_for!(RegisteredValidatorQueriesResponse), &out_dir);
     export_schema(&schema_for!(ValidatorPowerRatioResponse), &out_dir);
+    export_schema(&schema_for!(DenomValidatorResponse), &out_dir);
+    export_schema(&schema_for!(ValidatorSlashesResponse), &out_dir);
+    export_schema(&schema_for!(ValidatorLocksResponse), &out_dir);
+    export_schema(&schema_for!(RecomputeRoundTotalsDryRunResponse), &out_dir);
+    export_schema(&schema_for!(ProtocolStatsResponse), &out_dir);
 }
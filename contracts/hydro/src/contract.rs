@@ -1,51 +1,99 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 // entry_point is being used but for some reason clippy doesn't see that, hence the allow attribute here
 #[allow(unused_imports)]
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, Timestamp, Uint128,
+    entry_point, to_json_binary, to_json_vec, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut,
+    Env, HexBinary, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, Timestamp,
+    Uint128,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, Map, PrimaryKey};
 use cw_utils::must_pay;
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_sdk::bindings::query::NeutronQuery;
 use neutron_sdk::interchain_queries::v047::register_queries::new_register_staking_validators_query_msg;
 use neutron_sdk::sudo::msg::SudoMsg;
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
 use crate::lsm_integration::{
-    add_validator_shares_to_round_total, get_total_power_for_round,
-    get_validator_power_ratio_for_round, initialize_validator_store, validate_denom,
-    COSMOS_VALIDATOR_PREFIX,
+    add_lock_to_validator_index, add_validator_shares_to_round_total,
+    add_validator_start_shares_to_round_total, get_round_power_ratio_growth,
+    get_total_power_for_round, get_total_power_for_round_at_time,
+    get_validator_power_ratio_for_round, get_validator_shares_for_round,
+    initialize_validator_store, is_active_round_validator, is_validator_store_initialized,
+    remove_lock_from_validator_index, resolve_validator_from_denom,
+    update_proposal_score_due_to_power_ratio_change, update_total_power_due_to_power_ratio_change,
+    validate_denom, COSMOS_VALIDATOR_PREFIX,
+};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, LiquidityDeployment, LockVote, LockupImport, NotificationCategory,
+    ProposalToLockups, TrancheInfo, TributeQueryMsg, TributeRoundTributesResponse,
+    TributeWeightBonus, VoteIntent,
 };
-use crate::msg::{ExecuteMsg, InstantiateMsg, LiquidityDeployment, ProposalToLockups, TrancheInfo};
 use crate::query::{
-    AllUserLockupsResponse, AllUserLockupsWithTrancheInfosResponse, ConstantsResponse,
-    CurrentRoundResponse, ExpiredUserLockupsResponse, ICQManagersResponse,
-    LiquidityDeploymentResponse, LockEntryWithPower, LockupWithPerTrancheInfo,
-    PerTrancheLockupInfo, ProposalResponse, QueryMsg, RegisteredValidatorQueriesResponse,
-    RoundEndResponse, RoundProposalsResponse, RoundTotalVotingPowerResponse,
-    RoundTrancheLiquidityDeploymentsResponse, SpecificUserLockupsResponse,
-    SpecificUserLockupsWithTrancheInfosResponse, TopNProposalsResponse, TotalLockedTokensResponse,
-    TranchesResponse, UserVotesResponse, UserVotingPowerResponse, ValidatorPowerRatioResponse,
+    AdminActionsResponse, AllNotificationPreferencesResponse, AllUserLockupsResponse,
+    AllUserLockupsWithTrancheInfosResponse, AutoRefreshLock, AutoRefreshLocksResponse,
+    CapabilitiesResponse, ConstantsResponse, ContractFeatures, ContractLimits,
+    CurrentRoundResponse, DelegationsForUserResponse, DenomResolution, DenomValidatorResponse,
+    ExpiredUserLockupsResponse, ExportRawStateResponse, HashOnlyDescriptionTranchesResponse,
+    ICQManagersResponse, InvalidatedProposal, InvalidatedProposalsResponse,
+    LiquidityDeploymentResponse, LockAutoMergeResponse, LockEntryWithPower, LockOperator,
+    LockOperatorsResponse, LockupWithPerTrancheInfo, NotificationPreferencesResponse,
+    PendingUnlockEntry, PendingUnlocksResponse, PerTrancheLockupInfo, ProposalOrderingKey,
+    ProposalPowerDelta, ProposalResponse, ProposalSlotsResponse, ProposalTemplateResponse,
+    ProposalTemplatesResponse, ProposalTextLimitsResponse, ProposalVoter, ProposalVotersResponse,
+    ProtocolStatsResponse, QueryMsg, RawStateEntry, RawStateMap,
+    RecomputeRoundTotalsDryRunResponse, RegisteredValidatorQueriesResponse,
+    RelayedVoteNonceResponse, RoundCutoffsResponse, RoundDataPruningResponse, RoundEndResponse,
+    RoundProposalsResponse, RoundProtocolStats, RoundResultsResponse,
+    RoundTotalVotingPowerResponse, RoundTrancheLiquidityDeploymentsResponse,
+    SpecificUserLockupsResponse, SpecificUserLockupsWithTrancheInfosResponse, TemplateProposal,
+    TopNProposalsResponse, TotalLockedTokensResponse, TrancheCapabilities, TrancheParticipation,
+    TranchePowerScalingResponse, TrancheTributeWeightBonusResponse, TranchesResponse,
+    TributeContractResponse, UserNotificationPreferences, UserVotesResponse,
+    UserVotingPowerResponse, ValidatorLock, ValidatorLocksResponse, ValidatorPowerRatioResponse,
+    ValidatorSlashesResponse, VoteDelegation, VotePowerCapResponse, VotingExecutorResponse,
     WhitelistAdminsResponse, WhitelistResponse,
 };
 use crate::score_keeper::{
-    add_validator_shares_to_proposal, get_total_power_for_proposal,
-    remove_validator_shares_from_proposal,
+    add_vote_shares_to_proposal, get_capped_proposal_power, get_total_power_for_proposal,
+    get_tribute_weight_bonus_for_proposal, get_validator_shares_for_proposal,
+    get_vote_power_cap_factor, remove_vote_shares_from_proposal,
 };
 use crate::state::{
-    Constants, LockEntry, Proposal, RoundLockPowerSchedule, Tranche, ValidatorInfo, Vote,
-    VoteWithPower, CONSTANTS, ICQ_MANAGERS, LIQUIDITY_DEPLOYMENTS_MAP, LOCKED_TOKENS, LOCKS_MAP,
-    LOCK_ID, PROPOSAL_MAP, PROPS_BY_SCORE, PROP_ID, TRANCHE_ID, TRANCHE_MAP, VALIDATORS_INFO,
-    VALIDATORS_PER_ROUND, VALIDATORS_STORE_INITIALIZED, VALIDATOR_TO_QUERY_ID, VOTE_MAP,
-    VOTING_ALLOWED_ROUND, WHITELIST, WHITELIST_ADMINS,
+    get_props_by_score_key, AdminAction, AutoRefresh, Constants, FinalizedProposal,
+    InvalidatedProposalInfo, LockEntry, NotificationPreferences, PendingUnlock,
+    PowerRatioCorrection, Proposal, ProposalTemplate, RoundLockPowerSchedule, RoundResults,
+    Tranche, ValidatorInfo, Vote, VoteWithPower, VotingExecutor, ABSTENTIONS, ADMIN_ACTIONS_LOG,
+    ADMIN_ACTION_ID, AUTO_REFRESH_LOCKS, AUTO_REFRESH_QUEUE, CONSTANTS,
+    HASH_ONLY_DESCRIPTION_TRANCHES, ICQ_MANAGERS, INVALIDATED_PROPOSALS, LIQUIDITY_DEPLOYMENTS_MAP,
+    LOCKED_TOKENS, LOCKS_MAP, LOCKUPS_IMPORT_FINALIZED, LOCK_AUTO_MERGE_TOLERANCES, LOCK_ID,
+    LOCK_NOTES, LOCK_OPERATORS, LOCK_VALIDATORS, NOTIFICATION_PREFERENCES, OWNERS_BY_VOTE_POWER,
+    OWNER_VOTE_POWER, PENDING_UNLOCKS, POWER_RATIO_CORRECTIONS, PROPOSAL_LIMIT_OVERRIDES,
+    PROPOSAL_MAP, PROPOSAL_TEMPLATES, PROPOSAL_TEMPLATE_ID, PROPOSAL_VOTERS, PROPS_BY_SCORE,
+    PROP_ID, QUADRATIC_POWER_SCALING, RELAYED_VOTE_NONCES, ROUND_DATA_PRUNING,
+    ROUND_POWER_RATIO_GROWTH, ROUND_RESULTS, ROUND_STATS_FIRST_ROUND, ROUND_VOTERS,
+    SCALED_PROPOSAL_BONUS_SHARES_MAP, SCALED_PROPOSAL_SHARES_MAP, SCALED_ROUND_POWER_SHARES_MAP,
+    SCALED_ROUND_START_POWER_SHARES_MAP, TEMPLATE_PROPOSALS, TRANCHE_ID, TRANCHE_MAP,
+    TRIBUTE_CONTRACT, TRIBUTE_WEIGHT_BONUSES, VALIDATORS_INFO, VALIDATORS_PER_ROUND,
+    VALIDATORS_STORE_INITIALIZED, VALIDATOR_LOCKS, VALIDATOR_SLASHES, VALIDATOR_TO_QUERY_ID,
+    VOTE_DELEGATIONS, VOTE_MAP, VOTE_POWER_CAPS, VOTING_ALLOWED_ROUND, VOTING_EXECUTORS, WHITELIST,
+    WHITELIST_ADMINS,
+};
+use crate::stats::{
+    add_round_deployed_funds, get_round_stats, get_round_tranche_voted_power,
+    increment_round_proposals_count, record_round_voter, remove_round_deployed_funds,
+    remove_round_voter, update_round_tranche_voted_power,
 };
 use crate::validators_icqs::{
-    build_create_interchain_query_submsg, handle_delivered_interchain_query_result,
-    handle_submsg_reply, query_min_interchain_query_deposit,
+    build_claim_tributes_submsg, build_create_interchain_query_submsg,
+    handle_delivered_interchain_query_result, handle_submsg_reply,
+    query_min_interchain_query_deposit,
 };
+use crate::vote_relaying::verify_vote_intent_signature;
 
 /// Contract name that is used for migration.
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -58,6 +106,33 @@ pub const NATIVE_TOKEN_DENOM: &str = "untrn";
 
 pub const MIN_DEPLOYMENT_DURATION: u64 = 1;
 
+// Maximum length of the description summary for proposals in tranches that use hash-only descriptions.
+pub const MAX_DESCRIPTION_SUMMARY_LENGTH: usize = 280;
+
+// Maximum length of the opaque endpoint in the notification preferences, enough for a hash or a public key.
+pub const MAX_NOTIFICATION_ENDPOINT_LENGTH: usize = 256;
+
+// Maximum length of the notes that the lock owners attach to their lockups.
+pub const MAX_LOCK_NOTE_LENGTH: usize = 256;
+
+// Number of the most recent ended rounds whose data can't be pruned. Their validator sets are still used
+// to initialize the next rounds, and their proposal rankings to enter the liquidity deployments.
+pub const ROUND_DATA_RETENTION_ROUNDS: u64 = 3;
+
+// Number of rounds after the end of a round during which its tributes can be claimed. Until they pass, the data
+// of the round can only be pruned if the tribute contract holds no tributes for it that weren't refunded. Once they
+// pass, the data can be pruned regardless, after which the remaining tributes of the round can't be claimed anymore.
+pub const TRIBUTE_CLAIM_RETENTION_ROUNDS: u64 = 12;
+
+// Maximum number of tributes of a round checked when pruning it before its tribute claims expire.
+const TRIBUTES_QUERY_PAGE_SIZE: u32 = 100;
+
+// Number of the most recent admin actions kept in the admin actions log.
+pub const MAX_ADMIN_ACTIONS_LOG_ENTRIES: u64 = 1000;
+
+// Number of the best ranked proposals recorded in the results of a finalized round.
+pub const MAX_ROUND_RESULTS_PROPOSALS: usize = 100;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut<NeutronQuery>,
@@ -86,6 +161,15 @@ pub fn instantiate(
         max_deployment_duration: msg.max_deployment_duration,
         paused: false,
         round_lock_power_schedule: RoundLockPowerSchedule::new(msg.round_lock_power_schedule),
+        interpolate_lock_power: msg.interpolate_lock_power,
+        allow_pre_launch_locks: msg.allow_pre_launch_locks,
+        tribute_cutoff_duration: 0,
+        max_proposals_per_tranche_round: 0,
+        raw_export_enabled: false,
+        unlock_cooldown_duration: 0,
+        paused_messages: vec![],
+        max_proposal_title_length: 0,
+        max_proposal_description_length: 0,
     };
 
     CONSTANTS.save(deps.storage, &state)?;
@@ -151,6 +235,31 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if let Some(message_name) = get_pausable_message_name(&msg) {
+        let constants = CONSTANTS.load(deps.storage)?;
+        validate_message_is_not_paused(&constants, message_name)?;
+    }
+
+    let admin_action = match get_admin_action_type(&msg) {
+        Some(action_type) => Some((action_type, hash_execute_msg(&msg)?)),
+        None => None,
+    };
+
+    let response = execute_msg(deps.branch(), env.clone(), info.clone(), msg)?;
+
+    if let Some((action_type, params_hash)) = admin_action {
+        record_admin_action(deps.storage, &env, info.sender, action_type, params_hash)?;
+    }
+
+    Ok(response)
+}
+
+fn execute_msg(
     deps: DepsMut<NeutronQuery>,
     env: Env,
     info: MessageInfo,
@@ -158,11 +267,44 @@ pub fn execute(
 ) -> Result<Response<NeutronMsg>, ContractError> {
     match msg {
         ExecuteMsg::LockTokens { lock_duration } => lock_tokens(deps, env, info, lock_duration),
+        ExecuteMsg::LockTokensMultiple { lock_duration } => {
+            lock_tokens_multiple(deps, env, info, lock_duration)
+        }
         ExecuteMsg::RefreshLockDuration {
             lock_ids,
             lock_duration,
         } => refresh_lock_duration(deps, env, info, lock_ids, lock_duration),
-        ExecuteMsg::UnlockTokens { lock_ids } => unlock_tokens(deps, env, info, lock_ids),
+        ExecuteMsg::RefreshLockDurationOnBehalf {
+            owner,
+            lock_ids,
+            lock_duration,
+        } => refresh_lock_duration_on_behalf(deps, env, info, owner, lock_ids, lock_duration),
+        ExecuteMsg::SetLockOperator { lock_ids, operator } => {
+            set_lock_operator(deps, info, lock_ids, operator)
+        }
+        ExecuteMsg::SetAutoRefresh { lock_ids, enabled } => {
+            set_auto_refresh(deps, env, info, lock_ids, enabled)
+        }
+        ExecuteMsg::SetLockNote { lock_id, note } => set_lock_note(deps, info, lock_id, note),
+        ExecuteMsg::SetLockAutoMerge { tolerance } => set_lock_auto_merge(deps, info, tolerance),
+        ExecuteMsg::ProcessAutoRefresh { limit } => process_auto_refresh(deps, env, limit),
+        ExecuteMsg::UnlockTokens {
+            lock_ids,
+            claim_tributes,
+        } => unlock_tokens(
+            deps,
+            env,
+            info,
+            lock_ids,
+            claim_tributes.unwrap_or(false),
+            None,
+        ),
+        ExecuteMsg::UnlockTokensTo {
+            lock_ids,
+            recipient,
+            confirm_recipient,
+        } => unlock_tokens_to(deps, env, info, lock_ids, recipient, confirm_recipient),
+        ExecuteMsg::ClaimUnlocked {} => claim_unlocked(deps, env, info),
         ExecuteMsg::CreateProposal {
             round_id,
             tranche_id,
@@ -170,6 +312,9 @@ pub fn execute(
             description,
             deployment_duration,
             minimum_atom_liquidity_request,
+            description_hash,
+            description_uri,
+            max_vote_percentage,
         } => create_proposal(
             deps,
             env,
@@ -180,11 +325,45 @@ pub fn execute(
             description,
             deployment_duration,
             minimum_atom_liquidity_request,
+            description_hash,
+            description_uri,
+            max_vote_percentage,
         ),
         ExecuteMsg::Vote {
             tranche_id,
             proposals_votes,
         } => vote(deps, env, info, tranche_id, proposals_votes),
+        ExecuteMsg::VoteOnBehalf {
+            owner,
+            tranche_id,
+            proposals_votes,
+        } => vote_on_behalf(deps, env, info, owner, tranche_id, proposals_votes),
+        ExecuteMsg::Abstain {
+            tranche_id,
+            lock_ids,
+        } => abstain(deps, env, info, tranche_id, lock_ids),
+        ExecuteMsg::AbstainOnBehalf {
+            owner,
+            tranche_id,
+            lock_ids,
+        } => abstain_on_behalf(deps, env, info, owner, tranche_id, lock_ids),
+        ExecuteMsg::UnvoteAll { tranche_id, owner } => {
+            unvote_all(deps, env, info, tranche_id, owner)
+        }
+        ExecuteMsg::RelayedVote {
+            vote_intent,
+            pubkey,
+            signature,
+        } => relayed_vote(deps, env, info, vote_intent, pubkey, signature),
+        ExecuteMsg::DelegateVotingRights { lock_ids, delegate } => {
+            delegate_voting_rights(deps, info, lock_ids, delegate)
+        }
+        ExecuteMsg::RevokeDelegation { lock_ids } => revoke_delegation(deps, info, lock_ids),
+        ExecuteMsg::SetVotingExecutor {
+            executor,
+            max_proposals_per_round,
+        } => set_voting_executor(deps, info, executor, max_proposals_per_round),
+        ExecuteMsg::RemoveVotingExecutor {} => remove_voting_executor(deps, info),
         ExecuteMsg::AddAccountToWhitelist { address } => add_to_whitelist(deps, env, info, address),
         ExecuteMsg::RemoveAccountFromWhitelist { address } => {
             remove_from_whitelist(deps, env, info, address)
@@ -192,14 +371,87 @@ pub fn execute(
         ExecuteMsg::UpdateConfig {
             max_locked_tokens,
             max_deployment_duration,
-        } => update_config(deps, info, max_locked_tokens, max_deployment_duration),
+            tribute_cutoff_duration,
+            max_proposals_per_tranche_round,
+            raw_export_enabled,
+            unlock_cooldown_duration,
+            max_validator_shares_participating,
+            max_proposal_title_length,
+            max_proposal_description_length,
+        } => update_config(
+            deps,
+            info,
+            max_locked_tokens,
+            max_deployment_duration,
+            tribute_cutoff_duration,
+            max_proposals_per_tranche_round,
+            raw_export_enabled,
+            unlock_cooldown_duration,
+            max_validator_shares_participating,
+            max_proposal_title_length,
+            max_proposal_description_length,
+        ),
         ExecuteMsg::Pause {} => pause_contract(deps, info),
+        ExecuteMsg::PauseMessages { names } => pause_messages(deps, info, names),
+        ExecuteMsg::UnpauseMessages { names } => unpause_messages(deps, info, names),
         ExecuteMsg::AddTranche { tranche } => add_tranche(deps, info, tranche),
         ExecuteMsg::EditTranche {
             tranche_id,
             tranche_name,
             tranche_metadata,
         } => edit_tranche(deps, info, tranche_id, tranche_name, tranche_metadata),
+        ExecuteMsg::SetTrancheDescriptionMode {
+            tranche_id,
+            hash_only,
+        } => set_tranche_description_mode(deps, info, tranche_id, hash_only),
+        ExecuteMsg::SetTranchePowerScaling {
+            tranche_id,
+            quadratic,
+        } => set_tranche_power_scaling(deps, env, info, tranche_id, quadratic),
+        ExecuteMsg::SetTrancheTributeWeightBonus { tranche_id, bonus } => {
+            set_tranche_tribute_weight_bonus(deps, env, info, tranche_id, bonus)
+        }
+        ExecuteMsg::SetVotePowerCap {
+            max_power_percentage,
+        } => set_vote_power_cap(deps, env, info, max_power_percentage),
+        ExecuteMsg::SetTributeContract { address } => set_tribute_contract(deps, info, address),
+        ExecuteMsg::SetProposalLimitOverride {
+            round_id,
+            tranche_id,
+            max_proposals,
+        } => set_proposal_limit_override(deps, info, round_id, tranche_id, max_proposals),
+        ExecuteMsg::SetProposalVoteCap {
+            round_id,
+            tranche_id,
+            proposal_id,
+            max_vote_percentage,
+        } => set_proposal_vote_cap(
+            deps,
+            env,
+            info,
+            round_id,
+            tranche_id,
+            proposal_id,
+            max_vote_percentage,
+        ),
+        ExecuteMsg::InvalidateProposal {
+            round_id,
+            tranche_id,
+            proposal_id,
+            reason,
+        } => invalidate_proposal(deps, env, info, round_id, tranche_id, proposal_id, reason),
+        ExecuteMsg::RecomputeRoundTotals {
+            round_id,
+            validator,
+            corrected_ratio,
+            limit,
+        } => recompute_round_totals(deps, env, info, round_id, validator, corrected_ratio, limit),
+        ExecuteMsg::ImportLockups { entries, finalize } => {
+            import_lockups(deps, env, info, entries, finalize)
+        }
+        ExecuteMsg::IndexValidatorLocks { start_after, limit } => {
+            index_validator_locks(deps, info, start_after, limit)
+        }
         ExecuteMsg::CreateICQsForValidators { validators } => {
             create_icqs_for_validators(deps, env, info, validators)
         }
@@ -228,17 +480,194 @@ pub fn execute(
             };
             add_liquidity_deployment(deps, env, info, deployment)
         }
+        ExecuteMsg::SetNotificationPreferences {
+            endpoint,
+            categories,
+        } => set_notification_preferences(deps, env, info, endpoint, categories),
+        ExecuteMsg::RemoveNotificationPreferences {} => remove_notification_preferences(deps, info),
+        ExecuteMsg::RegisterProposalTemplate {
+            tranche_id,
+            title,
+            description,
+            deployment_duration,
+            minimum_atom_liquidity_request,
+            description_hash,
+            description_uri,
+        } => register_proposal_template(
+            deps,
+            info,
+            tranche_id,
+            title,
+            description,
+            deployment_duration,
+            minimum_atom_liquidity_request,
+            description_hash,
+            description_uri,
+        ),
+        ExecuteMsg::RemoveProposalTemplate { template_id } => {
+            remove_proposal_template(deps, info, template_id)
+        }
+        ExecuteMsg::InstantiateFromTemplate { template_id } => {
+            instantiate_from_template(deps, env, info, template_id)
+        }
         ExecuteMsg::RemoveLiquidityDeployment {
             round_id,
             tranche_id,
             proposal_id,
         } => remove_liquidity_deployment(deps, info, round_id, tranche_id, proposal_id),
+        ExecuteMsg::PruneRoundData { round_id, limit } => {
+            prune_round_data(deps, env, info, round_id, limit)
+        }
+        ExecuteMsg::FinalizeRound {
+            round_id,
+            tranche_id,
+        } => finalize_round(deps, env, info, round_id, tranche_id),
+    }
+}
+
+// Returns the action type under which the execution of the given message is recorded in the admin
+// actions log, or None if the message doesn't require admin privileges.
+fn get_admin_action_type(msg: &ExecuteMsg) -> Option<&'static str> {
+    match msg {
+        ExecuteMsg::AddAccountToWhitelist { .. } => Some("add_account_to_whitelist"),
+        ExecuteMsg::RemoveAccountFromWhitelist { .. } => Some("remove_account_from_whitelist"),
+        ExecuteMsg::UpdateConfig { .. } => Some("update_config"),
+        ExecuteMsg::Pause {} => Some("pause"),
+        ExecuteMsg::PauseMessages { .. } => Some("pause_messages"),
+        ExecuteMsg::UnpauseMessages { .. } => Some("unpause_messages"),
+        ExecuteMsg::AddTranche { .. } => Some("add_tranche"),
+        ExecuteMsg::EditTranche { .. } => Some("edit_tranche"),
+        ExecuteMsg::SetTrancheDescriptionMode { .. } => Some("set_tranche_description_mode"),
+        ExecuteMsg::SetTranchePowerScaling { .. } => Some("set_tranche_power_scaling"),
+        ExecuteMsg::SetTrancheTributeWeightBonus { .. } => Some("set_tranche_tribute_weight_bonus"),
+        ExecuteMsg::SetVotePowerCap { .. } => Some("set_vote_power_cap"),
+        ExecuteMsg::SetTributeContract { .. } => Some("set_tribute_contract"),
+        ExecuteMsg::SetProposalLimitOverride { .. } => Some("set_proposal_limit_override"),
+        ExecuteMsg::SetProposalVoteCap { .. } => Some("set_proposal_vote_cap"),
+        ExecuteMsg::InvalidateProposal { .. } => Some("invalidate_proposal"),
+        ExecuteMsg::RecomputeRoundTotals { .. } => Some("recompute_round_totals"),
+        ExecuteMsg::ImportLockups { .. } => Some("import_lockups"),
+        ExecuteMsg::IndexValidatorLocks { .. } => Some("index_validator_locks"),
+        ExecuteMsg::AddICQManager { .. } => Some("add_icq_manager"),
+        ExecuteMsg::RemoveICQManager { .. } => Some("remove_icq_manager"),
+        ExecuteMsg::WithdrawICQFunds { .. } => Some("withdraw_icq_funds"),
+        ExecuteMsg::AddLiquidityDeployment { .. } => Some("add_liquidity_deployment"),
+        ExecuteMsg::RemoveLiquidityDeployment { .. } => Some("remove_liquidity_deployment"),
+        ExecuteMsg::PruneRoundData { .. } => Some("prune_round_data"),
+        _ => None,
+    }
+}
+
+// Returns the name under which the given message can be paused through PauseMessages, or None if the
+// message can't be paused on its own. Admin messages are left out, so that the admins can always act
+// during an incident, including unpausing the messages.
+fn get_pausable_message_name(msg: &ExecuteMsg) -> Option<&'static str> {
+    match msg {
+        ExecuteMsg::LockTokens { .. } => Some("LockTokens"),
+        ExecuteMsg::LockTokensMultiple { .. } => Some("LockTokensMultiple"),
+        ExecuteMsg::RefreshLockDuration { .. } => Some("RefreshLockDuration"),
+        ExecuteMsg::RefreshLockDurationOnBehalf { .. } => Some("RefreshLockDurationOnBehalf"),
+        ExecuteMsg::SetLockOperator { .. } => Some("SetLockOperator"),
+        ExecuteMsg::SetAutoRefresh { .. } => Some("SetAutoRefresh"),
+        ExecuteMsg::SetLockNote { .. } => Some("SetLockNote"),
+        ExecuteMsg::SetLockAutoMerge { .. } => Some("SetLockAutoMerge"),
+        ExecuteMsg::ProcessAutoRefresh { .. } => Some("ProcessAutoRefresh"),
+        ExecuteMsg::UnlockTokens { .. } => Some("UnlockTokens"),
+        ExecuteMsg::UnlockTokensTo { .. } => Some("UnlockTokensTo"),
+        ExecuteMsg::ClaimUnlocked {} => Some("ClaimUnlocked"),
+        ExecuteMsg::CreateProposal { .. } => Some("CreateProposal"),
+        ExecuteMsg::Vote { .. } => Some("Vote"),
+        ExecuteMsg::VoteOnBehalf { .. } => Some("VoteOnBehalf"),
+        ExecuteMsg::Abstain { .. } => Some("Abstain"),
+        ExecuteMsg::AbstainOnBehalf { .. } => Some("AbstainOnBehalf"),
+        ExecuteMsg::UnvoteAll { .. } => Some("UnvoteAll"),
+        ExecuteMsg::RelayedVote { .. } => Some("RelayedVote"),
+        ExecuteMsg::DelegateVotingRights { .. } => Some("DelegateVotingRights"),
+        ExecuteMsg::RevokeDelegation { .. } => Some("RevokeDelegation"),
+        ExecuteMsg::SetVotingExecutor { .. } => Some("SetVotingExecutor"),
+        ExecuteMsg::RemoveVotingExecutor {} => Some("RemoveVotingExecutor"),
+        ExecuteMsg::CreateICQsForValidators { .. } => Some("CreateICQsForValidators"),
+        ExecuteMsg::SetNotificationPreferences { .. } => Some("SetNotificationPreferences"),
+        ExecuteMsg::RemoveNotificationPreferences {} => Some("RemoveNotificationPreferences"),
+        ExecuteMsg::InstantiateFromTemplate { .. } => Some("InstantiateFromTemplate"),
+        ExecuteMsg::FinalizeRound { .. } => Some("FinalizeRound"),
+        _ => None,
+    }
+}
+
+// Names of all messages that can be paused through PauseMessages; must match get_pausable_message_name.
+const PAUSABLE_MESSAGES: &[&str] = &[
+    "LockTokens",
+    "LockTokensMultiple",
+    "RefreshLockDuration",
+    "RefreshLockDurationOnBehalf",
+    "SetLockOperator",
+    "SetAutoRefresh",
+    "SetLockNote",
+    "SetLockAutoMerge",
+    "ProcessAutoRefresh",
+    "UnlockTokens",
+    "UnlockTokensTo",
+    "ClaimUnlocked",
+    "CreateProposal",
+    "Vote",
+    "VoteOnBehalf",
+    "Abstain",
+    "AbstainOnBehalf",
+    "UnvoteAll",
+    "RelayedVote",
+    "DelegateVotingRights",
+    "RevokeDelegation",
+    "SetVotingExecutor",
+    "RemoveVotingExecutor",
+    "CreateICQsForValidators",
+    "SetNotificationPreferences",
+    "RemoveNotificationPreferences",
+    "InstantiateFromTemplate",
+    "FinalizeRound",
+];
+
+fn hash_execute_msg(msg: &ExecuteMsg) -> StdResult<String> {
+    let digest = Sha256::digest(to_json_vec(msg)?);
+    Ok(HexBinary::from(digest.as_slice()).to_hex())
+}
+
+// Appends the admin action to the admin actions log, and removes the oldest entry
+// once the log holds more than MAX_ADMIN_ACTIONS_LOG_ENTRIES entries.
+fn record_admin_action(
+    storage: &mut dyn Storage,
+    env: &Env,
+    actor: Addr,
+    action_type: &str,
+    params_hash: String,
+) -> StdResult<()> {
+    let action_id = ADMIN_ACTION_ID.may_load(storage)?.unwrap_or_default();
+    ADMIN_ACTION_ID.save(storage, &(action_id + 1))?;
+
+    ADMIN_ACTIONS_LOG.save(
+        storage,
+        action_id,
+        &AdminAction {
+            action_id,
+            actor,
+            action_type: action_type.to_string(),
+            params_hash,
+            height: env.block.height,
+            timestamp: env.block.time,
+        },
+    )?;
+
+    if action_id >= MAX_ADMIN_ACTIONS_LOG_ENTRIES {
+        ADMIN_ACTIONS_LOG.remove(storage, action_id - MAX_ADMIN_ACTIONS_LOG_ENTRIES);
     }
+
+    Ok(())
 }
 
 // LockTokens(lock_duration):
 //     Receive tokens
 //     Validate against the accepted denom
+//     If the user enabled the auto merging and holds a matching lockup, merge the tokens into it
 //     Update voting power on proposals if user already voted for any
 //     Update total round power
 //     Create entry in LocksMap
@@ -257,7 +686,10 @@ fn lock_tokens(
         lock_duration,
     )?;
 
-    let current_round = compute_current_round_id(&env, &constants)?;
+    let current_round = compute_lock_round_id(&env, &constants)?;
+
+    validate_pre_launch_lock_end(&env, &constants, env.block.time.plus_nanos(lock_duration))?;
+
     initialize_validator_store(deps.storage, current_round)?;
 
     if info.funds.len() != 1 {
@@ -266,68 +698,44 @@ fn lock_tokens(
         )));
     }
 
-    let funds = info.funds[0].clone();
-
-    let validator =
-        validate_denom(deps.as_ref(), env.clone(), &constants, funds.denom).map_err(|err| {
-            ContractError::Std(StdError::generic_err(format!("validating denom: {}", err)))
-        })?;
-
-    // validate that this wouldn't cause the contract to have more locked tokens than the limit
-    let amount_to_lock = info.funds[0].amount.u128();
-    let locked_tokens = LOCKED_TOKENS.load(deps.storage)?;
-
-    if locked_tokens + amount_to_lock > constants.max_locked_tokens {
-        return Err(ContractError::Std(StdError::generic_err(
-            "The limit for locking tokens has been reached. No more tokens can be locked.",
-        )));
-    }
-
-    // validate that the user does not have too many locks
-    if get_lock_count(deps.as_ref(), info.sender.clone()) >= MAX_LOCK_ENTRIES {
-        return Err(ContractError::Std(StdError::generic_err(format!(
-            "User has too many locks, only {} locks allowed",
-            MAX_LOCK_ENTRIES
-        ))));
-    }
-
-    let lock_id = LOCK_ID.load(deps.storage)?;
-    LOCK_ID.save(deps.storage, &(lock_id + 1))?;
-    let lock_entry = LockEntry {
-        lock_id,
-        funds: info.funds[0].clone(),
-        lock_start: env.block.time,
-        lock_end: env.block.time.plus_nanos(lock_duration),
-    };
-    let lock_end = lock_entry.lock_end.nanos();
-    LOCKS_MAP.save(deps.storage, (info.sender.clone(), lock_id), &lock_entry)?;
-    LOCKED_TOKENS.save(deps.storage, &(locked_tokens + amount_to_lock))?;
-
-    // If user already voted for some proposals in the current round, update the voting power on those proposals.
     let mut deps = deps;
-    update_voting_power_on_proposals(
-        &mut deps,
+    if let Some(lock_to_merge) = find_lock_to_merge(
+        deps.as_ref(),
+        &env,
         &info.sender,
-        &constants,
-        current_round,
-        None,
-        lock_entry.clone(),
-        validator.clone(),
-    )?;
+        &info.funds[0],
+        lock_duration,
+    )? {
+        let lock_entry = merge_into_lock_entry(
+            &mut deps,
+            &env,
+            &constants,
+            &info.sender,
+            current_round,
+            lock_to_merge,
+            info.funds[0].clone(),
+            lock_duration,
+        )?;
 
-    // Calculate and update the total voting power info for current and all
-    // future rounds in which the user will have voting power greater than 0
-    let last_round_with_power = compute_round_id_for_timestamp(&constants, lock_end)? - 1;
+        return Ok(Response::new()
+            .add_attribute("action", "lock_tokens")
+            .add_attribute("sender", info.sender)
+            .add_attribute("lock_id", lock_entry.lock_id.to_string())
+            .add_attribute("merged_into_lock_id", lock_entry.lock_id.to_string())
+            .add_attribute("locked_tokens", info.funds[0].clone().to_string())
+            .add_attribute("lock_start", lock_entry.lock_start.to_string())
+            .add_attribute("lock_end", lock_entry.lock_end.to_string()));
+    }
 
-    update_total_time_weighted_shares(
+    let lock_entry = create_lock_entry(
         &mut deps,
+        &env,
         &constants,
+        &info.sender,
         current_round,
-        last_round_with_power,
-        lock_end,
-        validator,
-        lock_entry.funds.amount,
-        |_, _, _| Uint128::zero(),
+        info.funds[0].clone(),
+        env.block.time,
+        env.block.time.plus_nanos(lock_duration),
     )?;
 
     Ok(Response::new()
@@ -339,1868 +747,6253 @@ fn lock_tokens(
         .add_attribute("lock_end", lock_entry.lock_end.to_string()))
 }
 
-// Extends the lock duration of the guiven lock entries to be current_block_time + lock_duration,
-// assuming that this would actually increase the lock_end_time (so this *should not* be a way to make the lock time shorter).
-// Thus, for each lock entry the lock_end_time afterwards *must* be later than the lock_end_time before.
-// If this doesn't hold for any of the input lock entries, the function will return an error, and no
-// lock entries will be updated.
-// This should essentially have the same effect as removing the old locks and immediately re-locking all
-// the same funds for the new lock duration.
-fn refresh_lock_duration(
-    mut deps: DepsMut<NeutronQuery>,
+// LockTokensMultiple(lock_duration):
+//     Receive tokens of one or more denoms
+//     Validate that the user will not have too many locks after the new ones are created
+//     For each of the received coins, merge the tokens into a matching lockup if the user enabled the auto merging,
+//     or create a new lock entry otherwise, in the same way as LockTokens does
+fn lock_tokens_multiple(
+    deps: DepsMut<NeutronQuery>,
     env: Env,
     info: MessageInfo,
-    lock_ids: Vec<u64>,
     lock_duration: u64,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     let constants = CONSTANTS.load(deps.storage)?;
 
     validate_contract_is_not_paused(&constants)?;
-
     validate_lock_duration(
         &constants.round_lock_power_schedule,
         constants.lock_epoch_length,
         lock_duration,
     )?;
 
-    // if there are no lock_ids, return an error
-    if lock_ids.is_empty() {
+    let current_round = compute_lock_round_id(&env, &constants)?;
+
+    validate_pre_launch_lock_end(&env, &constants, env.block.time.plus_nanos(lock_duration))?;
+
+    initialize_validator_store(deps.storage, current_round)?;
+
+    if info.funds.is_empty() {
         return Err(ContractError::Std(StdError::generic_err(
-            "No lock_ids provided",
+            "Must provide at least one coin to lock",
         )));
     }
 
-    let current_round_id = compute_current_round_id(&env, &constants)?;
-    initialize_validator_store(deps.storage, current_round_id)?;
+    if get_lock_count(deps.as_ref(), info.sender.clone()) + info.funds.len() > MAX_LOCK_ENTRIES {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "User has too many locks, only {} locks allowed",
+            MAX_LOCK_ENTRIES
+        ))));
+    }
 
-    let mut response = Response::new()
-        .add_attribute("action", "refresh_lock_duration")
-        .add_attribute("sender", info.clone().sender)
-        .add_attribute("lock_count", lock_ids.len().to_string());
+    let mut deps = deps;
+    let mut lock_ids = vec![];
+    let mut merged_lock_ids = vec![];
+    let mut lock_end = env.block.time;
 
-    for lock_id in lock_ids {
-        let (new_lock_end, old_lock_end) = refresh_single_lock(
+    for funds in info.funds.iter() {
+        if let Some(lock_to_merge) =
+            find_lock_to_merge(deps.as_ref(), &env, &info.sender, funds, lock_duration)?
+        {
+            let lock_entry = merge_into_lock_entry(
+                &mut deps,
+                &env,
+                &constants,
+                &info.sender,
+                current_round,
+                lock_to_merge,
+                funds.clone(),
+                lock_duration,
+            )?;
+
+            lock_ids.push(lock_entry.lock_id.to_string());
+            merged_lock_ids.push(lock_entry.lock_id.to_string());
+            lock_end = lock_entry.lock_end;
+            continue;
+        }
+
+        let lock_entry = create_lock_entry(
             &mut deps,
-            &info,
             &env,
             &constants,
-            current_round_id,
-            lock_id,
-            lock_duration,
+            &info.sender,
+            current_round,
+            funds.clone(),
+            env.block.time,
+            env.block.time.plus_nanos(lock_duration),
         )?;
 
-        response = response.add_attribute(
-            format!("lock_id_{}_old_end", lock_id),
-            old_lock_end.to_string(),
-        );
-        response = response.add_attribute(
-            format!("lock_id_{}_new_end", lock_id),
-            new_lock_end.to_string(),
-        );
+        lock_ids.push(lock_entry.lock_id.to_string());
+        lock_end = lock_entry.lock_end;
     }
 
-    Ok(response)
+    let locked_tokens: Vec<String> = info.funds.iter().map(|coin| coin.to_string()).collect();
+
+    Ok(Response::new()
+        .add_attribute("action", "lock_tokens_multiple")
+        .add_attribute("sender", info.sender)
+        .add_attribute("lock_ids", lock_ids.join(","))
+        .add_attribute("merged_into_lock_ids", merged_lock_ids.join(","))
+        .add_attribute("locked_tokens", locked_tokens.join(","))
+        .add_attribute("lock_start", env.block.time.to_string())
+        .add_attribute("lock_end", lock_end.to_string()))
 }
 
-fn refresh_single_lock(
-    deps: &mut DepsMut<'_, NeutronQuery>,
-    info: &MessageInfo,
-    env: &Env,
-    constants: &Constants,
-    current_round_id: u64,
-    lock_id: u64,
-    new_lock_duration: u64,
-) -> Result<(u64, u64), ContractError> {
-    let mut lock_entry = LOCKS_MAP.load(deps.storage, (info.sender.clone(), lock_id))?;
-    let old_lock_entry = lock_entry.clone();
-    deps.api.debug(&format!("lock_entry: {:?}", lock_entry));
-    let new_lock_end = env.block.time.plus_nanos(new_lock_duration).nanos();
-    let old_lock_end = lock_entry.lock_end.nanos();
-    if new_lock_end <= old_lock_end {
+// IndexValidatorLocks(start_after, limit):
+//     Validate that the sender is a whitelist admin
+//     For up to `limit` locks after start_after that aren't in the validator locks index yet,
+//         resolve the validator from the lock denom and add the lock to the index
+//     Locks whose validator can't be resolved are skipped
+// Used to index the locks created before the index was introduced, in batches small enough to fit into a block.
+fn index_validator_locks(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    start_after: Option<(String, u64)>,
+    limit: u32,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    if limit == 0 {
         return Err(ContractError::Std(StdError::generic_err(
-            "Shortening locks is not allowed, new lock end time must be after the old lock end",
+            "Limit must be greater than zero",
         )));
     }
-    lock_entry.lock_end = Timestamp::from_nanos(new_lock_end);
-    LOCKS_MAP.save(deps.storage, (info.sender.clone(), lock_id), &lock_entry)?;
-    let validator_result = validate_denom(
-        deps.as_ref(),
-        env.clone(),
-        constants,
-        lock_entry.funds.denom.clone(),
-    );
-    if validator_result.is_err() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Lock denom is for a validator who is currently not in the set, try refreshing when the validator has enoug delegation",
-        )));
-    }
-    let validator = validator_result.unwrap();
-    update_voting_power_on_proposals(
-        deps,
-        &info.sender,
-        constants,
-        current_round_id,
-        Some(old_lock_entry),
-        lock_entry.clone(),
-        validator.clone(),
-    )?;
-    let old_last_round_with_power = compute_round_id_for_timestamp(constants, old_lock_end)? - 1;
-    let new_last_round_with_power = compute_round_id_for_timestamp(constants, new_lock_end)? - 1;
-    update_total_time_weighted_shares(
-        deps,
-        constants,
-        current_round_id,
-        new_last_round_with_power,
-        new_lock_end,
-        validator,
-        lock_entry.funds.amount,
-        |round, round_end, locked_amount| {
-            if round > old_last_round_with_power {
-                return Uint128::zero();
-            }
 
-            let old_lockup_length = old_lock_end - round_end.nanos();
-            scale_lockup_power(
-                &constants.round_lock_power_schedule,
-                constants.lock_epoch_length,
-                old_lockup_length,
-                locked_amount,
-            )
-        },
-    )?;
-    Ok((new_lock_end, old_lock_end))
-}
+    let start_after = match start_after {
+        Some((owner, lock_id)) => Some((deps.api.addr_validate(&owner)?, lock_id)),
+        None => None,
+    };
 
-// Validate that the lock duration (given in nanos) is either 1, 2, 3, 6, or 12 epochs
-fn validate_lock_duration(
-    round_lock_power_schedule: &RoundLockPowerSchedule,
-    lock_epoch_length: u64,
-    lock_duration: u64,
-) -> Result<(), ContractError> {
-    let lock_times = round_lock_power_schedule
-        .round_lock_power_schedule
-        .iter()
-        .map(|entry| entry.locked_rounds * lock_epoch_length)
-        .collect::<Vec<u64>>();
+    let locks = LOCKS_MAP
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<((Addr, u64), LockEntry)>>>()?;
 
-    if !lock_times.contains(&lock_duration) {
-        return Err(ContractError::Std(StdError::generic_err(format!(
-            "Lock duration must be one of: {:?}; but was: {}",
-            lock_times, lock_duration
-        ))));
+    let mut validators_by_denom: HashMap<String, Option<String>> = HashMap::new();
+    let mut indexed_locks = 0;
+    let mut skipped_locks = 0;
+
+    for ((owner, lock_id), lock_entry) in locks.iter() {
+        if LOCK_VALIDATORS.has(deps.storage, *lock_id) {
+            continue;
+        }
+
+        let denom = lock_entry.funds.denom.clone();
+        let validator = match validators_by_denom.get(&denom) {
+            Some(validator) => validator.clone(),
+            None => {
+                let validator =
+                    resolve_validator_from_denom(&deps.as_ref(), &constants, denom.clone()).ok();
+                validators_by_denom.insert(denom, validator.clone());
+
+                validator
+            }
+        };
+
+        match validator {
+            Some(validator) => {
+                add_lock_to_validator_index(deps.storage, &validator, owner, *lock_id)?;
+                indexed_locks += 1;
+            }
+            None => skipped_locks += 1,
+        }
     }
 
-    Ok(())
+    let mut response = Response::new()
+        .add_attribute("action", "index_validator_locks")
+        .add_attribute("sender", info.sender)
+        .add_attribute("indexed_locks", indexed_locks.to_string())
+        .add_attribute("skipped_locks", skipped_locks.to_string())
+        .add_attribute("completed", (locks.len() < limit as usize).to_string());
+
+    if let Some(((owner, lock_id), _)) = locks.last() {
+        response = response
+            .add_attribute("last_owner", owner.to_string())
+            .add_attribute("last_lock_id", lock_id.to_string());
+    }
+
+    Ok(response)
 }
 
-// UnlockTokens():
-//     Validate that the caller didn't vote in previous round
-//     Validate caller
-//     Validate `lock_end` < now
-//     Send `amount` tokens back to caller
-//     Delete entry from LocksMap
-fn unlock_tokens(
+// ImportLockups(entries, finalize):
+//     Validate that the sender is a whitelist admin and that the import wasn't finalized
+//     Validate that the sent funds match the total amount of the imported lockups for each denom
+//     For each entry, validate its lock times and create the lock entry for the owner in the same way
+//         as LockTokens does, keeping the lock start and lock end of the migrated lockup
+//     If finalize is set, reject any further imports
+// Used to migrate the users from another deployment without requiring each of them to lock their tokens again.
+fn import_lockups(
     deps: DepsMut<NeutronQuery>,
     env: Env,
     info: MessageInfo,
-    lock_ids: Option<Vec<u64>>,
+    entries: Vec<LockupImport>,
+    finalize: bool,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     let constants = CONSTANTS.load(deps.storage)?;
 
     validate_contract_is_not_paused(&constants)?;
-    // TODO: reenable this when we implement slashing
-    // validate_previous_round_vote(&deps, &env, info.sender.clone())?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
 
-    let locks_iter =
-        LOCKS_MAP
-            .prefix(info.sender.clone())
-            .range(deps.storage, None, None, Order::Ascending);
+    if LOCKUPS_IMPORT_FINALIZED
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Lockups import was already finalized",
+        )));
+    }
 
-    // If lock_ids is provided, filter locks to only those IDs
-    let locks: Vec<Result<(u64, LockEntry), StdError>> = if let Some(ids) = lock_ids {
-        locks_iter
-            .filter(|lock| {
-                if let Ok((id, _)) = lock {
-                    ids.contains(id)
-                } else {
-                    false
-                }
-            })
-            .collect()
-    } else {
-        locks_iter.collect()
-    };
+    if entries.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Must provide at least one lockup to import",
+        )));
+    }
 
-    let mut to_delete = vec![];
-    let mut total_unlocked_amount = Uint128::zero();
+    // validate that the sent funds exactly cover the imported lockups
+    let mut imported_amounts: BTreeMap<String, Uint128> = BTreeMap::new();
+    for entry in entries.iter() {
+        let amount = imported_amounts
+            .entry(entry.funds.denom.clone())
+            .or_default();
+        *amount = amount.checked_add(entry.funds.amount)?;
+    }
 
-    let mut response = Response::new()
-        .add_attribute("action", "unlock_tokens")
-        .add_attribute("sender", info.sender.to_string());
+    let mut sent_amounts: BTreeMap<String, Uint128> = BTreeMap::new();
+    for coin in info.funds.iter() {
+        let amount = sent_amounts.entry(coin.denom.clone()).or_default();
+        *amount = amount.checked_add(coin.amount)?;
+    }
 
-    let mut unlocked_lock_ids = vec![];
-    let mut unlocked_tokens = vec![];
+    if imported_amounts != sent_amounts {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Sent funds must match the total amount of the imported lockups: {:?}",
+            imported_amounts
+        ))));
+    }
 
-    for lock in locks {
-        let (lock_id, lock_entry) = lock?;
-        if lock_entry.lock_end < env.block.time {
-            // Send tokens back to caller
-            let send = Coin {
-                denom: lock_entry.funds.denom,
-                amount: lock_entry.funds.amount,
-            };
+    let max_lock_duration = constants
+        .round_lock_power_schedule
+        .round_lock_power_schedule
+        .iter()
+        .map(|entry| entry.locked_rounds * constants.lock_epoch_length)
+        .max()
+        .unwrap_or_default();
 
-            response = response.add_message(BankMsg::Send {
-                to_address: info.sender.to_string(),
-                amount: vec![send.clone()],
-            });
+    let current_round = compute_lock_round_id(&env, &constants)?;
+    initialize_validator_store(deps.storage, current_round)?;
 
-            total_unlocked_amount += send.amount;
+    let mut deps = deps;
+    let mut lock_ids = vec![];
 
-            // Delete entry from LocksMap
-            to_delete.push((info.sender.clone(), lock_id));
+    for entry in entries {
+        let owner = deps.api.addr_validate(&entry.owner)?;
 
-            unlocked_lock_ids.push(lock_id.to_string());
-            unlocked_tokens.push(send.to_string());
+        if entry.funds.amount.is_zero() {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Imported lockup of {} must not be empty",
+                owner
+            ))));
         }
-    }
 
-    // Delete unlocked locks
-    for (addr, lock_id) in to_delete {
-        LOCKS_MAP.remove(deps.storage, (addr, lock_id));
-    }
+        if entry.lock_start > env.block.time
+            || entry.lock_end <= env.block.time
+            || entry.lock_end.nanos() - env.block.time.nanos() > max_lock_duration
+        {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Imported lockup of {} must have started, must not have expired and must not end later than the longest lock duration from now",
+                owner
+            ))));
+        }
 
-    if !total_unlocked_amount.is_zero() {
-        LOCKED_TOKENS.update(
-            deps.storage,
-            |locked_tokens| -> Result<u128, ContractError> {
-                Ok(locked_tokens - total_unlocked_amount.u128())
-            },
+        validate_pre_launch_lock_end(&env, &constants, entry.lock_end)?;
+
+        let lock_entry = create_lock_entry(
+            &mut deps,
+            &env,
+            &constants,
+            &owner,
+            current_round,
+            entry.funds,
+            entry.lock_start,
+            entry.lock_end,
         )?;
-    }
 
-    Ok(response
-        .add_attribute("unlocked_lock_ids", unlocked_lock_ids.join(", "))
-        .add_attribute("unlocked_tokens", unlocked_tokens.join(", ")))
-}
+        lock_ids.push(lock_entry.lock_id.to_string());
+    }
 
-// prevent clippy from warning for unused function
-// TODO: reenable this when we enable slashing
-#[allow(dead_code)]
-fn validate_previous_round_vote(
-    deps: &DepsMut<NeutronQuery>,
-    env: &Env,
-    sender: Addr,
-) -> Result<(), ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
-    let current_round_id = compute_current_round_id(env, &constants)?;
-    if current_round_id > 0 {
-        let previous_round_id = current_round_id - 1;
-        for tranche_id in TRANCHE_MAP.keys(deps.storage, None, None, Order::Ascending) {
-            if VOTE_MAP
-                .prefix(((previous_round_id, tranche_id?), sender.clone()))
-                .range(deps.storage, None, None, Order::Ascending)
-                .count()
-                > 0
-            {
-                return Err(ContractError::Std(StdError::generic_err(
-                    "Tokens can not be unlocked, user voted for at least one proposal in previous round",
-                )));
-            }
-        }
+    if finalize {
+        LOCKUPS_IMPORT_FINALIZED.save(deps.storage, &true)?;
     }
 
-    Ok(())
+    Ok(Response::new()
+        .add_attribute("action", "import_lockups")
+        .add_attribute("sender", info.sender)
+        .add_attribute("lock_ids", lock_ids.join(","))
+        .add_attribute("finalized", finalize.to_string()))
 }
 
-// Creates a new proposal in the store.
-// It will:
-// * validate that the contract is not paused
-// * validate that the creator of the proposal is on the whitelist
-// Then, it will create the proposal in the specified tranche and in the specified round.
-// If no round_id is specified, the function will use the current round id.
+// Validates the denom of the given funds and the locking limits, creates the lock entry
+// and updates the voting power of the user and the total voting power accordingly.
 #[allow(clippy::too_many_arguments)]
-fn create_proposal(
-    deps: DepsMut<NeutronQuery>,
-    env: Env,
-    info: MessageInfo,
-    round_id: Option<u64>,
-    tranche_id: u64,
-    title: String,
-    description: String,
-    deployment_duration: u64,
-    minimum_atom_liquidity_request: Uint128,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
-    validate_contract_is_not_paused(&constants)?;
-
-    let current_round_id = compute_current_round_id(&env, &constants)?;
-    // this is just to initialize the store on the first action in each round
-    initialize_validator_store(deps.storage, current_round_id)?;
+fn create_lock_entry(
+    deps: &mut DepsMut<NeutronQuery>,
+    env: &Env,
+    constants: &Constants,
+    sender: &Addr,
+    current_round: u64,
+    funds: Coin,
+    lock_start: Timestamp,
+    lock_end: Timestamp,
+) -> Result<LockEntry, ContractError> {
+    let validator = validate_denom(deps.as_ref(), env.clone(), constants, funds.denom.clone())
+        .map_err(|err| {
+            ContractError::Std(StdError::generic_err(format!("validating denom: {}", err)))
+        })?;
 
-    // if no round_id is provided, use the current round
-    let round_id = round_id.unwrap_or(current_round_id);
+    // validate that this wouldn't cause the contract to have more locked tokens than the limit
+    let amount_to_lock = funds.amount.u128();
+    let locked_tokens = LOCKED_TOKENS.load(deps.storage)?;
 
-    if current_round_id > round_id {
+    if locked_tokens + amount_to_lock > constants.max_locked_tokens {
         return Err(ContractError::Std(StdError::generic_err(
-            "cannot create a proposal in a round that ended in the past",
+            "The limit for locking tokens has been reached. No more tokens can be locked.",
         )));
     }
 
-    // validate that the sender is on the whitelist
-    let whitelist = WHITELIST.load(deps.storage)?;
-
-    if !whitelist.contains(&info.sender) {
-        return Err(ContractError::Unauthorized);
-    }
-
-    // check that the tranche with the given id exists
-    TRANCHE_MAP.load(deps.storage, tranche_id)?;
-
-    // check that the deployment duration is within the allowed range
-    if deployment_duration < MIN_DEPLOYMENT_DURATION
-        || deployment_duration > constants.max_deployment_duration
-    {
+    // validate that the user does not have too many locks
+    if get_lock_count(deps.as_ref(), sender.clone()) >= MAX_LOCK_ENTRIES {
         return Err(ContractError::Std(StdError::generic_err(format!(
-            "Invalid deployment duration: {}. Must be between {} and {} rounds.",
-            deployment_duration, MIN_DEPLOYMENT_DURATION, constants.max_deployment_duration,
+            "User has too many locks, only {} locks allowed",
+            MAX_LOCK_ENTRIES
         ))));
     }
 
-    let proposal_id = PROP_ID.load(deps.storage)?;
-
-    let proposal = Proposal {
-        round_id,
-        tranche_id,
-        proposal_id,
-        power: Uint128::zero(),
-        percentage: Uint128::zero(),
-        title: title.trim().to_string(),
-        description: description.trim().to_string(),
-        deployment_duration,
-        minimum_atom_liquidity_request,
+    let lock_id = LOCK_ID.load(deps.storage)?;
+    LOCK_ID.save(deps.storage, &(lock_id + 1))?;
+    let lock_entry = LockEntry {
+        lock_id,
+        funds,
+        lock_start,
+        lock_end,
     };
+    let lock_end = lock_entry.lock_end.nanos();
 
-    PROP_ID.save(deps.storage, &(proposal_id + 1))?;
-    PROPOSAL_MAP.save(deps.storage, (round_id, tranche_id, proposal_id), &proposal)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "create_proposal")
-        .add_attribute("sender", info.sender)
-        .add_attribute("round_id", round_id.to_string())
-        .add_attribute("tranche_id", tranche_id.to_string())
-        .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("proposal_title", proposal.title)
-        .add_attribute("proposal_description", proposal.description)
-        .add_attribute(
-            "deployment_duration",
-            proposal.deployment_duration.to_string(),
-        )
-        .add_attribute(
-            "minimum_atom_liquidity_request",
-            proposal.minimum_atom_liquidity_request.to_string(),
-        ))
-}
-
-pub fn scale_lockup_power(
-    round_lock_power_schedule: &RoundLockPowerSchedule,
-    lock_epoch_length: u64,
-    lockup_time: u64,
-    raw_power: Uint128,
-) -> Uint128 {
-    for entry in round_lock_power_schedule.round_lock_power_schedule.iter() {
-        let needed_lock_time = entry.locked_rounds * lock_epoch_length;
-        if lockup_time <= needed_lock_time {
-            let power = entry
-                .power_scaling_factor
-                .saturating_mul(Decimal::from_ratio(raw_power, Uint128::one()));
-            return power.to_uint_floor();
-        }
-    }
-
-    // if lockup time is longer than the longest lock time, return the maximum power
-    let largest_multiplier = round_lock_power_schedule
-        .round_lock_power_schedule
-        .last()
-        .unwrap()
-        .power_scaling_factor;
-    largest_multiplier
-        .saturating_mul(Decimal::new(raw_power))
-        .to_uint_floor()
-}
+    LOCKS_MAP.save(deps.storage, (sender.clone(), lock_id), &lock_entry)?;
+    LOCKED_TOKENS.save(deps.storage, &(locked_tokens + amount_to_lock))?;
+    add_lock_to_validator_index(deps.storage, &validator, sender, lock_id)?;
 
-fn vote(
-    deps: DepsMut<NeutronQuery>,
-    env: Env,
-    info: MessageInfo,
-    tranche_id: u64,
-    proposals_votes: Vec<ProposalToLockups>,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    // This voting system is designed to allow for an unlimited number of proposals and an unlimited number of votes
-    // to be created, without being vulnerable to DOS. A naive implementation, where all votes or all proposals were iterated
-    // at the end of the round could be DOSed by creating a large number of votes or proposals. This is not a problem
-    // for this implementation, but this leads to some subtlety in the implementation.
-    // I will explain the overall principle here:
-    // - The information on which proposal is winning is updated each time someone votes, instead of being calculated at the end of the round.
-    // - This information is stored in a map called PROPS_BY_SCORE, which maps the score of a proposal to the proposal id.
-    // - At the end of the round, a single access to PROPS_BY_SCORE is made to get the winning proposal.
-    // - To enable switching votes (and for other stuff too), we store the vote in VOTE_MAP.
-    // - When a user votes the second time in a round, the information about their previous vote from VOTE_MAP is used to reverse the effect of their previous vote.
-    // - This leads to slightly higher gas costs for each vote, in exchange for a much lower gas cost at the end of the round.
-    let constants = CONSTANTS.load(deps.storage)?;
-    validate_contract_is_not_paused(&constants)?;
+    // If user already voted for some proposals in the current round, update the voting power on those proposals.
+    update_voting_power_on_proposals(
+        deps,
+        sender,
+        constants,
+        current_round,
+        None,
+        lock_entry.clone(),
+        validator.clone(),
+    )?;
 
-    let round_id = compute_current_round_id(&env, &constants)?;
-    // voting can never be the first action in a round (since one can only vote on proposals in the current round, and a proposal must be created first)
-    // however, to be safe, we initialize the validator store here, since this is more robust in case we change something about voting later
-    initialize_validator_store(deps.storage, round_id)?;
+    // Calculate and update the total voting power info for current and all
+    // future rounds in which the user will have voting power greater than 0
+    let last_round_with_power = compute_round_id_for_timestamp(constants, lock_end)? - 1;
 
-    // check that the tranche with the given id exists
-    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+    update_total_time_weighted_shares(
+        deps,
+        constants,
+        current_round,
+        last_round_with_power,
+        lock_end,
+        validator,
+        lock_entry.funds.amount,
+        |_, _, _| Uint128::zero(),
+    )?;
 
-    // compute the round end
-    let round_end = compute_round_end(&constants, round_id)?;
+    Ok(lock_entry)
+}
 
-    let mut response = Response::new()
-        .add_attribute("action", "vote")
-        .add_attribute("sender", info.sender.to_string());
+// Returns the non-expired lockup of the given user with the same denom as the given funds, whose lock end is
+// within the user's auto merge tolerance from the lock end of the new lock. If there are multiple such lockups,
+// the one with the closest lock end is returned. Returns None if the user didn't enable the auto merging.
+fn find_lock_to_merge(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    sender: &Addr,
+    funds: &Coin,
+    lock_duration: u64,
+) -> StdResult<Option<LockEntry>> {
+    let tolerance = match LOCK_AUTO_MERGE_TOLERANCES.may_load(deps.storage, sender.clone())? {
+        Some(tolerance) => tolerance,
+        None => return Ok(None),
+    };
 
-    // Check for duplicate proposal and lock IDs
-    let mut proposal_ids = HashSet::new();
-    let mut lock_ids = HashSet::new();
+    let new_lock_end = env.block.time.plus_nanos(lock_duration).nanos();
+    let mut closest_lock: Option<(u64, LockEntry)> = None;
 
-    for proposal_votes in proposals_votes.iter() {
-        if !proposal_ids.insert(proposal_votes.proposal_id) {
-            return Err(ContractError::Std(StdError::generic_err(format!(
-                "Duplicate proposal ID {} provided",
-                proposal_votes.proposal_id
-            ))));
+    for lock in LOCKS_MAP
+        .prefix(sender.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, lock_entry) = lock?;
+        if lock_entry.funds.denom != funds.denom || lock_entry.lock_end <= env.block.time {
+            continue;
         }
 
-        if proposal_votes.lock_ids.is_empty() {
-            return Err(ContractError::Std(StdError::generic_err(format!(
-                "No lock IDs provided to vote for proposal ID {}",
-                proposal_votes.proposal_id
-            ))));
+        let distance = lock_entry.lock_end.nanos().abs_diff(new_lock_end);
+        if distance > tolerance {
+            continue;
         }
 
-        for lock_id in proposal_votes.lock_ids.iter() {
-            if !lock_ids.insert(*lock_id) {
-                return Err(ContractError::Std(StdError::generic_err(format!(
-                    "Duplicate lock ID {} provided",
-                    lock_id
-                ))));
-            }
+        match &closest_lock {
+            Some((closest_distance, _)) if *closest_distance <= distance => {}
+            _ => closest_lock = Some((distance, lock_entry)),
         }
     }
 
-    if proposal_ids.is_empty() || lock_ids.is_empty() {
+    Ok(closest_lock.map(|(_, lock_entry)| lock_entry))
+}
+
+// Adds the given funds to the existing lock entry, extending its lock end if the new lock would end later,
+// and updates the voting power of the user and the total voting power accordingly. The merged tokens
+// follow the votes and the voting restrictions of the existing lock entry.
+#[allow(clippy::too_many_arguments)]
+fn merge_into_lock_entry(
+    deps: &mut DepsMut<NeutronQuery>,
+    env: &Env,
+    constants: &Constants,
+    sender: &Addr,
+    current_round: u64,
+    old_lock_entry: LockEntry,
+    funds: Coin,
+    lock_duration: u64,
+) -> Result<LockEntry, ContractError> {
+    let validator = validate_denom(deps.as_ref(), env.clone(), constants, funds.denom.clone())
+        .map_err(|err| {
+            ContractError::Std(StdError::generic_err(format!("validating denom: {}", err)))
+        })?;
+
+    // validate that this wouldn't cause the contract to have more locked tokens than the limit
+    let amount_to_lock = funds.amount.u128();
+    let locked_tokens = LOCKED_TOKENS.load(deps.storage)?;
+
+    if locked_tokens + amount_to_lock > constants.max_locked_tokens {
         return Err(ContractError::Std(StdError::generic_err(
-            "Must provide at least one proposal and lockup to vote",
+            "The limit for locking tokens has been reached. No more tokens can be locked.",
         )));
     }
 
-    // TODO: optimize so that all locks that voted for the same proposal are removed in single execution
-    for lock_id in lock_ids {
-        // Get any existing vote for this sender and reverse it- this may be a vote for a different proposal (if they are switching their vote),
-        // or it may be a vote for the same proposal (if they have increased their power by locking more and want to update their vote).
-        // TODO: this could be made more gas-efficient by using a separate path with fewer writes if the vote is for the same proposal
-        let vote = VOTE_MAP.may_load(
-            deps.storage,
-            ((round_id, tranche_id), info.sender.clone(), lock_id),
-        )?;
-        match vote {
-            Some(vote) => {
-                // Load the proposal in the vote
-                let mut proposal =
-                    PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, vote.prop_id))?;
+    let old_lock_end = old_lock_entry.lock_end.nanos();
+    let old_amount = old_lock_entry.funds.amount;
+    let new_lock_end = old_lock_end.max(env.block.time.plus_nanos(lock_duration).nanos());
 
-                // Remove proposal's old power in PROPS_BY_SCORE
-                PROPS_BY_SCORE.remove(
-                    deps.storage,
-                    (
-                        (round_id, proposal.tranche_id),
-                        proposal.power.into(),
-                        vote.prop_id,
-                    ),
-                );
+    let mut lock_entry = old_lock_entry.clone();
+    lock_entry.funds.amount = old_amount.checked_add(funds.amount)?;
+    lock_entry.lock_end = Timestamp::from_nanos(new_lock_end);
 
-                remove_validator_shares_from_proposal(
-                    deps.storage,
-                    round_id,
-                    vote.prop_id,
-                    vote.time_weighted_shares.0,
-                    vote.time_weighted_shares.1,
-                )?;
+    LOCKS_MAP.save(
+        deps.storage,
+        (sender.clone(), lock_entry.lock_id),
+        &lock_entry,
+    )?;
+    LOCKED_TOKENS.save(deps.storage, &(locked_tokens + amount_to_lock))?;
 
-                // save the new power into the proposal
-                let total_power =
-                    get_total_power_for_proposal(deps.as_ref().storage, vote.prop_id)?;
-                proposal.power = total_power.to_uint_ceil(); // TODO: decide whether we need to round or represent as decimals
+    update_voting_power_on_proposals(
+        deps,
+        sender,
+        constants,
+        current_round,
+        Some(old_lock_entry),
+        lock_entry.clone(),
+        validator.clone(),
+    )?;
 
-                // Save the proposal
-                PROPOSAL_MAP.save(
-                    deps.storage,
-                    (round_id, tranche_id, vote.prop_id),
-                    &proposal,
-                )?;
+    let old_last_round_with_power = compute_round_id_for_timestamp(constants, old_lock_end)? - 1;
+    let new_last_round_with_power = compute_round_id_for_timestamp(constants, new_lock_end)? - 1;
 
-                // Add proposal's new power in PROPS_BY_SCORE
-                if proposal.power > Uint128::zero() {
-                    PROPS_BY_SCORE.save(
-                        deps.storage,
-                        (
-                            (round_id, proposal.tranche_id),
-                            proposal.power.into(),
-                            vote.prop_id,
-                        ),
-                        &vote.prop_id,
-                    )?;
-                }
+    update_total_time_weighted_shares(
+        deps,
+        constants,
+        current_round,
+        new_last_round_with_power,
+        new_lock_end,
+        validator,
+        lock_entry.funds.amount,
+        |round, round_end, _| {
+            if round > old_last_round_with_power {
+                return Uint128::zero();
+            }
 
-                // Delete vote
-                VOTE_MAP.remove(
-                    deps.storage,
-                    ((round_id, tranche_id), info.sender.clone(), lock_id),
-                );
+            let old_lockup_length = old_lock_end - round_end.nanos();
+            scale_lockup_power(
+                &constants.round_lock_power_schedule,
+                constants.lock_epoch_length,
+                old_lockup_length,
+                old_amount,
+                constants.interpolate_lock_power,
+            )
+        },
+    )?;
 
-                // Delete voting round allowed info
-                VOTING_ALLOWED_ROUND.remove(deps.storage, (tranche_id, lock_id));
+    Ok(lock_entry)
+}
 
-                response = response.add_attribute(
-                    format!("lock_id_{}_old_proposal_id", lock_id),
-                    vote.prop_id.to_string(),
-                );
-            }
-            None => {
-                // If user didn't yet vote with the given lock in the given round and tranche, check
-                // if they voted in previous rounds for some proposal that spans multiple rounds.
-                // This means that users can change their vote during a round, because we don't
-                // check this if users already voted in the current round.
-                let voting_allowed_round =
-                    VOTING_ALLOWED_ROUND.may_load(deps.storage, (tranche_id, lock_id))?;
+// Extends the lock duration of the guiven lock entries to be current_block_time + lock_duration,
+// assuming that this would actually increase the lock_end_time (so this *should not* be a way to make the lock time shorter).
+// Thus, for each lock entry the lock_end_time afterwards *must* be later than the lock_end_time before.
+// If this doesn't hold for any of the input lock entries, the function will return an error, and no
+// lock entries will be updated.
+// This should essentially have the same effect as removing the old locks and immediately re-locking all
+// the same funds for the new lock duration.
+fn refresh_lock_duration(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    lock_ids: Vec<u64>,
+    lock_duration: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    refresh_locks(deps, env, info.sender, lock_ids, lock_duration)
+}
 
-                if let Some(voting_allowed_round) = voting_allowed_round {
-                    if voting_allowed_round > round_id {
-                        return Err(ContractError::Std(
-                        StdError::generic_err(format!(
-                            "Not allowed to vote with lock_id {} in tranche {}. Cannot vote again with this lock_id until round {}.",
-                            lock_id, tranche_id, voting_allowed_round))));
-                    }
-                }
+fn refresh_locks(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    lock_owner: Addr,
+    lock_ids: Vec<u64>,
+    lock_duration: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    validate_lock_duration(
+        &constants.round_lock_power_schedule,
+        constants.lock_epoch_length,
+        lock_duration,
+    )?;
+
+    // if there are no lock_ids, return an error
+    if lock_ids.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No lock_ids provided",
+        )));
+    }
+
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    initialize_validator_store(deps.storage, current_round_id)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "refresh_lock_duration")
+        .add_attribute("sender", lock_owner.clone())
+        .add_attribute("lock_count", lock_ids.len().to_string());
+
+    for lock_id in lock_ids {
+        let (new_lock_end, old_lock_end) = refresh_single_lock(
+            &mut deps,
+            &lock_owner,
+            &env,
+            &constants,
+            current_round_id,
+            lock_id,
+            lock_duration,
+        )?;
+
+        response = response.add_attribute(
+            format!("lock_id_{}_old_end", lock_id),
+            old_lock_end.to_string(),
+        );
+        response = response.add_attribute(
+            format!("lock_id_{}_new_end", lock_id),
+            new_lock_end.to_string(),
+        );
+    }
+
+    Ok(response)
+}
+
+// RefreshLockDurationOnBehalf(owner, lock_ids, lock_duration):
+//     Validate that the sender is the operator of all the given lockups of the owner
+//     Refresh the lockups in the same way as the owner would via RefreshLockDuration
+fn refresh_lock_duration_on_behalf(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    lock_ids: Vec<u64>,
+    lock_duration: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    let owner = deps.api.addr_validate(&owner)?;
+
+    for lock_id in lock_ids.iter() {
+        if !is_lock_operator(deps.storage, &owner, *lock_id, &info.sender)? {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "{} is not the operator of lock {}",
+                info.sender, lock_id
+            ))));
+        }
+    }
+
+    Ok(refresh_locks(deps, env, owner, lock_ids, lock_duration)?
+        .add_attribute("operator", info.sender))
+}
+
+// SetLockOperator(lock_ids, operator):
+//     Validate that the sender owns the given lockups and that the operator is not the sender
+//     Appoint the operator of the lockups, replacing any previous one, or remove it if no operator is given
+fn set_lock_operator(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    lock_ids: Vec<u64>,
+    operator: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    if lock_ids.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No lock_ids provided",
+        )));
+    }
+
+    let operator = operator
+        .map(|operator| deps.api.addr_validate(&operator))
+        .transpose()?;
+    if operator.as_ref() == Some(&info.sender) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Lock owner can't be the operator of their own locks",
+        )));
+    }
+
+    for lock_id in lock_ids.iter() {
+        LOCKS_MAP.load(deps.storage, (info.sender.clone(), *lock_id))?;
+
+        match &operator {
+            Some(operator) => {
+                LOCK_OPERATORS.save(deps.storage, (info.sender.clone(), *lock_id), operator)?
             }
-        };
+            None => LOCK_OPERATORS.remove(deps.storage, (info.sender.clone(), *lock_id)),
+        }
     }
 
-    let lock_epoch_length = constants.lock_epoch_length;
-    let mut voted_proposals = vec![];
-    let mut locks_voted = vec![];
-    let mut locks_skipped = vec![];
+    let lock_ids: Vec<String> = lock_ids.iter().map(|lock_id| lock_id.to_string()).collect();
 
-    for proposal_to_lockups in proposals_votes {
-        let proposal_id = proposal_to_lockups.proposal_id;
+    Ok(Response::new()
+        .add_attribute("action", "set_lock_operator")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "operator",
+            operator.map_or("none".to_string(), |operator| operator.to_string()),
+        )
+        .add_attribute("lock_ids", lock_ids.join(",")))
+}
 
-        // TODO: optimize so that proposal related stores are updated only once
-        for lock_id in proposal_to_lockups.lock_ids {
-            // If any of the lock_ids doesn't exist, or it belongs to a different user
-            // then error out and revert any changes that were made until now.
-            let lock_entry = LOCKS_MAP.load(deps.storage, (info.sender.clone(), lock_id))?;
+// SetLockNote(lock_id, note):
+//     Validate that the sender owns the given lockup
+//     Validate that the note isn't too long and holds no control characters
+//     Attach the note to the lockup, replacing any previous one, or remove it if no note is given
+fn set_lock_note(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    lock_id: u64,
+    note: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
 
-            // get the validator from the denom
-            let validator = match validate_denom(
-                deps.as_ref(),
-                env.clone(),
-                &constants,
-                lock_entry.clone().funds.denom,
-            ) {
-                Ok(validator) => validator,
-                Err(_) => {
-                    deps.api.debug(&
-                        format!(
-                            "Denom {} is not a valid validator denom; validator might not be in the current set of top validators by delegation",
-                            lock_entry.funds.denom
-                        ));
+    validate_contract_is_not_paused(&constants)?;
+
+    LOCKS_MAP.load(deps.storage, (info.sender.clone(), lock_id))?;
+
+    let note = note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+
+    match &note {
+        Some(note) => {
+            if note.chars().count() > MAX_LOCK_NOTE_LENGTH {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Lock note must not be longer than {} characters.",
+                    MAX_LOCK_NOTE_LENGTH
+                ))));
+            }
+
+            if note.chars().any(|c| c.is_control()) {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "Lock note must not contain control characters.",
+                )));
+            }
+
+            LOCK_NOTES.save(deps.storage, (info.sender.clone(), lock_id), note)?;
+        }
+        None => LOCK_NOTES.remove(deps.storage, (info.sender.clone(), lock_id)),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_lock_note")
+        .add_attribute("sender", info.sender)
+        .add_attribute("lock_id", lock_id.to_string())
+        .add_attribute("note", note.unwrap_or_default()))
+}
+
+// SetLockAutoMerge(tolerance):
+//     Validate that the tolerance isn't longer than the lock epoch length
+//     Store the tolerance for the sender, or remove it if no tolerance is given
+fn set_lock_auto_merge(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    tolerance: Option<u64>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    match tolerance {
+        Some(tolerance) => {
+            if tolerance > constants.lock_epoch_length {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Lock auto merge tolerance must not be longer than the lock epoch length of {} nanoseconds.",
+                    constants.lock_epoch_length
+                ))));
+            }
+
+            LOCK_AUTO_MERGE_TOLERANCES.save(deps.storage, info.sender.clone(), &tolerance)?;
+        }
+        None => LOCK_AUTO_MERGE_TOLERANCES.remove(deps.storage, info.sender.clone()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_lock_auto_merge")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "tolerance",
+            tolerance.map_or("none".to_string(), |tolerance| tolerance.to_string()),
+        ))
+}
+
+fn is_lock_operator(
+    storage: &dyn Storage,
+    owner: &Addr,
+    lock_id: u64,
+    address: &Addr,
+) -> StdResult<bool> {
+    Ok(LOCK_OPERATORS
+        .may_load(storage, (owner.clone(), lock_id))?
+        .as_ref()
+        == Some(address))
+}
+
+fn refresh_single_lock(
+    deps: &mut DepsMut<'_, NeutronQuery>,
+    owner: &Addr,
+    env: &Env,
+    constants: &Constants,
+    current_round_id: u64,
+    lock_id: u64,
+    new_lock_duration: u64,
+) -> Result<(u64, u64), ContractError> {
+    let mut lock_entry = LOCKS_MAP.load(deps.storage, (owner.clone(), lock_id))?;
+    let old_lock_entry = lock_entry.clone();
+    deps.api.debug(&format!("lock_entry: {:?}", lock_entry));
+    let new_lock_end = env.block.time.plus_nanos(new_lock_duration).nanos();
+    let old_lock_end = lock_entry.lock_end.nanos();
+    if new_lock_end <= old_lock_end {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Shortening locks is not allowed, new lock end time must be after the old lock end",
+        )));
+    }
+    lock_entry.lock_end = Timestamp::from_nanos(new_lock_end);
+    LOCKS_MAP.save(deps.storage, (owner.clone(), lock_id), &lock_entry)?;
+    let validator_result = validate_denom(
+        deps.as_ref(),
+        env.clone(),
+        constants,
+        lock_entry.funds.denom.clone(),
+    );
+    if validator_result.is_err() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Lock denom is for a validator who is currently not in the set, try refreshing when the validator has enoug delegation",
+        )));
+    }
+    let validator = validator_result.unwrap();
+    update_voting_power_on_proposals(
+        deps,
+        owner,
+        constants,
+        current_round_id,
+        Some(old_lock_entry),
+        lock_entry.clone(),
+        validator.clone(),
+    )?;
+    let old_last_round_with_power = compute_round_id_for_timestamp(constants, old_lock_end)? - 1;
+    let new_last_round_with_power = compute_round_id_for_timestamp(constants, new_lock_end)? - 1;
+    update_total_time_weighted_shares(
+        deps,
+        constants,
+        current_round_id,
+        new_last_round_with_power,
+        new_lock_end,
+        validator,
+        lock_entry.funds.amount,
+        |round, round_end, locked_amount| {
+            if round > old_last_round_with_power {
+                return Uint128::zero();
+            }
+
+            let old_lockup_length = old_lock_end - round_end.nanos();
+            scale_lockup_power(
+                &constants.round_lock_power_schedule,
+                constants.lock_epoch_length,
+                old_lockup_length,
+                locked_amount,
+                constants.interpolate_lock_power,
+            )
+        },
+    )?;
+    Ok((new_lock_end, old_lock_end))
+}
+
+// SetAutoRefresh(lock_ids, enabled):
+//     Validate that the sender owns the given lockups
+//     If enabling, validate that the lockups didn't expire, and store their remaining lock duration
+//     If disabling, remove the lockups from the auto refreshed ones
+fn set_auto_refresh(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    lock_ids: Vec<u64>,
+    enabled: bool,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    if lock_ids.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No lock_ids provided",
+        )));
+    }
+
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+
+    for lock_id in lock_ids.iter() {
+        let lock_entry = LOCKS_MAP.load(deps.storage, (info.sender.clone(), *lock_id))?;
+
+        if !enabled {
+            remove_auto_refresh(deps.storage, &info.sender, *lock_id)?;
+            continue;
+        }
+
+        if lock_entry.lock_end <= env.block.time {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Lock {} has expired, so it can't be auto refreshed",
+                lock_id
+            ))));
+        }
+
+        save_auto_refresh(
+            deps.storage,
+            &info.sender,
+            *lock_id,
+            &AutoRefresh {
+                lock_duration: lock_entry.lock_end.nanos() - env.block.time.nanos(),
+                last_refresh_round: current_round_id,
+            },
+        )?;
+    }
+
+    let lock_ids: Vec<String> = lock_ids.iter().map(|lock_id| lock_id.to_string()).collect();
+
+    Ok(Response::new()
+        .add_attribute("action", "set_auto_refresh")
+        .add_attribute("sender", info.sender)
+        .add_attribute("lock_ids", lock_ids.join(","))
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+// ProcessAutoRefresh(limit):
+//     For up to limit auto refreshed lockups that weren't refreshed in the current round yet:
+//         Skip the lockups whose validator is currently not in the set; they are retried in the next round
+//         Extend the lock end so that the remaining lock duration equals the stored one
+//         Update the voting power in the same way as RefreshLockDuration does
+fn process_auto_refresh(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    limit: u32,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    initialize_validator_store(deps.storage, current_round_id)?;
+
+    // The queue is ordered by the round of the last refresh, so the lockups that weren't refreshed in the
+    // current round yet come first, and the iteration stops at the ones that already were.
+    let locks_to_refresh = AUTO_REFRESH_QUEUE
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::exclusive((current_round_id, Addr::unchecked(""), 0))),
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<(u64, Addr, u64)>>>()?;
+
+    let mut refreshed_lock_ids = vec![];
+    let mut skipped_lock_ids = vec![];
+
+    for (_, owner, lock_id) in locks_to_refresh {
+        let lock_entry = LOCKS_MAP.load(deps.storage, (owner.clone(), lock_id))?;
+
+        let mut auto_refresh = AUTO_REFRESH_LOCKS.load(deps.storage, (owner.clone(), lock_id))?;
+        auto_refresh.last_refresh_round = current_round_id;
+        save_auto_refresh(deps.storage, &owner, lock_id, &auto_refresh)?;
+
+        if validate_denom(
+            deps.as_ref(),
+            env.clone(),
+            &constants,
+            lock_entry.funds.denom.clone(),
+        )
+        .is_err()
+        {
+            skipped_lock_ids.push(lock_id.to_string());
+            continue;
+        }
+
+        if env.block.time.plus_nanos(auto_refresh.lock_duration) > lock_entry.lock_end {
+            refresh_single_lock(
+                &mut deps,
+                &owner,
+                &env,
+                &constants,
+                current_round_id,
+                lock_id,
+                auto_refresh.lock_duration,
+            )?;
+        }
+
+        refreshed_lock_ids.push(lock_id.to_string());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "process_auto_refresh")
+        .add_attribute("refreshed_lock_ids", refreshed_lock_ids.join(","))
+        .add_attribute("skipped_lock_ids", skipped_lock_ids.join(",")))
+}
+
+// Stores the auto refresh settings of the lockup and moves it to the matching AUTO_REFRESH_QUEUE entry.
+fn save_auto_refresh(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    lock_id: u64,
+    auto_refresh: &AutoRefresh,
+) -> StdResult<()> {
+    remove_auto_refresh(storage, owner, lock_id)?;
+
+    AUTO_REFRESH_LOCKS.save(storage, (owner.clone(), lock_id), auto_refresh)?;
+    AUTO_REFRESH_QUEUE.save(
+        storage,
+        (auto_refresh.last_refresh_round, owner.clone(), lock_id),
+        &true,
+    )
+}
+
+// Removes the auto refresh settings of the lockup, if it has any, together with its AUTO_REFRESH_QUEUE entry.
+fn remove_auto_refresh(storage: &mut dyn Storage, owner: &Addr, lock_id: u64) -> StdResult<()> {
+    if let Some(auto_refresh) = AUTO_REFRESH_LOCKS.may_load(storage, (owner.clone(), lock_id))? {
+        AUTO_REFRESH_QUEUE.remove(
+            storage,
+            (auto_refresh.last_refresh_round, owner.clone(), lock_id),
+        );
+        AUTO_REFRESH_LOCKS.remove(storage, (owner.clone(), lock_id));
+    }
+
+    Ok(())
+}
+
+// Validate that the lock duration (given in nanos) is either 1, 2, 3, 6, or 12 epochs
+fn validate_lock_duration(
+    round_lock_power_schedule: &RoundLockPowerSchedule,
+    lock_epoch_length: u64,
+    lock_duration: u64,
+) -> Result<(), ContractError> {
+    let lock_times = round_lock_power_schedule
+        .round_lock_power_schedule
+        .iter()
+        .map(|entry| entry.locked_rounds * lock_epoch_length)
+        .collect::<Vec<u64>>();
+
+    if !lock_times.contains(&lock_duration) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Lock duration must be one of: {:?}; but was: {}",
+            lock_times, lock_duration
+        ))));
+    }
+
+    Ok(())
+}
+
+// UnlockTokens():
+//     Validate that the caller didn't vote in previous round
+//     Validate caller
+//     Validate `lock_end` < now
+//     Send `amount` tokens back to caller, or to the recipient if one is given. If the unlock cooldown
+//     is enabled, the tokens are instead held in PENDING_UNLOCKS until they are claimed via ClaimUnlocked
+//     Delete entry from LocksMap
+fn unlock_tokens(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    lock_ids: Option<Vec<u64>>,
+    claim_tributes: bool,
+    recipient: Option<Addr>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    // TODO: reenable this when we implement slashing
+    // validate_previous_round_vote(&deps, &env, info.sender.clone())?;
+
+    let locks_iter =
+        LOCKS_MAP
+            .prefix(info.sender.clone())
+            .range(deps.storage, None, None, Order::Ascending);
+
+    // If lock_ids is provided, filter locks to only those IDs
+    let locks: Vec<Result<(u64, LockEntry), StdError>> = if let Some(ids) = lock_ids {
+        locks_iter
+            .filter(|lock| {
+                if let Ok((id, _)) = lock {
+                    ids.contains(id)
+                } else {
+                    false
+                }
+            })
+            .collect()
+    } else {
+        locks_iter.collect()
+    };
+
+    let mut to_delete = vec![];
+    let mut total_unlocked_amount = Uint128::zero();
+
+    let mut response = Response::new()
+        .add_attribute("action", "unlock_tokens")
+        .add_attribute("sender", info.sender.to_string());
+
+    let recipient = match recipient {
+        Some(recipient) => {
+            response = response.add_attribute("recipient", recipient.to_string());
+            recipient
+        }
+        None => info.sender.clone(),
+    };
+
+    let claimable_at = env
+        .block
+        .time
+        .plus_nanos(constants.unlock_cooldown_duration);
+    if constants.unlock_cooldown_duration > 0 {
+        response = response.add_attribute("claimable_at", claimable_at.to_string());
+    }
+
+    let mut unlocked_lock_ids = vec![];
+    let mut unlocked_tokens = vec![];
+    let mut lock_votes = vec![];
+
+    for lock in locks {
+        let (lock_id, lock_entry) = lock?;
+        if lock_entry.lock_end < env.block.time {
+            // Send tokens back to caller
+            let send = Coin {
+                denom: lock_entry.funds.denom,
+                amount: lock_entry.funds.amount,
+            };
+
+            if constants.unlock_cooldown_duration > 0 {
+                PENDING_UNLOCKS.save(
+                    deps.storage,
+                    (info.sender.clone(), lock_id),
+                    &PendingUnlock {
+                        recipient: recipient.clone(),
+                        funds: send.clone(),
+                        claimable_at,
+                    },
+                )?;
+            } else {
+                response = response.add_message(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: vec![send.clone()],
+                });
+            }
+
+            total_unlocked_amount += send.amount;
+
+            // Delete entry from LocksMap
+            to_delete.push((info.sender.clone(), lock_id));
+
+            unlocked_lock_ids.push(lock_id.to_string());
+            unlocked_tokens.push(send.to_string());
+
+            if claim_tributes {
+                lock_votes.extend(get_lock_votes_in_ended_rounds(
+                    deps.as_ref(),
+                    &env,
+                    &constants,
+                    &info.sender,
+                    lock_id,
+                    lock_entry.lock_start,
+                )?);
+            }
+        }
+    }
+
+    // Delete unlocked locks
+    for (addr, lock_id) in to_delete {
+        LOCKS_MAP.remove(deps.storage, (addr.clone(), lock_id));
+        remove_auto_refresh(deps.storage, &addr, lock_id)?;
+        VOTE_DELEGATIONS.remove(deps.storage, (addr.clone(), lock_id));
+        LOCK_OPERATORS.remove(deps.storage, (addr.clone(), lock_id));
+        LOCK_NOTES.remove(deps.storage, (addr, lock_id));
+        remove_lock_from_validator_index(deps.storage, lock_id)?;
+    }
+
+    if !total_unlocked_amount.is_zero() {
+        LOCKED_TOKENS.update(
+            deps.storage,
+            |locked_tokens| -> Result<u128, ContractError> {
+                Ok(locked_tokens - total_unlocked_amount.u128())
+            },
+        )?;
+    }
+
+    // Ask the tribute contract to claim the tributes for the votes of the unlocked locks. The claim is
+    // sent as a SubMsg whose errors are ignored in reply(), so that it can never prevent the unlock.
+    if !lock_votes.is_empty() {
+        match TRIBUTE_CONTRACT.may_load(deps.storage)? {
+            Some(tribute_contract) => {
+                response = response.add_submessage(build_claim_tributes_submsg(
+                    tribute_contract,
+                    info.sender.to_string(),
+                    lock_votes,
+                )?);
+            }
+            None => {
+                response = response.add_attribute("claim_tributes", "skipped_no_tribute_contract");
+            }
+        }
+    }
+
+    Ok(response
+        .add_attribute("unlocked_lock_ids", unlocked_lock_ids.join(", "))
+        .add_attribute("unlocked_tokens", unlocked_tokens.join(", ")))
+}
+
+// ClaimUnlocked():
+//     Validate that the contract isn't paused
+//     Send the tokens of all the pending unlocks of the sender whose cooldown has passed to their recipients
+//     Delete the claimed entries from PENDING_UNLOCKS
+fn claim_unlocked(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    let claimable = PENDING_UNLOCKS
+        .prefix(info.sender.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|entry| match entry {
+            Ok((_, pending_unlock)) => pending_unlock.claimable_at <= env.block.time,
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<(u64, PendingUnlock)>>>()?;
+
+    if claimable.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No pending unlocks can be claimed yet",
+        )));
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "claim_unlocked")
+        .add_attribute("sender", info.sender.to_string());
+
+    let mut claimed_lock_ids = vec![];
+    let mut claimed_tokens = vec![];
+
+    for (lock_id, pending_unlock) in claimable {
+        PENDING_UNLOCKS.remove(deps.storage, (info.sender.clone(), lock_id));
+
+        response = response.add_message(BankMsg::Send {
+            to_address: pending_unlock.recipient.to_string(),
+            amount: vec![pending_unlock.funds.clone()],
+        });
+
+        claimed_lock_ids.push(lock_id.to_string());
+        claimed_tokens.push(pending_unlock.funds.to_string());
+    }
+
+    Ok(response
+        .add_attribute("claimed_lock_ids", claimed_lock_ids.join(", "))
+        .add_attribute("claimed_tokens", claimed_tokens.join(", ")))
+}
+
+// UnlockTokensTo(lock_ids, recipient, confirm_recipient):
+//     Validate that the sender confirmed the recipient
+//     Validate the recipient address, which can't be the Hydro contract itself
+//     Unlock the given locks in the same way as UnlockTokens, sending the tokens to the recipient
+fn unlock_tokens_to(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    lock_ids: Vec<u64>,
+    recipient: String,
+    confirm_recipient: bool,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if !confirm_recipient {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Recipient of the unlocked tokens must be confirmed",
+        )));
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    if recipient == env.contract.address {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Unlocked tokens can't be sent to the Hydro contract",
+        )));
+    }
+
+    unlock_tokens(deps, env, info, Some(lock_ids), false, Some(recipient))
+}
+
+// Returns the votes that the given lock cast in the rounds that have ended since the lock was created.
+// The number of storage reads is bounded by the number of rounds the lock lasted times the number of tranches.
+fn get_lock_votes_in_ended_rounds(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    constants: &Constants,
+    owner: &Addr,
+    lock_id: u64,
+    lock_start: Timestamp,
+) -> StdResult<Vec<LockVote>> {
+    // no round has ended before the first round start
+    if env.block.time < constants.first_round_start {
+        return Ok(vec![]);
+    }
+
+    let current_round_id = compute_current_round_id(env, constants)?;
+    // pre-launch locks could have voted starting from the round 0
+    let start_round_id = if lock_start < constants.first_round_start {
+        0
+    } else {
+        compute_round_id_for_timestamp(constants, lock_start.nanos())?
+    };
+
+    let tranche_ids = TRANCHE_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    let mut lock_votes = vec![];
+    for round_id in start_round_id..current_round_id {
+        for tranche_id in tranche_ids.iter() {
+            if let Some(vote) = VOTE_MAP.may_load(
+                deps.storage,
+                ((round_id, *tranche_id), owner.clone(), lock_id),
+            )? {
+                lock_votes.push(LockVote {
+                    lock_id,
+                    round_id,
+                    tranche_id: *tranche_id,
+                    proposal_id: vote.prop_id,
+                });
+            }
+        }
+    }
+
+    Ok(lock_votes)
+}
+
+// prevent clippy from warning for unused function
+// TODO: reenable this when we enable slashing
+#[allow(dead_code)]
+fn validate_previous_round_vote(
+    deps: &DepsMut<NeutronQuery>,
+    env: &Env,
+    sender: Addr,
+) -> Result<(), ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(env, &constants)?;
+    if current_round_id > 0 {
+        let previous_round_id = current_round_id - 1;
+        for tranche_id in TRANCHE_MAP.keys(deps.storage, None, None, Order::Ascending) {
+            if VOTE_MAP
+                .prefix(((previous_round_id, tranche_id?), sender.clone()))
+                .range(deps.storage, None, None, Order::Ascending)
+                .count()
+                > 0
+            {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "Tokens can not be unlocked, user voted for at least one proposal in previous round",
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Creates a new proposal in the store.
+// It will:
+// * validate that the contract is not paused
+// * validate that the creator of the proposal is on the whitelist
+// Then, it will create the proposal in the specified tranche and in the specified round.
+// If no round_id is specified, the function will use the current round id.
+#[allow(clippy::too_many_arguments)]
+fn create_proposal(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    round_id: Option<u64>,
+    tranche_id: u64,
+    title: String,
+    description: String,
+    deployment_duration: u64,
+    minimum_atom_liquidity_request: Uint128,
+    description_hash: Option<String>,
+    description_uri: Option<String>,
+    max_vote_percentage: Option<Decimal>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    // this is just to initialize the store on the first action in each round
+    initialize_validator_store(deps.storage, current_round_id)?;
+
+    // if no round_id is provided, use the current round
+    let round_id = round_id.unwrap_or(current_round_id);
+
+    if current_round_id > round_id {
+        return Err(ContractError::Std(StdError::generic_err(
+            "cannot create a proposal in a round that ended in the past",
+        )));
+    }
+
+    // validate that the sender is on the whitelist
+    let whitelist = WHITELIST.load(deps.storage)?;
+
+    if !whitelist.contains(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    // check that the tranche with the given id exists
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    let title = title.trim().to_string();
+    let description = description.trim().to_string();
+    validate_proposal_text(&constants, &title, &description)?;
+
+    let hash_only = HASH_ONLY_DESCRIPTION_TRANCHES
+        .may_load(deps.storage, tranche_id)?
+        .unwrap_or(false);
+    let description_reference =
+        validate_description_reference(hash_only, &description, description_hash, description_uri)?;
+
+    // check that the deployment duration is within the allowed range
+    validate_deployment_duration(&constants, deployment_duration)?;
+
+    validate_max_vote_percentage(max_vote_percentage)?;
+
+    // check that the proposal limit for the given round and tranche hasn't been reached
+    if let Some(max_proposals) =
+        get_max_proposals_per_tranche_round(deps.storage, &constants, round_id, tranche_id)?
+    {
+        if get_proposals_count(deps.storage, round_id, tranche_id) >= max_proposals {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Maximum number of proposals ({}) reached for round {} and tranche {}",
+                max_proposals, round_id, tranche_id
+            ))));
+        }
+    }
+
+    let proposal_id = PROP_ID.load(deps.storage)?;
+
+    let proposal = Proposal {
+        round_id,
+        tranche_id,
+        proposal_id,
+        power: Uint128::zero(),
+        percentage: Uint128::zero(),
+        title,
+        description,
+        deployment_duration,
+        minimum_atom_liquidity_request,
+        description_hash: description_reference.as_ref().map(|(hash, _)| hash.clone()),
+        max_vote_percentage,
+    };
+
+    PROP_ID.save(deps.storage, &(proposal_id + 1))?;
+    PROPOSAL_MAP.save(deps.storage, (round_id, tranche_id, proposal_id), &proposal)?;
+    increment_round_proposals_count(deps.storage, round_id)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "create_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("proposal_title", proposal.title)
+        .add_attribute("proposal_description", proposal.description)
+        .add_attribute(
+            "deployment_duration",
+            proposal.deployment_duration.to_string(),
+        )
+        .add_attribute(
+            "minimum_atom_liquidity_request",
+            proposal.minimum_atom_liquidity_request.to_string(),
+        );
+
+    if let Some((description_hash, description_uri)) = description_reference {
+        response = response
+            .add_attribute("proposal_description_hash", description_hash)
+            .add_attribute("proposal_description_uri", description_uri);
+    }
+
+    if let Some(max_vote_percentage) = max_vote_percentage {
+        response = response.add_attribute("max_vote_percentage", max_vote_percentage.to_string());
+    }
+
+    Ok(response)
+}
+
+fn validate_max_vote_percentage(max_vote_percentage: Option<Decimal>) -> Result<(), ContractError> {
+    if let Some(max_vote_percentage) = max_vote_percentage {
+        if max_vote_percentage.is_zero() || max_vote_percentage > Decimal::percent(10000) {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Maximum vote percentage must be greater than 0 and at most 100",
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the maximum number of proposals that can be created in the given round and tranche,
+// or None if the number of proposals isn't limited.
+fn get_max_proposals_per_tranche_round(
+    storage: &dyn Storage,
+    constants: &Constants,
+    round_id: u64,
+    tranche_id: u64,
+) -> StdResult<Option<u64>> {
+    let max_proposals = PROPOSAL_LIMIT_OVERRIDES
+        .may_load(storage, (round_id, tranche_id))?
+        .unwrap_or(constants.max_proposals_per_tranche_round);
+
+    Ok(match max_proposals {
+        0 => None,
+        max_proposals => Some(max_proposals),
+    })
+}
+
+fn get_proposals_count(storage: &dyn Storage, round_id: u64, tranche_id: u64) -> u64 {
+    PROPOSAL_MAP
+        .prefix((round_id, tranche_id))
+        .keys_raw(storage, None, None, Order::Ascending)
+        .count() as u64
+}
+
+fn validate_deployment_duration(
+    constants: &Constants,
+    deployment_duration: u64,
+) -> Result<(), ContractError> {
+    if deployment_duration < MIN_DEPLOYMENT_DURATION
+        || deployment_duration > constants.max_deployment_duration
+    {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Invalid deployment duration: {}. Must be between {} and {} rounds.",
+            deployment_duration, MIN_DEPLOYMENT_DURATION, constants.max_deployment_duration,
+        ))));
+    }
+
+    Ok(())
+}
+
+// Validates that the proposal title and description don't exceed the configured lengths, and don't hold
+// control characters that could break the rendering of proposals. Line feeds and tabs are still allowed
+// in descriptions.
+fn validate_proposal_text(
+    constants: &Constants,
+    title: &str,
+    description: &str,
+) -> Result<(), ContractError> {
+    if constants.max_proposal_title_length != 0
+        && title.chars().count() as u64 > constants.max_proposal_title_length
+    {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Proposal title must not be longer than {} characters.",
+            constants.max_proposal_title_length
+        ))));
+    }
+
+    if constants.max_proposal_description_length != 0
+        && description.chars().count() as u64 > constants.max_proposal_description_length
+    {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Proposal description must not be longer than {} characters.",
+            constants.max_proposal_description_length
+        ))));
+    }
+
+    if title.chars().any(|c| c.is_control()) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Proposal title must not contain control characters.",
+        )));
+    }
+
+    if description
+        .chars()
+        .any(|c| c.is_control() && c != '\n' && c != '\t')
+    {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Proposal description must not contain control characters other than line feeds and tabs.",
+        )));
+    }
+
+    Ok(())
+}
+
+// Validates the reference to the off-chain proposal description. In tranches that use hash-only
+// descriptions, both the hash and the URI of the full description must be provided, and the
+// on-chain description is limited to a short summary. Returns the normalized (hash, uri) pair,
+// or None for tranches in which full descriptions are stored on-chain.
+fn validate_description_reference(
+    hash_only: bool,
+    description: &str,
+    description_hash: Option<String>,
+    description_uri: Option<String>,
+) -> Result<Option<(String, String)>, ContractError> {
+    if !hash_only {
+        if description_hash.is_some() || description_uri.is_some() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Description hash and URI can only be provided for tranches that use hash-only descriptions.",
+            )));
+        }
+
+        return Ok(None);
+    }
+
+    let (description_hash, description_uri) = match (description_hash, description_uri) {
+        (Some(hash), Some(uri)) => (hash.trim().to_lowercase(), uri.trim().to_string()),
+        _ => {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Description hash and URI must be provided for tranches that use hash-only descriptions.",
+            )))
+        }
+    };
+
+    // the hash is expected to be a hex encoded SHA-256 digest of the full description
+    if description_hash.len() != 64 || !description_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Description hash must be a hex encoded SHA-256 hash.",
+        )));
+    }
+
+    if description_uri.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Description URI must not be empty.",
+        )));
+    }
+
+    if description.chars().count() > MAX_DESCRIPTION_SUMMARY_LENGTH {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Description summary must not be longer than {} characters.",
+            MAX_DESCRIPTION_SUMMARY_LENGTH
+        ))));
+    }
+
+    Ok(Some((description_hash, description_uri)))
+}
+
+// Scales the raw power by the factor from the lock power schedule that corresponds to the remaining lockup time.
+// By default, the factor changes in steps, i.e. it is the factor of the first schedule entry whose lock time is
+// not shorter than the remaining lockup time. If interpolate_lock_power is set, the factor is instead linearly
+// interpolated between the factors of the two schedule entries surrounding the remaining lockup time, so that
+// the power decreases continuously as the lockup time runs out, instead of dropping at the schedule boundaries.
+pub fn scale_lockup_power(
+    round_lock_power_schedule: &RoundLockPowerSchedule,
+    lock_epoch_length: u64,
+    lockup_time: u64,
+    raw_power: Uint128,
+    interpolate_lock_power: bool,
+) -> Uint128 {
+    let mut previous_entry: Option<(u64, Decimal)> = None;
+
+    for entry in round_lock_power_schedule.round_lock_power_schedule.iter() {
+        let needed_lock_time = entry.locked_rounds * lock_epoch_length;
+        if lockup_time <= needed_lock_time {
+            let power_scaling_factor = match previous_entry {
+                Some((previous_lock_time, previous_factor))
+                    if interpolate_lock_power && entry.power_scaling_factor > previous_factor =>
+                {
+                    previous_factor
+                        + (entry.power_scaling_factor - previous_factor)
+                            * Decimal::from_ratio(
+                                lockup_time - previous_lock_time,
+                                needed_lock_time - previous_lock_time,
+                            )
+                }
+                _ => entry.power_scaling_factor,
+            };
+
+            let power =
+                power_scaling_factor.saturating_mul(Decimal::from_ratio(raw_power, Uint128::one()));
+            return power.to_uint_floor();
+        }
+
+        previous_entry = Some((needed_lock_time, entry.power_scaling_factor));
+    }
+
+    // if lockup time is longer than the longest lock time, return the maximum power
+    let largest_multiplier = round_lock_power_schedule
+        .round_lock_power_schedule
+        .last()
+        .unwrap()
+        .power_scaling_factor;
+    largest_multiplier
+        .saturating_mul(Decimal::from_ratio(raw_power, Uint128::one()))
+        .to_uint_floor()
+}
+
+fn vote(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    tranche_id: u64,
+    proposals_votes: Vec<ProposalToLockups>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    cast_votes(deps, env, info.sender, tranche_id, proposals_votes)
+}
+
+// VoteOnBehalf(owner, tranche_id, proposals_votes):
+//     Validate that the sender is the voting executor of the owner, or that all the given lockups
+//     of the owner are delegated to the sender
+//     Vote with the lockups in the same way as the owner would via Vote
+//     If the sender is the voting executor, validate that the lockups of the owner didn't end up voting
+//     for more proposals in the current round than the executor is allowed to
+fn vote_on_behalf(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    tranche_id: u64,
+    proposals_votes: Vec<ProposalToLockups>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    let owner = deps.api.addr_validate(&owner)?;
+
+    let voting_executor = VOTING_EXECUTORS
+        .may_load(deps.storage, owner.clone())?
+        .filter(|voting_executor| voting_executor.executor == info.sender);
+
+    if let Some(voting_executor) = voting_executor {
+        let round_id = compute_current_round_id(&env, &constants)?;
+        let voted_proposals = get_voted_proposals_count_after_votes(
+            deps.storage,
+            &owner,
+            round_id,
+            tranche_id,
+            &proposals_votes,
+        )?;
+        if voted_proposals > voting_executor.max_proposals_per_round {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Voting executor {} can vote for at most {} proposals per round",
+                info.sender, voting_executor.max_proposals_per_round
+            ))));
+        }
+
+        return Ok(cast_votes(deps, env, owner, tranche_id, proposals_votes)?
+            .add_attribute("executor", info.sender));
+    }
+
+    validate_voting_rights_delegated(
+        deps.storage,
+        &owner,
+        &info.sender,
+        proposals_votes
+            .iter()
+            .flat_map(|proposal_votes| proposal_votes.lock_ids.iter()),
+    )?;
+
+    Ok(cast_votes(deps, env, owner, tranche_id, proposals_votes)?
+        .add_attribute("delegate", info.sender))
+}
+
+// Validates that the voting rights of each of the given lockups of the owner are delegated to the sender,
+// or that the sender is the operator of the lockup.
+fn validate_voting_rights_delegated<'a>(
+    storage: &dyn Storage,
+    owner: &Addr,
+    sender: &Addr,
+    lock_ids: impl Iterator<Item = &'a u64>,
+) -> Result<(), ContractError> {
+    for lock_id in lock_ids {
+        let delegate = VOTE_DELEGATIONS.may_load(storage, (owner.clone(), *lock_id))?;
+        if delegate.as_ref() != Some(sender) && !is_lock_operator(storage, owner, *lock_id, sender)?
+        {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Voting rights of lock {} are not delegated to {}",
+                lock_id, sender
+            ))));
+        }
+    }
+
+    Ok(())
+}
+
+// RelayedVote(vote_intent, pubkey, signature):
+//     Validate that the vote intent was signed by the voter, that it is for the current round, that it
+//     hasn't expired and that its nonce is the next relayed vote nonce of the voter
+//     Increment the relayed vote nonce of the voter
+//     Vote with the lockups of the voter in the same way as the voter would via Vote
+fn relayed_vote(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    vote_intent: VoteIntent,
+    pubkey: Binary,
+    signature: Binary,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    let voter =
+        verify_vote_intent_signature(&deps.as_ref(), &env, &vote_intent, &pubkey, &signature)?;
+
+    let round_id = compute_current_round_id(&env, &constants)?;
+    if vote_intent.round_id != round_id {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Vote intent is for round {}, but the current round is {}",
+            vote_intent.round_id, round_id
+        ))));
+    }
+
+    if env.block.time >= vote_intent.expiry {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Vote intent has expired",
+        )));
+    }
+
+    let next_nonce = RELAYED_VOTE_NONCES
+        .may_load(deps.storage, voter.clone())?
+        .unwrap_or_default();
+    if vote_intent.nonce != next_nonce {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Invalid vote intent nonce {}; expected nonce {}",
+            vote_intent.nonce, next_nonce
+        ))));
+    }
+    RELAYED_VOTE_NONCES.save(deps.storage, voter.clone(), &(next_nonce + 1))?;
+
+    Ok(cast_votes(
+        deps,
+        env,
+        voter,
+        vote_intent.tranche_id,
+        vote_intent.proposals_votes,
+    )?
+    .add_attribute("relayer", info.sender)
+    .add_attribute("nonce", vote_intent.nonce.to_string()))
+}
+
+// Abstain(tranche_id, lock_ids):
+//     Abstain with the given lockups of the sender
+fn abstain(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    tranche_id: u64,
+    lock_ids: Vec<u64>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    record_abstentions(deps, env, info.sender, tranche_id, lock_ids)
+}
+
+// AbstainOnBehalf(owner, tranche_id, lock_ids):
+//     Validate that the sender is the voting executor of the owner, or that all the given lockups
+//     of the owner are delegated to the sender or operated by the sender
+//     Abstain with the lockups in the same way as the owner would via Abstain
+fn abstain_on_behalf(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    tranche_id: u64,
+    lock_ids: Vec<u64>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    let owner = deps.api.addr_validate(&owner)?;
+
+    let is_voting_executor = VOTING_EXECUTORS
+        .may_load(deps.storage, owner.clone())?
+        .is_some_and(|voting_executor| voting_executor.executor == info.sender);
+
+    if is_voting_executor {
+        return Ok(record_abstentions(deps, env, owner, tranche_id, lock_ids)?
+            .add_attribute("executor", info.sender));
+    }
+
+    validate_voting_rights_delegated(deps.storage, &owner, &info.sender, lock_ids.iter())?;
+
+    Ok(record_abstentions(deps, env, owner, tranche_id, lock_ids)?
+        .add_attribute("delegate", info.sender))
+}
+
+// Validates that the given lockups of the owner could vote in the tranche in the current round, but didn't,
+// and records that they abstain in the tranche in the current round. The lockups can still vote later in the
+// round, which removes the abstention.
+fn record_abstentions(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    lock_owner: Addr,
+    tranche_id: u64,
+    lock_ids: Vec<u64>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    if lock_ids.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No lock_ids provided",
+        )));
+    }
+
+    let round_id = compute_current_round_id(&env, &constants)?;
+    initialize_validator_store(deps.storage, round_id)?;
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    let round_end = compute_round_end(&constants, round_id)?;
+
+    for lock_id in lock_ids.iter() {
+        let lock_entry = LOCKS_MAP.load(deps.storage, (lock_owner.clone(), *lock_id))?;
+
+        let key = ((round_id, tranche_id), lock_owner.clone(), *lock_id);
+        if VOTE_MAP.has(deps.storage, key.clone()) {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Lock {} already voted in round {} and tranche {}",
+                lock_id, round_id, tranche_id
+            ))));
+        }
+
+        // the lockups that still can't vote due to their vote in a previous round have no vote to reserve
+        if let Some(voting_allowed_round) =
+            VOTING_ALLOWED_ROUND.may_load(deps.storage, (tranche_id, *lock_id))?
+        {
+            if voting_allowed_round > round_id
+                && !lock_voted_for_invalidated_proposal(
+                    deps.storage,
+                    &constants,
+                    &lock_owner,
+                    round_id,
+                    tranche_id,
+                    *lock_id,
+                    voting_allowed_round,
+                )?
+            {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Not allowed to vote with lock_id {} in tranche {}. Cannot vote again with this lock_id until round {}.",
+                    lock_id, tranche_id, voting_allowed_round
+                ))));
+            }
+        }
+
+        let has_voting_power = validate_denom(
+            deps.as_ref(),
+            env.clone(),
+            &constants,
+            lock_entry.funds.denom.clone(),
+        )
+        .is_ok()
+            && !get_lock_time_weighted_shares(
+                &constants.round_lock_power_schedule,
+                round_end,
+                lock_entry,
+                constants.lock_epoch_length,
+                constants.interpolate_lock_power,
+            )
+            .is_zero();
+        if !has_voting_power {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Lock {} has no voting power in round {}",
+                lock_id, round_id
+            ))));
+        }
+
+        ABSTENTIONS.save(deps.storage, key, &env.block.time)?;
+    }
+
+    let lock_ids: Vec<String> = lock_ids.iter().map(|lock_id| lock_id.to_string()).collect();
+
+    Ok(Response::new()
+        .add_attribute("action", "abstain")
+        .add_attribute("sender", lock_owner)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("lock_ids", lock_ids.join(","))
+        .add_attribute("vote_reserved", "true"))
+}
+
+// UnvoteAll(tranche_id, owner):
+//     If the owner is given and isn't the sender, validate that the sender is the voting executor of the owner,
+//     or that the voting rights of all the voted lockups of the owner are delegated to the sender
+//     Load the votes of the owner's lockups in the given tranche in the current round
+//     Remove the shares of each vote from its proposal, delete the vote and allow the lockup to vote again
+//     Update the power of each affected proposal once, after all the votes are removed
+fn unvote_all(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    tranche_id: u64,
+    owner: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    let round_id = compute_current_round_id(&env, &constants)?;
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    let owner = match owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender.clone(),
+    };
+
+    let votes = VOTE_MAP
+        .prefix(((round_id, tranche_id), owner.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(MAX_LOCK_ENTRIES)
+        .collect::<StdResult<Vec<(u64, Vote)>>>()?;
+
+    if votes.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "No votes to remove in round {} and tranche {}",
+            round_id, tranche_id
+        ))));
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "unvote_all")
+        .add_attribute("sender", owner.to_string())
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string());
+
+    if owner != info.sender {
+        let is_voting_executor = VOTING_EXECUTORS
+            .may_load(deps.storage, owner.clone())?
+            .is_some_and(|voting_executor| voting_executor.executor == info.sender);
+
+        if is_voting_executor {
+            response = response.add_attribute("executor", info.sender.to_string());
+        } else {
+            validate_voting_rights_delegated(
+                deps.storage,
+                &owner,
+                &info.sender,
+                votes.iter().map(|(lock_id, _)| lock_id),
+            )?;
+            response = response.add_attribute("delegate", info.sender.to_string());
+        }
+    }
+
+    let mut affected_proposals = BTreeSet::new();
+    for (lock_id, vote) in votes {
+        remove_vote_shares_from_proposal(
+            deps.storage,
+            round_id,
+            vote.prop_id,
+            vote.time_weighted_shares.0,
+            vote.time_weighted_shares.1,
+            vote.tribute_weight_multiplier,
+        )?;
+
+        VOTE_MAP.remove(
+            deps.storage,
+            ((round_id, tranche_id), owner.clone(), lock_id),
+        );
+        PROPOSAL_VOTERS.remove(
+            deps.storage,
+            ((round_id, tranche_id), vote.prop_id, lock_id),
+        );
+        VOTING_ALLOWED_ROUND.remove(deps.storage, (tranche_id, lock_id));
+
+        affected_proposals.insert(vote.prop_id);
+        response = response.add_attribute(
+            format!("lock_id_{}_old_proposal_id", lock_id),
+            vote.prop_id.to_string(),
+        );
+    }
+
+    for proposal_id in affected_proposals.iter() {
+        let proposal = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, *proposal_id))?;
+        update_proposal_and_props_by_score_maps(deps.storage, round_id, tranche_id, &proposal)?;
+    }
+
+    if get_vote_power_cap(deps.storage, round_id)?.is_some() {
+        update_owner_vote_power(deps.storage, round_id, tranche_id, &owner)?;
+    }
+
+    remove_round_voter(deps.storage, round_id, &owner)?;
+
+    let proposal_ids: Vec<String> = affected_proposals
+        .iter()
+        .map(|proposal_id| proposal_id.to_string())
+        .collect();
+
+    Ok(response.add_attribute("proposal_ids", proposal_ids.join(",")))
+}
+
+// DelegateVotingRights(lock_ids, delegate):
+//     Validate that the sender owns the given lockups and that the delegate is not the sender
+//     Appoint the delegate to vote with the lockups, replacing any previous delegate
+fn delegate_voting_rights(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    lock_ids: Vec<u64>,
+    delegate: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    if lock_ids.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No lock_ids provided",
+        )));
+    }
+
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Can't delegate voting rights to the lock owner",
+        )));
+    }
+
+    for lock_id in lock_ids.iter() {
+        LOCKS_MAP.load(deps.storage, (info.sender.clone(), *lock_id))?;
+        VOTE_DELEGATIONS.save(deps.storage, (info.sender.clone(), *lock_id), &delegate)?;
+    }
+
+    let lock_ids: Vec<String> = lock_ids.iter().map(|lock_id| lock_id.to_string()).collect();
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate_voting_rights")
+        .add_attribute("sender", info.sender)
+        .add_attribute("delegate", delegate)
+        .add_attribute("lock_ids", lock_ids.join(",")))
+}
+
+// SetVotingExecutor(executor, max_proposals_per_round):
+//     Validate that the executor is not the sender and that it may vote for at least one proposal
+//     Register the executor that can vote with all the lockups of the sender, replacing any previous one
+fn set_voting_executor(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    executor: String,
+    max_proposals_per_round: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    let executor = deps.api.addr_validate(&executor)?;
+    if executor == info.sender {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Lock owner can't be their own voting executor",
+        )));
+    }
+
+    if max_proposals_per_round == 0 {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Voting executor must be allowed to vote for at least one proposal per round",
+        )));
+    }
+
+    VOTING_EXECUTORS.save(
+        deps.storage,
+        info.sender.clone(),
+        &VotingExecutor {
+            executor: executor.clone(),
+            max_proposals_per_round,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_voting_executor")
+        .add_attribute("sender", info.sender)
+        .add_attribute("executor", executor)
+        .add_attribute(
+            "max_proposals_per_round",
+            max_proposals_per_round.to_string(),
+        ))
+}
+
+// RemoveVotingExecutor():
+//     Remove the voting executor of the sender, if there is one
+fn remove_voting_executor(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    VOTING_EXECUTORS.remove(deps.storage, info.sender.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_voting_executor")
+        .add_attribute("sender", info.sender))
+}
+
+// Returns the number of distinct proposals, across all tranches, that the lockups of the given owner would
+// vote for in the given round once the given votes are cast. The existing votes of the lockups that are
+// voting again in the given tranche are replaced by their new votes.
+fn get_voted_proposals_count_after_votes(
+    storage: &dyn Storage,
+    owner: &Addr,
+    round_id: u64,
+    voting_tranche_id: u64,
+    proposals_votes: &[ProposalToLockups],
+) -> StdResult<u64> {
+    let revoting_lock_ids: HashSet<u64> = proposals_votes
+        .iter()
+        .flat_map(|proposal_votes| proposal_votes.lock_ids.iter().cloned())
+        .collect();
+
+    let mut voted_proposals: HashSet<(u64, u64)> = proposals_votes
+        .iter()
+        .map(|proposal_votes| (voting_tranche_id, proposal_votes.proposal_id))
+        .collect();
+
+    for tranche_id in TRANCHE_MAP.keys(storage, None, None, Order::Ascending) {
+        let tranche_id = tranche_id?;
+        for vote in VOTE_MAP
+            .prefix(((round_id, tranche_id), owner.clone()))
+            .range(storage, None, None, Order::Ascending)
+        {
+            let (lock_id, vote) = vote?;
+            if tranche_id == voting_tranche_id && revoting_lock_ids.contains(&lock_id) {
+                continue;
+            }
+
+            voted_proposals.insert((tranche_id, vote.prop_id));
+        }
+    }
+
+    Ok(voted_proposals.len() as u64)
+}
+
+// RevokeDelegation(lock_ids):
+//     Remove the delegates of the given lockups of the sender, if there are any
+fn revoke_delegation(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    lock_ids: Vec<u64>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    for lock_id in lock_ids.iter() {
+        VOTE_DELEGATIONS.remove(deps.storage, (info.sender.clone(), *lock_id));
+    }
+
+    let lock_ids: Vec<String> = lock_ids.iter().map(|lock_id| lock_id.to_string()).collect();
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_delegation")
+        .add_attribute("sender", info.sender)
+        .add_attribute("lock_ids", lock_ids.join(",")))
+}
+
+fn cast_votes(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    lock_owner: Addr,
+    tranche_id: u64,
+    proposals_votes: Vec<ProposalToLockups>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    // This voting system is designed to allow for an unlimited number of proposals and an unlimited number of votes
+    // to be created, without being vulnerable to DOS. A naive implementation, where all votes or all proposals were iterated
+    // at the end of the round could be DOSed by creating a large number of votes or proposals. This is not a problem
+    // for this implementation, but this leads to some subtlety in the implementation.
+    // I will explain the overall principle here:
+    // - The information on which proposal is winning is updated each time someone votes, instead of being calculated at the end of the round.
+    // - This information is stored in a map called PROPS_BY_SCORE, which maps the score of a proposal to the proposal id.
+    // - At the end of the round, a single access to PROPS_BY_SCORE is made to get the winning proposal.
+    // - To enable switching votes (and for other stuff too), we store the vote in VOTE_MAP.
+    // - When a user votes the second time in a round, the information about their previous vote from VOTE_MAP is used to reverse the effect of their previous vote.
+    // - This leads to slightly higher gas costs for each vote, in exchange for a much lower gas cost at the end of the round.
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    let round_id = compute_current_round_id(&env, &constants)?;
+    // voting can never be the first action in a round (since one can only vote on proposals in the current round, and a proposal must be created first)
+    // however, to be safe, we initialize the validator store here, since this is more robust in case we change something about voting later
+    initialize_validator_store(deps.storage, round_id)?;
+
+    // check that the tranche with the given id exists
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    // compute the round end
+    let round_end = compute_round_end(&constants, round_id)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "vote")
+        .add_attribute("sender", lock_owner.to_string());
+
+    // Check for duplicate proposal and lock IDs
+    let mut proposal_ids = HashSet::new();
+    let mut lock_ids = HashSet::new();
+
+    for proposal_votes in proposals_votes.iter() {
+        if !proposal_ids.insert(proposal_votes.proposal_id) {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Duplicate proposal ID {} provided",
+                proposal_votes.proposal_id
+            ))));
+        }
+
+        if INVALIDATED_PROPOSALS.has(
+            deps.storage,
+            (round_id, tranche_id, proposal_votes.proposal_id),
+        ) {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Proposal {} in round {} and tranche {} has been invalidated and can't be voted for",
+                proposal_votes.proposal_id, round_id, tranche_id
+            ))));
+        }
+
+        if proposal_votes.lock_ids.is_empty() {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "No lock IDs provided to vote for proposal ID {}",
+                proposal_votes.proposal_id
+            ))));
+        }
+
+        for lock_id in proposal_votes.lock_ids.iter() {
+            if !lock_ids.insert(*lock_id) {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Duplicate lock ID {} provided",
+                    lock_id
+                ))));
+            }
+        }
+    }
+
+    if proposal_ids.is_empty() || lock_ids.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Must provide at least one proposal and lockup to vote",
+        )));
+    }
+
+    // TODO: optimize so that all locks that voted for the same proposal are removed in single execution
+    for lock_id in lock_ids {
+        // Get any existing vote for this sender and reverse it- this may be a vote for a different proposal (if they are switching their vote),
+        // or it may be a vote for the same proposal (if they have increased their power by locking more and want to update their vote).
+        // TODO: this could be made more gas-efficient by using a separate path with fewer writes if the vote is for the same proposal
+        let vote = VOTE_MAP.may_load(
+            deps.storage,
+            ((round_id, tranche_id), lock_owner.clone(), lock_id),
+        )?;
+        match vote {
+            Some(vote) => {
+                // Load the proposal in the vote
+                let mut proposal =
+                    PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, vote.prop_id))?;
+
+                // Remove proposal's old power in PROPS_BY_SCORE
+                PROPS_BY_SCORE.remove(
+                    deps.storage,
+                    get_props_by_score_key(round_id, tranche_id, proposal.power, vote.prop_id),
+                );
+
+                remove_vote_shares_from_proposal(
+                    deps.storage,
+                    round_id,
+                    vote.prop_id,
+                    vote.time_weighted_shares.0,
+                    vote.time_weighted_shares.1,
+                    vote.tribute_weight_multiplier,
+                )?;
+
+                // save the new power into the proposal
+                let old_power = proposal.power;
+                let total_power =
+                    get_total_power_for_proposal(deps.as_ref().storage, vote.prop_id)?;
+                proposal.power = total_power.to_uint_ceil(); // TODO: decide whether we need to round or represent as decimals
+
+                update_round_tranche_voted_power(
+                    deps.storage,
+                    round_id,
+                    tranche_id,
+                    old_power,
+                    proposal.power,
+                )?;
+
+                // Save the proposal
+                PROPOSAL_MAP.save(
+                    deps.storage,
+                    (round_id, tranche_id, vote.prop_id),
+                    &proposal,
+                )?;
+
+                // Add proposal's new power in PROPS_BY_SCORE, unless the proposal was invalidated
+                if proposal.power > Uint128::zero()
+                    && !INVALIDATED_PROPOSALS
+                        .has(deps.storage, (round_id, tranche_id, vote.prop_id))
+                {
+                    PROPS_BY_SCORE.save(
+                        deps.storage,
+                        get_props_by_score_key(round_id, tranche_id, proposal.power, vote.prop_id),
+                        &vote.prop_id,
+                    )?;
+                }
+
+                // Delete vote
+                VOTE_MAP.remove(
+                    deps.storage,
+                    ((round_id, tranche_id), lock_owner.clone(), lock_id),
+                );
+                PROPOSAL_VOTERS.remove(
+                    deps.storage,
+                    ((round_id, tranche_id), vote.prop_id, lock_id),
+                );
+
+                // Delete voting round allowed info
+                VOTING_ALLOWED_ROUND.remove(deps.storage, (tranche_id, lock_id));
+
+                response = response.add_attribute(
+                    format!("lock_id_{}_old_proposal_id", lock_id),
+                    vote.prop_id.to_string(),
+                );
+            }
+            None => {
+                // If user didn't yet vote with the given lock in the given round and tranche, check
+                // if they voted in previous rounds for some proposal that spans multiple rounds.
+                // This means that users can change their vote during a round, because we don't
+                // check this if users already voted in the current round.
+                let voting_allowed_round =
+                    VOTING_ALLOWED_ROUND.may_load(deps.storage, (tranche_id, lock_id))?;
+
+                if let Some(voting_allowed_round) = voting_allowed_round {
+                    if voting_allowed_round > round_id
+                        && !lock_voted_for_invalidated_proposal(
+                            deps.storage,
+                            &constants,
+                            &lock_owner,
+                            round_id,
+                            tranche_id,
+                            lock_id,
+                            voting_allowed_round,
+                        )?
+                    {
+                        return Err(ContractError::Std(
+                        StdError::generic_err(format!(
+                            "Not allowed to vote with lock_id {} in tranche {}. Cannot vote again with this lock_id until round {}.",
+                            lock_id, tranche_id, voting_allowed_round))));
+                    }
+                }
+            }
+        };
+    }
+
+    let lock_epoch_length = constants.lock_epoch_length;
+    let mut voted_proposals = vec![];
+    let mut locks_voted = vec![];
+    let mut locks_skipped = vec![];
+
+    for proposal_to_lockups in proposals_votes {
+        let proposal_id = proposal_to_lockups.proposal_id;
+
+        // TODO: optimize so that proposal related stores are updated only once
+        for lock_id in proposal_to_lockups.lock_ids {
+            // If any of the lock_ids doesn't exist, or it belongs to a different user
+            // then error out and revert any changes that were made until now.
+            let lock_entry = LOCKS_MAP.load(deps.storage, (lock_owner.clone(), lock_id))?;
+
+            // get the validator from the denom
+            let validator = match validate_denom(
+                deps.as_ref(),
+                env.clone(),
+                &constants,
+                lock_entry.clone().funds.denom,
+            ) {
+                Ok(validator) => validator,
+                Err(_) => {
+                    deps.api.debug(&
+                        format!(
+                            "Denom {} is not a valid validator denom; validator might not be in the current set of top validators by delegation",
+                            lock_entry.funds.denom
+                        ));
+
+                    // skip this lock entry, since the locked shares do not belong to a validator that we want to take into account
+                    locks_skipped.push(lock_entry.lock_id);
+                    continue;
+                }
+            };
+
+            let scaled_shares = Decimal::from_ratio(
+                get_lock_time_weighted_shares(
+                    &constants.round_lock_power_schedule,
+                    round_end,
+                    lock_entry.clone(),
+                    lock_epoch_length,
+                    constants.interpolate_lock_power,
+                ),
+                Uint128::one(),
+            );
+
+            // skip the lock entries that give zero voting power
+            if scaled_shares.is_zero() {
+                locks_skipped.push(lock_entry.lock_id);
+                continue;
+            }
+
+            let proposal = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+
+            // skip lock entries that don't span long enough to be allowed to vote for this proposal
+            if !can_lock_vote_for_proposal(round_id, &constants, &lock_entry, &proposal)? {
+                locks_skipped.push(lock_entry.lock_id);
+                continue;
+            }
+
+            let tribute_weight_multiplier = get_tribute_weight_multiplier(
+                deps.storage,
+                tranche_id,
+                round_id,
+                round_end,
+                &lock_entry,
+            )?;
+
+            // add the validator shares to the proposal
+            add_vote_shares_to_proposal(
+                deps.storage,
+                round_id,
+                proposal_id,
+                validator.to_string(),
+                scaled_shares,
+                tribute_weight_multiplier,
+            )?;
+
+            // update the proposal in the proposal map, as well as the props by score map
+            update_proposal_and_props_by_score_maps(deps.storage, round_id, tranche_id, &proposal)?;
+
+            // Create vote in Votemap
+            let vote = Vote {
+                prop_id: proposal_id,
+                time_weighted_shares: (validator, scaled_shares),
+                tribute_weight_multiplier,
+            };
+            VOTE_MAP.save(
+                deps.storage,
+                ((round_id, tranche_id), lock_owner.clone(), lock_id),
+                &vote,
+            )?;
+            PROPOSAL_VOTERS.save(
+                deps.storage,
+                ((round_id, tranche_id), proposal_id, lock_id),
+                &lock_owner,
+            )?;
+
+            let voting_allowed_round = round_id + proposal.deployment_duration;
+            VOTING_ALLOWED_ROUND.save(
+                deps.storage,
+                (tranche_id, lock_id),
+                &voting_allowed_round,
+            )?;
+
+            // voting uses up the vote that the lockup reserved by abstaining, if it did
+            let abstention_key = ((round_id, tranche_id), lock_owner.clone(), lock_id);
+            if ABSTENTIONS.has(deps.storage, abstention_key.clone()) {
+                ABSTENTIONS.remove(deps.storage, abstention_key);
+            }
+
+            locks_voted.push(lock_entry.lock_id);
+        }
+
+        voted_proposals.push(proposal_id);
+    }
+
+    if !locks_voted.is_empty() {
+        record_round_voter(deps.storage, round_id, &lock_owner)?;
+    } else {
+        // the previous votes of the locks were removed, even if none of them could vote again
+        remove_round_voter(deps.storage, round_id, &lock_owner)?;
+    }
+
+    if is_quadratic_power_scaling_enabled(deps.storage, tranche_id, round_id)? {
+        apply_quadratic_power_scaling(&mut deps, &constants, round_id, tranche_id, &lock_owner)?;
+    }
+
+    if get_vote_power_cap(deps.storage, round_id)?.is_some() {
+        update_owner_vote_power(deps.storage, round_id, tranche_id, &lock_owner)?;
+    }
+
+    let to_string = |input: &Vec<u64>| {
+        input
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+
+    Ok(response
+        .add_attribute("proposal_id", to_string(&voted_proposals))
+        .add_attribute("locks_voted", to_string(&locks_voted))
+        .add_attribute("locks_skipped", to_string(&locks_skipped)))
+}
+
+// Checks whether the vote that set the VOTING_ALLOWED_ROUND of the given lock was cast for a proposal that
+// got invalidated afterwards, in which case the lock is allowed to vote again right away. That vote was cast
+// in a round that is at most max_deployment_duration rounds before the voting allowed round, so only those
+// rounds need to be checked.
+fn lock_voted_for_invalidated_proposal(
+    storage: &dyn Storage,
+    constants: &Constants,
+    sender: &Addr,
+    round_id: u64,
+    tranche_id: u64,
+    lock_id: u64,
+    voting_allowed_round: u64,
+) -> StdResult<bool> {
+    let first_round_id = voting_allowed_round.saturating_sub(constants.max_deployment_duration);
+
+    for prev_round_id in (first_round_id..round_id).rev() {
+        if let Some(vote) = VOTE_MAP.may_load(
+            storage,
+            ((prev_round_id, tranche_id), sender.clone(), lock_id),
+        )? {
+            return Ok(
+                INVALIDATED_PROPOSALS.has(storage, (prev_round_id, tranche_id, vote.prop_id))
+            );
+        }
+    }
+
+    Ok(false)
+}
+
+// Returns the time-weighted amount of shares locked in the given lock entry in a round with the given end time,
+// and using the given lock epoch length.
+pub fn get_lock_time_weighted_shares(
+    round_lock_power_schedule: &RoundLockPowerSchedule,
+    round_end: Timestamp,
+    lock_entry: LockEntry,
+    lock_epoch_length: u64,
+    interpolate_lock_power: bool,
+) -> Uint128 {
+    if round_end.nanos() > lock_entry.lock_end.nanos() {
+        return Uint128::zero();
+    }
+    let lockup_length = lock_entry.lock_end.nanos() - round_end.nanos();
+    scale_lockup_power(
+        round_lock_power_schedule,
+        lock_epoch_length,
+        lockup_length,
+        lock_entry.funds.amount,
+        interpolate_lock_power,
+    )
+}
+
+// Returns the power of the lock at the given time within the round that ends at round_end.
+// If the interpolate_lock_power is set, the power decreases linearly over the round, from the power
+// the lock has at the start of the round to the one it has at the end of the round, so that it doesn't
+// drop at the round boundaries. Otherwise, the power stays at its round end value for the whole round.
+// The votes are cast with the round end power, since that is when the round is tallied, so the interpolated
+// power is only reported by the queries, next to the round end power.
+pub fn get_lock_time_weighted_shares_at_time(
+    constants: &Constants,
+    round_end: Timestamp,
+    lock_entry: LockEntry,
+    time: Timestamp,
+) -> Uint128 {
+    let end_power = get_lock_time_weighted_shares(
+        &constants.round_lock_power_schedule,
+        round_end,
+        lock_entry.clone(),
+        constants.lock_epoch_length,
+        constants.interpolate_lock_power,
+    );
+
+    // locks that expire before the round ends have no power in that round
+    if !constants.interpolate_lock_power || end_power.is_zero() {
+        return end_power;
+    }
+
+    let start_power = get_lock_time_weighted_shares(
+        &constants.round_lock_power_schedule,
+        round_end.minus_nanos(constants.round_length),
+        lock_entry,
+        constants.lock_epoch_length,
+        constants.interpolate_lock_power,
+    );
+
+    interpolate_within_round(
+        constants,
+        round_end,
+        time,
+        Decimal::from_ratio(start_power, Uint128::one()),
+        Decimal::from_ratio(end_power, Uint128::one()),
+    )
+    .to_uint_floor()
+}
+
+// Linearly interpolates between the value at the start and the value at the end of the round that ends
+// at round_end. Times outside of the round are clamped to its start or end.
+pub fn interpolate_within_round(
+    constants: &Constants,
+    round_end: Timestamp,
+    time: Timestamp,
+    start_value: Decimal,
+    end_value: Decimal,
+) -> Decimal {
+    let round_start = round_end.minus_nanos(constants.round_length);
+    let time = time.clamp(round_start, round_end);
+    let remaining = Decimal::from_ratio(round_end.nanos() - time.nanos(), constants.round_length);
+
+    if start_value >= end_value {
+        end_value + (start_value - end_value) * remaining
+    } else {
+        end_value - (end_value - start_value) * remaining
+    }
+}
+
+// Adds a new account address to the whitelist.
+fn add_to_whitelist(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    // Add address to whitelist
+    let mut whitelist = WHITELIST.load(deps.storage)?;
+    let whitelist_account_addr = deps.api.addr_validate(&address)?;
+
+    // return an error if the account address is already in the whitelist
+    if whitelist.contains(&whitelist_account_addr) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Address already in whitelist",
+        )));
+    }
+
+    whitelist.push(whitelist_account_addr.clone());
+    WHITELIST.save(deps.storage, &whitelist)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_to_whitelist")
+        .add_attribute("sender", info.sender)
+        .add_attribute("added_whitelist_address", whitelist_account_addr))
+}
+
+// Removes an account address from the whitelist.
+fn remove_from_whitelist(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    // Remove the account address from the whitelist
+    let mut whitelist = WHITELIST.load(deps.storage)?;
+
+    let whitelist_account_addr = deps.api.addr_validate(&address)?;
+
+    whitelist.retain(|cp| cp != whitelist_account_addr);
+    WHITELIST.save(deps.storage, &whitelist)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_from_whitelist")
+        .add_attribute("sender", info.sender)
+        .add_attribute("removed_whitelist_address", whitelist_account_addr))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    max_locked_tokens: Option<u128>,
+    max_deployment_duration: Option<u64>,
+    tribute_cutoff_duration: Option<u64>,
+    max_proposals_per_tranche_round: Option<u64>,
+    raw_export_enabled: Option<bool>,
+    unlock_cooldown_duration: Option<u64>,
+    max_validator_shares_participating: Option<u64>,
+    max_proposal_title_length: Option<u64>,
+    max_proposal_description_length: Option<u64>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let mut constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender);
+
+    if let Some(max_locked_tokens) = max_locked_tokens {
+        constants.max_locked_tokens = max_locked_tokens;
+        response = response.add_attribute("new_max_locked_tokens", max_locked_tokens.to_string());
+    }
+
+    if let Some(max_deployment_duration) = max_deployment_duration {
+        constants.max_deployment_duration = max_deployment_duration;
+        response = response.add_attribute(
+            "new_max_deployment_duration",
+            max_deployment_duration.to_string(),
+        );
+    }
+
+    if let Some(tribute_cutoff_duration) = tribute_cutoff_duration {
+        if tribute_cutoff_duration >= constants.round_length {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Tribute cutoff duration must be shorter than the round length.",
+            )));
+        }
+
+        constants.tribute_cutoff_duration = tribute_cutoff_duration;
+        response = response.add_attribute(
+            "new_tribute_cutoff_duration",
+            tribute_cutoff_duration.to_string(),
+        );
+    }
+
+    if let Some(max_proposals_per_tranche_round) = max_proposals_per_tranche_round {
+        constants.max_proposals_per_tranche_round = max_proposals_per_tranche_round;
+        response = response.add_attribute(
+            "new_max_proposals_per_tranche_round",
+            max_proposals_per_tranche_round.to_string(),
+        );
+    }
+
+    if let Some(raw_export_enabled) = raw_export_enabled {
+        constants.raw_export_enabled = raw_export_enabled;
+        response = response.add_attribute("new_raw_export_enabled", raw_export_enabled.to_string());
+    }
+
+    if let Some(unlock_cooldown_duration) = unlock_cooldown_duration {
+        constants.unlock_cooldown_duration = unlock_cooldown_duration;
+        response = response.add_attribute(
+            "new_unlock_cooldown_duration",
+            unlock_cooldown_duration.to_string(),
+        );
+    }
+
+    // The validators that fall out of the top N due to a decrease keep their power in the current round.
+    // They are left out when the validator store of the next round gets initialized, and their
+    // interchain queries are removed once their next results get submitted.
+    if let Some(max_validator_shares_participating) = max_validator_shares_participating {
+        if max_validator_shares_participating == 0 {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Max validator shares participating must be greater than zero.",
+            )));
+        }
+
+        constants.max_validator_shares_participating = max_validator_shares_participating;
+        response = response.add_attribute(
+            "new_max_validator_shares_participating",
+            max_validator_shares_participating.to_string(),
+        );
+    }
+
+    if let Some(max_proposal_title_length) = max_proposal_title_length {
+        constants.max_proposal_title_length = max_proposal_title_length;
+        response = response.add_attribute(
+            "new_max_proposal_title_length",
+            max_proposal_title_length.to_string(),
+        );
+    }
+
+    if let Some(max_proposal_description_length) = max_proposal_description_length {
+        constants.max_proposal_description_length = max_proposal_description_length;
+        response = response.add_attribute(
+            "new_max_proposal_description_length",
+            max_proposal_description_length.to_string(),
+        );
+    }
+
+    CONSTANTS.save(deps.storage, &constants)?;
+
+    Ok(response)
+}
+
+// Pause:
+//     Validate that the contract isn't already paused
+//     Validate sender is whitelist admin
+//     Set paused to true and save the changes
+fn pause_contract(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let mut constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    constants.paused = true;
+    CONSTANTS.save(deps.storage, &constants)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause_contract")
+        .add_attribute("sender", info.sender)
+        .add_attribute("paused", "true"))
+}
+
+// PauseMessages(names):
+//     Validate that the contract isn't paused
+//     Validate sender is whitelist admin
+//     Validate that all given messages can be paused
+//     Add the messages that aren't already paused to the paused messages
+fn pause_messages(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    names: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let mut constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+    validate_pausable_message_names(&names)?;
+
+    for name in names {
+        if !constants.paused_messages.contains(&name) {
+            constants.paused_messages.push(name);
+        }
+    }
+
+    CONSTANTS.save(deps.storage, &constants)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause_messages")
+        .add_attribute("sender", info.sender)
+        .add_attribute("paused_messages", constants.paused_messages.join(",")))
+}
+
+// UnpauseMessages(names):
+//     Validate that the contract isn't paused
+//     Validate sender is whitelist admin
+//     Validate that all given messages can be paused
+//     Remove the messages from the paused messages
+fn unpause_messages(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    names: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let mut constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+    validate_pausable_message_names(&names)?;
+
+    constants
+        .paused_messages
+        .retain(|paused_message| !names.contains(paused_message));
+
+    CONSTANTS.save(deps.storage, &constants)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause_messages")
+        .add_attribute("sender", info.sender)
+        .add_attribute("paused_messages", constants.paused_messages.join(",")))
+}
+
+// AddTranche:
+//     Validate that the contract isn't paused
+//     Validate sender is whitelist admin
+//     Validate that the tranche with the same name doesn't already exist
+//     Add new tranche to the store
+fn add_tranche(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    tranche: TrancheInfo,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    let tranche_name = tranche.name.trim().to_string();
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+    validate_tranche_name_uniqueness(&deps, &tranche_name)?;
+
+    let tranche_id = TRANCHE_ID.load(deps.storage)?;
+    let tranche = Tranche {
+        id: tranche_id,
+        name: tranche_name,
+        metadata: tranche.metadata,
+    };
+
+    TRANCHE_MAP.save(deps.storage, tranche_id, &tranche)?;
+    TRANCHE_ID.save(deps.storage, &(tranche_id + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_tranche")
+        .add_attribute("sender", info.sender)
+        .add_attribute("tranche id", tranche.id.to_string())
+        .add_attribute("tranche name", tranche.name)
+        .add_attribute("tranche metadata", tranche.metadata))
+}
+
+// EditTranche:
+//     Validate that the contract isn't paused
+//     Validate sender is whitelist admin
+//     Validate that the tranche with the given id exists
+//     Validate that the tranche with the same name doesn't already exist
+//     Update the tranche in the store
+fn edit_tranche(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    tranche_id: u64,
+    tranche_name: Option<String>,
+    tranche_metadata: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    let mut tranche = TRANCHE_MAP.load(deps.storage, tranche_id)?;
+    let old_tranche_name = tranche.name.clone();
+    let old_tranche_metadata = tranche.metadata.clone();
+
+    if let Some(new_tranche_name) = tranche_name {
+        let new_tranche_name = new_tranche_name.trim().to_string();
+        // If a new name is provided, we don't allow for it to be equal with
+        // any of existing tranche names, including the one being updated.
+        // If user wants to update only metadata they should provide None for tranche_name.
+        validate_tranche_name_uniqueness(&deps, &new_tranche_name)?;
+
+        tranche.name = new_tranche_name
+    };
+
+    tranche.metadata = tranche_metadata.unwrap_or(tranche.metadata);
+    TRANCHE_MAP.save(deps.storage, tranche.id, &tranche)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "edit_tranche")
+        .add_attribute("sender", info.sender)
+        .add_attribute("tranche id", tranche.id.to_string())
+        .add_attribute("old tranche name", old_tranche_name)
+        .add_attribute("old tranche metadata", old_tranche_metadata)
+        .add_attribute("new tranche name", tranche.name)
+        .add_attribute("new tranche metadata", tranche.metadata))
+}
+
+// SetTrancheDescriptionMode(tranche_id, hash_only):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the tranche exists
+//     Set whether proposals created in the tranche store only a hash of their description
+fn set_tranche_description_mode(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    tranche_id: u64,
+    hash_only: bool,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    if hash_only {
+        HASH_ONLY_DESCRIPTION_TRANCHES.save(deps.storage, tranche_id, &true)?;
+    } else {
+        HASH_ONLY_DESCRIPTION_TRANCHES.remove(deps.storage, tranche_id);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_tranche_description_mode")
+        .add_attribute("sender", info.sender)
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("hash_only", hash_only.to_string()))
+}
+
+// SetTranchePowerScaling(tranche_id, quadratic):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the tranche exists
+//     Set whether the tranche uses quadratic power scaling, starting from the next round
+fn set_tranche_power_scaling(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    tranche_id: u64,
+    quadratic: bool,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    // apply the change from the next round, so that all votes in the current round are scaled in the same way
+    let activation_round_id = compute_current_round_id(&env, &constants)? + 1;
+    QUADRATIC_POWER_SCALING.save(deps.storage, (tranche_id, activation_round_id), &quadratic)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_tranche_power_scaling")
+        .add_attribute("sender", info.sender)
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("quadratic", quadratic.to_string())
+        .add_attribute("activation_round_id", activation_round_id.to_string()))
+}
+
+// SetTrancheTributeWeightBonus(tranche_id, bonus):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the tranche exists and that the bonus multiplier is greater than 1
+//     Set the tribute weight bonus of the tranche, or disable it if no bonus is provided,
+//     starting from the next round
+fn set_tranche_tribute_weight_bonus(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    tranche_id: u64,
+    bonus: Option<TributeWeightBonus>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    if let Some(bonus) = &bonus {
+        if bonus.multiplier <= Decimal::one() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Tribute weight bonus multiplier must be greater than 1",
+            )));
+        }
+    }
+
+    // apply the change from the next round, so that all votes in the current round get the same bonus
+    let activation_round_id = compute_current_round_id(&env, &constants)? + 1;
+    TRIBUTE_WEIGHT_BONUSES.save(deps.storage, (tranche_id, activation_round_id), &bonus)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "set_tranche_tribute_weight_bonus")
+        .add_attribute("sender", info.sender)
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("activation_round_id", activation_round_id.to_string());
+
+    if let Some(bonus) = bonus {
+        response = response
+            .add_attribute("min_lock_duration", bonus.min_lock_duration.to_string())
+            .add_attribute("multiplier", bonus.multiplier.to_string());
+    }
+
+    Ok(response)
+}
+
+// SetVotePowerCap(max_power_percentage):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the percentage is greater than 0 and at most 100
+//     Set the cap on the power of each owner within a tranche, or remove it if no percentage is provided,
+//     starting from the next round
+fn set_vote_power_cap(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    max_power_percentage: Option<Decimal>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    if let Some(max_power_percentage) = max_power_percentage {
+        if max_power_percentage.is_zero() || max_power_percentage > Decimal::percent(10000) {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Maximum power percentage must be greater than 0 and at most 100",
+            )));
+        }
+    }
+
+    // apply the change from the next round, so that all votes in the current round are capped in the same way
+    let activation_round_id = compute_current_round_id(&env, &constants)? + 1;
+    VOTE_POWER_CAPS.save(deps.storage, activation_round_id, &max_power_percentage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_vote_power_cap")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "max_power_percentage",
+            max_power_percentage.map_or("none".to_string(), |cap| cap.to_string()),
+        )
+        .add_attribute("activation_round_id", activation_round_id.to_string()))
+}
+
+// SetTributeContract(address):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Set the tribute contract that claims the tributes on unlock, or remove it if no address is provided
+fn set_tribute_contract(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    address: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    let response = Response::new()
+        .add_attribute("action", "set_tribute_contract")
+        .add_attribute("sender", info.sender);
+
+    Ok(match address {
+        Some(address) => {
+            let tribute_contract = deps.api.addr_validate(&address)?;
+            TRIBUTE_CONTRACT.save(deps.storage, &tribute_contract)?;
+
+            response.add_attribute("tribute_contract", tribute_contract)
+        }
+        None => {
+            TRIBUTE_CONTRACT.remove(deps.storage);
+
+            response.add_attribute("tribute_contract", "none")
+        }
+    })
+}
+
+// SetProposalLimitOverride(round_id, tranche_id, max_proposals):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the tranche exists
+//     Override the maximum number of proposals for the given round and tranche,
+//     or remove the override if max_proposals is not provided
+fn set_proposal_limit_override(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+    max_proposals: Option<u64>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    let response = Response::new()
+        .add_attribute("action", "set_proposal_limit_override")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string());
+
+    Ok(match max_proposals {
+        Some(max_proposals) => {
+            PROPOSAL_LIMIT_OVERRIDES.save(deps.storage, (round_id, tranche_id), &max_proposals)?;
+            response.add_attribute("max_proposals", max_proposals.to_string())
+        }
+        None => {
+            PROPOSAL_LIMIT_OVERRIDES.remove(deps.storage, (round_id, tranche_id));
+            response.add_attribute("max_proposals", "none")
+        }
+    })
+}
+
+// SetProposalVoteCap(round_id, tranche_id, proposal_id, max_vote_percentage):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the proposal exists in the current or a future round
+//     Validate that the cap, if provided, is a valid percentage
+//     Set the maximum percentage of the total round power that counts towards the proposal score,
+//     or remove the cap if max_vote_percentage is not provided
+fn set_proposal_vote_cap(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+    proposal_id: u64,
+    max_vote_percentage: Option<Decimal>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    if current_round_id > round_id {
+        return Err(ContractError::Std(StdError::generic_err(
+            "cannot set the vote cap of a proposal from a round that ended in the past",
+        )));
+    }
+
+    validate_max_vote_percentage(max_vote_percentage)?;
+
+    let mut proposal = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+    proposal.max_vote_percentage = max_vote_percentage;
+    PROPOSAL_MAP.save(deps.storage, (round_id, tranche_id, proposal_id), &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_proposal_vote_cap")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute(
+            "max_vote_percentage",
+            max_vote_percentage.map_or("none".to_string(), |cap| cap.to_string()),
+        ))
+}
+
+// InvalidateProposal(round_id, tranche_id, proposal_id, reason):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the proposal exists in the current round and that it wasn't already invalidated
+//     Remove the proposal from PROPS_BY_SCORE, so that it can't end up among the top N proposals
+//     Mark the proposal as invalidated, which prevents any further votes for it and allows
+//     the locks that voted for it to vote again regardless of VOTING_ALLOWED_ROUND
+fn invalidate_proposal(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+    proposal_id: u64,
+    reason: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    let reason = reason.trim().to_string();
+    if reason.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Reason for invalidating the proposal must be provided",
+        )));
+    }
+
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    if round_id != current_round_id {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Only proposals from the current round can be invalidated. Current round: {}",
+            current_round_id
+        ))));
+    }
+
+    let proposal = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+
+    if INVALIDATED_PROPOSALS.has(deps.storage, (round_id, tranche_id, proposal_id)) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Proposal {} in round {} and tranche {} has already been invalidated",
+            proposal_id, round_id, tranche_id
+        ))));
+    }
+
+    PROPS_BY_SCORE.remove(
+        deps.storage,
+        get_props_by_score_key(round_id, tranche_id, proposal.power, proposal_id),
+    );
+
+    INVALIDATED_PROPOSALS.save(
+        deps.storage,
+        (round_id, tranche_id, proposal_id),
+        &InvalidatedProposalInfo {
+            reason: reason.clone(),
+            invalidated_at: env.block.time,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "invalidate_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("proposal_power", proposal.power.to_string())
+        .add_attribute("reason", reason))
+}
+
+// RecomputeRoundTotals:
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the round has ended
+//     If no correction is in progress for the round and validator, store the corrected
+//         power ratio in VALIDATORS_INFO, which also corrects the total power of the round
+//     Recompute the power of up to `limit` proposals of the round that were not processed yet
+// Used to correct the history if a wrong power ratio was recorded for a validator during a round.
+// Rounds with many proposals can be processed by sending this message multiple times.
+fn recompute_round_totals(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    validator: String,
+    corrected_ratio: Decimal,
+    limit: u32,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    if limit == 0 {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Limit must be greater than zero",
+        )));
+    }
+
+    validate_round_ended_for_power_ratio_correction(&env, &constants, round_id)?;
+
+    if ROUND_DATA_PRUNING.has(deps.storage, round_id) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Data of round {} was pruned",
+            round_id
+        ))));
+    }
+
+    let mut correction = match POWER_RATIO_CORRECTIONS
+        .may_load(deps.storage, (round_id, validator.clone()))?
+    {
+        Some(correction) => {
+            if correction.corrected_power_ratio != corrected_ratio {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                        "Correction of the power ratio of validator {} in round {} to {} is already in progress",
+                        validator, round_id, correction.corrected_power_ratio
+                    ))));
+            }
+
+            correction
+        }
+        None => {
+            let mut validator_info = load_round_validator_info(deps.storage, round_id, &validator)?;
+            if validator_info.power_ratio == corrected_ratio {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Power ratio of validator {} in round {} is already {}",
+                    validator, round_id, corrected_ratio
+                ))));
+            }
+
+            let correction = PowerRatioCorrection {
+                old_power_ratio: validator_info.power_ratio,
+                corrected_power_ratio: corrected_ratio,
+                last_processed_proposal: None,
+            };
+
+            validator_info.power_ratio = corrected_ratio;
+            VALIDATORS_INFO.save(deps.storage, (round_id, validator.clone()), &validator_info)?;
+
+            update_total_power_due_to_power_ratio_change(
+                deps.storage,
+                &validator,
+                round_id,
+                correction.old_power_ratio,
+                corrected_ratio,
+            )?;
+
+            correction
+        }
+    };
+
+    let start = correction.last_processed_proposal.map(Bound::exclusive);
+    let proposals = PROPOSAL_MAP
+        .sub_prefix(round_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize + 1)
+        .collect::<StdResult<Vec<((u64, u64), Proposal)>>>()?;
+
+    let completed = proposals.len() <= limit as usize;
+    let mut processed_proposals = 0;
+
+    for ((tranche_id, proposal_id), proposal) in proposals.into_iter().take(limit as usize) {
+        update_proposal_score_due_to_power_ratio_change(
+            deps.storage,
+            &validator,
+            round_id,
+            tranche_id,
+            proposal,
+            correction.old_power_ratio,
+            correction.corrected_power_ratio,
+        )?;
+
+        correction.last_processed_proposal = Some((tranche_id, proposal_id));
+        processed_proposals += 1;
+    }
+
+    if completed {
+        POWER_RATIO_CORRECTIONS.remove(deps.storage, (round_id, validator.clone()));
+    } else {
+        POWER_RATIO_CORRECTIONS.save(deps.storage, (round_id, validator.clone()), &correction)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "recompute_round_totals")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("validator", validator)
+        .add_attribute("old_power_ratio", correction.old_power_ratio.to_string())
+        .add_attribute(
+            "corrected_power_ratio",
+            correction.corrected_power_ratio.to_string(),
+        )
+        .add_attribute("processed_proposals", processed_proposals.to_string())
+        .add_attribute("completed", completed.to_string()))
+}
+
+// Power ratios are only corrected for rounds that have ended, since the power ratios
+// of the current round will still be updated by the interchain query results.
+fn validate_round_ended_for_power_ratio_correction(
+    env: &Env,
+    constants: &Constants,
+    round_id: u64,
+) -> StdResult<()> {
+    let current_round_id = compute_current_round_id(env, constants)?;
+    if round_id >= current_round_id {
+        return Err(StdError::generic_err(format!(
+            "Power ratio can only be corrected for rounds that have ended. Current round: {}",
+            current_round_id
+        )));
+    }
+
+    Ok(())
+}
+
+fn load_round_validator_info(
+    storage: &dyn Storage,
+    round_id: u64,
+    validator: &str,
+) -> StdResult<ValidatorInfo> {
+    VALIDATORS_INFO
+        .may_load(storage, (round_id, validator.to_string()))?
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "Validator {} was not active in round {}",
+                validator, round_id
+            ))
+        })
+}
+
+// PruneRoundData(round_id, limit):
+//     Validate that the contract isn't paused
+//     Validate that the sender is a whitelist admin
+//     Validate that the round ended more than ROUND_DATA_RETENTION_ROUNDS rounds ago
+//     Validate that no power ratio correction is in progress for the round
+//     If the tribute claims of the round haven't expired yet, validate that the tribute contract doesn't hold any
+//     tributes for the round that weren't refunded
+//     Delete up to `limit` entries of the per-round maps of the round: its votes and their indexes, proposal rankings,
+//     abstentions, voters, validator sets and the validator and proposal shares. The proposals and templates whose
+//     entries are visited also count towards the limit
+//     Record the pruning progress of the round, including the last visited proposal and template
+fn prune_round_data(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    limit: u32,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    if limit == 0 {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Limit must be greater than zero",
+        )));
+    }
+
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    if round_id + ROUND_DATA_RETENTION_ROUNDS >= current_round_id {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Data of round {} is still retained. Current round: {}",
+            round_id, current_round_id
+        ))));
+    }
+
+    if POWER_RATIO_CORRECTIONS
+        .prefix(round_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Power ratio correction is in progress for round {}",
+            round_id
+        ))));
+    }
+
+    // the tributes are checked only before the pruning of the round starts, since no tributes can be added to
+    // rounds that ended
+    if round_id + TRIBUTE_CLAIM_RETENTION_ROUNDS >= current_round_id
+        && !ROUND_DATA_PRUNING.has(deps.storage, round_id)
+    {
+        validate_round_has_no_outstanding_tributes(&deps, round_id)?;
+    }
+
+    // the validator store of the current round is seeded from the latest initialized round, so it must be
+    // initialized before the validator set of an older round can be removed
+    initialize_validator_store(deps.storage, current_round_id)?;
+
+    let limit = limit as usize;
+    let mut removed_entries = 0;
+
+    let tranche_ids = TRANCHE_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    for tranche_id in tranche_ids {
+        let votes = VOTE_MAP
+            .sub_prefix((round_id, tranche_id))
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(limit - removed_entries)
+            .collect::<StdResult<Vec<((Addr, u64), Vote)>>>()?;
+        removed_entries += votes.len();
+
+        for ((voter, lock_id), vote) in votes {
+            VOTE_MAP.remove(deps.storage, ((round_id, tranche_id), voter, lock_id));
+            PROPOSAL_VOTERS.remove(
+                deps.storage,
+                ((round_id, tranche_id), vote.prop_id, lock_id),
+            );
+        }
+
+        let ranked_proposals = PROPS_BY_SCORE
+            .sub_prefix((round_id, tranche_id))
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(limit - removed_entries)
+            .collect::<StdResult<Vec<(u128, u64)>>>()?;
+        removed_entries += ranked_proposals.len();
+
+        for (score, tie_breaker) in ranked_proposals {
+            PROPS_BY_SCORE.remove(deps.storage, ((round_id, tranche_id), score, tie_breaker));
+        }
+
+        let abstentions = ABSTENTIONS
+            .sub_prefix((round_id, tranche_id))
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(limit - removed_entries)
+            .collect::<StdResult<Vec<(Addr, u64)>>>()?;
+        removed_entries += abstentions.len();
+
+        for (owner, lock_id) in abstentions {
+            ABSTENTIONS.remove(deps.storage, ((round_id, tranche_id), owner, lock_id));
+        }
+
+        let owners = OWNERS_BY_VOTE_POWER
+            .sub_prefix((round_id, tranche_id))
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(limit - removed_entries)
+            .collect::<StdResult<Vec<(u128, Addr)>>>()?;
+        removed_entries += owners.len();
+
+        for (power, owner) in owners {
+            OWNERS_BY_VOTE_POWER
+                .remove(deps.storage, ((round_id, tranche_id), power, owner.clone()));
+            OWNER_VOTE_POWER.remove(deps.storage, ((round_id, tranche_id), owner));
+        }
+    }
+
+    let mut pruning = ROUND_DATA_PRUNING
+        .may_load(deps.storage, round_id)?
+        .unwrap_or_default();
+
+    // The proposals are kept, so the shares of the proposals are pruned one proposal after the other, resuming
+    // after the last proposal whose shares were fully removed. Each visited proposal counts towards the limit.
+    let start = pruning.last_processed_proposal.map(Bound::exclusive);
+    let proposals = PROPOSAL_MAP
+        .sub_prefix(round_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit - removed_entries)
+        .collect::<StdResult<Vec<(u64, u64)>>>()?;
+
+    for (tranche_id, proposal_id) in proposals {
+        if removed_entries >= limit {
+            break;
+        }
+        removed_entries += 1;
+
+        let shares = SCALED_PROPOSAL_SHARES_MAP
+            .prefix(proposal_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(limit - removed_entries)
+            .collect::<StdResult<Vec<String>>>()?;
+        removed_entries += shares.len();
+
+        for validator in shares {
+            SCALED_PROPOSAL_SHARES_MAP.remove(deps.storage, (proposal_id, validator));
+        }
+
+        let bonus_shares = SCALED_PROPOSAL_BONUS_SHARES_MAP
+            .prefix(proposal_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(limit - removed_entries)
+            .collect::<StdResult<Vec<String>>>()?;
+        removed_entries += bonus_shares.len();
+
+        for validator in bonus_shares {
+            SCALED_PROPOSAL_BONUS_SHARES_MAP.remove(deps.storage, (proposal_id, validator));
+        }
+
+        let has_remaining_shares = SCALED_PROPOSAL_SHARES_MAP
+            .prefix(proposal_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+            || SCALED_PROPOSAL_BONUS_SHARES_MAP
+                .prefix(proposal_id)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .next()
+                .is_some();
+        if has_remaining_shares {
+            break;
+        }
+
+        pruning.last_processed_proposal = Some((tranche_id, proposal_id));
+    }
+
+    let voters = ROUND_VOTERS
+        .prefix(round_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit - removed_entries)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    removed_entries += voters.len();
+
+    for voter in voters {
+        ROUND_VOTERS.remove(deps.storage, (round_id, voter));
+    }
+
+    let validators = VALIDATORS_PER_ROUND
+        .sub_prefix(round_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit - removed_entries)
+        .collect::<StdResult<Vec<(u128, String)>>>()?;
+    removed_entries += validators.len();
+
+    for (power, validator) in validators {
+        VALIDATORS_PER_ROUND.remove(deps.storage, (round_id, power, validator));
+    }
+
+    removed_entries += remove_round_validator_entries(
+        deps.storage,
+        &VALIDATORS_INFO,
+        round_id,
+        limit - removed_entries,
+    )?;
+    removed_entries += remove_round_validator_entries(
+        deps.storage,
+        &SCALED_ROUND_POWER_SHARES_MAP,
+        round_id,
+        limit - removed_entries,
+    )?;
+    removed_entries += remove_round_validator_entries(
+        deps.storage,
+        &SCALED_ROUND_START_POWER_SHARES_MAP,
+        round_id,
+        limit - removed_entries,
+    )?;
+
+    if ROUND_POWER_RATIO_GROWTH.has(deps.storage, round_id) && removed_entries < limit {
+        ROUND_POWER_RATIO_GROWTH.remove(deps.storage, round_id);
+        removed_entries += 1;
+    }
+
+    // the templates are visited in the same way, each of them counting towards the limit
+    let start = pruning.last_processed_template.map(Bound::exclusive);
+    let template_ids = PROPOSAL_TEMPLATES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit - removed_entries)
+        .collect::<StdResult<Vec<u64>>>()?;
+    removed_entries += template_ids.len();
+
+    for template_id in template_ids {
+        TEMPLATE_PROPOSALS.remove(deps.storage, (template_id, round_id));
+        pruning.last_processed_template = Some(template_id);
+    }
+
+    pruning.removed_entries += removed_entries as u64;
+    pruning.completed = removed_entries < limit;
+    ROUND_DATA_PRUNING.save(deps.storage, round_id, &pruning)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "prune_round_data")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("removed_entries", removed_entries.to_string())
+        .add_attribute("completed", pruning.completed.to_string()))
+}
+
+// Removes up to `limit` entries of the given map, keyed by the round and the validator address, for the given
+// round. Returns the number of removed entries.
+fn remove_round_validator_entries<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    map: &Map<(u64, String), T>,
+    round_id: u64,
+    limit: usize,
+) -> StdResult<usize> {
+    let validators = map
+        .prefix(round_id)
+        .keys(storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    for validator in validators.iter() {
+        map.remove(storage, (round_id, validator.clone()));
+    }
+
+    Ok(validators.len())
+}
+
+// FinalizeRound(round_id, tranche_id):
+//     Validate that the round has ended and wasn't finalized yet in the given tranche
+//     Validate that no power ratio correction is in progress for the round and that its data wasn't pruned
+//     Record the top MAX_ROUND_RESULTS_PROPOSALS proposals of the round and tranche in ROUND_RESULTS
+fn finalize_round(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    if round_id >= current_round_id {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Round {} can't be finalized before it ends. Current round: {}",
+            round_id, current_round_id
+        ))));
+    }
+
+    if ROUND_RESULTS.has(deps.storage, (round_id, tranche_id)) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Round {} was already finalized in tranche {}",
+            round_id, tranche_id
+        ))));
+    }
+
+    if POWER_RATIO_CORRECTIONS
+        .prefix(round_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Power ratio correction is in progress for round {}",
+            round_id
+        ))));
+    }
+
+    if ROUND_DATA_PRUNING.has(deps.storage, round_id) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Data of round {} was pruned",
+            round_id
+        ))));
+    }
+
+    let top_proposals = query_top_n_proposals(
+        deps.as_ref(),
+        round_id,
+        tranche_id,
+        MAX_ROUND_RESULTS_PROPOSALS,
+    )?;
+    let total_round_power = get_total_power_for_round(deps.as_ref(), round_id)?.to_uint_ceil();
+
+    let proposals: Vec<FinalizedProposal> = top_proposals
+        .proposals
+        .iter()
+        .zip(top_proposals.ordering_keys.iter())
+        .zip(top_proposals.percentages.iter())
+        .map(|((proposal, ordering_key), percentage)| FinalizedProposal {
+            proposal_id: proposal.proposal_id,
+            power: proposal.power,
+            score: ordering_key.score,
+            percentage: *percentage,
+        })
+        .collect();
+
+    let proposal_ids: Vec<String> = proposals
+        .iter()
+        .map(|proposal| proposal.proposal_id.to_string())
+        .collect();
+
+    ROUND_RESULTS.save(
+        deps.storage,
+        (round_id, tranche_id),
+        &RoundResults {
+            finalized_at: env.block.time,
+            total_round_power,
+            proposals,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "finalize_round")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("total_round_power", total_round_power.to_string())
+        .add_attribute("proposal_ids", proposal_ids.join(",")))
+}
+
+// The tribute claims rely on the votes of the round, so a round can't be pruned before its tribute claims expire
+// while the tribute contract holds any of its tributes that weren't refunded. To keep the gas bounded, only a single
+// page of tributes is checked, and rounds with more tributes than that have to wait for their claims to expire.
+fn validate_round_has_no_outstanding_tributes(
+    deps: &DepsMut<NeutronQuery>,
+    round_id: u64,
+) -> Result<(), ContractError> {
+    let Some(tribute_contract) = TRIBUTE_CONTRACT.may_load(deps.storage)? else {
+        return Ok(());
+    };
+
+    let claims_expiry_round = round_id + TRIBUTE_CLAIM_RETENTION_ROUNDS + 1;
+
+    let response: TributeRoundTributesResponse = deps.querier.query_wasm_smart(
+        &tribute_contract,
+        &TributeQueryMsg::RoundTributes {
+            round_id,
+            start_from: 0,
+            limit: TRIBUTES_QUERY_PAGE_SIZE,
+        },
+    )?;
+
+    if let Some(tribute) = response.tributes.iter().find(|tribute| !tribute.refunded) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Round {} still holds tribute {}; its data can be pruned once its tribute claims expire in round {}",
+            round_id, tribute.tribute_id, claims_expiry_round
+        ))));
+    }
+
+    if response.tributes.len() >= TRIBUTES_QUERY_PAGE_SIZE as usize {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Round {} holds too many tributes to check; its data can be pruned once its tribute claims expire in round {}",
+            round_id, claims_expiry_round
+        ))));
+    }
+
+    Ok(())
+}
+
+// CreateICQsForValidators:
+//     Validate that the contract isn't paused
+//     Validate that the first round has started
+//     Validate received validator addresses
+//     Validate that the sender paid enough deposit for ICQs creation
+//     Create ICQ for each of the valid addresses
+fn create_icqs_for_validators(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    validators: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+    // This function will return error if the first round hasn't started yet, unless pre-launch locks
+    // are allowed. Handling the results of the interchain queries relies on the round being known.
+    let round_id = compute_lock_round_id(&env, &constants)?;
+    initialize_validator_store(deps.storage, round_id)?;
+
+    let mut valid_addresses = HashSet::new();
+    for validator in validators
+        .iter()
+        .map(|validator| validator.trim().to_owned())
+    {
+        if !valid_addresses.contains(&validator)
+            && validator.starts_with(COSMOS_VALIDATOR_PREFIX)
+            && bech32::decode(&validator).is_ok()
+            && !VALIDATOR_TO_QUERY_ID.has(deps.storage, validator.clone())
+        {
+            valid_addresses.insert(validator);
+        }
+    }
+
+    let is_icq_manager = validate_address_is_icq_manager(&deps, info.sender.clone()).is_ok();
+
+    // icq_manager can create ICQs without paying for them; in this case, the
+    // funds are implicitly provided by the contract, and these can thus either be funds
+    // sent to the contract beforehand, or they could be escrowed funds
+    // that were returned to the contract when previous Interchain Queries were removed
+    // amd the escrowed funds were removed
+    if !is_icq_manager {
+        validate_icq_deposit_funds_sent(deps, &info, valid_addresses.len() as u64)?;
+    }
+
+    let mut register_icqs_submsgs = vec![];
+    for validator_address in valid_addresses.clone() {
+        let msg = new_register_staking_validators_query_msg(
+            constants.hub_connection_id.clone(),
+            vec![validator_address.clone()],
+            constants.icq_update_period,
+        )
+        .map_err(|err| {
+            StdError::generic_err(format!(
+                "Failed to create staking validators interchain query. Error: {}",
+                err
+            ))
+        })?;
+
+        register_icqs_submsgs.push(build_create_interchain_query_submsg(
+            msg,
+            validator_address,
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "create_icqs_for_validators")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "validator_addresses",
+            valid_addresses
+                .into_iter()
+                .collect::<Vec<String>>()
+                .join(", "),
+        )
+        .add_submessages(register_icqs_submsgs))
+}
+
+// Validates that enough funds were sent to create ICQs for the given validator addresses.
+fn validate_icq_deposit_funds_sent(
+    deps: DepsMut<'_, NeutronQuery>,
+    info: &MessageInfo,
+    num_created_icqs: u64,
+) -> Result<(), ContractError> {
+    let min_icq_deposit = query_min_interchain_query_deposit(&deps.as_ref())?;
+    let sent_token = must_pay(info, &min_icq_deposit.denom)?;
+    let min_icqs_deposit = min_icq_deposit.amount.u128() * (num_created_icqs as u128);
+    if min_icqs_deposit > sent_token.u128() {
+        return Err(ContractError::Std(
+            StdError::generic_err(format!("Insufficient tokens sent to pay for {} interchain queries deposits. Sent: {}, Required: {}", num_created_icqs, Coin::new(sent_token, NATIVE_TOKEN_DENOM), Coin::new(min_icqs_deposit, NATIVE_TOKEN_DENOM)))));
+    }
+
+    Ok(())
+}
+
+fn add_icq_manager(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    let user_addr = deps.api.addr_validate(&address)?;
+
+    let is_icq_manager = validate_address_is_icq_manager(&deps, user_addr.clone());
+    if is_icq_manager.is_ok() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Address is already an ICQ manager",
+        )));
+    }
+
+    ICQ_MANAGERS.save(deps.storage, user_addr.clone(), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_icq_manager")
+        .add_attribute("address", user_addr)
+        .add_attribute("sender", info.sender))
+}
+
+fn remove_icq_manager(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    let user_addr = deps.api.addr_validate(&address)?;
+
+    let free_icq_creators = validate_address_is_icq_manager(&deps, user_addr.clone());
+    if free_icq_creators.is_err() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Address is not an ICQ manager",
+        )));
+    }
+
+    ICQ_MANAGERS.remove(deps.storage, user_addr.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_icq_manager")
+        .add_attribute("address", user_addr)
+        .add_attribute("sender", info.sender))
+}
+
+// SetNotificationPreferences(endpoint, categories):
+//     Validate that the contract isn't paused
+//     Validate that the endpoint is not empty and not longer than MAX_NOTIFICATION_ENDPOINT_LENGTH
+//     Validate that at least one category is given
+//     Save the notification preferences of the sender, replacing the existing ones
+fn set_notification_preferences(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    endpoint: String,
+    mut categories: Vec<NotificationCategory>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    let endpoint = endpoint.trim().to_string();
+    if endpoint.is_empty() || endpoint.len() > MAX_NOTIFICATION_ENDPOINT_LENGTH {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Notification endpoint must be between 1 and {} characters long",
+            MAX_NOTIFICATION_ENDPOINT_LENGTH
+        ))));
+    }
+
+    categories.sort();
+    categories.dedup();
+    if categories.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "At least one notification category must be provided",
+        )));
+    }
+
+    NOTIFICATION_PREFERENCES.save(
+        deps.storage,
+        info.sender.clone(),
+        &NotificationPreferences {
+            endpoint,
+            categories,
+            updated_at: env.block.time,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_notification_preferences")
+        .add_attribute("sender", info.sender))
+}
+
+fn remove_notification_preferences(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+
+    NOTIFICATION_PREFERENCES.remove(deps.storage, info.sender.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_notification_preferences")
+        .add_attribute("sender", info.sender))
+}
+
+// RegisterProposalTemplate(tranche_id, title, description, deployment_duration, minimum_atom_liquidity_request,
+//     description_hash, description_uri):
+//     Validate that the contract isn't paused
+//     Validate that the sender is on the whitelist
+//     Validate the template data the same way as the data of a new proposal
+//     Save the template under the next template id
+#[allow(clippy::too_many_arguments)]
+fn register_proposal_template(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    tranche_id: u64,
+    title: String,
+    description: String,
+    deployment_duration: u64,
+    minimum_atom_liquidity_request: Uint128,
+    description_hash: Option<String>,
+    description_uri: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    let whitelist = WHITELIST.load(deps.storage)?;
+    if !whitelist.contains(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    let title = title.trim().to_string();
+    let description = description.trim().to_string();
+    validate_proposal_text(&constants, &title, &description)?;
+
+    let hash_only = HASH_ONLY_DESCRIPTION_TRANCHES
+        .may_load(deps.storage, tranche_id)?
+        .unwrap_or(false);
+    let description_reference =
+        validate_description_reference(hash_only, &description, description_hash, description_uri)?;
+
+    validate_deployment_duration(&constants, deployment_duration)?;
+
+    let template_id = PROPOSAL_TEMPLATE_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let (description_hash, description_uri) = description_reference.unzip();
+
+    let template = ProposalTemplate {
+        template_id,
+        creator: info.sender.clone(),
+        tranche_id,
+        title,
+        description,
+        deployment_duration,
+        minimum_atom_liquidity_request,
+        description_hash,
+        description_uri,
+    };
+
+    PROPOSAL_TEMPLATE_ID.save(deps.storage, &(template_id + 1))?;
+    PROPOSAL_TEMPLATES.save(deps.storage, template_id, &template)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_proposal_template")
+        .add_attribute("sender", info.sender)
+        .add_attribute("template_id", template_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string()))
+}
+
+// RemoveProposalTemplate(template_id):
+//     Validate that the sender is either the template creator or a whitelist admin
+//     Remove the template; the proposals already created from it are not affected
+fn remove_proposal_template(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    template_id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_contract_is_not_paused(&constants)?;
+
+    let template = PROPOSAL_TEMPLATES.load(deps.storage, template_id)?;
+    if template.creator != info.sender {
+        validate_sender_is_whitelist_admin(&deps, &info)?;
+    }
+
+    PROPOSAL_TEMPLATES.remove(deps.storage, template_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_proposal_template")
+        .add_attribute("sender", info.sender)
+        .add_attribute("template_id", template_id.to_string()))
+}
+
+// InstantiateFromTemplate(template_id):
+//     Validate that the sender is the creator of the template
+//     Validate that no proposal was created from the template in the current round
+//     Create the proposal in the current round from the template data
+//     Link the new proposal to the proposals previously created from the same template
+fn instantiate_from_template(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    template_id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+
+    let template = PROPOSAL_TEMPLATES.load(deps.storage, template_id)?;
+    if template.creator != info.sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if TEMPLATE_PROPOSALS.has(deps.storage, (template_id, current_round_id)) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Proposal from template {} was already created in round {}",
+            template_id, current_round_id
+        ))));
+    }
+
+    let predecessor = TEMPLATE_PROPOSALS
+        .prefix(template_id)
+        .range(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+
+    let proposal_id = PROP_ID.load(deps.storage)?;
+    let mut response = create_proposal(
+        deps.branch(),
+        env,
+        info,
+        Some(current_round_id),
+        template.tranche_id,
+        template.title,
+        template.description,
+        template.deployment_duration,
+        template.minimum_atom_liquidity_request,
+        template.description_hash,
+        template.description_uri,
+        None,
+    )?;
+
+    TEMPLATE_PROPOSALS.save(deps.storage, (template_id, current_round_id), &proposal_id)?;
+
+    response = response.add_attribute("template_id", template_id.to_string());
+    if let Some((predecessor_round_id, predecessor_proposal_id)) = predecessor {
+        response = response
+            .add_attribute("predecessor_round_id", predecessor_round_id.to_string())
+            .add_attribute(
+                "predecessor_proposal_id",
+                predecessor_proposal_id.to_string(),
+            );
+    }
+
+    Ok(response)
+}
+
+// Tries to withdraw the given amount of the NATIVE_TOKEN_DENOM from
+// the contract. These will in practice be funds that
+// were returned to the contract when Interchain Queries
+// were removed because a validator fell out of the
+// top validators.
+fn withdraw_icq_funds(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_address_is_icq_manager(&deps, info.sender.clone())?;
+
+    // send the amount of native tokens to the sender
+    let send = Coin {
+        denom: NATIVE_TOKEN_DENOM.to_string(),
+        amount,
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_icq_escrows")
+        .add_attribute("sender", info.sender.clone())
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![send],
+        }))
+}
+
+// This function will add a given liquidity deployment to the deployments that were performed.
+// This will not actually perform any movement of funds; it is assumed to be called when
+// a trusted party, e.g. a multisig, has performed some deployment,
+// and the contract should be updated to reflect this.
+// This will return an error if:
+// * the given round has not started yet
+// * the given tranche does not exist
+// * the given proposal does not exist
+// * there already is a deployment for the given round, tranche, and proposal
+pub fn add_liquidity_deployment(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    deployment: LiquidityDeployment,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    let round_id = deployment.round_id;
+    let tranche_id = deployment.tranche_id;
+    let proposal_id = deployment.proposal_id;
+
+    // check that the round has started
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    if round_id > current_round_id {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Cannot add liquidity deployment for a round that has not started yet",
+        )));
+    }
+
+    // check that the tranche with the given id exists
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    // check that the proposal with the given id exists
+    PROPOSAL_MAP
+        .load(deps.storage, (round_id, tranche_id, proposal_id))
+        .map_err(|_| {
+            ContractError::Std(StdError::generic_err(format!(
+                "Proposal for round {}, tranche {}, and id {} does not exist",
+                round_id, tranche_id, proposal_id
+            )))
+        })?;
+
+    // check that there is no deployment for the given round, tranche, and proposal
+    if LIQUIDITY_DEPLOYMENTS_MAP
+        .may_load(deps.storage, (round_id, tranche_id, proposal_id))?
+        .is_some()
+    {
+        return Err(ContractError::Std(StdError::generic_err(
+            "There already is a deployment for the given round, tranche, and proposal",
+        )));
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "add_liquidity_deployment")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute(
+            "deployment",
+            serde_json_wasm::to_string(&deployment).map_err(|_| {
+                ContractError::Std(StdError::generic_err("Failed to serialize deployment"))
+            })?,
+        );
+
+    LIQUIDITY_DEPLOYMENTS_MAP.save(
+        deps.storage,
+        (round_id, tranche_id, proposal_id),
+        &deployment,
+    )?;
+    add_round_deployed_funds(deps.storage, round_id, &deployment.deployed_funds)?;
+
+    Ok(response)
+}
+
+// This function will remove a given liquidity deployment from the deployments that were performed.
+// The main purpose is to correct a faulty entry added via add_liquidity_deployment.
+// This will return an error if the deployment does not exist.
+pub fn remove_liquidity_deployment(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+    proposal_id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let constants = CONSTANTS.load(deps.storage)?;
+
+    validate_contract_is_not_paused(&constants)?;
+    validate_sender_is_whitelist_admin(&deps, &info)?;
+
+    // check that the deployment exists
+    let deployment =
+        LIQUIDITY_DEPLOYMENTS_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+
+    let response = Response::new()
+        .add_attribute("action", "remove_liquidity_deployment")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string());
+
+    LIQUIDITY_DEPLOYMENTS_MAP.remove(deps.storage, (round_id, tranche_id, proposal_id));
+    remove_round_deployed_funds(deps.storage, round_id, &deployment.deployed_funds)?;
+
+    Ok(response)
+}
+
+fn validate_sender_is_whitelist_admin(
+    deps: &DepsMut<NeutronQuery>,
+    info: &MessageInfo,
+) -> Result<(), ContractError> {
+    let whitelist_admins = WHITELIST_ADMINS.load(deps.storage)?;
+    if !whitelist_admins.contains(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+fn validate_address_is_icq_manager(
+    deps: &DepsMut<NeutronQuery>,
+    address: Addr,
+) -> Result<(), ContractError> {
+    let is_manager = ICQ_MANAGERS.may_load(deps.storage, address)?;
+    if is_manager.is_none() {
+        return Err(ContractError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+fn validate_contract_is_not_paused(constants: &Constants) -> Result<(), ContractError> {
+    match constants.paused {
+        true => Err(ContractError::Paused),
+        false => Ok(()),
+    }
+}
+
+fn validate_message_is_not_paused(
+    constants: &Constants,
+    message_name: &str,
+) -> Result<(), ContractError> {
+    if constants
+        .paused_messages
+        .iter()
+        .any(|paused_message| paused_message == message_name)
+    {
+        return Err(ContractError::MessagePaused(message_name.to_string()));
+    }
+
+    Ok(())
+}
+
+fn validate_pausable_message_names(names: &[String]) -> Result<(), ContractError> {
+    if names.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Must provide at least one message name",
+        )));
+    }
+
+    for name in names {
+        if !PAUSABLE_MESSAGES.contains(&name.as_str()) {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Message {} can't be paused",
+                name
+            ))));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_tranche_name_uniqueness(
+    deps: &DepsMut<NeutronQuery>,
+    tranche_name: &String,
+) -> Result<(), ContractError> {
+    for tranche_entry in TRANCHE_MAP.range(deps.storage, None, None, Order::Ascending) {
+        let (_, tranche) = tranche_entry?;
+        if tranche.name == *tranche_name {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Tranche with the given name already exists. Duplicate tranche names are not allowed.",
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Constants {} => to_json_binary(&query_constants(deps)?),
+        QueryMsg::Capabilities {} => to_json_binary(&query_capabilities(deps, env)?),
+        QueryMsg::Tranches {} => to_json_binary(&query_tranches(deps)?),
+        QueryMsg::TranchePowerScaling {
+            tranche_id,
+            round_id,
+        } => to_json_binary(&query_tranche_power_scaling(deps, tranche_id, round_id)?),
+        QueryMsg::TrancheTributeWeightBonus {
+            tranche_id,
+            round_id,
+        } => to_json_binary(&query_tranche_tribute_weight_bonus(
+            deps, tranche_id, round_id,
+        )?),
+        QueryMsg::VotePowerCap { round_id } => {
+            to_json_binary(&query_vote_power_cap(deps, round_id)?)
+        }
+        QueryMsg::ProposalTextLimits {} => to_json_binary(&query_proposal_text_limits(deps)?),
+        QueryMsg::HashOnlyDescriptionTranches {} => {
+            to_json_binary(&query_hash_only_description_tranches(deps)?)
+        }
+        QueryMsg::TributeContract {} => to_json_binary(&query_tribute_contract(deps)?),
+        QueryMsg::ProposalSlots {
+            round_id,
+            tranche_id,
+        } => to_json_binary(&query_proposal_slots(deps, round_id, tranche_id)?),
+        QueryMsg::InvalidatedProposals {
+            round_id,
+            tranche_id,
+        } => to_json_binary(&query_invalidated_proposals(deps, round_id, tranche_id)?),
+        QueryMsg::AllUserLockups {
+            address,
+            start_from,
+            start_after,
+            limit,
+            include_metadata,
+        } => {
+            let mut res =
+                query_all_user_lockups(deps, env, address.clone(), start_from, start_after, limit)?;
+            if include_metadata.unwrap_or(false) {
+                add_lock_notes(deps, address, res.lockups.iter_mut())?;
+            }
+            to_json_binary(&res)
+        }
+        QueryMsg::SpecificUserLockups {
+            address,
+            lock_ids,
+            include_metadata,
+        } => {
+            let mut res = query_specific_user_lockups(deps, env, address.clone(), lock_ids)?;
+            if include_metadata.unwrap_or(false) {
+                add_lock_notes(deps, address, res.lockups.iter_mut())?;
+            }
+            to_json_binary(&res)
+        }
+        QueryMsg::PendingUnlocks { address } => {
+            to_json_binary(&query_pending_unlocks(deps, address)?)
+        }
+        QueryMsg::AutoRefreshLocks { address } => {
+            to_json_binary(&query_auto_refresh_locks(deps, address)?)
+        }
+        QueryMsg::DelegationsForUser { address } => {
+            to_json_binary(&query_delegations_for_user(deps, address)?)
+        }
+        QueryMsg::VotingExecutor { owner } => to_json_binary(&query_voting_executor(deps, owner)?),
+        QueryMsg::RelayedVoteNonce { address } => {
+            to_json_binary(&query_relayed_vote_nonce(deps, address)?)
+        }
+        QueryMsg::LockOperators { address } => {
+            to_json_binary(&query_lock_operators(deps, address)?)
+        }
+        QueryMsg::LockAutoMerge { address } => {
+            to_json_binary(&query_lock_auto_merge(deps, address)?)
+        }
+        QueryMsg::AllUserLockupsWithTrancheInfos {
+            address,
+            start_from,
+            start_after,
+            limit,
+            include_metadata,
+        } => {
+            let mut res = query_all_user_lockups_with_tranche_infos(
+                deps,
+                env,
+                address.clone(),
+                start_from,
+                start_after,
+                limit,
+            )?;
+            if include_metadata.unwrap_or(false) {
+                add_lock_notes(
+                    deps,
+                    address,
+                    res.lockups_with_per_tranche_infos
+                        .iter_mut()
+                        .map(|lockup| &mut lockup.lock_with_power),
+                )?;
+            }
+            to_json_binary(&res)
+        }
+        QueryMsg::SpecificUserLockupsWithTrancheInfos {
+            address,
+            lock_ids,
+            include_metadata,
+        } => {
+            let mut res = query_specific_user_lockups_with_tranche_infos(
+                deps,
+                env,
+                address.clone(),
+                lock_ids,
+            )?;
+            if include_metadata.unwrap_or(false) {
+                add_lock_notes(
+                    deps,
+                    address,
+                    res.lockups_with_per_tranche_infos
+                        .iter_mut()
+                        .map(|lockup| &mut lockup.lock_with_power),
+                )?;
+            }
+            to_json_binary(&res)
+        }
+        QueryMsg::ExpiredUserLockups {
+            address,
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_expired_user_lockups(
+            deps,
+            env,
+            address,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::UserVotingPower { address } => {
+            to_json_binary(&query_user_voting_power(deps, env, address)?)
+        }
+        QueryMsg::UserVotes {
+            round_id,
+            tranche_id,
+            address,
+        } => to_json_binary(&query_user_votes(deps, round_id, tranche_id, address)?),
+        QueryMsg::Proposal {
+            round_id,
+            tranche_id,
+            proposal_id,
+        } => to_json_binary(&query_proposal(deps, round_id, tranche_id, proposal_id)?),
+        QueryMsg::ProposalVoters {
+            round_id,
+            tranche_id,
+            proposal_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_proposal_voters(
+            deps,
+            round_id,
+            tranche_id,
+            proposal_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::RoundTotalVotingPower { round_id } => {
+            to_json_binary(&query_round_total_power(deps, env, round_id)?)
+        }
+        QueryMsg::RoundProposals {
+            round_id,
+            tranche_id,
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_round_tranche_proposals(
+            deps,
+            round_id,
+            tranche_id,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::CurrentRound {} => to_json_binary(&query_current_round_id(deps, env)?),
+        QueryMsg::RoundEnd { round_id } => to_json_binary(&query_round_end(deps, round_id)?),
+        QueryMsg::RoundCutoffs { round_id } => {
+            to_json_binary(&query_round_cutoffs(deps, round_id)?)
+        }
+        QueryMsg::TopNProposals {
+            round_id,
+            tranche_id,
+            number_of_proposals,
+        } => to_json_binary(&query_top_n_proposals(
+            deps,
+            round_id,
+            tranche_id,
+            number_of_proposals,
+        )?),
+        QueryMsg::Whitelist {} => to_json_binary(&query_whitelist(deps)?),
+        QueryMsg::WhitelistAdmins {} => to_json_binary(&query_whitelist_admins(deps)?),
+        QueryMsg::TotalLockedTokens {} => to_json_binary(&query_total_locked_tokens(deps)?),
+        QueryMsg::RegisteredValidatorQueries {} => {
+            to_json_binary(&query_registered_validator_queries(deps)?)
+        }
+        QueryMsg::ValidatorPowerRatio {
+            validator,
+            round_id,
+        } => to_json_binary(&query_validator_power_ratio(deps, validator, round_id)?),
+        QueryMsg::DenomValidator { denom } => {
+            to_json_binary(&query_denom_validator(deps, env, denom)?)
+        }
+        QueryMsg::CanLockDenomAtRound { denom, round_id } => {
+            to_json_binary(&query_can_lock_denom_at_round(deps, env, denom, round_id)?)
+        }
+        QueryMsg::ValidatorSlashes {} => to_json_binary(&query_validator_slashes(deps)?),
+        QueryMsg::ValidatorLocks {
+            validator,
+            start_after,
+            limit,
+        } => to_json_binary(&query_validator_locks(deps, validator, start_after, limit)?),
+        QueryMsg::RecomputeRoundTotalsDryRun {
+            round_id,
+            validator,
+            corrected_ratio,
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_recompute_round_totals_dry_run(
+            deps,
+            env,
+            round_id,
+            validator,
+            corrected_ratio,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ICQManagers {} => to_json_binary(&query_icq_managers(deps)?),
+        QueryMsg::LiquidityDeployment {
+            round_id,
+            tranche_id,
+            proposal_id,
+        } => to_json_binary(&query_liquidity_deployment(
+            deps,
+            round_id,
+            tranche_id,
+            proposal_id,
+        )?),
+        QueryMsg::RoundTrancheLiquidityDeployments {
+            round_id,
+            tranche_id,
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_round_tranche_liquidity_deployments(
+            deps,
+            round_id,
+            tranche_id,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ProtocolStats { start_after, limit } => {
+            to_json_binary(&query_protocol_stats(deps, env, start_after, limit)?)
+        }
+        QueryMsg::NotificationPreferences { address } => {
+            to_json_binary(&query_notification_preferences(deps, address)?)
+        }
+        QueryMsg::AllNotificationPreferences {
+            category,
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_all_notification_preferences(
+            deps,
+            category,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ProposalTemplate { template_id } => {
+            to_json_binary(&query_proposal_template(deps, template_id)?)
+        }
+        QueryMsg::ProposalTemplates {
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_proposal_templates(
+            deps,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ExportRawState {
+            map,
+            start_after,
+            limit,
+        } => to_json_binary(&query_export_raw_state(deps, map, start_after, limit)?),
+        QueryMsg::RoundDataPruning { round_id } => {
+            to_json_binary(&query_round_data_pruning(deps, round_id)?)
+        }
+        QueryMsg::RoundResults {
+            round_id,
+            tranche_id,
+        } => to_json_binary(&query_round_results(deps, round_id, tranche_id)?),
+        QueryMsg::AdminActions { start_after, limit } => {
+            to_json_binary(&query_admin_actions(deps, start_after, limit)?)
+        }
+    }
+}
 
-                    // skip this lock entry, since the locked shares do not belong to a validator that we want to take into account
-                    locks_skipped.push(lock_entry.lock_id);
-                    continue;
-                }
-            };
+fn query_liquidity_deployment(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+    proposal_id: u64,
+) -> StdResult<LiquidityDeploymentResponse> {
+    let deployment =
+        LIQUIDITY_DEPLOYMENTS_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+    Ok(LiquidityDeploymentResponse {
+        liquidity_deployment: deployment,
+    })
+}
 
-            let scaled_shares = Decimal::from_ratio(
-                get_lock_time_weighted_shares(
-                    &constants.round_lock_power_schedule,
-                    round_end,
-                    lock_entry.clone(),
-                    lock_epoch_length,
-                ),
-                Uint128::one(),
-            );
+pub fn query_round_tranche_liquidity_deployments(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+    start_from: u64,
+    start_after: Option<u64>,
+    limit: u64,
+) -> StdResult<RoundTrancheLiquidityDeploymentsResponse> {
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
 
-            // skip the lock entries that give zero voting power
-            if scaled_shares.is_zero() {
-                locks_skipped.push(lock_entry.lock_id);
-                continue;
-            }
+    let mut deployments = vec![];
+    for deployment in LIQUIDITY_DEPLOYMENTS_MAP
+        .prefix((round_id, tranche_id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .skip(skip)
+        .take(limit as usize)
+    {
+        let (_, deployment) = deployment?;
+        deployments.push(deployment);
+    }
 
-            let proposal = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+    let next_key = get_next_key(&deployments, limit as usize, |deployment| {
+        deployment.proposal_id
+    });
 
-            // skip lock entries that don't span long enough to be allowed to vote for this proposal
-            if !can_lock_vote_for_proposal(round_id, &constants, &lock_entry, &proposal)? {
-                locks_skipped.push(lock_entry.lock_id);
-                continue;
-            }
+    Ok(RoundTrancheLiquidityDeploymentsResponse {
+        liquidity_deployments: deployments,
+        next_key,
+    })
+}
 
-            // add the validator shares to the proposal
-            add_validator_shares_to_proposal(
-                deps.storage,
-                round_id,
-                proposal_id,
-                validator.to_string(),
-                scaled_shares,
-            )?;
+pub fn query_round_total_power(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    round_id: u64,
+) -> StdResult<RoundTotalVotingPowerResponse> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    let total_round_power = get_total_power_for_round(deps, round_id)?;
+    let interpolated_total_round_power =
+        get_total_power_for_round_at_time(deps, &constants, round_id, env.block.time)?;
+    Ok(RoundTotalVotingPowerResponse {
+        total_voting_power: total_round_power.to_uint_ceil(), // TODO: decide on rounding
+        interpolated_total_voting_power: interpolated_total_round_power.to_uint_ceil(),
+    })
+}
 
-            // update the proposal in the proposal map, as well as the props by score map
-            update_proposal_and_props_by_score_maps(deps.storage, round_id, tranche_id, &proposal)?;
+// Returns the participation statistics for a page of rounds up to and including the current one.
+// All values are read from the aggregates that are kept up to date during voting, so no per-round
+// maps need to be iterated.
+pub fn query_protocol_stats(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<ProtocolStatsResponse> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    let first_round_id = start_after.map_or(0, |round_id| round_id + 1);
+    let end_round_id = (current_round_id + 1).min(first_round_id.saturating_add(limit as u64));
 
-            // Create vote in Votemap
-            let vote = Vote {
-                prop_id: proposal_id,
-                time_weighted_shares: (validator, scaled_shares),
+    let tranche_ids = TRANCHE_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    let mut rounds = vec![];
+    for round_id in first_round_id..end_round_id {
+        let round_stats = get_round_stats(deps.storage, round_id)?;
+        let total_voting_power = get_total_power_for_round(deps, round_id)?.to_uint_ceil(); // TODO: decide on rounding
+
+        let mut tranches = vec![];
+        for tranche_id in tranche_ids.iter() {
+            let voted_power = get_round_tranche_voted_power(deps.storage, round_id, *tranche_id)?;
+            let participation = if total_voting_power.is_zero() {
+                Decimal::zero()
+            } else {
+                Decimal::from_ratio(voted_power, total_voting_power)
             };
-            VOTE_MAP.save(
-                deps.storage,
-                ((round_id, tranche_id), info.sender.clone(), lock_id),
-                &vote,
-            )?;
-
-            let voting_allowed_round = round_id + proposal.deployment_duration;
-            VOTING_ALLOWED_ROUND.save(
-                deps.storage,
-                (tranche_id, lock_id),
-                &voting_allowed_round,
-            )?;
 
-            locks_voted.push(lock_entry.lock_id);
+            tranches.push(TrancheParticipation {
+                tranche_id: *tranche_id,
+                voted_power,
+                participation,
+            });
         }
 
-        voted_proposals.push(proposal_id);
+        rounds.push(RoundProtocolStats {
+            round_id,
+            total_voting_power,
+            tranches,
+            unique_voters: round_stats.unique_voters,
+            proposals_count: round_stats.proposals_count,
+            deployed_funds: round_stats.deployed_funds,
+        });
     }
 
-    let to_string = |input: &Vec<u64>| {
-        input
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",")
-    };
+    let next_key = get_next_key(&rounds, limit as usize, |round_stats| round_stats.round_id);
 
-    Ok(response
-        .add_attribute("proposal_id", to_string(&voted_proposals))
-        .add_attribute("locks_voted", to_string(&locks_voted))
-        .add_attribute("locks_skipped", to_string(&locks_skipped)))
+    Ok(ProtocolStatsResponse {
+        rounds,
+        first_tracked_round_id: ROUND_STATS_FIRST_ROUND
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        next_key,
+    })
 }
 
-// Returns the time-weighted amount of shares locked in the given lock entry in a round with the given end time,
-// and using the given lock epoch length.
-pub fn get_lock_time_weighted_shares(
-    round_lock_power_schedule: &RoundLockPowerSchedule,
-    round_end: Timestamp,
-    lock_entry: LockEntry,
-    lock_epoch_length: u64,
-) -> Uint128 {
-    if round_end.nanos() > lock_entry.lock_end.nanos() {
-        return Uint128::zero();
-    }
-    let lockup_length = lock_entry.lock_end.nanos() - round_end.nanos();
-    scale_lockup_power(
-        round_lock_power_schedule,
-        lock_epoch_length,
-        lockup_length,
-        lock_entry.funds.amount,
-    )
+pub fn query_constants(deps: Deps<NeutronQuery>) -> StdResult<ConstantsResponse> {
+    Ok(ConstantsResponse {
+        constants: CONSTANTS.load(deps.storage)?,
+    })
 }
 
-// Adds a new account address to the whitelist.
-fn add_to_whitelist(
-    deps: DepsMut<NeutronQuery>,
-    _env: Env,
-    info: MessageInfo,
-    address: String,
-) -> Result<Response<NeutronMsg>, ContractError> {
+// Returns the version of the contract together with its optional features and limits, so that
+// the frontends and integrating contracts can adapt to the configuration of each deployment.
+pub fn query_capabilities(deps: Deps<NeutronQuery>, env: Env) -> StdResult<CapabilitiesResponse> {
     let constants = CONSTANTS.load(deps.storage)?;
+    let contract_version = get_contract_version(deps.storage)?;
 
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
-
-    // Add address to whitelist
-    let mut whitelist = WHITELIST.load(deps.storage)?;
-    let whitelist_account_addr = deps.api.addr_validate(&address)?;
-
-    // return an error if the account address is already in the whitelist
-    if whitelist.contains(&whitelist_account_addr) {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Address already in whitelist",
-        )));
-    }
+    // Before the first round starts, report the tranche settings that will apply to round 0
+    let round_id = compute_current_round_id(&env, &constants).unwrap_or(0);
 
-    whitelist.push(whitelist_account_addr.clone());
-    WHITELIST.save(deps.storage, &whitelist)?;
+    let tranches = TRANCHE_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|tranche_id| {
+            let tranche_id = tranche_id?;
 
-    Ok(Response::new()
-        .add_attribute("action", "add_to_whitelist")
-        .add_attribute("sender", info.sender)
-        .add_attribute("added_whitelist_address", whitelist_account_addr))
+            Ok(TrancheCapabilities {
+                tranche_id,
+                hash_only_descriptions: HASH_ONLY_DESCRIPTION_TRANCHES
+                    .may_load(deps.storage, tranche_id)?
+                    .unwrap_or(false),
+                quadratic_power_scaling: is_quadratic_power_scaling_enabled(
+                    deps.storage,
+                    tranche_id,
+                    round_id,
+                )?,
+            })
+        })
+        .collect::<StdResult<Vec<TrancheCapabilities>>>()?;
+
+    Ok(CapabilitiesResponse {
+        contract_name: contract_version.contract,
+        contract_version: contract_version.version,
+        features: ContractFeatures {
+            tribute_contract_set: TRIBUTE_CONTRACT.may_load(deps.storage)?.is_some(),
+            interpolate_lock_power: constants.interpolate_lock_power,
+            pre_launch_locks: constants.allow_pre_launch_locks,
+            raw_export: constants.raw_export_enabled,
+            unlock_cooldown: constants.unlock_cooldown_duration != 0,
+            tribute_cutoff: constants.tribute_cutoff_duration != 0,
+            vote_power_cap: get_vote_power_cap(deps.storage, round_id)?.is_some(),
+        },
+        tranches,
+        limits: ContractLimits {
+            max_lock_entries: MAX_LOCK_ENTRIES as u64,
+            max_locked_tokens: Uint128::new(constants.max_locked_tokens),
+            max_validator_shares_participating: constants.max_validator_shares_participating,
+            max_deployment_duration: constants.max_deployment_duration,
+            max_proposals_per_tranche_round: constants.max_proposals_per_tranche_round,
+        },
+    })
 }
 
-// Removes an account address from the whitelist.
-fn remove_from_whitelist(
-    deps: DepsMut<NeutronQuery>,
-    _env: Env,
-    info: MessageInfo,
+fn get_user_lockups_with_predicate(
+    deps: Deps<NeutronQuery>,
+    env: Env,
     address: String,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
-
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
-
-    // Remove the account address from the whitelist
-    let mut whitelist = WHITELIST.load(deps.storage)?;
-
-    let whitelist_account_addr = deps.api.addr_validate(&address)?;
-
-    whitelist.retain(|cp| cp != whitelist_account_addr);
-    WHITELIST.save(deps.storage, &whitelist)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "remove_from_whitelist")
-        .add_attribute("sender", info.sender)
-        .add_attribute("removed_whitelist_address", whitelist_account_addr))
-}
-
-fn update_config(
-    deps: DepsMut<NeutronQuery>,
-    info: MessageInfo,
-    max_locked_tokens: Option<u128>,
-    max_deployment_duration: Option<u64>,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let mut constants = CONSTANTS.load(deps.storage)?;
-
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
-
-    let mut response = Response::new()
-        .add_attribute("action", "update_config")
-        .add_attribute("sender", info.sender);
+    predicate: impl FnMut(&LockEntry) -> bool,
+    start_from: u32,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<Vec<LockEntryWithPower>> {
+    let addr = deps.api.addr_validate(&address)?;
 
-    if let Some(max_locked_tokens) = max_locked_tokens {
-        constants.max_locked_tokens = max_locked_tokens;
-        response = response.add_attribute("new_max_locked_tokens", max_locked_tokens.to_string());
-    }
+    let raw_lockups = query_user_lockups(deps, addr, predicate, start_from, start_after, limit);
 
-    if let Some(max_deployment_duration) = max_deployment_duration {
-        constants.max_deployment_duration = max_deployment_duration;
-        response = response.add_attribute(
-            "new_max_deployment_duration",
-            max_deployment_duration.to_string(),
-        );
-    }
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    let round_end = compute_round_end(&constants, current_round_id)?;
 
-    CONSTANTS.save(deps.storage, &constants)?;
+    // enrich the lockups by computing the voting power for each lockup
+    let enriched_lockups = raw_lockups
+        .iter()
+        .map(|lock| {
+            to_lockup_with_power(
+                deps,
+                env.clone(),
+                &constants,
+                current_round_id,
+                round_end,
+                lock.clone(),
+            )
+        })
+        .collect();
 
-    Ok(response)
+    Ok(enriched_lockups)
 }
 
-// Pause:
-//     Validate that the contract isn't already paused
-//     Validate sender is whitelist admin
-//     Set paused to true and save the changes
-fn pause_contract(
-    deps: DepsMut<NeutronQuery>,
-    info: MessageInfo,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let mut constants = CONSTANTS.load(deps.storage)?;
+pub fn query_all_user_lockups(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    address: String,
+    start_from: u32,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<AllUserLockupsResponse> {
+    let lockups = get_user_lockups_with_predicate(
+        deps,
+        env,
+        address,
+        |_| true,
+        start_from,
+        start_after,
+        limit,
+    )?;
+    let next_key = get_next_key(&lockups, limit as usize, |lockup| lockup.lock_entry.lock_id);
 
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
+    Ok(AllUserLockupsResponse { lockups, next_key })
+}
 
-    constants.paused = true;
-    CONSTANTS.save(deps.storage, &constants)?;
+// Fills in the notes that the owner attached to the given lockups.
+fn add_lock_notes<'a>(
+    deps: Deps<NeutronQuery>,
+    address: String,
+    lockups: impl Iterator<Item = &'a mut LockEntryWithPower>,
+) -> StdResult<()> {
+    let owner = deps.api.addr_validate(&address)?;
+    for lockup in lockups {
+        lockup.note =
+            LOCK_NOTES.may_load(deps.storage, (owner.clone(), lockup.lock_entry.lock_id))?;
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "pause_contract")
-        .add_attribute("sender", info.sender)
-        .add_attribute("paused", "true"))
+    Ok(())
 }
 
-// AddTranche:
-//     Validate that the contract isn't paused
-//     Validate sender is whitelist admin
-//     Validate that the tranche with the same name doesn't already exist
-//     Add new tranche to the store
-fn add_tranche(
-    deps: DepsMut<NeutronQuery>,
-    info: MessageInfo,
-    tranche: TrancheInfo,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
-    let tranche_name = tranche.name.trim().to_string();
+pub fn query_specific_user_lockups(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    address: String,
+    lock_ids: Vec<u64>,
+) -> StdResult<SpecificUserLockupsResponse> {
+    let lock_ids_set: HashSet<u64> = lock_ids.into_iter().collect();
 
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
-    validate_tranche_name_uniqueness(&deps, &tranche_name)?;
+    let lockups = get_user_lockups_with_predicate(
+        deps,
+        env,
+        address,
+        |lock| lock_ids_set.contains(&lock.lock_id),
+        0,
+        None,
+        lock_ids_set.len() as u32,
+    )?;
 
-    let tranche_id = TRANCHE_ID.load(deps.storage)?;
-    let tranche = Tranche {
-        id: tranche_id,
-        name: tranche_name,
-        metadata: tranche.metadata,
-    };
+    Ok(SpecificUserLockupsResponse { lockups })
+}
 
-    TRANCHE_MAP.save(deps.storage, tranche_id, &tranche)?;
-    TRANCHE_ID.save(deps.storage, &(tranche_id + 1))?;
+pub fn query_pending_unlocks(
+    deps: Deps<NeutronQuery>,
+    address: String,
+) -> StdResult<PendingUnlocksResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let pending_unlocks = PENDING_UNLOCKS
+        .prefix(address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            entry.map(|(lock_id, pending_unlock)| PendingUnlockEntry {
+                lock_id,
+                pending_unlock,
+            })
+        })
+        .collect::<StdResult<Vec<PendingUnlockEntry>>>()?;
 
-    Ok(Response::new()
-        .add_attribute("action", "add_tranche")
-        .add_attribute("sender", info.sender)
-        .add_attribute("tranche id", tranche.id.to_string())
-        .add_attribute("tranche name", tranche.name)
-        .add_attribute("tranche metadata", tranche.metadata))
+    Ok(PendingUnlocksResponse { pending_unlocks })
 }
 
-// EditTranche:
-//     Validate that the contract isn't paused
-//     Validate sender is whitelist admin
-//     Validate that the tranche with the given id exists
-//     Validate that the tranche with the same name doesn't already exist
-//     Update the tranche in the store
-fn edit_tranche(
-    deps: DepsMut<NeutronQuery>,
-    info: MessageInfo,
-    tranche_id: u64,
-    tranche_name: Option<String>,
-    tranche_metadata: Option<String>,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
+pub fn query_auto_refresh_locks(
+    deps: Deps<NeutronQuery>,
+    address: String,
+) -> StdResult<AutoRefreshLocksResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let locks = AUTO_REFRESH_LOCKS
+        .prefix(address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            entry.map(|(lock_id, auto_refresh)| AutoRefreshLock {
+                lock_id,
+                auto_refresh,
+            })
+        })
+        .collect::<StdResult<Vec<AutoRefreshLock>>>()?;
 
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
+    Ok(AutoRefreshLocksResponse { locks })
+}
 
-    let mut tranche = TRANCHE_MAP.load(deps.storage, tranche_id)?;
-    let old_tranche_name = tranche.name.clone();
-    let old_tranche_metadata = tranche.metadata.clone();
+pub fn query_delegations_for_user(
+    deps: Deps<NeutronQuery>,
+    address: String,
+) -> StdResult<DelegationsForUserResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let delegations = VOTE_DELEGATIONS
+        .prefix(address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(lock_id, delegate)| VoteDelegation { lock_id, delegate }))
+        .collect::<StdResult<Vec<VoteDelegation>>>()?;
 
-    if let Some(new_tranche_name) = tranche_name {
-        let new_tranche_name = new_tranche_name.trim().to_string();
-        // If a new name is provided, we don't allow for it to be equal with
-        // any of existing tranche names, including the one being updated.
-        // If user wants to update only metadata they should provide None for tranche_name.
-        validate_tranche_name_uniqueness(&deps, &new_tranche_name)?;
+    Ok(DelegationsForUserResponse { delegations })
+}
 
-        tranche.name = new_tranche_name
-    };
+pub fn query_relayed_vote_nonce(
+    deps: Deps<NeutronQuery>,
+    address: String,
+) -> StdResult<RelayedVoteNonceResponse> {
+    let address = deps.api.addr_validate(&address)?;
 
-    tranche.metadata = tranche_metadata.unwrap_or(tranche.metadata);
-    TRANCHE_MAP.save(deps.storage, tranche.id, &tranche)?;
+    Ok(RelayedVoteNonceResponse {
+        next_nonce: RELAYED_VOTE_NONCES
+            .may_load(deps.storage, address)?
+            .unwrap_or_default(),
+    })
+}
 
-    Ok(Response::new()
-        .add_attribute("action", "edit_tranche")
-        .add_attribute("sender", info.sender)
-        .add_attribute("tranche id", tranche.id.to_string())
-        .add_attribute("old tranche name", old_tranche_name)
-        .add_attribute("old tranche metadata", old_tranche_metadata)
-        .add_attribute("new tranche name", tranche.name)
-        .add_attribute("new tranche metadata", tranche.metadata))
+pub fn query_voting_executor(
+    deps: Deps<NeutronQuery>,
+    owner: String,
+) -> StdResult<VotingExecutorResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+
+    Ok(VotingExecutorResponse {
+        voting_executor: VOTING_EXECUTORS.may_load(deps.storage, owner)?,
+    })
 }
 
-// CreateICQsForValidators:
-//     Validate that the contract isn't paused
-//     Validate that the first round has started
-//     Validate received validator addresses
-//     Validate that the sender paid enough deposit for ICQs creation
-//     Create ICQ for each of the valid addresses
-fn create_icqs_for_validators(
-    deps: DepsMut<NeutronQuery>,
-    env: Env,
-    info: MessageInfo,
-    validators: Vec<String>,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
-    validate_contract_is_not_paused(&constants)?;
-    // This function will return error if the first round hasn't started yet. It is necessarry
-    // that it has started, since handling the results of the interchain queries relies on this.
-    let round_id = compute_current_round_id(&env, &constants)?;
-    initialize_validator_store(deps.storage, round_id)?;
+pub fn query_lock_operators(
+    deps: Deps<NeutronQuery>,
+    address: String,
+) -> StdResult<LockOperatorsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let operators = LOCK_OPERATORS
+        .prefix(address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(lock_id, operator)| LockOperator { lock_id, operator }))
+        .collect::<StdResult<Vec<LockOperator>>>()?;
 
-    let mut valid_addresses = HashSet::new();
-    for validator in validators
-        .iter()
-        .map(|validator| validator.trim().to_owned())
-    {
-        if !valid_addresses.contains(&validator)
-            && validator.starts_with(COSMOS_VALIDATOR_PREFIX)
-            && bech32::decode(&validator).is_ok()
-            && !VALIDATOR_TO_QUERY_ID.has(deps.storage, validator.clone())
-        {
-            valid_addresses.insert(validator);
-        }
-    }
+    Ok(LockOperatorsResponse { operators })
+}
 
-    let is_icq_manager = validate_address_is_icq_manager(&deps, info.sender.clone()).is_ok();
+pub fn query_lock_auto_merge(
+    deps: Deps<NeutronQuery>,
+    address: String,
+) -> StdResult<LockAutoMergeResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let tolerance = LOCK_AUTO_MERGE_TOLERANCES.may_load(deps.storage, address)?;
 
-    // icq_manager can create ICQs without paying for them; in this case, the
-    // funds are implicitly provided by the contract, and these can thus either be funds
-    // sent to the contract beforehand, or they could be escrowed funds
-    // that were returned to the contract when previous Interchain Queries were removed
-    // amd the escrowed funds were removed
-    if !is_icq_manager {
-        validate_icq_deposit_funds_sent(deps, &info, valid_addresses.len() as u64)?;
-    }
+    Ok(LockAutoMergeResponse { tolerance })
+}
 
-    let mut register_icqs_submsgs = vec![];
-    for validator_address in valid_addresses.clone() {
-        let msg = new_register_staking_validators_query_msg(
-            constants.hub_connection_id.clone(),
-            vec![validator_address.clone()],
-            constants.icq_update_period,
-        )
-        .map_err(|err| {
-            StdError::generic_err(format!(
-                "Failed to create staking validators interchain query. Error: {}",
-                err
-            ))
-        })?;
+// Helper function to handle the common logic for both query functions
+fn enrich_lockups_with_tranche_infos(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    address: String,
+    lockups: Vec<LockEntryWithPower>,
+) -> StdResult<Vec<LockupWithPerTrancheInfo>> {
+    let converted_addr = deps.api.addr_validate(&address)?;
 
-        register_icqs_submsgs.push(build_create_interchain_query_submsg(
-            msg,
-            validator_address,
-        )?);
-    }
+    let tranche_ids = TRANCHE_MAP
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|tranche| tranche.unwrap().1.id)
+        .collect::<Vec<u64>>();
 
-    Ok(Response::new()
-        .add_attribute("action", "create_icqs_for_validators")
-        .add_attribute("sender", info.sender)
-        .add_attribute(
-            "validator_addresses",
-            valid_addresses
-                .into_iter()
-                .collect::<Vec<String>>()
-                .join(", "),
-        )
-        .add_submessages(register_icqs_submsgs))
-}
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(&env, &constants)?;
 
-// Validates that enough funds were sent to create ICQs for the given validator addresses.
-fn validate_icq_deposit_funds_sent(
-    deps: DepsMut<'_, NeutronQuery>,
-    info: &MessageInfo,
-    num_created_icqs: u64,
-) -> Result<(), ContractError> {
-    let min_icq_deposit = query_min_interchain_query_deposit(&deps.as_ref())?;
-    let sent_token = must_pay(info, &min_icq_deposit.denom)?;
-    let min_icqs_deposit = min_icq_deposit.amount.u128() * (num_created_icqs as u128);
-    if min_icqs_deposit > sent_token.u128() {
-        return Err(ContractError::Std(
-            StdError::generic_err(format!("Insufficient tokens sent to pay for {} interchain queries deposits. Sent: {}, Required: {}", num_created_icqs, Coin::new(sent_token, NATIVE_TOKEN_DENOM), Coin::new(min_icqs_deposit, NATIVE_TOKEN_DENOM)))));
-    }
+    // enrich lockups with some info per tranche
+    let lockups_with_per_tranche_info: Vec<LockupWithPerTrancheInfo> = lockups
+        .iter()
+        .map(|lock| {
+            // iterate all tranches
+            let tranche_infos = tranche_ids
+                .iter()
+                .filter_map(|tranche_id| {
+                    // add which proposal the lock voted for
+                    let voted_for_proposal_res: StdResult<Option<u64>> = VOTE_MAP
+                        .may_load(
+                            deps.storage,
+                            (
+                                (current_round_id, *tranche_id),
+                                converted_addr.clone(),
+                                lock.lock_entry.lock_id,
+                            ),
+                        )
+                        .map(|vote| vote.map(|v| v.prop_id));
 
-    Ok(())
-}
+                    // if there was an error in the store while loading the vote for the lockup,
+                    // we filter out the tranche by returning None
+                    if voted_for_proposal_res.is_err() {
+                        return None;
+                    }
 
-fn add_icq_manager(
-    deps: DepsMut<NeutronQuery>,
-    info: MessageInfo,
-    address: String,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
+                    let voted_for_proposal = voted_for_proposal_res.unwrap();
 
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
+                    let next_round_voting_allowed_res: StdResult<u64> =
+                        if voted_for_proposal.is_some() {
+                            // if the proposal voted in this round, we ignore the VOTING_ALLOWED_ROUND map,
+                            // since it just contains the future information on when the lockup will be able to vote again
+                            // if it doesn't change the vote, but it can vote in the current round either way
+                            Ok(current_round_id)
+                        } else {
+                            // if the lockup has not voted in this round, VOTING_ALLOWED_ROUND does contain
+                            // current information on whether the lockup can vote right now or not
+                            // unless the lockup voted for a proposal that got invalidated in the meantime
+                            VOTING_ALLOWED_ROUND
+                                .may_load(deps.storage, (*tranche_id, lock.lock_entry.lock_id))
+                                .and_then(|voting_allowed_round| match voting_allowed_round {
+                                    Some(voting_allowed_round)
+                                        if voting_allowed_round > current_round_id
+                                            && lock_voted_for_invalidated_proposal(
+                                                deps.storage,
+                                                &constants,
+                                                &converted_addr,
+                                                current_round_id,
+                                                *tranche_id,
+                                                lock.lock_entry.lock_id,
+                                                voting_allowed_round,
+                                            )? =>
+                                    {
+                                        Ok(current_round_id)
+                                    }
+                                    Some(voting_allowed_round) => Ok(voting_allowed_round),
+                                    None => Ok(current_round_id),
+                                })
+                        };
 
-    let user_addr = deps.api.addr_validate(&address)?;
+                    // if there was an error in the store while loading the next allowed round for voting,
+                    // we filter out the tranche by returning None
+                    if next_round_voting_allowed_res.is_err() {
+                        return None;
+                    }
 
-    let is_icq_manager = validate_address_is_icq_manager(&deps, user_addr.clone());
-    if is_icq_manager.is_ok() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Address is already an ICQ manager",
-        )));
-    }
+                    let next_round_voting_allowed = next_round_voting_allowed_res.unwrap();
 
-    ICQ_MANAGERS.save(deps.storage, user_addr.clone(), &true)?;
+                    let abstaining = ABSTENTIONS.has(
+                        deps.storage,
+                        (
+                            (current_round_id, *tranche_id),
+                            converted_addr.clone(),
+                            lock.lock_entry.lock_id,
+                        ),
+                    );
 
-    Ok(Response::new()
-        .add_attribute("action", "add_icq_manager")
-        .add_attribute("address", user_addr)
-        .add_attribute("sender", info.sender))
+                    // return the info for this tranche
+                    Some(PerTrancheLockupInfo {
+                        tranche_id: *tranche_id,
+                        next_round_lockup_can_vote: next_round_voting_allowed,
+                        current_voted_on_proposal: voted_for_proposal,
+                        abstaining,
+                    })
+                })
+                .collect::<Vec<PerTrancheLockupInfo>>();
+            // combine the info for all tranches
+            LockupWithPerTrancheInfo {
+                lock_with_power: lock.clone(),
+                per_tranche_info: tranche_infos,
+            }
+        })
+        .collect();
+
+    // return the enriched lockups
+    Ok(lockups_with_per_tranche_info)
 }
 
-fn remove_icq_manager(
-    deps: DepsMut<NeutronQuery>,
-    info: MessageInfo,
+pub fn query_all_user_lockups_with_tranche_infos(
+    deps: Deps<NeutronQuery>,
+    env: Env,
     address: String,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
+    start_from: u32,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<AllUserLockupsWithTrancheInfosResponse> {
+    let lockups = query_all_user_lockups(
+        deps,
+        env.clone(),
+        address.clone(),
+        start_from,
+        start_after,
+        limit,
+    )?;
+    let enriched_lockups = enrich_lockups_with_tranche_infos(deps, env, address, lockups.lockups)?;
+    Ok(AllUserLockupsWithTrancheInfosResponse {
+        lockups_with_per_tranche_infos: enriched_lockups,
+        next_key: lockups.next_key,
+    })
+}
 
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
+pub fn query_specific_user_lockups_with_tranche_infos(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    address: String,
+    lock_ids: Vec<u64>,
+) -> StdResult<SpecificUserLockupsWithTrancheInfosResponse> {
+    let lockups = query_specific_user_lockups(deps, env.clone(), address.clone(), lock_ids)?;
+    let enriched_lockups = enrich_lockups_with_tranche_infos(deps, env, address, lockups.lockups)?;
 
-    let user_addr = deps.api.addr_validate(&address)?;
+    Ok(SpecificUserLockupsWithTrancheInfosResponse {
+        lockups_with_per_tranche_infos: enriched_lockups,
+    })
+}
 
-    let free_icq_creators = validate_address_is_icq_manager(&deps, user_addr.clone());
-    if free_icq_creators.is_err() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Address is not an ICQ manager",
-        )));
-    }
+pub fn query_expired_user_lockups(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    address: String,
+    start_from: u32,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<ExpiredUserLockupsResponse> {
+    let user_address = deps.api.addr_validate(&address)?;
+    let expired_lockup_predicate = |l: &LockEntry| l.lock_end < env.block.time;
 
-    ICQ_MANAGERS.remove(deps.storage, user_addr.clone());
+    let lockups = query_user_lockups(
+        deps,
+        user_address,
+        expired_lockup_predicate,
+        start_from,
+        start_after,
+        limit,
+    );
+    let next_key = get_next_key(&lockups, limit as usize, |lockup| lockup.lock_id);
 
-    Ok(Response::new()
-        .add_attribute("action", "remove_icq_manager")
-        .add_attribute("address", user_addr)
-        .add_attribute("sender", info.sender))
+    Ok(ExpiredUserLockupsResponse { lockups, next_key })
 }
 
-// Tries to withdraw the given amount of the NATIVE_TOKEN_DENOM from
-// the contract. These will in practice be funds that
-// were returned to the contract when Interchain Queries
-// were removed because a validator fell out of the
-// top validators.
-fn withdraw_icq_funds(
-    deps: DepsMut<NeutronQuery>,
-    info: MessageInfo,
-    amount: Uint128,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
-
-    validate_contract_is_not_paused(&constants)?;
-    validate_address_is_icq_manager(&deps, info.sender.clone())?;
+pub fn query_proposal(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+    proposal_id: u64,
+) -> StdResult<ProposalResponse> {
+    let proposal = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+    let tribute_weight = Decimal::from_ratio(proposal.power, Uint128::one()).checked_add(
+        get_tribute_weight_bonus_for_proposal(deps.storage, proposal_id)?,
+    )?;
 
-    // send the amount of native tokens to the sender
-    let send = Coin {
-        denom: NATIVE_TOKEN_DENOM.to_string(),
-        amount,
+    // the total round power is only needed if the proposal or the votes of its owners are capped
+    let capped_power = if proposal.max_vote_percentage.is_none()
+        && get_vote_power_cap(deps.storage, round_id)?.is_none()
+    {
+        proposal.power
+    } else {
+        let total_round_power = get_total_power_for_round(deps, round_id)?;
+        let vote_power_cap_excess =
+            get_vote_power_cap_excess(deps, round_id, tranche_id, total_round_power)?;
+
+        get_capped_proposal_power(
+            &proposal,
+            vote_power_cap_excess
+                .get(&proposal_id)
+                .copied()
+                .unwrap_or_default(),
+            total_round_power.to_uint_ceil(),
+        )?
     };
 
-    Ok(Response::new()
-        .add_attribute("action", "withdraw_icq_escrows")
-        .add_attribute("sender", info.sender.clone())
-        .add_message(BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: vec![send],
-        }))
+    Ok(ProposalResponse {
+        proposal,
+        tribute_weight,
+        capped_power,
+    })
 }
 
-// This function will add a given liquidity deployment to the deployments that were performed.
-// This will not actually perform any movement of funds; it is assumed to be called when
-// a trusted party, e.g. a multisig, has performed some deployment,
-// and the contract should be updated to reflect this.
-// This will return an error if:
-// * the given round has not started yet
-// * the given tranche does not exist
-// * the given proposal does not exist
-// * there already is a deployment for the given round, tranche, and proposal
-pub fn add_liquidity_deployment(
-    deps: DepsMut<NeutronQuery>,
+pub fn query_user_voting_power(
+    deps: Deps<NeutronQuery>,
     env: Env,
-    info: MessageInfo,
-    deployment: LiquidityDeployment,
-) -> Result<Response<NeutronMsg>, ContractError> {
+    address: String,
+) -> StdResult<UserVotingPowerResponse> {
+    let user_address = deps.api.addr_validate(&address)?;
     let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(&env, &constants)?;
+    let round_end = compute_round_end(&constants, current_round_id)?;
 
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
+    let (voting_power, interpolated_voting_power) = LOCKS_MAP
+        .prefix(user_address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|l| l.unwrap().1)
+        .filter(|l| l.lock_end > round_end)
+        .map(|lockup| {
+            to_lockup_with_power(
+                deps,
+                env.clone(),
+                &constants,
+                current_round_id,
+                round_end,
+                lockup,
+            )
+        })
+        .fold(
+            (0, 0),
+            |(voting_power, interpolated_voting_power), lockup| {
+                (
+                    voting_power + lockup.current_voting_power.u128(),
+                    interpolated_voting_power + lockup.interpolated_voting_power.u128(),
+                )
+            },
+        );
 
-    let round_id = deployment.round_id;
-    let tranche_id = deployment.tranche_id;
-    let proposal_id = deployment.proposal_id;
+    Ok(UserVotingPowerResponse {
+        voting_power,
+        interpolated_voting_power,
+    })
+}
 
-    // check that the round has started
-    let current_round_id = compute_current_round_id(&env, &constants)?;
-    if round_id > current_round_id {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Cannot add liquidity deployment for a round that has not started yet",
-        )));
-    }
+// This function queries user votes for the given round and tranche.
+// It goes through all user votes per lock_id and groups them by the
+// proposal ID. The returned result will contain one VoteWithPower per
+// each proposal ID, with the total power summed up from all lock IDs
+// used to vote for that proposal. The votes that are referring to the
+// validators that later dropped out from the top N will be filtered out.
+pub fn query_user_votes(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+    user_address: String,
+) -> StdResult<UserVotesResponse> {
+    let user_address = deps.api.addr_validate(&user_address)?;
+    let mut voted_proposals_power_sum: HashMap<u64, (Decimal, Decimal)> = HashMap::new();
 
-    // check that the tranche with the given id exists
-    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+    let votes = VOTE_MAP
+        .prefix(((round_id, tranche_id), user_address.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|vote| match vote {
+            Err(_) => None,
+            Ok(vote) => Some(vote.1),
+        })
+        .collect::<Vec<Vote>>();
+
+    for vote in votes {
+        let vote_validator = vote.time_weighted_shares.0;
+        // If the validator was active in the given round, we will get its power ratio.
+        // If it wasn't we will get 0, which means we should filter out this vote.
+        let val_power_ratio =
+            get_validator_power_ratio_for_round(deps.storage, round_id, vote_validator)?;
+
+        let vote_power = vote.time_weighted_shares.1.checked_mul(val_power_ratio)?;
+        if vote_power == Decimal::zero() {
+            continue;
+        }
 
-    // check that the proposal with the given id exists
-    PROPOSAL_MAP
-        .load(deps.storage, (round_id, tranche_id, proposal_id))
-        .map_err(|_| {
-            ContractError::Std(StdError::generic_err(format!(
-                "Proposal for round {}, tranche {}, and id {} does not exist",
-                round_id, tranche_id, proposal_id
-            )))
-        })?;
+        let vote_tribute_weight = match vote.tribute_weight_multiplier {
+            None => vote_power,
+            Some(multiplier) => vote_power.checked_mul(multiplier)?,
+        };
 
-    // check that there is no deployment for the given round, tranche, and proposal
-    if LIQUIDITY_DEPLOYMENTS_MAP
-        .may_load(deps.storage, (round_id, tranche_id, proposal_id))?
-        .is_some()
-    {
-        return Err(ContractError::Std(StdError::generic_err(
-            "There already is a deployment for the given round, tranche, and proposal",
-        )));
+        let (current_power, current_tribute_weight) =
+            match voted_proposals_power_sum.get(&vote.prop_id) {
+                None => (Decimal::zero(), Decimal::zero()),
+                Some(current) => *current,
+            };
+
+        let new_power = current_power.checked_add(vote_power)?;
+        let new_tribute_weight = current_tribute_weight.checked_add(vote_tribute_weight)?;
+        voted_proposals_power_sum.insert(vote.prop_id, (new_power, new_tribute_weight));
     }
 
-    let response = Response::new()
-        .add_attribute("action", "add_liquidity_deployment")
-        .add_attribute("sender", info.sender)
-        .add_attribute("round_id", round_id.to_string())
-        .add_attribute("tranche_id", tranche_id.to_string())
-        .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute(
-            "deployment",
-            serde_json_wasm::to_string(&deployment).map_err(|_| {
-                ContractError::Std(StdError::generic_err("Failed to serialize deployment"))
-            })?,
-        );
+    if voted_proposals_power_sum.is_empty() {
+        return Err(StdError::generic_err(
+            "User didn't vote in the given round and tranche",
+        ));
+    }
 
-    LIQUIDITY_DEPLOYMENTS_MAP.save(
-        deps.storage,
-        (round_id, tranche_id, proposal_id),
-        &deployment,
-    )?;
+    let votes: Vec<VoteWithPower> = voted_proposals_power_sum
+        .into_iter()
+        .map(|(prop_id, (power, tribute_weight))| VoteWithPower {
+            prop_id,
+            power,
+            tribute_weight,
+        })
+        .collect();
 
-    Ok(response)
+    Ok(UserVotesResponse { votes })
 }
 
-// This function will remove a given liquidity deployment from the deployments that were performed.
-// The main purpose is to correct a faulty entry added via add_liquidity_deployment.
-// This will return an error if the deployment does not exist.
-pub fn remove_liquidity_deployment(
-    deps: DepsMut<NeutronQuery>,
-    info: MessageInfo,
+pub fn query_proposal_voters(
+    deps: Deps<NeutronQuery>,
     round_id: u64,
     tranche_id: u64,
     proposal_id: u64,
-) -> Result<Response<NeutronMsg>, ContractError> {
-    let constants = CONSTANTS.load(deps.storage)?;
-
-    validate_contract_is_not_paused(&constants)?;
-    validate_sender_is_whitelist_admin(&deps, &info)?;
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<ProposalVotersResponse> {
+    let (start, _) = get_pagination_start(start_after, 0);
+    let voters = PROPOSAL_VOTERS
+        .prefix(((round_id, tranche_id), proposal_id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|voter| {
+            let (lock_id, owner) = voter?;
+            let vote = VOTE_MAP.load(
+                deps.storage,
+                ((round_id, tranche_id), owner.clone(), lock_id),
+            )?;
+            let val_power_ratio = get_validator_power_ratio_for_round(
+                deps.storage,
+                round_id,
+                vote.time_weighted_shares.0,
+            )?;
 
-    // check that the deployment exists
-    LIQUIDITY_DEPLOYMENTS_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+            Ok(ProposalVoter {
+                lock_id,
+                owner,
+                power: vote.time_weighted_shares.1.checked_mul(val_power_ratio)?,
+            })
+        })
+        .collect::<StdResult<Vec<ProposalVoter>>>()?;
+    let next_key = get_next_key(&voters, limit as usize, |voter| voter.lock_id);
 
-    let response = Response::new()
-        .add_attribute("action", "remove_liquidity_deployment")
-        .add_attribute("sender", info.sender)
-        .add_attribute("round_id", round_id.to_string())
-        .add_attribute("tranche_id", tranche_id.to_string())
-        .add_attribute("proposal_id", proposal_id.to_string());
+    Ok(ProposalVotersResponse { voters, next_key })
+}
 
-    LIQUIDITY_DEPLOYMENTS_MAP.remove(deps.storage, (round_id, tranche_id, proposal_id));
+pub fn query_round_tranche_proposals(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+    start_from: u32,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<RoundProposalsResponse> {
+    if TRANCHE_MAP.load(deps.storage, tranche_id).is_err() {
+        return Err(StdError::generic_err("Tranche does not exist"));
+    }
 
-    Ok(response)
-}
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
+    let props = PROPOSAL_MAP
+        .prefix((round_id, tranche_id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .skip(skip)
+        .take(limit as usize);
 
-fn validate_sender_is_whitelist_admin(
-    deps: &DepsMut<NeutronQuery>,
-    info: &MessageInfo,
-) -> Result<(), ContractError> {
-    let whitelist_admins = WHITELIST_ADMINS.load(deps.storage)?;
-    if !whitelist_admins.contains(&info.sender) {
-        return Err(ContractError::Unauthorized);
+    let mut proposals = vec![];
+    for proposal in props {
+        let (_, proposal) = proposal?;
+        proposals.push(proposal);
     }
 
-    Ok(())
+    let next_key = get_next_key(&proposals, limit as usize, |proposal| proposal.proposal_id);
+
+    Ok(RoundProposalsResponse {
+        proposals,
+        next_key,
+    })
 }
 
-fn validate_address_is_icq_manager(
-    deps: &DepsMut<NeutronQuery>,
-    address: Addr,
-) -> Result<(), ContractError> {
-    let is_manager = ICQ_MANAGERS.may_load(deps.storage, address)?;
-    if is_manager.is_none() {
-        return Err(ContractError::Unauthorized);
-    }
+pub fn query_current_round_id(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+) -> StdResult<CurrentRoundResponse> {
+    let constants = &CONSTANTS.load(deps.storage)?;
+    let round_id = compute_round_id_for_timestamp(constants, env.block.time.nanos())?;
 
-    Ok(())
+    let round_end = compute_round_end(constants, round_id)?;
+
+    Ok(CurrentRoundResponse {
+        round_id,
+        round_end,
+    })
 }
 
-fn validate_contract_is_not_paused(constants: &Constants) -> Result<(), ContractError> {
-    match constants.paused {
-        true => Err(ContractError::Paused),
-        false => Ok(()),
-    }
+pub fn query_round_end(deps: Deps<NeutronQuery>, round_id: u64) -> StdResult<RoundEndResponse> {
+    let constants = &CONSTANTS.load(deps.storage)?;
+    let round_end = compute_round_end(constants, round_id)?;
+
+    Ok(RoundEndResponse { round_end })
 }
 
-fn validate_tranche_name_uniqueness(
-    deps: &DepsMut<NeutronQuery>,
-    tranche_name: &String,
-) -> Result<(), ContractError> {
-    for tranche_entry in TRANCHE_MAP.range(deps.storage, None, None, Order::Ascending) {
-        let (_, tranche) = tranche_entry?;
-        if tranche.name == *tranche_name {
-            return Err(ContractError::Std(StdError::generic_err(
-                "Tranche with the given name already exists. Duplicate tranche names are not allowed.",
-            )));
-        }
-    }
+pub fn query_round_cutoffs(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+) -> StdResult<RoundCutoffsResponse> {
+    let constants = &CONSTANTS.load(deps.storage)?;
+    let round_end = compute_round_end(constants, round_id)?;
+    let tribute_cutoff = round_end.minus_nanos(constants.tribute_cutoff_duration);
 
-    Ok(())
+    Ok(RoundCutoffsResponse {
+        round_id,
+        tribute_cutoff,
+        round_end,
+    })
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Constants {} => to_json_binary(&query_constants(deps)?),
-        QueryMsg::Tranches {} => to_json_binary(&query_tranches(deps)?),
-        QueryMsg::AllUserLockups {
-            address,
-            start_from,
-            limit,
-        } => to_json_binary(&query_all_user_lockups(
-            deps, env, address, start_from, limit,
-        )?),
-        QueryMsg::SpecificUserLockups { address, lock_ids } => {
-            to_json_binary(&query_specific_user_lockups(deps, env, address, lock_ids)?)
-        }
-        QueryMsg::AllUserLockupsWithTrancheInfos {
-            address,
-            start_from,
-            limit,
-        } => to_json_binary(&query_all_user_lockups_with_tranche_infos(
-            deps, env, address, start_from, limit,
-        )?),
-        QueryMsg::SpecificUserLockupsWithTrancheInfos { address, lock_ids } => to_json_binary(
-            &query_specific_user_lockups_with_tranche_infos(deps, env, address, lock_ids)?,
-        ),
-        QueryMsg::ExpiredUserLockups {
-            address,
-            start_from,
-            limit,
-        } => to_json_binary(&query_expired_user_lockups(
-            deps, env, address, start_from, limit,
-        )?),
-        QueryMsg::UserVotingPower { address } => {
-            to_json_binary(&query_user_voting_power(deps, env, address)?)
-        }
-        QueryMsg::UserVotes {
-            round_id,
-            tranche_id,
-            address,
-        } => to_json_binary(&query_user_votes(deps, round_id, tranche_id, address)?),
-        QueryMsg::Proposal {
-            round_id,
-            tranche_id,
-            proposal_id,
-        } => to_json_binary(&query_proposal(deps, round_id, tranche_id, proposal_id)?),
-        QueryMsg::RoundTotalVotingPower { round_id } => {
-            to_json_binary(&query_round_total_power(deps, round_id)?)
-        }
-        QueryMsg::RoundProposals {
-            round_id,
-            tranche_id,
-            start_from,
-            limit,
-        } => to_json_binary(&query_round_tranche_proposals(
-            deps, round_id, tranche_id, start_from, limit,
-        )?),
-        QueryMsg::CurrentRound {} => to_json_binary(&query_current_round_id(deps, env)?),
-        QueryMsg::RoundEnd { round_id } => to_json_binary(&query_round_end(deps, round_id)?),
-        QueryMsg::TopNProposals {
-            round_id,
-            tranche_id,
-            number_of_proposals,
-        } => to_json_binary(&query_top_n_proposals(
-            deps,
-            round_id,
-            tranche_id,
-            number_of_proposals,
-        )?),
-        QueryMsg::Whitelist {} => to_json_binary(&query_whitelist(deps)?),
-        QueryMsg::WhitelistAdmins {} => to_json_binary(&query_whitelist_admins(deps)?),
-        QueryMsg::TotalLockedTokens {} => to_json_binary(&query_total_locked_tokens(deps)?),
-        QueryMsg::RegisteredValidatorQueries {} => {
-            to_json_binary(&query_registered_validator_queries(deps)?)
-        }
-        QueryMsg::ValidatorPowerRatio {
-            validator,
-            round_id,
-        } => to_json_binary(&query_validator_power_ratio(deps, validator, round_id)?),
-        QueryMsg::ICQManagers {} => to_json_binary(&query_icq_managers(deps)?),
-        QueryMsg::LiquidityDeployment {
-            round_id,
-            tranche_id,
-            proposal_id,
-        } => to_json_binary(&query_liquidity_deployment(
-            deps,
-            round_id,
-            tranche_id,
-            proposal_id,
-        )?),
-        QueryMsg::RoundTrancheLiquidityDeployments {
-            round_id,
-            tranche_id,
-            start_from,
-            limit,
-        } => to_json_binary(&query_round_tranche_liquidity_deployments(
-            deps, round_id, tranche_id, start_from, limit,
-        )?),
+pub fn query_top_n_proposals(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+    num: usize,
+) -> StdResult<TopNProposalsResponse> {
+    if TRANCHE_MAP.load(deps.storage, tranche_id).is_err() {
+        return Err(StdError::generic_err("Tranche does not exist"));
+    }
+
+    // get total voting power for the round
+    let total_round_power = get_total_power_for_round(deps, round_id)?;
+    let total_voting_power = total_round_power.to_uint_ceil(); // TODO: decide on rounding
+
+    let vote_power_cap_excess =
+        get_vote_power_cap_excess(deps, round_id, tranche_id, total_round_power)?;
+
+    // Iterate through PROPS_BY_SCORE to find the top num props. The map is ordered by the power of the
+    // proposals, while their score is their capped power, which depends on the total round power and is
+    // therefore computed here. Since the capped power is never above the power, the iteration can stop
+    // once the power of the next proposal is below the score of the num-th best proposal found so far.
+    // Proposals with equal score are returned in ascending order of their ids.
+    let mut top_props: Vec<(ProposalOrderingKey, Proposal)> = vec![];
+    if num > 0 {
+        for entry in PROPS_BY_SCORE.sub_prefix((round_id, tranche_id)).range(
+            deps.storage,
+            None,
+            None,
+            Order::Descending,
+        ) {
+            let ((power, _), proposal_id) = entry?;
+            if top_props.len() >= num && Uint128::new(power) < top_props[num - 1].0.score {
+                break;
+            }
+
+            let prop = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+            let score = get_capped_proposal_power(
+                &prop,
+                vote_power_cap_excess
+                    .get(&proposal_id)
+                    .copied()
+                    .unwrap_or_default(),
+                total_voting_power,
+            )?;
+            top_props.push((ProposalOrderingKey { score, proposal_id }, prop));
+            top_props.sort_by(|(first, _), (second, _)| {
+                second
+                    .score
+                    .cmp(&first.score)
+                    .then(first.proposal_id.cmp(&second.proposal_id))
+            });
+        }
+    }
+    top_props.truncate(num);
+
+    let (ordering_keys, mut top_props): (Vec<ProposalOrderingKey>, Vec<Proposal>) =
+        top_props.into_iter().unzip();
+
+    // The percentages are computed from the scores, so that capped proposals report their capped share
+    // and the percentages follow the ordering of the proposals.
+    let mut percentages = vec![];
+    for (prop, ordering_key) in top_props.iter_mut().zip(ordering_keys.iter()) {
+        let percentage = if total_voting_power.is_zero() {
+            // if total voting power is zero, each proposal must necessarily have 0 score
+            // avoid division by zero and set percentage to 0
+            Decimal::zero()
+        } else {
+            Decimal::checked_from_ratio(ordering_key.score * Uint128::new(100), total_voting_power)
+                .map_err(|err| StdError::generic_err(err.to_string()))?
+        };
+
+        // the integer percentage is kept for compatibility, truncated from the precise one
+        prop.percentage = percentage.to_uint_floor();
+        percentages.push(percentage);
     }
+
+    // return top props
+    Ok(TopNProposalsResponse {
+        proposals: top_props,
+        ordering_keys,
+        percentages,
+    })
 }
 
-fn query_liquidity_deployment(
+pub fn query_tranches(deps: Deps<NeutronQuery>) -> StdResult<TranchesResponse> {
+    let tranches = TRANCHE_MAP
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|t| t.unwrap().1)
+        .collect();
+
+    Ok(TranchesResponse { tranches })
+}
+
+pub fn query_tranche_power_scaling(
     deps: Deps<NeutronQuery>,
-    round_id: u64,
     tranche_id: u64,
-    proposal_id: u64,
-) -> StdResult<LiquidityDeploymentResponse> {
-    let deployment =
-        LIQUIDITY_DEPLOYMENTS_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
-    Ok(LiquidityDeploymentResponse {
-        liquidity_deployment: deployment,
+    round_id: u64,
+) -> StdResult<TranchePowerScalingResponse> {
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
+
+    Ok(TranchePowerScalingResponse {
+        quadratic: is_quadratic_power_scaling_enabled(deps.storage, tranche_id, round_id)?,
     })
 }
 
-pub fn query_round_tranche_liquidity_deployments(
+pub fn query_tranche_tribute_weight_bonus(
     deps: Deps<NeutronQuery>,
-    round_id: u64,
     tranche_id: u64,
-    start_from: u64,
-    limit: u64,
-) -> StdResult<RoundTrancheLiquidityDeploymentsResponse> {
-    let mut deployments = vec![];
-    for deployment in LIQUIDITY_DEPLOYMENTS_MAP
-        .prefix((round_id, tranche_id))
-        .range(deps.storage, None, None, Order::Ascending)
-        .skip(start_from as usize)
-        .take(limit as usize)
-    {
-        let (_, deployment) = deployment?;
-        deployments.push(deployment);
-    }
+    round_id: u64,
+) -> StdResult<TrancheTributeWeightBonusResponse> {
+    TRANCHE_MAP.load(deps.storage, tranche_id)?;
 
-    Ok(RoundTrancheLiquidityDeploymentsResponse {
-        liquidity_deployments: deployments,
+    Ok(TrancheTributeWeightBonusResponse {
+        bonus: get_tranche_tribute_weight_bonus(deps.storage, tranche_id, round_id)?,
     })
 }
 
-pub fn query_round_total_power(
+pub fn query_vote_power_cap(
     deps: Deps<NeutronQuery>,
     round_id: u64,
-) -> StdResult<RoundTotalVotingPowerResponse> {
-    let total_round_power = get_total_power_for_round(deps, round_id)?;
-    Ok(RoundTotalVotingPowerResponse {
-        total_voting_power: total_round_power.to_uint_ceil(), // TODO: decide on rounding
+) -> StdResult<VotePowerCapResponse> {
+    Ok(VotePowerCapResponse {
+        max_power_percentage: get_vote_power_cap(deps.storage, round_id)?,
     })
 }
 
-pub fn query_constants(deps: Deps<NeutronQuery>) -> StdResult<ConstantsResponse> {
-    Ok(ConstantsResponse {
-        constants: CONSTANTS.load(deps.storage)?,
+pub fn query_proposal_text_limits(
+    deps: Deps<NeutronQuery>,
+) -> StdResult<ProposalTextLimitsResponse> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    let get_limit = |limit: u64| if limit == 0 { None } else { Some(limit) };
+
+    Ok(ProposalTextLimitsResponse {
+        max_title_length: get_limit(constants.max_proposal_title_length),
+        max_description_length: get_limit(constants.max_proposal_description_length),
+        max_description_summary_length: MAX_DESCRIPTION_SUMMARY_LENGTH as u64,
     })
 }
 
-fn get_user_lockups_with_predicate(
+pub fn query_proposal_slots(
     deps: Deps<NeutronQuery>,
-    env: Env,
-    address: String,
-    predicate: impl FnMut(&LockEntry) -> bool,
-    start_from: u32,
-    limit: u32,
-) -> StdResult<Vec<LockEntryWithPower>> {
-    let addr = deps.api.addr_validate(&address)?;
+    round_id: u64,
+    tranche_id: u64,
+) -> StdResult<ProposalSlotsResponse> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    let max_proposals =
+        get_max_proposals_per_tranche_round(deps.storage, &constants, round_id, tranche_id)?;
+    let proposals_count = get_proposals_count(deps.storage, round_id, tranche_id);
+
+    Ok(ProposalSlotsResponse {
+        max_proposals,
+        proposals_count,
+        remaining_slots: max_proposals
+            .map(|max_proposals| max_proposals.saturating_sub(proposals_count)),
+    })
+}
 
-    let raw_lockups = query_user_lockups(deps, addr, predicate, start_from, limit);
+pub fn query_invalidated_proposals(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+) -> StdResult<InvalidatedProposalsResponse> {
+    let proposals = INVALIDATED_PROPOSALS
+        .prefix((round_id, tranche_id))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            entry.map(|(proposal_id, info)| InvalidatedProposal {
+                proposal_id,
+                reason: info.reason,
+                invalidated_at: info.invalidated_at,
+            })
+        })
+        .collect::<StdResult<Vec<InvalidatedProposal>>>()?;
 
-    let constants = CONSTANTS.load(deps.storage)?;
-    let current_round_id = compute_current_round_id(&env, &constants)?;
-    let round_end = compute_round_end(&constants, current_round_id)?;
+    Ok(InvalidatedProposalsResponse { proposals })
+}
 
-    // enrich the lockups by computing the voting power for each lockup
-    let enriched_lockups = raw_lockups
-        .iter()
-        .map(|lock| {
-            to_lockup_with_power(
-                deps,
-                env.clone(),
-                &constants,
-                current_round_id,
-                round_end,
-                lock.clone(),
-            )
+pub fn query_tribute_contract(deps: Deps<NeutronQuery>) -> StdResult<TributeContractResponse> {
+    Ok(TributeContractResponse {
+        tribute_contract: TRIBUTE_CONTRACT.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_hash_only_description_tranches(
+    deps: Deps<NeutronQuery>,
+) -> StdResult<HashOnlyDescriptionTranchesResponse> {
+    let tranche_ids = HASH_ONLY_DESCRIPTION_TRANCHES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .filter_map(|l| {
+            if l.is_err() {
+                deps.api.debug(&format!(
+                    "Error when querying hash-only description tranches: {:?}",
+                    l
+                ));
+            }
+            l.ok()
         })
         .collect();
 
-    Ok(enriched_lockups)
+    Ok(HashOnlyDescriptionTranchesResponse { tranche_ids })
 }
 
-pub fn query_all_user_lockups(
+fn query_user_lockups(
     deps: Deps<NeutronQuery>,
-    env: Env,
-    address: String,
+    user_address: Addr,
+    predicate: impl FnMut(&LockEntry) -> bool,
     start_from: u32,
+    start_after: Option<u64>,
     limit: u32,
-) -> StdResult<AllUserLockupsResponse> {
-    let lockups = get_user_lockups_with_predicate(deps, env, address, |_| true, start_from, limit)?;
-    Ok(AllUserLockupsResponse { lockups })
+) -> Vec<LockEntry> {
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
+    LOCKS_MAP
+        .prefix(user_address)
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|l| l.unwrap().1)
+        .filter(predicate)
+        .skip(skip)
+        .take(limit as usize)
+        .collect()
 }
 
-pub fn query_specific_user_lockups(
-    deps: Deps<NeutronQuery>,
-    env: Env,
-    address: String,
-    lock_ids: Vec<u64>,
-) -> StdResult<SpecificUserLockupsResponse> {
-    let lock_ids_set: HashSet<u64> = lock_ids.into_iter().collect();
+pub fn query_whitelist(deps: Deps<NeutronQuery>) -> StdResult<WhitelistResponse> {
+    Ok(WhitelistResponse {
+        whitelist: WHITELIST.load(deps.storage)?,
+    })
+}
 
-    let lockups = get_user_lockups_with_predicate(
-        deps,
-        env,
-        address,
-        |lock| lock_ids_set.contains(&lock.lock_id),
-        0,
-        lock_ids_set.len() as u32,
-    )?;
+pub fn query_whitelist_admins(deps: Deps<NeutronQuery>) -> StdResult<WhitelistAdminsResponse> {
+    Ok(WhitelistAdminsResponse {
+        admins: WHITELIST_ADMINS.load(deps.storage)?,
+    })
+}
 
-    Ok(SpecificUserLockupsResponse { lockups })
+pub fn query_total_locked_tokens(deps: Deps<NeutronQuery>) -> StdResult<TotalLockedTokensResponse> {
+    Ok(TotalLockedTokensResponse {
+        total_locked_tokens: LOCKED_TOKENS.load(deps.storage)?,
+    })
 }
 
-// Helper function to handle the common logic for both query functions
-fn enrich_lockups_with_tranche_infos(
+// Returns all the validator queries that are registered
+// by the contract right now, for each query returning the address of the validator it is
+// associated with, plus its query id.
+pub fn query_registered_validator_queries(
     deps: Deps<NeutronQuery>,
-    env: Env,
-    address: String,
-    lockups: Vec<LockEntryWithPower>,
-) -> StdResult<Vec<LockupWithPerTrancheInfo>> {
-    let converted_addr = deps.api.addr_validate(&address)?;
-
-    let tranche_ids = TRANCHE_MAP
+) -> StdResult<RegisteredValidatorQueriesResponse> {
+    let query_ids = VALIDATOR_TO_QUERY_ID
         .range(deps.storage, None, None, Order::Ascending)
-        .map(|tranche| tranche.unwrap().1.id)
-        .collect::<Vec<u64>>();
-
-    let constants = CONSTANTS.load(deps.storage)?;
-    let current_round_id = compute_current_round_id(&env, &constants)?;
-
-    // enrich lockups with some info per tranche
-    let lockups_with_per_tranche_info: Vec<LockupWithPerTrancheInfo> = lockups
-        .iter()
-        .map(|lock| {
-            // iterate all tranches
-            let tranche_infos = tranche_ids
-                .iter()
-                .filter_map(|tranche_id| {
-                    // add which proposal the lock voted for
-                    let voted_for_proposal_res: StdResult<Option<u64>> = VOTE_MAP
-                        .may_load(
-                            deps.storage,
-                            (
-                                (current_round_id, *tranche_id),
-                                converted_addr.clone(),
-                                lock.lock_entry.lock_id,
-                            ),
-                        )
-                        .map(|vote| vote.map(|v| v.prop_id));
-
-                    // if there was an error in the store while loading the vote for the lockup,
-                    // we filter out the tranche by returning None
-                    if voted_for_proposal_res.is_err() {
-                        return None;
-                    }
-
-                    let voted_for_proposal = voted_for_proposal_res.unwrap();
-
-                    let next_round_voting_allowed_res: StdResult<u64> =
-                        if voted_for_proposal.is_some() {
-                            // if the proposal voted in this round, we ignore the VOTING_ALLOWED_ROUND map,
-                            // since it just contains the future information on when the lockup will be able to vote again
-                            // if it doesn't change the vote, but it can vote in the current round either way
-                            Ok(current_round_id)
-                        } else {
-                            // if the lockup has not voted in this round, VOTING_ALLOWED_ROUND does contain
-                            // current information on whether the lockup can vote right now or not
-                            VOTING_ALLOWED_ROUND
-                                .may_load(deps.storage, (*tranche_id, lock.lock_entry.lock_id))
-                                .map(|voting_allowed_round| {
-                                    voting_allowed_round.unwrap_or(current_round_id)
-                                })
-                        };
-
-                    // if there was an error in the store while loading the next allowed round for voting,
-                    // we filter out the tranche by returning None
-                    if next_round_voting_allowed_res.is_err() {
-                        return None;
-                    }
-
-                    let next_round_voting_allowed = next_round_voting_allowed_res.unwrap();
-
-                    // return the info for this tranche
-                    Some(PerTrancheLockupInfo {
-                        tranche_id: *tranche_id,
-                        next_round_lockup_can_vote: next_round_voting_allowed,
-                        current_voted_on_proposal: voted_for_proposal,
-                    })
-                })
-                .collect::<Vec<PerTrancheLockupInfo>>();
-            // combine the info for all tranches
-            LockupWithPerTrancheInfo {
-                lock_with_power: lock.clone(),
-                per_tranche_info: tranche_infos,
+        .filter_map(|l| {
+            if l.is_err() {
+                deps.api
+                    .debug(&format!("Error when querying validator query id: {:?}", l));
             }
+            l.ok()
         })
         .collect();
 
-    // return the enriched lockups
-    Ok(lockups_with_per_tranche_info)
-}
-
-pub fn query_all_user_lockups_with_tranche_infos(
-    deps: Deps<NeutronQuery>,
-    env: Env,
-    address: String,
-    start_from: u32,
-    limit: u32,
-) -> StdResult<AllUserLockupsWithTrancheInfosResponse> {
-    let lockups = query_all_user_lockups(deps, env.clone(), address.clone(), start_from, limit)?;
-    let enriched_lockups = enrich_lockups_with_tranche_infos(deps, env, address, lockups.lockups)?;
-    Ok(AllUserLockupsWithTrancheInfosResponse {
-        lockups_with_per_tranche_infos: enriched_lockups,
-    })
+    Ok(RegisteredValidatorQueriesResponse { query_ids })
 }
 
-pub fn query_specific_user_lockups_with_tranche_infos(
+pub fn query_validators_info(
     deps: Deps<NeutronQuery>,
-    env: Env,
-    address: String,
-    lock_ids: Vec<u64>,
-) -> StdResult<SpecificUserLockupsWithTrancheInfosResponse> {
-    let lockups = query_specific_user_lockups(deps, env.clone(), address.clone(), lock_ids)?;
-    let enriched_lockups = enrich_lockups_with_tranche_infos(deps, env, address, lockups.lockups)?;
-
-    Ok(SpecificUserLockupsWithTrancheInfosResponse {
-        lockups_with_per_tranche_infos: enriched_lockups,
-    })
+    round_id: u64,
+) -> StdResult<Vec<ValidatorInfo>> {
+    Ok(VALIDATORS_INFO
+        .prefix(round_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|l| l.unwrap().1)
+        .collect())
 }
 
-pub fn query_expired_user_lockups(
+pub fn query_validators_per_round(
     deps: Deps<NeutronQuery>,
-    env: Env,
-    address: String,
-    start_from: u32,
-    limit: u32,
-) -> StdResult<ExpiredUserLockupsResponse> {
-    let user_address = deps.api.addr_validate(&address)?;
-    let expired_lockup_predicate = |l: &LockEntry| l.lock_end < env.block.time;
-
-    Ok(ExpiredUserLockupsResponse {
-        lockups: query_user_lockups(
-            deps,
-            user_address,
-            expired_lockup_predicate,
-            start_from,
-            limit,
-        ),
-    })
+    round_id: u64,
+) -> StdResult<Vec<(u128, String)>> {
+    Ok(VALIDATORS_PER_ROUND
+        .sub_prefix(round_id)
+        .range(deps.storage, None, None, Order::Descending)
+        .map(|l| l.unwrap().0)
+        .collect())
 }
 
-pub fn query_proposal(
+// Returns the power ratio of a validator for a given round
+// This will return an error if there is an error parsing the store,
+// but will return 0 if there is no power ratio for the given validator and the round.
+pub fn query_validator_power_ratio(
     deps: Deps<NeutronQuery>,
+    validator: String,
     round_id: u64,
-    tranche_id: u64,
-    proposal_id: u64,
-) -> StdResult<ProposalResponse> {
-    Ok(ProposalResponse {
-        proposal: PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?,
-    })
+) -> StdResult<ValidatorPowerRatioResponse> {
+    get_validator_power_ratio_for_round(deps.storage, round_id, validator)
+        .map(|r| ValidatorPowerRatioResponse { ratio: r }) // error can stay untouched
 }
 
-pub fn query_user_voting_power(
+// Resolves the given denom to the validator whose LSM shares it represents, in the same way as
+// validate_denom() does when the tokens are locked, but returns the reason instead of an error
+// if the denom can't be locked in the current round.
+pub fn query_denom_validator(
     deps: Deps<NeutronQuery>,
     env: Env,
-    address: String,
-) -> StdResult<UserVotingPowerResponse> {
-    let user_address = deps.api.addr_validate(&address)?;
+    denom: String,
+) -> StdResult<DenomValidatorResponse> {
     let constants = CONSTANTS.load(deps.storage)?;
-    let current_round_id = compute_current_round_id(&env, &constants)?;
-    let round_end = compute_round_end(&constants, current_round_id)?;
-
-    let voting_power = LOCKS_MAP
-        .prefix(user_address)
-        .range(deps.storage, None, None, Order::Ascending)
-        .map(|l| l.unwrap().1)
-        .filter(|l| l.lock_end > round_end)
-        .map(|lockup| {
-            to_lockup_with_power(
-                deps,
-                env.clone(),
-                &constants,
-                current_round_id,
-                round_end,
-                lockup,
-            )
-            .current_voting_power
-            .u128()
-        })
-        .sum();
+    let round_id = compute_lock_round_id(&env, &constants)?;
 
-    Ok(UserVotingPowerResponse { voting_power })
+    get_denom_validator_at_round(deps, &constants, denom, round_id, round_id)
 }
 
-// This function queries user votes for the given round and tranche.
-// It goes through all user votes per lock_id and groups them by the
-// proposal ID. The returned result will contain one VoteWithPower per
-// each proposal ID, with the total power summed up from all lock IDs
-// used to vote for that proposal. The votes that are referring to the
-// validators that later dropped out from the top N will be filtered out.
-pub fn query_user_votes(
+// Resolves the given denom in the same way as DenomValidator, but checks whether the validator was in the
+// validator set of the given round. The validator set of a round is only stored once the round sees any
+// activity, so for the rounds without it, the validator set of the last preceding round that has it is used,
+// same as it would be copied over when the round gets initialized.
+pub fn query_can_lock_denom_at_round(
     deps: Deps<NeutronQuery>,
+    env: Env,
+    denom: String,
     round_id: u64,
-    tranche_id: u64,
-    user_address: String,
-) -> StdResult<UserVotesResponse> {
-    let user_address = deps.api.addr_validate(&user_address)?;
-    let mut voted_proposals_power_sum: HashMap<u64, Decimal> = HashMap::new();
-
-    let votes = VOTE_MAP
-        .prefix(((round_id, tranche_id), user_address.clone()))
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|vote| match vote {
-            Err(_) => None,
-            Ok(vote) => Some(vote.1),
-        })
-        .collect::<Vec<Vote>>();
+) -> StdResult<DenomValidatorResponse> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_lock_round_id(&env, &constants)?;
 
-    for vote in votes {
-        let vote_validator = vote.time_weighted_shares.0;
-        // If the validator was active in the given round, we will get its power ratio.
-        // If it wasn't we will get 0, which means we should filter out this vote.
-        let val_power_ratio =
-            get_validator_power_ratio_for_round(deps.storage, round_id, vote_validator)?;
+    if round_id > current_round_id {
+        return Err(StdError::generic_err(format!(
+            "Round {} has not started yet",
+            round_id
+        )));
+    }
 
-        let vote_power = vote.time_weighted_shares.1.checked_mul(val_power_ratio)?;
-        if vote_power == Decimal::zero() {
-            continue;
-        }
+    let mut validator_set_round_id = round_id;
+    while validator_set_round_id > 0
+        && !is_validator_store_initialized(deps.storage, validator_set_round_id)
+    {
+        validator_set_round_id -= 1;
+    }
 
-        let current_power = match voted_proposals_power_sum.get(&vote.prop_id) {
-            None => Decimal::zero(),
-            Some(current_power) => *current_power,
-        };
+    get_denom_validator_at_round(deps, &constants, denom, round_id, validator_set_round_id)
+}
 
-        let new_power = current_power.checked_add(vote_power)?;
-        voted_proposals_power_sum.insert(vote.prop_id, new_power);
-    }
+fn get_denom_validator_at_round(
+    deps: Deps<NeutronQuery>,
+    constants: &Constants,
+    denom: String,
+    round_id: u64,
+    validator_set_round_id: u64,
+) -> StdResult<DenomValidatorResponse> {
+    let resolution = match resolve_validator_from_denom(&deps, constants, denom.clone()) {
+        Err(err) => DenomResolution::NotLockable {
+            validator: None,
+            reason: err.to_string(),
+        },
+        Ok(validator)
+            if !is_active_round_validator(deps.storage, validator_set_round_id, &validator) =>
+        {
+            DenomResolution::NotLockable {
+                reason: format!(
+                    "Validator {} is not among the top {} validators by delegated tokens in round {}",
+                    validator, constants.max_validator_shares_participating, round_id
+                ),
+                validator: Some(validator),
+            }
+        }
+        Ok(validator) => DenomResolution::Lockable {
+            power_ratio: get_validator_power_ratio_for_round(
+                deps.storage,
+                validator_set_round_id,
+                validator.clone(),
+            )?,
+            validator,
+        },
+    };
 
-    if voted_proposals_power_sum.is_empty() {
-        return Err(StdError::generic_err(
-            "User didn't vote in the given round and tranche",
-        ));
-    }
+    Ok(DenomValidatorResponse {
+        denom,
+        round_id,
+        resolution,
+    })
+}
 
-    let votes: Vec<VoteWithPower> = voted_proposals_power_sum
-        .into_iter()
-        .map(|vote| VoteWithPower {
-            prop_id: vote.0,
-            power: vote.1,
+pub fn query_validator_slashes(deps: Deps<NeutronQuery>) -> StdResult<ValidatorSlashesResponse> {
+    let slashes = VALIDATOR_SLASHES
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|l| {
+            if l.is_err() {
+                deps.api
+                    .debug(&format!("Error when querying validator slashes: {:?}", l));
+            }
+            l.ok()
         })
         .collect();
 
-    Ok(UserVotesResponse { votes })
+    Ok(ValidatorSlashesResponse { slashes })
 }
 
-pub fn query_round_tranche_proposals(
+pub fn query_validator_locks(
     deps: Deps<NeutronQuery>,
-    round_id: u64,
-    tranche_id: u64,
-    start_from: u32,
+    validator: String,
+    start_after: Option<u64>,
     limit: u32,
-) -> StdResult<RoundProposalsResponse> {
-    if TRANCHE_MAP.load(deps.storage, tranche_id).is_err() {
-        return Err(StdError::generic_err("Tranche does not exist"));
-    }
-
-    let props = PROPOSAL_MAP
-        .prefix((round_id, tranche_id))
-        .range(deps.storage, None, None, Order::Ascending)
-        .skip(start_from as usize)
-        .take(limit as usize);
+) -> StdResult<ValidatorLocksResponse> {
+    let locks = VALIDATOR_LOCKS
+        .prefix(validator)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|lock| lock.map(|(lock_id, owner)| ValidatorLock { lock_id, owner }))
+        .collect::<StdResult<Vec<ValidatorLock>>>()?;
 
-    let mut proposals = vec![];
-    for proposal in props {
-        let (_, proposal) = proposal?;
-        proposals.push(proposal);
-    }
+    let next_key = get_next_key(&locks, limit as usize, |lock| lock.lock_id);
 
-    Ok(RoundProposalsResponse { proposals })
+    Ok(ValidatorLocksResponse { locks, next_key })
 }
 
-pub fn query_current_round_id(
+#[allow(clippy::too_many_arguments)]
+pub fn query_recompute_round_totals_dry_run(
     deps: Deps<NeutronQuery>,
     env: Env,
-) -> StdResult<CurrentRoundResponse> {
-    let constants = &CONSTANTS.load(deps.storage)?;
-    let round_id = compute_round_id_for_timestamp(constants, env.block.time.nanos())?;
-
-    let round_end = compute_round_end(constants, round_id)?;
+    round_id: u64,
+    validator: String,
+    corrected_ratio: Decimal,
+    start_from: u32,
+    start_after: Option<(u64, u64)>,
+    limit: u32,
+) -> StdResult<RecomputeRoundTotalsDryRunResponse> {
+    let constants = CONSTANTS.load(deps.storage)?;
+    validate_round_ended_for_power_ratio_correction(&env, &constants, round_id)?;
 
-    Ok(CurrentRoundResponse {
-        round_id,
-        round_end,
-    })
-}
+    let current_power_ratio =
+        load_round_validator_info(deps.storage, round_id, &validator)?.power_ratio;
+    let correction =
+        POWER_RATIO_CORRECTIONS.may_load(deps.storage, (round_id, validator.clone()))?;
 
-pub fn query_round_end(deps: Deps<NeutronQuery>, round_id: u64) -> StdResult<RoundEndResponse> {
-    let constants = &CONSTANTS.load(deps.storage)?;
-    let round_end = compute_round_end(constants, round_id)?;
+    let round_shares = get_validator_shares_for_round(deps.storage, round_id, validator.clone())?;
+    let current_round_total_power = get_total_power_for_round(deps, round_id)?;
+    let corrected_round_total_power = current_round_total_power
+        - round_shares * current_power_ratio
+        + round_shares * corrected_ratio;
 
-    Ok(RoundEndResponse { round_end })
-}
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
+    let mut proposals = vec![];
+    let mut scanned_keys = vec![];
+    for proposal in PROPOSAL_MAP
+        .sub_prefix(round_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .skip(skip)
+        .take(limit as usize)
+    {
+        let ((tranche_id, proposal_id), proposal) = proposal?;
+        scanned_keys.push((tranche_id, proposal_id));
 
-pub fn query_top_n_proposals(
-    deps: Deps<NeutronQuery>,
-    round_id: u64,
-    tranche_id: u64,
-    num: usize,
-) -> StdResult<TopNProposalsResponse> {
-    if TRANCHE_MAP.load(deps.storage, tranche_id).is_err() {
-        return Err(StdError::generic_err("Tranche does not exist"));
-    }
+        let shares =
+            get_validator_shares_for_proposal(deps.storage, proposal_id, validator.clone())?;
+        if shares.is_zero() {
+            continue;
+        }
 
-    // Iterate through PROPS_BY_SCORE to find the top num props
-    let top_prop_ids: Vec<u64> = PROPS_BY_SCORE
-        .sub_prefix((round_id, tranche_id))
-        .range(deps.storage, None, None, Order::Descending)
-        .take(num)
-        .map(|x| match x {
-            Ok((_, prop_id)) => prop_id,
-            Err(_) => 0, // Handle the error case appropriately
-        })
-        .collect();
+        // while a correction is in progress, the proposals that were not processed
+        // yet still have their power computed using the old power ratio
+        let applied_power_ratio = match &correction {
+            Some(correction)
+                if correction
+                    .last_processed_proposal
+                    .is_none_or(|last| (tranche_id, proposal_id) > last) =>
+            {
+                correction.old_power_ratio
+            }
+            _ => current_power_ratio,
+        };
 
-    let mut top_props = vec![];
+        let corrected_power = (get_total_power_for_proposal(deps.storage, proposal_id)?
+            - shares * applied_power_ratio
+            + shares * corrected_ratio)
+            .to_uint_ceil();
 
-    for prop_id in top_prop_ids {
-        let prop = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, prop_id))?;
-        top_props.push(prop);
+        if corrected_power != proposal.power {
+            proposals.push(ProposalPowerDelta {
+                tranche_id,
+                proposal_id,
+                current_power: proposal.power,
+                corrected_power,
+            });
+        }
     }
 
-    // get total voting power for the round
-    let total_voting_power = get_total_power_for_round(deps, round_id)?.to_uint_ceil(); // TODO: decide on rounding
-
-    let top_proposals = top_props
-        .into_iter()
-        .map(|mut prop| {
-            prop.percentage = if total_voting_power.is_zero() {
-                // if total voting power is zero, each proposal must necessarily have 0 score
-                // avoid division by zero and set percentage to 0
-                Uint128::zero()
-            } else {
-                (prop.power * Uint128::new(100)) / total_voting_power
-            };
-            prop
-        })
-        .collect();
-
-    // return top props
-    Ok(TopNProposalsResponse {
-        proposals: top_proposals,
+    Ok(RecomputeRoundTotalsDryRunResponse {
+        current_power_ratio,
+        corrected_power_ratio: corrected_ratio,
+        current_round_total_power,
+        corrected_round_total_power,
+        proposals,
+        next_key: get_next_key(&scanned_keys, limit as usize, |key| *key),
     })
 }
 
-pub fn query_tranches(deps: Deps<NeutronQuery>) -> StdResult<TranchesResponse> {
-    let tranches = TRANCHE_MAP
-        .range(deps.storage, None, None, Order::Ascending)
-        .map(|t| t.unwrap().1)
-        .collect();
+pub fn query_notification_preferences(
+    deps: Deps<NeutronQuery>,
+    address: String,
+) -> StdResult<NotificationPreferencesResponse> {
+    let address = deps.api.addr_validate(&address)?;
 
-    Ok(TranchesResponse { tranches })
+    Ok(NotificationPreferencesResponse {
+        preferences: NOTIFICATION_PREFERENCES.may_load(deps.storage, address)?,
+    })
 }
 
-fn query_user_lockups(
+pub fn query_all_notification_preferences(
     deps: Deps<NeutronQuery>,
-    user_address: Addr,
-    predicate: impl FnMut(&LockEntry) -> bool,
+    category: Option<NotificationCategory>,
     start_from: u32,
+    start_after: Option<String>,
     limit: u32,
-) -> Vec<LockEntry> {
-    LOCKS_MAP
-        .prefix(user_address)
-        .range(deps.storage, None, None, Order::Ascending)
-        .map(|l| l.unwrap().1)
-        .filter(predicate)
-        .skip(start_from as usize)
+) -> StdResult<AllNotificationPreferencesResponse> {
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
+
+    let preferences = NOTIFICATION_PREFERENCES
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|entry| match (entry, &category) {
+            (Ok((_, preferences)), Some(category)) => preferences.categories.contains(category),
+            _ => true,
+        })
+        .skip(skip)
         .take(limit as usize)
-        .collect()
+        .map(|entry| {
+            entry.map(|(address, preferences)| UserNotificationPreferences {
+                address,
+                preferences,
+            })
+        })
+        .collect::<StdResult<Vec<UserNotificationPreferences>>>()?;
+    let next_key = get_next_key(&preferences, limit as usize, |preferences| {
+        preferences.address.to_string()
+    });
+
+    Ok(AllNotificationPreferencesResponse {
+        preferences,
+        next_key,
+    })
 }
 
-pub fn query_whitelist(deps: Deps<NeutronQuery>) -> StdResult<WhitelistResponse> {
-    Ok(WhitelistResponse {
-        whitelist: WHITELIST.load(deps.storage)?,
+pub fn query_proposal_template(
+    deps: Deps<NeutronQuery>,
+    template_id: u64,
+) -> StdResult<ProposalTemplateResponse> {
+    let template = PROPOSAL_TEMPLATES.load(deps.storage, template_id)?;
+    let proposals = TEMPLATE_PROPOSALS
+        .prefix(template_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            entry.map(|(round_id, proposal_id)| TemplateProposal {
+                round_id,
+                proposal_id,
+            })
+        })
+        .collect::<StdResult<Vec<TemplateProposal>>>()?;
+
+    Ok(ProposalTemplateResponse {
+        template,
+        proposals,
     })
 }
 
-pub fn query_whitelist_admins(deps: Deps<NeutronQuery>) -> StdResult<WhitelistAdminsResponse> {
-    Ok(WhitelistAdminsResponse {
-        admins: WHITELIST_ADMINS.load(deps.storage)?,
+pub fn query_round_data_pruning(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+) -> StdResult<RoundDataPruningResponse> {
+    Ok(RoundDataPruningResponse {
+        pruning: ROUND_DATA_PRUNING.may_load(deps.storage, round_id)?,
     })
 }
 
-pub fn query_total_locked_tokens(deps: Deps<NeutronQuery>) -> StdResult<TotalLockedTokensResponse> {
-    Ok(TotalLockedTokensResponse {
-        total_locked_tokens: LOCKED_TOKENS.load(deps.storage)?,
+pub fn query_round_results(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+) -> StdResult<RoundResultsResponse> {
+    Ok(RoundResultsResponse {
+        results: ROUND_RESULTS.may_load(deps.storage, (round_id, tranche_id))?,
     })
 }
 
-// Returns all the validator queries that are registered
-// by the contract right now, for each query returning the address of the validator it is
-// associated with, plus its query id.
-pub fn query_registered_validator_queries(
+pub fn query_admin_actions(
     deps: Deps<NeutronQuery>,
-) -> StdResult<RegisteredValidatorQueriesResponse> {
-    let query_ids = VALIDATOR_TO_QUERY_ID
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|l| {
-            if l.is_err() {
-                deps.api
-                    .debug(&format!("Error when querying validator query id: {:?}", l));
-            }
-            l.ok()
-        })
-        .collect();
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<AdminActionsResponse> {
+    let actions = ADMIN_ACTIONS_LOG
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|entry| entry.map(|(_, action)| action))
+        .collect::<StdResult<Vec<AdminAction>>>()?;
+    let next_key = get_next_key(&actions, limit as usize, |action| action.action_id);
 
-    Ok(RegisteredValidatorQueriesResponse { query_ids })
+    Ok(AdminActionsResponse { actions, next_key })
 }
 
-pub fn query_validators_info(
+pub fn query_proposal_templates(
     deps: Deps<NeutronQuery>,
-    round_id: u64,
-) -> StdResult<Vec<ValidatorInfo>> {
-    Ok(VALIDATORS_INFO
-        .prefix(round_id)
-        .range(deps.storage, None, None, Order::Ascending)
-        .map(|l| l.unwrap().1)
-        .collect())
+    start_from: u32,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<ProposalTemplatesResponse> {
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
+    let templates = PROPOSAL_TEMPLATES
+        .range(deps.storage, start, None, Order::Ascending)
+        .skip(skip)
+        .take(limit as usize)
+        .map(|entry| entry.map(|(_, template)| template))
+        .collect::<StdResult<Vec<ProposalTemplate>>>()?;
+    let next_key = get_next_key(&templates, limit as usize, |template| template.template_id);
+
+    Ok(ProposalTemplatesResponse {
+        templates,
+        next_key,
+    })
 }
 
-pub fn query_validators_per_round(
-    deps: Deps<NeutronQuery>,
-    round_id: u64,
-) -> StdResult<Vec<(u128, String)>> {
-    Ok(VALIDATORS_PER_ROUND
-        .sub_prefix(round_id)
-        .range(deps.storage, None, None, Order::Descending)
-        .map(|l| l.unwrap().0)
-        .collect())
+// Paginated queries start after the given key if one is provided. Otherwise, they fall back to
+// the deprecated offset-based pagination and skip the given number of entries.
+pub fn get_pagination_start<'a, K: PrimaryKey<'a>>(
+    start_after: Option<K>,
+    start_from: usize,
+) -> (Option<Bound<'a, K>>, usize) {
+    match start_after {
+        Some(start_after) => (Some(Bound::exclusive(start_after)), 0),
+        None => (None, start_from),
+    }
 }
 
-// Returns the power ratio of a validator for a given round
-// This will return an error if there is an error parsing the store,
-// but will return 0 if there is no power ratio for the given validator and the round.
-pub fn query_validator_power_ratio(
+// Returns the key of the last entry of a page, to be used as start_after to get the next page,
+// or None if the page holds fewer than limit entries, since then there are no more entries.
+pub fn get_next_key<T, K>(page: &[T], limit: usize, key: impl Fn(&T) -> K) -> Option<K> {
+    if page.len() < limit {
+        return None;
+    }
+
+    page.last().map(key)
+}
+
+pub fn query_export_raw_state(
     deps: Deps<NeutronQuery>,
-    validator: String,
-    round_id: u64,
-) -> StdResult<ValidatorPowerRatioResponse> {
-    get_validator_power_ratio_for_round(deps.storage, round_id, validator)
-        .map(|r| ValidatorPowerRatioResponse { ratio: r }) // error can stay untouched
+    map: RawStateMap,
+    start_after: Option<Binary>,
+    limit: u32,
+) -> StdResult<ExportRawStateResponse> {
+    if !CONSTANTS.load(deps.storage)?.raw_export_enabled {
+        return Err(StdError::generic_err("Raw state export is not enabled"));
+    }
+
+    let limit = limit as usize;
+    let entries = match map {
+        RawStateMap::Locks => export_raw_entries(deps.storage, &LOCKS_MAP, start_after, limit),
+        RawStateMap::Votes => export_raw_entries(deps.storage, &VOTE_MAP, start_after, limit),
+        RawStateMap::Proposals => {
+            export_raw_entries(deps.storage, &PROPOSAL_MAP, start_after, limit)
+        }
+    }?;
+    let next_key = get_next_key(&entries, limit, |entry| entry.key.clone());
+
+    Ok(ExportRawStateResponse { entries, next_key })
+}
+
+fn export_raw_entries<'a, K, T>(
+    storage: &dyn Storage,
+    map: &Map<K, T>,
+    start_after: Option<Binary>,
+    limit: usize,
+) -> StdResult<Vec<RawStateEntry>>
+where
+    K: PrimaryKey<'a>,
+    T: Serialize + DeserializeOwned,
+{
+    let start = start_after.map(|key| Bound::ExclusiveRaw(key.to_vec()));
+
+    map.range_raw(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| {
+            let (key, value) = entry?;
+            Ok(RawStateEntry {
+                key: Binary::from(key),
+                value: to_json_binary(&value)?,
+            })
+        })
+        .collect()
 }
 
 pub fn query_icq_managers(deps: Deps<NeutronQuery>) -> StdResult<ICQManagersResponse> {
@@ -2225,6 +7018,32 @@ pub fn compute_current_round_id(env: &Env, constants: &Constants) -> StdResult<u
     compute_round_id_for_timestamp(constants, env.block.time.nanos())
 }
 
+// Returns the round to which new locks and the validator information are attributed. Before the first round
+// starts, this is round 0 if the contract allows pre-launch locks, so that pre-launch locks give voting power
+// from round 0 on. All other actions, including voting, still require the first round to have started.
+pub fn compute_lock_round_id(env: &Env, constants: &Constants) -> StdResult<u64> {
+    if constants.allow_pre_launch_locks && env.block.time < constants.first_round_start {
+        return Ok(0);
+    }
+
+    compute_current_round_id(env, constants)
+}
+
+// Locks created before the first round starts must give voting power at least in round 0.
+fn validate_pre_launch_lock_end(
+    env: &Env,
+    constants: &Constants,
+    lock_end: Timestamp,
+) -> Result<(), ContractError> {
+    if env.block.time < constants.first_round_start && lock_end < compute_round_end(constants, 0)? {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Locks created before the first round starts must not expire before the end of round 0",
+        )));
+    }
+
+    Ok(())
+}
+
 fn compute_round_id_for_timestamp(constants: &Constants, timestamp: u64) -> StdResult<u64> {
     // If the first round has not started yet, return an error
     if timestamp < constants.first_round_start.nanos() {
@@ -2268,6 +7087,7 @@ fn update_voting_power_on_proposals(
             round_end,
             lock_entry.clone(),
             lock_epoch_length,
+            constants.interpolate_lock_power,
         ),
     };
     let new_scaled_shares = get_lock_time_weighted_shares(
@@ -2275,6 +7095,7 @@ fn update_voting_power_on_proposals(
         round_end,
         new_lock_entry.clone(),
         lock_epoch_length,
+        constants.interpolate_lock_power,
     );
 
     // With a future code changes, it could happen that the new power becomes less than
@@ -2306,6 +7127,11 @@ fn update_voting_power_on_proposals(
         )?;
 
         if let Some(mut vote) = vote {
+            // votes for invalidated proposals don't get any additional power
+            if INVALIDATED_PROPOSALS.has(deps.storage, (current_round, tranche_id, vote.prop_id)) {
+                continue;
+            }
+
             let current_vote_shares = if vote.time_weighted_shares.0.eq(&validator) {
                 vote.time_weighted_shares.1
             } else {
@@ -2349,6 +7175,16 @@ fn update_voting_power_on_proposals(
             // in this case we should insert voting allowed info as well. If user is refreshing a lockup
             // that was already used for voting, then this information is already saved in the store.
             if old_lock_entry.is_none() {
+                PROPOSAL_VOTERS.save(
+                    deps.storage,
+                    (
+                        (current_round, tranche_id),
+                        vote.prop_id,
+                        new_lock_entry.lock_id,
+                    ),
+                    sender,
+                )?;
+
                 let voting_allowed_round = current_round + proposal.deployment_duration;
                 VOTING_ALLOWED_ROUND.save(
                     deps.storage,
@@ -2358,20 +7194,22 @@ fn update_voting_power_on_proposals(
             }
 
             if power_change.is_increased {
-                add_validator_shares_to_proposal(
+                add_vote_shares_to_proposal(
                     deps.storage,
                     current_round,
                     vote.prop_id,
                     validator.clone(),
                     power_change.scaled_power_change,
+                    vote.tribute_weight_multiplier,
                 )?;
             } else {
-                remove_validator_shares_from_proposal(
+                remove_vote_shares_from_proposal(
                     deps.storage,
                     current_round,
                     vote.prop_id,
                     validator.clone(),
                     power_change.scaled_power_change,
+                    vote.tribute_weight_multiplier,
                 )?;
             }
 
@@ -2381,12 +7219,321 @@ fn update_voting_power_on_proposals(
                 tranche_id,
                 &proposal,
             )?;
+
+            if is_quadratic_power_scaling_enabled(deps.storage, tranche_id, current_round)? {
+                apply_quadratic_power_scaling(deps, constants, current_round, tranche_id, sender)?;
+            }
+
+            if get_vote_power_cap(deps.storage, current_round)?.is_some() {
+                update_owner_vote_power(deps.storage, current_round, tranche_id, sender)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn is_quadratic_power_scaling_enabled(
+    storage: &dyn Storage,
+    tranche_id: u64,
+    round_id: u64,
+) -> StdResult<bool> {
+    let setting = QUADRATIC_POWER_SCALING
+        .prefix(tranche_id)
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(round_id)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?;
+
+    Ok(setting.map(|(_, quadratic)| quadratic).unwrap_or(false))
+}
+
+pub fn get_tranche_tribute_weight_bonus(
+    storage: &dyn Storage,
+    tranche_id: u64,
+    round_id: u64,
+) -> StdResult<Option<TributeWeightBonus>> {
+    let setting = TRIBUTE_WEIGHT_BONUSES
+        .prefix(tranche_id)
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(round_id)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?;
+
+    Ok(setting.and_then(|(_, bonus)| bonus))
+}
+
+// Returns the percentage of the total round power that the locks of a single owner can contribute
+// within a tranche in the given round, or None if the vote power isn't capped in that round.
+pub fn get_vote_power_cap(storage: &dyn Storage, round_id: u64) -> StdResult<Option<Decimal>> {
+    let setting = VOTE_POWER_CAPS
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(round_id)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?;
+
+    Ok(setting.and_then(|(_, max_power_percentage)| max_power_percentage))
+}
+
+// Recomputes the power of the votes of the given owner within the given round and tranche, using the current
+// validator power ratios, and updates its position in OWNERS_BY_VOTE_POWER.
+fn update_owner_vote_power(
+    storage: &mut dyn Storage,
+    round_id: u64,
+    tranche_id: u64,
+    owner: &Addr,
+) -> StdResult<()> {
+    let votes = VOTE_MAP
+        .prefix(((round_id, tranche_id), owner.clone()))
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, Vote)>>>()?;
+
+    let mut owner_power = Decimal::zero();
+    for (_, vote) in votes {
+        let (validator, shares) = vote.time_weighted_shares;
+        let power_ratio = get_validator_power_ratio_for_round(storage, round_id, validator)?;
+        owner_power = owner_power.checked_add(shares.checked_mul(power_ratio)?)?;
+    }
+
+    let key = ((round_id, tranche_id), owner.clone());
+    if let Some(old_power) = OWNER_VOTE_POWER.may_load(storage, key.clone())? {
+        OWNERS_BY_VOTE_POWER.remove(storage, ((round_id, tranche_id), old_power, owner.clone()));
+    }
+
+    let owner_power = owner_power.to_uint_ceil().u128();
+    if owner_power == 0 {
+        OWNER_VOTE_POWER.remove(storage, key);
+        return Ok(());
+    }
+
+    OWNER_VOTE_POWER.save(storage, key, &owner_power)?;
+    OWNERS_BY_VOTE_POWER.save(
+        storage,
+        ((round_id, tranche_id), owner_power, owner.clone()),
+        &true,
+    )?;
+
+    Ok(())
+}
+
+// Returns the power that the owners above the vote power cap contribute to each proposal of the given round and
+// tranche beyond the cap, which is ignored when the votes are tallied. The cap is computed from the given total
+// round power, so that it follows the total round power at the time of the tally. The power of each owner above
+// the cap is reduced proportionally across all the proposals the owner voted for.
+pub fn get_vote_power_cap_excess(
+    deps: Deps<NeutronQuery>,
+    round_id: u64,
+    tranche_id: u64,
+    total_round_power: Decimal,
+) -> StdResult<HashMap<u64, Decimal>> {
+    let mut excess_per_proposal: HashMap<u64, Decimal> = HashMap::new();
+
+    let Some(max_power_percentage) = get_vote_power_cap(deps.storage, round_id)? else {
+        return Ok(excess_per_proposal);
+    };
+
+    let max_power = total_round_power
+        .checked_mul(max_power_percentage)?
+        .checked_div(Decimal::from_ratio(100u128, 1u128))
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    // The stored owner powers don't follow the later increases of the validator power ratios, so they are scaled
+    // by the growth of the power ratios in the round to get an upper bound of the current owner powers, while the
+    // current powers are recomputed from the votes of the owners. The number of owners above the cap is bounded
+    // by 100 / max_power_percentage, since their total power can't be above the total round power.
+    let power_ratio_growth = get_round_power_ratio_growth(deps.storage, round_id)?;
+
+    for owner in OWNERS_BY_VOTE_POWER
+        .sub_prefix((round_id, tranche_id))
+        .keys(deps.storage, None, None, Order::Descending)
+    {
+        let (owner_power, owner) = owner?;
+        if Decimal::from_ratio(owner_power, Uint128::one()).checked_mul(power_ratio_growth)?
+            <= max_power
+        {
+            break;
+        }
+
+        let votes = VOTE_MAP
+            .prefix(((round_id, tranche_id), owner))
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(u64, Vote)>>>()?;
+
+        let mut owner_power = Decimal::zero();
+        let mut votes_power = vec![];
+        for (_, vote) in votes {
+            // the votes for invalidated proposals don't count towards the power of the owner
+            if INVALIDATED_PROPOSALS.has(deps.storage, (round_id, tranche_id, vote.prop_id)) {
+                continue;
+            }
+
+            let (validator, shares) = vote.time_weighted_shares;
+            let power_ratio =
+                get_validator_power_ratio_for_round(deps.storage, round_id, validator)?;
+            let vote_power = shares.checked_mul(power_ratio)?;
+
+            owner_power = owner_power.checked_add(vote_power)?;
+            votes_power.push((vote.prop_id, vote_power));
+        }
+
+        let cap_factor =
+            get_vote_power_cap_factor(owner_power, total_round_power, max_power_percentage)?;
+        if cap_factor == Decimal::one() {
+            continue;
+        }
+
+        for (proposal_id, vote_power) in votes_power {
+            let excess = excess_per_proposal.entry(proposal_id).or_default();
+            *excess = excess.checked_add(vote_power.checked_mul(Decimal::one() - cap_factor)?)?;
+        }
+    }
+
+    Ok(excess_per_proposal)
+}
+
+// Returns the tribute weight multiplier of a vote with the given lock in the given round,
+// or None if the lock doesn't remain locked long enough after the round end to qualify for the bonus.
+fn get_tribute_weight_multiplier(
+    storage: &dyn Storage,
+    tranche_id: u64,
+    round_id: u64,
+    round_end: Timestamp,
+    lock_entry: &LockEntry,
+) -> StdResult<Option<Decimal>> {
+    Ok(
+        get_tranche_tribute_weight_bonus(storage, tranche_id, round_id)?
+            .filter(|bonus| {
+                lock_entry
+                    .lock_end
+                    .nanos()
+                    .saturating_sub(round_end.nanos())
+                    >= bonus.min_lock_duration
+            })
+            .map(|bonus| bonus.multiplier),
+    )
+}
+
+// In tranches with quadratic power scaling, the shares of all votes of an owner in the round are multiplied
+// by the same factor sqrt(P)/P, where P is the aggregate (linear) power of all the locks the owner voted with.
+// This makes the total power of the owner within the tranche equal to sqrt(P). The factor is recomputed each time
+// the owner votes in the tranche or changes the power of a lock that voted in it, using the validator power ratios
+// at that time. Scaled shares are rounded down to the Decimal precision, while the proposal power is rounded up,
+// as for all other proposals.
+fn apply_quadratic_power_scaling(
+    deps: &mut DepsMut<NeutronQuery>,
+    constants: &Constants,
+    round_id: u64,
+    tranche_id: u64,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    let round_end = compute_round_end(constants, round_id)?;
+
+    let votes = VOTE_MAP
+        .prefix(((round_id, tranche_id), sender.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, Vote)>>>()?;
+
+    // compute the unscaled shares of each vote and the aggregate power of the owner
+    let mut votes_with_linear_shares = vec![];
+    let mut aggregate_power = Decimal::zero();
+
+    for (lock_id, vote) in votes {
+        let lock_entry = match LOCKS_MAP.may_load(deps.storage, (sender.clone(), lock_id))? {
+            Some(lock_entry) => lock_entry,
+            None => continue,
+        };
+
+        let linear_shares = Decimal::from_ratio(
+            get_lock_time_weighted_shares(
+                &constants.round_lock_power_schedule,
+                round_end,
+                lock_entry,
+                constants.lock_epoch_length,
+                constants.interpolate_lock_power,
+            ),
+            Uint128::one(),
+        );
+        let power_ratio = get_validator_power_ratio_for_round(
+            deps.storage,
+            round_id,
+            vote.time_weighted_shares.0.clone(),
+        )?;
+
+        aggregate_power = aggregate_power.checked_add(linear_shares.checked_mul(power_ratio)?)?;
+        votes_with_linear_shares.push((lock_id, vote, linear_shares));
+    }
+
+    let scaling_factor = get_quadratic_scaling_factor(aggregate_power);
+
+    let mut updated_proposals = BTreeSet::new();
+    for (lock_id, mut vote, linear_shares) in votes_with_linear_shares {
+        let (validator, current_shares) = vote.time_weighted_shares.clone();
+        let scaled_shares = linear_shares.checked_mul(scaling_factor)?;
+
+        if scaled_shares == current_shares {
+            continue;
+        }
+
+        if scaled_shares > current_shares {
+            add_vote_shares_to_proposal(
+                deps.storage,
+                round_id,
+                vote.prop_id,
+                validator,
+                scaled_shares.checked_sub(current_shares)?,
+                vote.tribute_weight_multiplier,
+            )?;
+        } else {
+            remove_vote_shares_from_proposal(
+                deps.storage,
+                round_id,
+                vote.prop_id,
+                validator,
+                current_shares.checked_sub(scaled_shares)?,
+                vote.tribute_weight_multiplier,
+            )?;
         }
+
+        vote.time_weighted_shares.1 = scaled_shares;
+        VOTE_MAP.save(
+            deps.storage,
+            ((round_id, tranche_id), sender.clone(), lock_id),
+            &vote,
+        )?;
+
+        updated_proposals.insert(vote.prop_id);
+    }
+
+    for proposal_id in updated_proposals {
+        let proposal = PROPOSAL_MAP.load(deps.storage, (round_id, tranche_id, proposal_id))?;
+        update_proposal_and_props_by_score_maps(deps.storage, round_id, tranche_id, &proposal)?;
     }
 
     Ok(())
 }
 
+// Returns the factor sqrt(P)/P by which the shares of an owner with the aggregate power P are scaled.
+// Aggregate powers of at most 1 are not scaled, since that would increase the power of the owner.
+pub fn get_quadratic_scaling_factor(aggregate_power: Decimal) -> Decimal {
+    if aggregate_power <= Decimal::one() {
+        return Decimal::one();
+    }
+
+    aggregate_power.sqrt() / aggregate_power
+}
+
 // This function will lookup the vote that needs to be updated when user locks
 // more tokens or refreshes the existing lockup. Whether some vote should be
 // updated or not is determined by the following logic:
@@ -2406,19 +7553,16 @@ pub fn get_vote_for_update(
     validator: &str,
 ) -> Result<Option<Vote>, ContractError> {
     Ok(match old_lock_entry {
-        Some(old_lock_entry) => {
-            match VOTE_MAP.load(
+        Some(old_lock_entry) => VOTE_MAP
+            .load(
                 deps.storage,
                 (
                     (current_round, tranche_id),
                     sender.clone(),
                     old_lock_entry.lock_id,
                 ),
-            ) {
-                Ok(vote) => Some(vote),
-                Err(_) => None,
-            }
-        }
+            )
+            .ok(),
         None => {
             let mut voted_proposals: HashSet<u64> = HashSet::new();
 
@@ -2439,10 +7583,12 @@ pub fn get_vote_for_update(
                         "Failed to obtain proposal id that user voted on",
                     ))?;
 
-                    // Create a vote with 0 power, which will be updated later
+                    // Create a vote with 0 power, which will be updated later. The new lock doesn't
+                    // get the tribute weight bonus, unless it is used to vote explicitly.
                     Some(Vote {
                         prop_id,
                         time_weighted_shares: (validator.to_string(), Decimal::zero()),
+                        tribute_weight_multiplier: None,
                     })
                 }
                 _ => None,
@@ -2479,24 +7625,28 @@ fn update_proposal_and_props_by_score_maps(
     // Delete the proposal's old power in PROPS_BY_SCORE
     PROPS_BY_SCORE.remove(
         storage,
-        ((round_id, tranche_id), proposal.power.into(), proposal_id),
+        get_props_by_score_key(round_id, tranche_id, proposal.power, proposal_id),
     );
 
     // Get the new total power of the proposal
+    let old_power = proposal.power;
     let total_power = get_total_power_for_proposal(storage, proposal_id)?;
 
     // Save the new power into the proposal
     proposal.power = total_power.to_uint_ceil();
+    update_round_tranche_voted_power(storage, round_id, tranche_id, old_power, proposal.power)?;
 
     // Save the proposal
     PROPOSAL_MAP.save(storage, (round_id, tranche_id, proposal_id), &proposal)?;
 
-    // Save the proposal's new power in PROPS_BY_SCORE
-    PROPS_BY_SCORE.save(
-        storage,
-        ((round_id, tranche_id), proposal.power.into(), proposal_id),
-        &proposal_id,
-    )?;
+    // Save the proposal's new power in PROPS_BY_SCORE, unless the proposal was invalidated
+    if !INVALIDATED_PROPOSALS.has(storage, (round_id, tranche_id, proposal_id)) {
+        PROPS_BY_SCORE.save(
+            storage,
+            get_props_by_score_key(round_id, tranche_id, proposal.power, proposal_id),
+            &proposal_id,
+        )?;
+    }
 
     Ok(())
 }
@@ -2523,11 +7673,36 @@ where
             constants.lock_epoch_length,
             lockup_length,
             amount,
+            constants.interpolate_lock_power,
         );
         let old_voting_power = get_old_voting_power(round, round_end, amount);
         let scaled_shares = Decimal::from_ratio(scaled_amount, Uint128::one())
             - Decimal::from_ratio(old_voting_power, Uint128::one());
 
+        // keep track of the shares as of the start of the round, to interpolate the power within the round
+        if constants.interpolate_lock_power {
+            let round_start = round_end.minus_nanos(constants.round_length);
+            let scaled_start_amount = scale_lockup_power(
+                &constants.round_lock_power_schedule,
+                constants.lock_epoch_length,
+                lock_end - round_start.nanos(),
+                amount,
+                constants.interpolate_lock_power,
+            );
+            let old_start_voting_power = get_old_voting_power(round, round_start, amount);
+            let scaled_start_shares = Decimal::from_ratio(scaled_start_amount, Uint128::one())
+                - Decimal::from_ratio(old_start_voting_power, Uint128::one());
+
+            if !scaled_start_shares.is_zero() {
+                add_validator_start_shares_to_round_total(
+                    deps.storage,
+                    round,
+                    shares_validator.clone(),
+                    scaled_start_shares,
+                )?;
+            }
+        }
+
         // save some gas if there was no power change
         if scaled_shares.is_zero() {
             continue;
@@ -2568,6 +7743,8 @@ fn to_lockup_with_power(
             LockEntryWithPower {
                 lock_entry,
                 current_voting_power: Uint128::zero(),
+                interpolated_voting_power: Uint128::zero(),
+                note: None,
             }
         }
         Ok(validator) => {
@@ -2581,21 +7758,41 @@ fn to_lockup_with_power(
                     LockEntryWithPower {
                         lock_entry,
                         current_voting_power: Uint128::zero(),
+                        interpolated_voting_power: Uint128::zero(),
+                        note: None,
                     }
                 }
                 Ok(validator_power_ratio) => {
-                    let time_weighted_shares = get_lock_time_weighted_shares(
+                    let to_voting_power = |time_weighted_shares: Uint128| {
+                        validator_power_ratio
+                            .checked_mul(Decimal::from_ratio(time_weighted_shares, Uint128::one()))
+                    };
+
+                    let current_voting_power = to_voting_power(get_lock_time_weighted_shares(
                         &constants.round_lock_power_schedule,
                         round_end,
                         lock_entry.clone(),
                         constants.lock_epoch_length,
-                    );
-
-                    let current_voting_power = validator_power_ratio
-                        .checked_mul(Decimal::from_ratio(time_weighted_shares, Uint128::one()));
+                        constants.interpolate_lock_power,
+                    ));
+                    let interpolated_voting_power =
+                        to_voting_power(get_lock_time_weighted_shares_at_time(
+                            constants,
+                            round_end,
+                            lock_entry.clone(),
+                            env.block.time,
+                        ));
 
-                    match current_voting_power {
-                        Err(_) => {
+                    match (current_voting_power, interpolated_voting_power) {
+                        (Ok(current_voting_power), Ok(interpolated_voting_power)) => {
+                            LockEntryWithPower {
+                                lock_entry,
+                                current_voting_power: current_voting_power.to_uint_ceil(),
+                                interpolated_voting_power: interpolated_voting_power.to_uint_ceil(),
+                                note: None,
+                            }
+                        }
+                        _ => {
                             // if there was an overflow error, log this but return 0
                             deps.api.debug(&format!(
                                 "Overflow error when computing voting power for lock: {:?}",
@@ -2605,12 +7802,10 @@ fn to_lockup_with_power(
                             LockEntryWithPower {
                                 lock_entry: lock_entry.clone(),
                                 current_voting_power: Uint128::zero(),
+                                interpolated_voting_power: Uint128::zero(),
+                                note: None,
                             }
                         }
-                        Ok(current_voting_power) => LockEntryWithPower {
-                            lock_entry,
-                            current_voting_power: current_voting_power.to_uint_ceil(),
-                        },
                     }
                 }
             }
@@ -22,4 +22,7 @@ pub enum ContractError {
 
     #[error("Paused")]
     Paused,
+
+    #[error("Message {0} is paused")]
+    MessagePaused(String),
 }
@@ -6,7 +6,9 @@ pub mod msg;
 pub mod query;
 pub mod score_keeper;
 pub mod state;
+pub mod stats;
 pub mod validators_icqs;
+pub mod vote_relaying;
 
 #[cfg(test)]
 mod testing;
@@ -28,3 +30,9 @@ mod testing_fractional_voting;
 
 #[cfg(test)]
 mod testing_deployments;
+
+#[cfg(test)]
+mod testing_benchmarks;
+
+#[cfg(test)]
+mod testing_vote_relaying;
@@ -1,16 +1,22 @@
-use cosmwasm_std::{Decimal, Deps, Env, Order, StdError, StdResult, Storage};
+use cosmwasm_std::{Addr, Decimal, Deps, Env, Order, StdError, StdResult, Storage, Timestamp};
 
 use neutron_sdk::bindings::query::NeutronQuery;
 use neutron_std::types::ibc::applications::transfer::v1::{DenomTrace, TransferQuerier};
 
 use crate::state::{
-    ValidatorInfo, SCALED_ROUND_POWER_SHARES_MAP, VALIDATORS_INFO, VALIDATORS_PER_ROUND,
-    VALIDATORS_STORE_INITIALIZED,
+    ValidatorInfo, CONSTANTS, LOCK_VALIDATORS, ROUND_POWER_RATIO_GROWTH,
+    SCALED_ROUND_POWER_SHARES_MAP, SCALED_ROUND_START_POWER_SHARES_MAP, TOTAL_ROUND_POWER,
+    TOTAL_ROUND_START_POWER, VALIDATORS_INFO, VALIDATORS_PER_ROUND, VALIDATORS_STORE_INITIALIZED,
+    VALIDATOR_LOCKS,
 };
 use crate::{
-    contract::compute_current_round_id,
+    contract::{compute_lock_round_id, compute_round_end, interpolate_within_round},
     score_keeper::{get_total_power_for_proposal, update_power_ratio_for_proposal},
-    state::{Constants, Proposal, PROPOSAL_MAP, PROPS_BY_SCORE, TRANCHE_MAP},
+    state::{
+        get_props_by_score_key, Constants, Proposal, INVALIDATED_PROPOSALS, PROPOSAL_MAP,
+        PROPS_BY_SCORE, TRANCHE_MAP,
+    },
+    stats::update_round_tranche_voted_power,
 };
 
 pub const IBC_TOKEN_PREFIX: &str = "ibc/";
@@ -31,7 +37,7 @@ pub fn validate_denom(
     denom: String,
 ) -> StdResult<String> {
     let validator = resolve_validator_from_denom(&deps, constants, denom)?;
-    let round_id = compute_current_round_id(&env, constants)?;
+    let round_id = compute_lock_round_id(&env, constants)?;
     let max_validators = constants.max_validator_shares_participating;
 
     if is_active_round_validator(deps.storage, round_id, &validator) {
@@ -147,6 +153,14 @@ pub fn update_scores_due_to_power_ratio_change(
     old_power_ratio: Decimal,
     new_power_ratio: Decimal,
 ) -> StdResult<()> {
+    update_total_power_due_to_power_ratio_change(
+        storage,
+        validator,
+        round_id,
+        old_power_ratio,
+        new_power_ratio,
+    )?;
+
     // go through each tranche in the TRANCHE_MAP and collect its tranche_id
     let tranche_ids: Vec<u64> = TRANCHE_MAP
         .range(storage, None, None, Order::Ascending)
@@ -169,66 +183,197 @@ pub fn update_scores_due_to_power_ratio_change(
             .collect();
 
         for proposal in proposals {
-            // update the power ratio for the proposal
-            update_power_ratio_for_proposal(
+            update_proposal_score_due_to_power_ratio_change(
                 storage,
-                proposal.proposal_id,
-                validator.to_string(),
+                validator,
+                round_id,
+                tranche_id,
+                proposal,
                 old_power_ratio,
                 new_power_ratio,
             )?;
+        }
+    }
+    Ok(())
+}
 
-            // create a mutable copy of the proposal that we can safely manipulate in this loop
-            let mut proposal_copy = proposal.clone();
+// Recomputes the power of a single proposal after the power ratio of the given validator
+// changed from old_power_ratio to new_power_ratio, and updates the PROPS_BY_SCORE
+// and the voted power of the round and tranche accordingly.
+pub fn update_proposal_score_due_to_power_ratio_change(
+    storage: &mut dyn Storage,
+    validator: &str,
+    round_id: u64,
+    tranche_id: u64,
+    proposal: Proposal,
+    old_power_ratio: Decimal,
+    new_power_ratio: Decimal,
+) -> StdResult<()> {
+    // update the power ratio for the proposal
+    update_power_ratio_for_proposal(
+        storage,
+        proposal.proposal_id,
+        validator.to_string(),
+        old_power_ratio,
+        new_power_ratio,
+    )?;
+
+    // create a mutable copy of the proposal that we can safely manipulate
+    let mut proposal_copy = proposal.clone();
+
+    // save the new power for the proposal in the store
+    proposal_copy.power =
+        get_total_power_for_proposal(storage, proposal_copy.proposal_id)?.to_uint_ceil();
+
+    PROPOSAL_MAP.save(
+        storage,
+        (round_id, tranche_id, proposal.proposal_id),
+        &proposal_copy,
+    )?;
+
+    update_round_tranche_voted_power(
+        storage,
+        round_id,
+        tranche_id,
+        proposal.power,
+        proposal_copy.power,
+    )?;
+
+    // remove proposals old score
+    PROPS_BY_SCORE.remove(
+        storage,
+        get_props_by_score_key(round_id, tranche_id, proposal.power, proposal.proposal_id),
+    );
+
+    // invalidated proposals are kept out of PROPS_BY_SCORE
+    if INVALIDATED_PROPOSALS.has(storage, (round_id, tranche_id, proposal.proposal_id)) {
+        return Ok(());
+    }
 
-            // save the new power for the proposal in the store
-            proposal_copy.power =
-                get_total_power_for_proposal(storage, proposal_copy.proposal_id)?.to_uint_ceil();
+    PROPS_BY_SCORE.save(
+        storage,
+        get_props_by_score_key(
+            round_id,
+            tranche_id,
+            proposal_copy.power,
+            proposal_copy.proposal_id,
+        ),
+        &proposal_copy.proposal_id,
+    )
+}
 
-            PROPOSAL_MAP.save(
-                storage,
-                (round_id, tranche_id, proposal.proposal_id),
-                &proposal_copy,
-            )?;
+pub fn get_total_power_for_round(deps: Deps<NeutronQuery>, round_id: u64) -> StdResult<Decimal> {
+    Ok(TOTAL_ROUND_POWER
+        .may_load(deps.storage, round_id)?
+        .unwrap_or_default())
+}
 
-            // remove proposals old score
-            PROPS_BY_SCORE.remove(
-                storage,
-                (
-                    (round_id, tranche_id),
-                    proposal.power.into(),
-                    proposal.proposal_id,
-                ),
-            );
-
-            PROPS_BY_SCORE.save(
-                storage,
-                (
-                    (round_id, tranche_id),
-                    proposal_copy.power.into(),
-                    proposal_copy.proposal_id,
-                ),
-                &proposal_copy.proposal_id,
-            )?;
-        }
+// Returns the total power of the round at the given time. If the interpolate_lock_power is set, the total
+// power is linearly interpolated between its value at the start and at the end of the round, in the same
+// way as the power of each lock. Otherwise, it is the power at the end of the round.
+pub fn get_total_power_for_round_at_time(
+    deps: Deps<NeutronQuery>,
+    constants: &Constants,
+    round_id: u64,
+    time: Timestamp,
+) -> StdResult<Decimal> {
+    let end_power = get_total_power_for_round(deps, round_id)?;
+    if !constants.interpolate_lock_power {
+        return Ok(end_power);
     }
-    Ok(())
+
+    let start_power = TOTAL_ROUND_START_POWER
+        .may_load(deps.storage, round_id)?
+        .unwrap_or_default();
+
+    Ok(interpolate_within_round(
+        constants,
+        compute_round_end(constants, round_id)?,
+        time,
+        start_power,
+        end_power,
+    ))
 }
 
-pub fn get_total_power_for_round(deps: Deps<NeutronQuery>, round_id: u64) -> StdResult<Decimal> {
-    // get the current validators for that round
-    let validators = get_round_validators(deps, round_id);
+// Computes the total power of the round from the shares and the power ratios of all validators
+// in the round, and stores it as the checkpoint of the round's total power.
+pub fn recompute_total_power_for_round(storage: &mut dyn Storage, round_id: u64) -> StdResult<()> {
+    let validators = VALIDATORS_INFO
+        .prefix(round_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|validator| validator.map(|(_, validator_info)| validator_info))
+        .collect::<StdResult<Vec<ValidatorInfo>>>()?;
 
-    // compute the total power
     let mut total = Decimal::zero();
+    let mut start_total = Decimal::zero();
     for validator in validators {
-        let shares = SCALED_ROUND_POWER_SHARES_MAP
-            .may_load(deps.storage, (round_id, validator.address.clone()))?
-            .unwrap_or(Decimal::zero());
+        let shares = get_validator_shares_for_round(storage, round_id, validator.address.clone())?;
         total += shares * validator.power_ratio;
+
+        let start_shares =
+            get_validator_start_shares_for_round(storage, round_id, validator.address)?;
+        start_total += start_shares * validator.power_ratio;
+    }
+
+    // the start of round shares only exist if the interpolate_lock_power is set
+    if !start_total.is_zero() {
+        TOTAL_ROUND_START_POWER.save(storage, round_id, &start_total)?;
+    }
+
+    TOTAL_ROUND_POWER.save(storage, round_id, &total)
+}
+
+// Applies the change of the validator's power ratio to the checkpoint of the round's total power.
+pub fn update_total_power_due_to_power_ratio_change(
+    storage: &mut dyn Storage,
+    validator: &str,
+    round_id: u64,
+    old_power_ratio: Decimal,
+    new_power_ratio: Decimal,
+) -> StdResult<()> {
+    if new_power_ratio > old_power_ratio && !old_power_ratio.is_zero() {
+        let growth = get_round_power_ratio_growth(storage, round_id)?;
+        ROUND_POWER_RATIO_GROWTH.save(
+            storage,
+            round_id,
+            &growth.checked_mul(
+                new_power_ratio
+                    .checked_div(old_power_ratio)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?,
+            )?,
+        )?;
+    }
+
+    let start_shares =
+        get_validator_start_shares_for_round(storage, round_id, validator.to_string())?;
+    if !start_shares.is_zero() {
+        let start_total = TOTAL_ROUND_START_POWER
+            .may_load(storage, round_id)?
+            .unwrap_or_default();
+        let new_start_total = (start_total + start_shares * new_power_ratio)
+            .saturating_sub(start_shares * old_power_ratio);
+        TOTAL_ROUND_START_POWER.save(storage, round_id, &new_start_total)?;
+    }
+
+    let shares = get_validator_shares_for_round(storage, round_id, validator.to_string())?;
+    if shares.is_zero() {
+        return Ok(());
     }
 
-    Ok(total)
+    let total = TOTAL_ROUND_POWER
+        .may_load(storage, round_id)?
+        .unwrap_or_default();
+
+    // the total is updated incrementally, so it can be off from shares * old_power_ratio by a rounding error
+    let new_total = (total + shares * new_power_ratio).saturating_sub(shares * old_power_ratio);
+    TOTAL_ROUND_POWER.save(storage, round_id, &new_total)
+}
+
+// Returns the product of all increases of the validator power ratios within the given round.
+pub fn get_round_power_ratio_growth(storage: &dyn Storage, round_id: u64) -> StdResult<Decimal> {
+    Ok(ROUND_POWER_RATIO_GROWTH
+        .may_load(storage, round_id)?
+        .unwrap_or(Decimal::one()))
 }
 
 pub fn add_validator_shares_to_round_total(
@@ -239,7 +384,56 @@ pub fn add_validator_shares_to_round_total(
 ) -> StdResult<()> {
     let current_shares = get_validator_shares_for_round(storage, round_id, validator.clone())?;
     let new_shares = current_shares + num_shares;
-    SCALED_ROUND_POWER_SHARES_MAP.save(storage, (round_id, validator), &new_shares)
+    SCALED_ROUND_POWER_SHARES_MAP.save(storage, (round_id, validator.clone()), &new_shares)?;
+
+    // the shares of the validators that are not in the round's validator set don't count towards the total
+    let power_ratio = get_validator_power_ratio_for_round(storage, round_id, validator)?;
+    if power_ratio.is_zero() {
+        return Ok(());
+    }
+
+    let total = TOTAL_ROUND_POWER
+        .may_load(storage, round_id)?
+        .unwrap_or_default();
+    TOTAL_ROUND_POWER.save(storage, round_id, &(total + num_shares * power_ratio))
+}
+
+// Same as add_validator_shares_to_round_total(), but for the shares as of the start of the round.
+// Only used if the interpolate_lock_power is set.
+pub fn add_validator_start_shares_to_round_total(
+    storage: &mut dyn Storage,
+    round_id: u64,
+    validator: String,
+    num_shares: Decimal,
+) -> StdResult<()> {
+    let current_shares =
+        get_validator_start_shares_for_round(storage, round_id, validator.clone())?;
+    let new_shares = current_shares + num_shares;
+    SCALED_ROUND_START_POWER_SHARES_MAP.save(
+        storage,
+        (round_id, validator.clone()),
+        &new_shares,
+    )?;
+
+    let power_ratio = get_validator_power_ratio_for_round(storage, round_id, validator)?;
+    if power_ratio.is_zero() {
+        return Ok(());
+    }
+
+    let total = TOTAL_ROUND_START_POWER
+        .may_load(storage, round_id)?
+        .unwrap_or_default();
+    TOTAL_ROUND_START_POWER.save(storage, round_id, &(total + num_shares * power_ratio))
+}
+
+pub fn get_validator_start_shares_for_round(
+    storage: &dyn Storage,
+    round_id: u64,
+    validator: String,
+) -> StdResult<Decimal> {
+    Ok(SCALED_ROUND_START_POWER_SHARES_MAP
+        .may_load(storage, (round_id, validator))?
+        .unwrap_or(Decimal::zero()))
 }
 
 pub fn get_validator_shares_for_round(
@@ -289,7 +483,9 @@ pub fn initialize_validator_store(storage: &mut dyn Storage, round_id: u64) -> S
 // If the store for this round has not been initialized yet, initialize_validator_store_helper copies the information from the last round
 // to seed the store. This is only done starting in the second round.
 // Explicitly, it initializes the VALIDATORS_INFO and the VALIDATORS_PER_ROUND
-// for this round by copying the information from the previous round.
+// for this round by copying the information of the top max_validator_shares_participating validators from the previous round.
+// If max_validator_shares_participating was decreased in the meantime, the validators that fell out of the top N are not copied,
+// so their power ratio in this round is zero, while their power in the previous rounds stays unchanged.
 // If the store of the previous round has not been initialized yet, it returns an error.
 // If the store for this round has already been initialized, or the round_id is for the first round, this function does nothing.
 pub fn initialize_validator_store_helper(
@@ -310,7 +506,8 @@ pub fn initialize_validator_store_helper(
     }
 
     // copy the information from the previous round
-    let val_infos = load_validators_infos(storage, round_id - 1)?;
+    let max_validators = CONSTANTS.load(storage)?.max_validator_shares_participating;
+    let val_infos = load_top_validators_infos(storage, round_id - 1, max_validators)?;
 
     for val_info in val_infos {
         let address = val_info.clone().address;
@@ -327,17 +524,48 @@ pub fn initialize_validator_store_helper(
             .unwrap();
     }
 
+    // the shares for this round may have been added before its validators were known
+    recompute_total_power_for_round(storage, round_id)?;
+
     // store that we have initialized the store for this round
     VALIDATORS_STORE_INITIALIZED.save(storage, round_id, &true)?;
 
     Ok(())
 }
 
-// load_validators_infos needs to be its own function to borrow the storage
-fn load_validators_infos(storage: &dyn Storage, round_id: u64) -> StdResult<Vec<ValidatorInfo>> {
-    VALIDATORS_INFO
-        .prefix(round_id)
-        .range(storage, None, None, Order::Ascending)
-        .map(|val_info_res| val_info_res.map(|val_info| val_info.1))
+// load_top_validators_infos needs to be its own function to borrow the storage
+fn load_top_validators_infos(
+    storage: &dyn Storage,
+    round_id: u64,
+    max_validators: u64,
+) -> StdResult<Vec<ValidatorInfo>> {
+    VALIDATORS_PER_ROUND
+        .sub_prefix(round_id)
+        .range(storage, None, None, Order::Descending)
+        .take(max_validators as usize)
+        .map(|validator_res| {
+            validator_res
+                .and_then(|(_, address)| VALIDATORS_INFO.load(storage, (round_id, address)))
+        })
         .collect()
 }
+
+pub fn add_lock_to_validator_index(
+    storage: &mut dyn Storage,
+    validator: &str,
+    owner: &Addr,
+    lock_id: u64,
+) -> StdResult<()> {
+    VALIDATOR_LOCKS.save(storage, (validator.to_string(), lock_id), owner)?;
+    LOCK_VALIDATORS.save(storage, lock_id, &validator.to_string())
+}
+
+// Does nothing if the lock was created before the index was introduced and it wasn't indexed yet.
+pub fn remove_lock_from_validator_index(storage: &mut dyn Storage, lock_id: u64) -> StdResult<()> {
+    if let Some(validator) = LOCK_VALIDATORS.may_load(storage, lock_id)? {
+        VALIDATOR_LOCKS.remove(storage, (validator, lock_id));
+        LOCK_VALIDATORS.remove(storage, lock_id);
+    }
+
+    Ok(())
+}
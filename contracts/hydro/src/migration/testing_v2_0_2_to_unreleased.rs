@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use cosmwasm_std::{testing::mock_env, Addr, Decimal, Timestamp, Uint128};
+use cosmwasm_std::{testing::mock_env, Addr, Decimal, Order, Timestamp, Uint128};
 use cw2::{get_contract_version, set_contract_version};
 use cw_storage_plus::Item;
 
@@ -10,7 +10,11 @@ use crate::{
         migrate::{migrate, CONTRACT_VERSION_UNRELEASED, CONTRACT_VERSION_V2_0_2},
         unreleased::{ConstantsUNRELEASED, ConstantsV2_0_2, MigrateMsgUNRELEASED},
     },
-    state::{Proposal, RoundLockPowerSchedule, Vote, PROPOSAL_MAP, VOTE_MAP, VOTING_ALLOWED_ROUND},
+    state::{
+        get_props_by_score_key, Proposal, RoundLockPowerSchedule, Vote, PROPOSAL_MAP,
+        PROPOSAL_VOTERS, PROPS_BY_SCORE, ROUND_STATS_FIRST_ROUND, VOTE_MAP, VOTING_ALLOWED_ROUND,
+    },
+    stats::get_round_stats,
     testing::{
         get_default_instantiate_msg, get_message_info, ONE_MONTH_IN_NANO_SECONDS, VALIDATOR_1,
     },
@@ -99,6 +103,15 @@ fn test_constants_migration() {
             (6, Decimal::from_str("2").unwrap()),
             (12, Decimal::from_str("4").unwrap()),
         ]),
+        interpolate_lock_power: false,
+        tribute_cutoff_duration: 0,
+        max_proposals_per_tranche_round: 0,
+        allow_pre_launch_locks: false,
+        raw_export_enabled: false,
+        unlock_cooldown_duration: 0,
+        paused_messages: vec![],
+        max_proposal_title_length: 0,
+        max_proposal_description_length: 0,
     };
     let res = NEW_CONSTANTS.load(&deps.storage);
     assert!(
@@ -199,6 +212,8 @@ fn test_voting_allowed_info_migration() {
             description: "Proposal 9 Description".to_string(),
             deployment_duration: 3,
             minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            max_vote_percentage: None,
         },
         Proposal {
             round_id,
@@ -210,6 +225,8 @@ fn test_voting_allowed_info_migration() {
             description: "Proposal 10 Description".to_string(),
             deployment_duration: 4,
             minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            max_vote_percentage: None,
         },
     ];
 
@@ -328,6 +345,7 @@ fn test_voting_allowed_info_migration() {
             &Vote {
                 prop_id: test_case.vote.1.proposal_id,
                 time_weighted_shares,
+                tribute_weight_multiplier: None,
             },
         );
         assert!(
@@ -372,7 +390,27 @@ fn test_voting_allowed_info_migration() {
             "voting allowed round doesn't match expected value; got: {}, expected: {}",
             voting_allowed_round, test_case.voting_info_after.round_id
         );
+
+        // the votes are indexed by the proposal they were cast for
+        let voter = PROPOSAL_VOTERS.load(
+            &deps.storage,
+            (
+                (round_id, tranche_id),
+                test_case.vote.1.proposal_id,
+                test_case.vote.1.lock_id,
+            ),
+        );
+        assert_eq!(test_case.vote.0, voter.unwrap());
     }
+
+    // the statistics of the current round are built from its proposals and votes
+    let round_stats = get_round_stats(&deps.storage, round_id).unwrap();
+    assert_eq!(2, round_stats.proposals_count);
+    assert_eq!(2, round_stats.unique_voters);
+    assert_eq!(
+        round_id,
+        ROUND_STATS_FIRST_ROUND.load(&deps.storage).unwrap()
+    );
 }
 
 struct VotingInfoMigrationTest {
@@ -387,3 +425,93 @@ struct VotingAllowedInfoTest {
     pub lock_id: u64,
     pub round_id: u64,
 }
+
+#[test]
+fn test_props_by_score_migration() {
+    let (mut deps, mut env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+
+    let first_round_start = Timestamp::from_nanos(1730851140000000000);
+    env.block.time = first_round_start;
+
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+
+    // Instantiate the contract
+    let mut instantiate_msg = get_default_instantiate_msg(&deps.api);
+    instantiate_msg.first_round_start = first_round_start;
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_msg.clone(),
+    )
+    .unwrap();
+
+    // Override contract version so that we can run the migration
+    let res = set_contract_version(&mut deps.storage, CONTRACT_NAME, CONTRACT_VERSION_V2_0_2);
+    assert!(
+        res.is_ok(),
+        "failed to set contract version before running the migration"
+    );
+
+    const OLD_CONSTANTS: Item<ConstantsV2_0_2> = Item::new("constants");
+
+    // Override the constants so that they have old data structure stored before running the migration
+    let old_constants = ConstantsV2_0_2 {
+        round_length: 2628000000000000,
+        lock_epoch_length: 2628000000000000,
+        first_round_start,
+        max_locked_tokens: 20000000000,
+        max_validator_shares_participating: 500,
+        hub_connection_id: "connection-0".to_string(),
+        hub_transfer_channel_id: "channel-1".to_string(),
+        icq_update_period: 109000,
+        paused: false,
+        is_in_pilot_mode: true,
+        max_deployment_duration: 12,
+    };
+    OLD_CONSTANTS
+        .save(&mut deps.storage, &old_constants)
+        .unwrap();
+
+    // save the entries with the old keys, in which the proposal with the larger id wins the tie,
+    // both for the past round 0 and for the current round 1
+    let (past_round_id, round_id, tranche_id) = (0, 1, 1);
+    let old_entries = vec![(2000u128, 3u64), (2000u128, 5u64), (1000u128, 4u64)];
+    for entries_round_id in [past_round_id, round_id] {
+        for (score, prop_id) in old_entries.iter() {
+            PROPS_BY_SCORE
+                .save(
+                    &mut deps.storage,
+                    ((entries_round_id, tranche_id), *score, *prop_id),
+                    prop_id,
+                )
+                .unwrap();
+        }
+    }
+
+    env.block.time = env.block.time.plus_nanos(old_constants.round_length + 1);
+
+    let res = migrate(deps.as_mut(), env.clone(), MigrateMsgUNRELEASED {});
+    assert!(res.is_ok(), "migration failed: {}", res.unwrap_err());
+
+    // the entries of the current round are moved to the new keys, so the earlier proposal wins the tie
+    let prop_ids: Vec<u64> = PROPS_BY_SCORE
+        .sub_prefix((round_id, tranche_id))
+        .range(&deps.storage, None, None, Order::Descending)
+        .map(|entry| entry.unwrap().1)
+        .collect();
+    assert_eq!(vec![3, 5, 4], prop_ids);
+
+    for (score, prop_id) in old_entries.iter() {
+        assert!(PROPS_BY_SCORE.has(
+            &deps.storage,
+            get_props_by_score_key(round_id, tranche_id, Uint128::new(*score), *prop_id)
+        ));
+    }
+
+    // the entries of the past round keep their old keys
+    for (score, prop_id) in old_entries {
+        assert!(PROPS_BY_SCORE.has(&deps.storage, ((past_round_id, tranche_id), score, prop_id)));
+    }
+}
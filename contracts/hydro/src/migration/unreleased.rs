@@ -1,7 +1,7 @@
 use std::{collections::HashMap, str::FromStr};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, DepsMut, Env, Order, Storage, Timestamp};
+use cosmwasm_std::{Addr, Decimal, DepsMut, Env, Order, StdResult, Storage, Timestamp, Uint128};
 use cw_storage_plus::Item;
 use neutron_sdk::bindings::query::NeutronQuery;
 use schemars::JsonSchema;
@@ -10,9 +10,14 @@ use serde::{Deserialize, Serialize};
 use crate::{
     contract::compute_current_round_id,
     error::ContractError,
+    lsm_integration::recompute_total_power_for_round,
     state::{
-        RoundLockPowerSchedule, CONSTANTS, PROPOSAL_MAP, TRANCHE_MAP, VOTE_MAP,
-        VOTING_ALLOWED_ROUND,
+        get_props_by_score_key, RoundLockPowerSchedule, CONSTANTS, PROPOSAL_MAP, PROPOSAL_VOTERS,
+        PROPS_BY_SCORE, ROUND_STATS_FIRST_ROUND, TRANCHE_MAP, VALIDATORS_STORE_INITIALIZED,
+        VOTE_MAP, VOTING_ALLOWED_ROUND,
+    },
+    stats::{
+        increment_round_proposals_count, record_round_voter, update_round_tranche_voted_power,
     },
 };
 
@@ -47,6 +52,15 @@ pub struct ConstantsUNRELEASED {
     pub paused: bool,
     pub max_deployment_duration: u64,
     pub round_lock_power_schedule: RoundLockPowerSchedule,
+    pub interpolate_lock_power: bool,
+    pub tribute_cutoff_duration: u64,
+    pub max_proposals_per_tranche_round: u64,
+    pub allow_pre_launch_locks: bool,
+    pub raw_export_enabled: bool,
+    pub unlock_cooldown_duration: u64,
+    pub paused_messages: Vec<String>,
+    pub max_proposal_title_length: u64,
+    pub max_proposal_description_length: u64,
 }
 
 impl ConstantsUNRELEASED {
@@ -63,6 +77,17 @@ impl ConstantsUNRELEASED {
             paused: old_constants.paused,
             max_deployment_duration: old_constants.max_deployment_duration,
             round_lock_power_schedule: RoundLockPowerSchedule::new(get_default_power_schedule()),
+            // existing deployments keep the stepwise lock power
+            interpolate_lock_power: false,
+            tribute_cutoff_duration: 0,
+            max_proposals_per_tranche_round: 0,
+            // the first round of existing deployments has already started
+            allow_pre_launch_locks: false,
+            raw_export_enabled: false,
+            unlock_cooldown_duration: 0,
+            paused_messages: vec![],
+            max_proposal_title_length: 0,
+            max_proposal_description_length: 0,
         }
     }
 }
@@ -84,6 +109,10 @@ pub fn migrate_v2_0_2_to_unreleased(
 ) -> Result<(), ContractError> {
     migrate_constants(deps.storage)?;
     migrate_voting_allowed_info(deps, &env)?;
+    migrate_props_by_score(deps, &env)?;
+    migrate_proposal_voters(deps, &env)?;
+    migrate_total_round_power(deps.storage)?;
+    migrate_round_stats(deps, &env)?;
 
     Ok(())
 }
@@ -108,10 +137,7 @@ fn migrate_voting_allowed_info(
 
     let tranche_ids: Vec<u64> = TRANCHE_MAP
         .keys(deps.storage, None, None, Order::Ascending)
-        .filter_map(|tranche_id| match tranche_id {
-            Ok(tranche_id) => Some(tranche_id),
-            Err(_) => None,
-        })
+        .filter_map(|tranche_id| tranche_id.ok())
         .collect();
 
     // don't rely on migration being run in round 1, even though it probably will
@@ -168,6 +194,147 @@ fn migrate_voting_allowed_info(
     Ok(())
 }
 
+// Previously, the PROPS_BY_SCORE keys held the prop_id as the last element, which made the proposal
+// with the larger id win the tie between proposals with equal score. The keys now hold a tie breaker
+// instead, so the entries of the current round are moved to the keys built by get_props_by_score_key().
+// The entries of the past rounds are left as they are, since their proposal powers don't change anymore,
+// and the top N proposals query orders the proposals with equal score by their ids regardless of the key.
+fn migrate_props_by_score(
+    deps: &mut DepsMut<NeutronQuery>,
+    env: &Env,
+) -> Result<(), ContractError> {
+    // migrate_constants() must be executed first
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(env, &constants)?;
+
+    let tranche_ids = TRANCHE_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    for tranche_id in tranche_ids {
+        let old_entries = PROPS_BY_SCORE
+            .sub_prefix((current_round_id, tranche_id))
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<((u128, u64), u64)>>>()?;
+
+        for ((score, key_prop_id), prop_id) in old_entries {
+            // skip the entries whose key already holds the tie breaker
+            if key_prop_id != prop_id {
+                continue;
+            }
+
+            PROPS_BY_SCORE.remove(
+                deps.storage,
+                ((current_round_id, tranche_id), score, key_prop_id),
+            );
+            PROPS_BY_SCORE.save(
+                deps.storage,
+                get_props_by_score_key(current_round_id, tranche_id, Uint128::new(score), prop_id),
+                &prop_id,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+// Builds the PROPOSAL_VOTERS index from the votes cast in the current round before it was introduced.
+// The votes of the past rounds are not indexed, since that would require going through all the historical
+// votes in a single transaction, so the ProposalVoters query returns no voters for the past rounds.
+fn migrate_proposal_voters(
+    deps: &mut DepsMut<NeutronQuery>,
+    env: &Env,
+) -> Result<(), ContractError> {
+    // migrate_constants() must be executed first
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(env, &constants)?;
+
+    let tranche_ids = TRANCHE_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    for tranche_id in tranche_ids {
+        let round_tranche = (current_round_id, tranche_id);
+        let votes = VOTE_MAP
+            .sub_prefix(round_tranche)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|vote| vote.map(|(key, vote)| (key, vote.prop_id)))
+            .collect::<StdResult<Vec<((Addr, u64), u64)>>>()?;
+
+        for ((voter, lock_id), prop_id) in votes {
+            PROPOSAL_VOTERS.save(deps.storage, (round_tranche, prop_id, lock_id), &voter)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Stores the checkpoint of the total power for each round whose validator store was initialized. The rounds
+// that weren't initialized yet get their checkpoint once their validator store gets initialized.
+fn migrate_total_round_power(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let round_ids = VALIDATORS_STORE_INITIALIZED
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|round| match round {
+            Ok((round_id, true)) => Some(Ok(round_id)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    for round_id in round_ids {
+        recompute_total_power_for_round(storage, round_id)?;
+    }
+
+    Ok(())
+}
+
+// Builds the round statistics of the current round from its proposals and votes, so that the statistics
+// of the current round are complete. The earlier rounds are not backfilled, since that would require going
+// through all the historical votes in a single transaction. Instead, the current round is recorded as the
+// first round with complete statistics, which the ProtocolStats query reports.
+fn migrate_round_stats(deps: &mut DepsMut<NeutronQuery>, env: &Env) -> Result<(), ContractError> {
+    // migrate_constants() must be executed first
+    let constants = CONSTANTS.load(deps.storage)?;
+    let current_round_id = compute_current_round_id(env, &constants)?;
+
+    let tranche_ids = TRANCHE_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    for tranche_id in tranche_ids {
+        let proposal_powers = PROPOSAL_MAP
+            .prefix((current_round_id, tranche_id))
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|proposal| proposal.map(|(_, proposal)| proposal.power))
+            .collect::<StdResult<Vec<Uint128>>>()?;
+
+        for proposal_power in proposal_powers {
+            increment_round_proposals_count(deps.storage, current_round_id)?;
+            update_round_tranche_voted_power(
+                deps.storage,
+                current_round_id,
+                tranche_id,
+                Uint128::zero(),
+                proposal_power,
+            )?;
+        }
+
+        let voters = VOTE_MAP
+            .sub_prefix((current_round_id, tranche_id))
+            .keys(deps.storage, None, None, Order::Ascending)
+            .map(|vote_key| vote_key.map(|(voter, _)| voter))
+            .collect::<StdResult<Vec<Addr>>>()?;
+
+        for voter in voters {
+            record_round_voter(deps.storage, current_round_id, &voter)?;
+        }
+    }
+
+    ROUND_STATS_FIRST_ROUND.save(deps.storage, &current_round_id)?;
+
+    Ok(())
+}
+
 pub struct VoteMigrationInfo {
     pub lock_id: u64,
     pub proposal_id: u64,
@@ -215,6 +215,8 @@ fn migrate_votes(deps: &mut DepsMut<NeutronQuery>) -> Result<(), ContractError>
                     round_end,
                     lock_entry.clone(),
                     lock_epoch_length,
+                    // lock power was always stepwise at the time of this migration
+                    false,
                 ),
                 Uint128::one(),
             );
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Coin, Decimal, Timestamp, Uint128};
+use cosmwasm_std::{Binary, Coin, Decimal, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -25,6 +25,12 @@ pub struct InstantiateMsg {
     // The first element is the round number, the second element is the lock power.
     // See the RoundLockPowerSchedule struct for more information.
     pub round_lock_power_schedule: Vec<(u64, Decimal)>,
+    // If set, the lock power changes continuously over time, instead of in steps
+    // defined by the round_lock_power_schedule. This can't be changed later.
+    pub interpolate_lock_power: bool,
+    // If set, tokens can be locked before the first round starts. Such locks give voting power
+    // starting from round 0, so that the first round doesn't start without any voting power.
+    pub allow_pre_launch_locks: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -41,13 +47,63 @@ pub enum ExecuteMsg {
     LockTokens {
         lock_duration: u64,
     },
+    // Creates a separate lock entry for each of the sent coins, all with the same lock duration.
+    #[cw_orch(payable)]
+    LockTokensMultiple {
+        lock_duration: u64,
+    },
     RefreshLockDuration {
         lock_ids: Vec<u64>,
         lock_duration: u64,
     },
+    // Refreshes the lockups of the owner that appointed the sender as their operator.
+    RefreshLockDurationOnBehalf {
+        owner: String,
+        lock_ids: Vec<u64>,
+        lock_duration: u64,
+    },
+    // Appoints the operator of the given lockups, who can vote with them and refresh them, but can't unlock them.
+    // If no operator is given, the current operator of the lockups is removed.
+    SetLockOperator {
+        lock_ids: Vec<u64>,
+        operator: Option<String>,
+    },
+    // Once enabled, the given lockups keep their current remaining lock duration, as their lock end
+    // gets extended once per round by anyone calling ProcessAutoRefresh.
+    SetAutoRefresh {
+        lock_ids: Vec<u64>,
+        enabled: bool,
+    },
+    // Attaches the note to the given lockup, replacing any previous one, or removes it if no note is given.
+    SetLockNote {
+        lock_id: u64,
+        note: Option<String>,
+    },
+    // If a tolerance is given, the new locks of the sender are merged into their existing lockup of the same
+    // denom whose lock end is within the tolerance (in nanoseconds), instead of creating new lockups.
+    // If no tolerance is given, the auto merging is disabled.
+    SetLockAutoMerge {
+        tolerance: Option<u64>,
+    },
+    // Permissionless; refreshes up to limit of the auto refreshed lockups not yet refreshed in the current round.
+    ProcessAutoRefresh {
+        limit: u32,
+    },
     UnlockTokens {
         lock_ids: Option<Vec<u64>>,
+        // If set to true, the tribute contract is asked to claim the tributes for the votes of the
+        // unlocked locks in the same transaction. Failing claims never prevent the tokens from being unlocked.
+        claim_tributes: Option<bool>,
+    },
+    // Unlocks the given locks and sends the tokens to the recipient instead of the lock owner.
+    // confirm_recipient must be set to true, as a safeguard against sending the tokens to a wrong address.
+    UnlockTokensTo {
+        lock_ids: Vec<u64>,
+        recipient: String,
+        confirm_recipient: bool,
     },
+    // Sends the tokens of the sender's unlocked lockups whose unlock cooldown has passed.
+    ClaimUnlocked {},
     CreateProposal {
         round_id: Option<u64>,
         tranche_id: u64,
@@ -55,11 +111,66 @@ pub enum ExecuteMsg {
         description: String,
         deployment_duration: u64,
         minimum_atom_liquidity_request: Uint128,
+        // Required in tranches that use hash-only descriptions, and not allowed in other tranches.
+        // In that case, the description should contain only a short summary of the proposal.
+        description_hash: Option<String>,
+        description_uri: Option<String>,
+        // Maximum percentage (0-100) of the total round power that can count towards the proposal score.
+        max_vote_percentage: Option<Decimal>,
     },
     Vote {
         tranche_id: u64,
         proposals_votes: Vec<ProposalToLockups>,
     },
+    // Votes with the lockups of the owner whose voting rights were delegated to the sender,
+    // or that appointed the sender as their operator or voting executor.
+    VoteOnBehalf {
+        owner: String,
+        tranche_id: u64,
+        proposals_votes: Vec<ProposalToLockups>,
+    },
+    // Marks the given lockups of the sender as intentionally abstaining in the given tranche in the
+    // current round. The lockups can still vote later in the round.
+    Abstain {
+        tranche_id: u64,
+        lock_ids: Vec<u64>,
+    },
+    // Abstains with the lockups of the owner whose voting rights were delegated to the sender,
+    // or that appointed the sender as their operator or voting executor.
+    AbstainOnBehalf {
+        owner: String,
+        tranche_id: u64,
+        lock_ids: Vec<u64>,
+    },
+    // Removes all the votes that the lockups of the sender cast in the given tranche in the current round.
+    // If the owner is given, removes the votes of the owner's lockups instead. In that case the sender must be
+    // the voting executor of the owner, or the voting rights of the voted lockups must be delegated to the sender,
+    // or the sender must be their operator.
+    UnvoteAll {
+        tranche_id: u64,
+        owner: Option<String>,
+    },
+    // Casts the votes that the voter signed off-chain, so that the relayer pays the fees instead of the voter.
+    // The pubkey is the compressed secp256k1 public key of the voter's account.
+    RelayedVote {
+        vote_intent: VoteIntent,
+        pubkey: Binary,
+        signature: Binary,
+    },
+    DelegateVotingRights {
+        lock_ids: Vec<u64>,
+        delegate: String,
+    },
+    RevokeDelegation {
+        lock_ids: Vec<u64>,
+    },
+    // Registers the executor that can vote with all the lockups of the sender via VoteOnBehalf,
+    // as long as the lockups don't vote for more than max_proposals_per_round proposals in a round.
+    SetVotingExecutor {
+        executor: String,
+        max_proposals_per_round: u64,
+    },
+    RemoveVotingExecutor {},
     AddAccountToWhitelist {
         address: String,
     },
@@ -69,8 +180,26 @@ pub enum ExecuteMsg {
     UpdateConfig {
         max_locked_tokens: Option<u128>,
         max_deployment_duration: Option<u64>,
+        tribute_cutoff_duration: Option<u64>,
+        max_proposals_per_tranche_round: Option<u64>,
+        raw_export_enabled: Option<bool>,
+        // Only applies to the tokens unlocked after the change.
+        unlock_cooldown_duration: Option<u64>,
+        // Changes to the number of validators whose shares can be locked apply from the next round.
+        max_validator_shares_participating: Option<u64>,
+        // Only apply to the proposals and proposal templates created after the change.
+        max_proposal_title_length: Option<u64>,
+        max_proposal_description_length: Option<u64>,
     },
     Pause {},
+    // Pauses or unpauses only the given messages, e.g. LockTokens, while the rest of the contract keeps
+    // working. The admin messages can't be paused this way.
+    PauseMessages {
+        names: Vec<String>,
+    },
+    UnpauseMessages {
+        names: Vec<String>,
+    },
     AddTranche {
         tranche: TrancheInfo,
     },
@@ -79,6 +208,63 @@ pub enum ExecuteMsg {
         tranche_name: Option<String>,
         tranche_metadata: Option<String>,
     },
+    SetTrancheDescriptionMode {
+        tranche_id: u64,
+        hash_only: bool,
+    },
+    SetTranchePowerScaling {
+        tranche_id: u64,
+        quadratic: bool,
+    },
+    SetTrancheTributeWeightBonus {
+        tranche_id: u64,
+        bonus: Option<TributeWeightBonus>,
+    },
+    // Limits the power that the locks of a single owner can contribute within a tranche to the given
+    // percentage of the total round power, starting from the next round. Removes the cap if not provided.
+    SetVotePowerCap {
+        max_power_percentage: Option<Decimal>,
+    },
+    SetTributeContract {
+        address: Option<String>,
+    },
+    SetProposalLimitOverride {
+        round_id: u64,
+        tranche_id: u64,
+        max_proposals: Option<u64>,
+    },
+    SetProposalVoteCap {
+        round_id: u64,
+        tranche_id: u64,
+        proposal_id: u64,
+        max_vote_percentage: Option<Decimal>,
+    },
+    InvalidateProposal {
+        round_id: u64,
+        tranche_id: u64,
+        proposal_id: u64,
+        reason: String,
+    },
+    RecomputeRoundTotals {
+        round_id: u64,
+        validator: String,
+        corrected_ratio: Decimal,
+        limit: u32,
+    },
+    // Creates the given lockups, migrated from another deployment, on behalf of their owners. The sent funds
+    // must match the total amount of the imported lockups for each denom. Once an import with finalize set
+    // to true is done, no more lockups can be imported.
+    #[cw_orch(payable)]
+    ImportLockups {
+        entries: Vec<LockupImport>,
+        finalize: bool,
+    },
+    // Adds up to `limit` locks after start_after, given as (owner, lock_id), that were created before the
+    // validator locks index was introduced to that index. Can be sent multiple times to index all locks.
+    IndexValidatorLocks {
+        start_after: Option<(String, u64)>,
+        limit: u32,
+    },
     #[serde(rename = "create_icqs_for_validators")]
     #[cw_orch(payable)]
     CreateICQsForValidators {
@@ -113,6 +299,46 @@ pub enum ExecuteMsg {
         tranche_id: u64,
         proposal_id: u64,
     },
+
+    SetNotificationPreferences {
+        endpoint: String,
+        categories: Vec<NotificationCategory>,
+    },
+
+    RemoveNotificationPreferences {},
+
+    RegisterProposalTemplate {
+        tranche_id: u64,
+        title: String,
+        description: String,
+        deployment_duration: u64,
+        minimum_atom_liquidity_request: Uint128,
+        description_hash: Option<String>,
+        description_uri: Option<String>,
+    },
+
+    RemoveProposalTemplate {
+        template_id: u64,
+    },
+
+    // Creates a proposal in the current round from the given template.
+    InstantiateFromTemplate {
+        template_id: u64,
+    },
+
+    // Deletes up to `limit` entries of the votes, voters, proposal rankings and validator set of a round
+    // that ended long ago. Large rounds can be pruned by sending this message multiple times.
+    PruneRoundData {
+        round_id: u64,
+        limit: u32,
+    },
+
+    // Records the best ranked proposals of the given round and tranche once the round has ended, so that
+    // the results can't change afterwards. Can be sent by anyone, but only once per round and tranche.
+    FinalizeRound {
+        round_id: u64,
+        tranche_id: u64,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -122,6 +348,85 @@ pub struct ProposalToLockups {
     pub lock_ids: Vec<u64>,
 }
 
+// Votes signed off-chain by the voter. The nonce must match the next relayed vote nonce of the voter,
+// and the intent can only be relayed in the given round and before the expiry.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteIntent {
+    pub voter: String,
+    pub round_id: u64,
+    pub tranche_id: u64,
+    pub proposals_votes: Vec<ProposalToLockups>,
+    pub nonce: u64,
+    pub expiry: Timestamp,
+}
+
+// Locks that remain locked for at least min_lock_duration after the end of the round in which they vote
+// get their tribute weight multiplied by the multiplier. Their voting power is not affected.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TributeWeightBonus {
+    pub min_lock_duration: u64,
+    pub multiplier: Decimal,
+}
+
+// Events that off-chain notifier services can notify the users about, if the users opted in for them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    LockExpiry,
+    TributeAvailable,
+    ProposalWon,
+}
+
+// Message that is sent to the tribute contract when the user asks for the tributes to be claimed on unlock.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TributeExecuteMsg {
+    ClaimForLocks {
+        voter_address: String,
+        lock_votes: Vec<LockVote>,
+    },
+}
+
+// Queries that are sent to the tribute contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TributeQueryMsg {
+    RoundTributes {
+        round_id: u64,
+        start_from: u32,
+        limit: u32,
+    },
+}
+
+// Part of the tribute contract response to the RoundTributes query that is used by Hydro.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TributeRoundTributesResponse {
+    pub tributes: Vec<TributeRefundStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TributeRefundStatus {
+    pub tribute_id: u64,
+    pub refunded: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupImport {
+    pub owner: String,
+    pub funds: Coin,
+    pub lock_start: Timestamp,
+    pub lock_end: Timestamp,
+}
+
+// Vote of a lock in a round that has ended.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockVote {
+    pub lock_id: u64,
+    pub round_id: u64,
+    pub tranche_id: u64,
+    pub proposal_id: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct LiquidityDeployment {
     pub round_id: u64,
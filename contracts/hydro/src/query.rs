@@ -1,9 +1,13 @@
 use crate::{
-    msg::LiquidityDeployment,
-    state::{Constants, LockEntry, Proposal, Tranche, VoteWithPower},
+    msg::{LiquidityDeployment, NotificationCategory, TributeWeightBonus},
+    state::{
+        AdminAction, AutoRefresh, Constants, LockEntry, NotificationPreferences, PendingUnlock,
+        Proposal, ProposalTemplate, RoundDataPruning, RoundResults, Tranche, ValidatorSlashInfo,
+        VoteWithPower, VotingExecutor,
+    },
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -15,18 +19,78 @@ pub enum QueryMsg {
     #[returns(ConstantsResponse)]
     Constants {},
 
+    #[returns(CapabilitiesResponse)]
+    Capabilities {},
+
     #[returns(TranchesResponse)]
     Tranches {},
 
+    #[returns(TranchePowerScalingResponse)]
+    TranchePowerScaling { tranche_id: u64, round_id: u64 },
+
+    #[returns(TrancheTributeWeightBonusResponse)]
+    TrancheTributeWeightBonus { tranche_id: u64, round_id: u64 },
+
+    #[returns(VotePowerCapResponse)]
+    VotePowerCap { round_id: u64 },
+
+    #[returns(HashOnlyDescriptionTranchesResponse)]
+    HashOnlyDescriptionTranches {},
+
+    #[returns(TributeContractResponse)]
+    TributeContract {},
+
+    #[returns(ProposalSlotsResponse)]
+    ProposalSlots { round_id: u64, tranche_id: u64 },
+
+    #[returns(ProposalTextLimitsResponse)]
+    ProposalTextLimits {},
+
+    #[returns(InvalidatedProposalsResponse)]
+    InvalidatedProposals { round_id: u64, tranche_id: u64 },
+
+    // Paginated queries return the entries after start_after, which is the key of the last entry of
+    // the previous page (e.g. the lock or proposal id). Their responses hold that key as next_key, which
+    // is None once a page holds fewer than limit entries, i.e. there are no more entries. The start_from
+    // offset is deprecated and will be removed in the next release, since each page rereads all the
+    // skipped entries. It is ignored if start_after is set.
     #[returns(AllUserLockupsResponse)]
+    // The lockup queries return the notes attached to the lockups only if include_metadata is set.
     AllUserLockups {
         address: String,
         start_from: u32,
+        start_after: Option<u64>,
         limit: u32,
+        include_metadata: Option<bool>,
     },
 
     #[returns(SpecificUserLockupsResponse)]
-    SpecificUserLockups { address: String, lock_ids: Vec<u64> },
+    SpecificUserLockups {
+        address: String,
+        lock_ids: Vec<u64>,
+        include_metadata: Option<bool>,
+    },
+
+    #[returns(AutoRefreshLocksResponse)]
+    AutoRefreshLocks { address: String },
+
+    #[returns(PendingUnlocksResponse)]
+    PendingUnlocks { address: String },
+
+    #[returns(DelegationsForUserResponse)]
+    DelegationsForUser { address: String },
+
+    #[returns(VotingExecutorResponse)]
+    VotingExecutor { owner: String },
+
+    #[returns(RelayedVoteNonceResponse)]
+    RelayedVoteNonce { address: String },
+
+    #[returns(LockOperatorsResponse)]
+    LockOperators { address: String },
+
+    #[returns(LockAutoMergeResponse)]
+    LockAutoMerge { address: String },
 
     // a version of the AllUserLockups query where additional information
     // is returned
@@ -34,16 +98,23 @@ pub enum QueryMsg {
     AllUserLockupsWithTrancheInfos {
         address: String,
         start_from: u32,
+        start_after: Option<u64>,
         limit: u32,
+        include_metadata: Option<bool>,
     },
 
     #[returns(SpecificUserLockupsWithTrancheInfosResponse)]
-    SpecificUserLockupsWithTrancheInfos { address: String, lock_ids: Vec<u64> },
+    SpecificUserLockupsWithTrancheInfos {
+        address: String,
+        lock_ids: Vec<u64>,
+        include_metadata: Option<bool>,
+    },
 
     #[returns(ExpiredUserLockupsResponse)]
     ExpiredUserLockups {
         address: String,
         start_from: u32,
+        start_after: Option<u64>,
         limit: u32,
     },
 
@@ -63,6 +134,9 @@ pub enum QueryMsg {
     #[returns(RoundEndResponse)]
     RoundEnd { round_id: u64 },
 
+    #[returns(RoundCutoffsResponse)]
+    RoundCutoffs { round_id: u64 },
+
     #[returns(RoundTotalVotingPowerResponse)]
     RoundTotalVotingPower { round_id: u64 },
 
@@ -71,6 +145,7 @@ pub enum QueryMsg {
         round_id: u64,
         tranche_id: u64,
         start_from: u32,
+        start_after: Option<u64>,
         limit: u32,
     },
 
@@ -81,6 +156,17 @@ pub enum QueryMsg {
         proposal_id: u64,
     },
 
+    // The voters are indexed from the round in which the contract was migrated to support this query on.
+    // For the earlier rounds, no voters are returned.
+    #[returns(ProposalVotersResponse)]
+    ProposalVoters {
+        round_id: u64,
+        tranche_id: u64,
+        proposal_id: u64,
+        start_after: Option<u64>,
+        limit: u32,
+    },
+
     #[returns(TopNProposalsResponse)]
     TopNProposals {
         round_id: u64,
@@ -106,6 +192,35 @@ pub enum QueryMsg {
     #[returns(ValidatorPowerRatioResponse)]
     ValidatorPowerRatio { validator: String, round_id: u64 },
 
+    #[returns(DenomValidatorResponse)]
+    DenomValidator { denom: String },
+
+    // Same as DenomValidator, but checks the denom against the validator set of the given round,
+    // e.g. to find out why the tokens couldn't be locked in a past round.
+    #[returns(DenomValidatorResponse)]
+    CanLockDenomAtRound { denom: String, round_id: u64 },
+
+    #[returns(ValidatorSlashesResponse)]
+    ValidatorSlashes {},
+
+    // Returns the locks holding the shares of the given validator, i.e. the locks affected by its slashes.
+    #[returns(ValidatorLocksResponse)]
+    ValidatorLocks {
+        validator: String,
+        start_after: Option<u64>,
+        limit: u32,
+    },
+
+    #[returns(RecomputeRoundTotalsDryRunResponse)]
+    RecomputeRoundTotalsDryRun {
+        round_id: u64,
+        validator: String,
+        corrected_ratio: Decimal,
+        start_from: u32,
+        start_after: Option<(u64, u64)>,
+        limit: u32,
+    },
+
     #[returns(LiquidityDeploymentResponse)]
     LiquidityDeployment {
         round_id: u64,
@@ -118,8 +233,68 @@ pub enum QueryMsg {
         round_id: u64,
         tranche_id: u64,
         start_from: u64,
+        start_after: Option<u64>,
         limit: u64,
     },
+
+    // Returns the statistics of the rounds after start_after, up to and including the current round.
+    #[returns(ProtocolStatsResponse)]
+    ProtocolStats {
+        start_after: Option<u64>,
+        limit: u32,
+    },
+
+    #[returns(NotificationPreferencesResponse)]
+    NotificationPreferences { address: String },
+
+    // Returns the notification preferences of all users, optionally only of those
+    // that opted in for the given category, ordered by the user address.
+    #[returns(AllNotificationPreferencesResponse)]
+    AllNotificationPreferences {
+        category: Option<NotificationCategory>,
+        start_from: u32,
+        start_after: Option<String>,
+        limit: u32,
+    },
+
+    #[returns(ProposalTemplateResponse)]
+    ProposalTemplate { template_id: u64 },
+
+    #[returns(ProposalTemplatesResponse)]
+    ProposalTemplates {
+        start_from: u32,
+        start_after: Option<u64>,
+        limit: u32,
+    },
+
+    // Returns the raw entries of the given state map in the order of their raw keys, starting
+    // after the given raw key. Only available if raw_export_enabled is set in the config.
+    #[returns(ExportRawStateResponse)]
+    ExportRawState {
+        map: RawStateMap,
+        start_after: Option<Binary>,
+        limit: u32,
+    },
+
+    #[returns(RoundDataPruningResponse)]
+    RoundDataPruning { round_id: u64 },
+
+    #[returns(RoundResultsResponse)]
+    RoundResults { round_id: u64, tranche_id: u64 },
+
+    // Returns the recorded admin actions in the order they were executed, starting after the given action id.
+    #[returns(AdminActionsResponse)]
+    AdminActions {
+        start_after: Option<u64>,
+        limit: u32,
+    },
+}
+
+#[cw_serde]
+pub enum RawStateMap {
+    Locks,
+    Votes,
+    Proposals,
 }
 
 #[cw_serde]
@@ -127,18 +302,120 @@ pub struct ConstantsResponse {
     pub constants: Constants,
 }
 
+// Optional features of the contract, as currently configured.
+#[cw_serde]
+pub struct ContractFeatures {
+    pub tribute_contract_set: bool,
+    pub interpolate_lock_power: bool,
+    pub pre_launch_locks: bool,
+    pub raw_export: bool,
+    pub unlock_cooldown: bool,
+    pub tribute_cutoff: bool,
+    pub vote_power_cap: bool,
+}
+
+// Per-tranche features in effect in the current round.
+#[cw_serde]
+pub struct TrancheCapabilities {
+    pub tranche_id: u64,
+    pub hash_only_descriptions: bool,
+    pub quadratic_power_scaling: bool,
+}
+
+// Limits enforced by the contract. Zero max_proposals_per_tranche_round means that the number
+// of proposals is not limited.
+#[cw_serde]
+pub struct ContractLimits {
+    pub max_lock_entries: u64,
+    pub max_locked_tokens: Uint128,
+    pub max_validator_shares_participating: u64,
+    pub max_deployment_duration: u64,
+    pub max_proposals_per_tranche_round: u64,
+}
+
+#[cw_serde]
+pub struct CapabilitiesResponse {
+    pub contract_name: String,
+    pub contract_version: String,
+    pub features: ContractFeatures,
+    pub tranches: Vec<TrancheCapabilities>,
+    pub limits: ContractLimits,
+}
+
 #[cw_serde]
 pub struct TranchesResponse {
     pub tranches: Vec<Tranche>,
 }
 
+#[cw_serde]
+pub struct TranchePowerScalingResponse {
+    pub quadratic: bool,
+}
+
+#[cw_serde]
+pub struct TrancheTributeWeightBonusResponse {
+    pub bonus: Option<TributeWeightBonus>,
+}
+
+// The max_power_percentage is None if the vote power isn't capped in the given round.
+#[cw_serde]
+pub struct VotePowerCapResponse {
+    pub max_power_percentage: Option<Decimal>,
+}
+
+#[cw_serde]
+pub struct TributeContractResponse {
+    pub tribute_contract: Option<Addr>,
+}
+
+// Ids of the tranches in which proposals store only a hash of their full description.
+#[cw_serde]
+pub struct HashOnlyDescriptionTranchesResponse {
+    pub tranche_ids: Vec<u64>,
+}
+
+// If max_proposals is None, the number of proposals in the given round and tranche is not limited.
+#[cw_serde]
+pub struct ProposalSlotsResponse {
+    pub max_proposals: Option<u64>,
+    pub proposals_count: u64,
+    pub remaining_slots: Option<u64>,
+}
+
+// The lengths are given in characters, and are None if not limited. The description summary length
+// applies to the proposals in the tranches that use hash-only descriptions. Titles must not contain
+// control characters, and descriptions must not contain any besides line feeds and tabs.
+#[cw_serde]
+pub struct ProposalTextLimitsResponse {
+    pub max_title_length: Option<u64>,
+    pub max_description_length: Option<u64>,
+    pub max_description_summary_length: u64,
+}
+
+#[cw_serde]
+pub struct InvalidatedProposal {
+    pub proposal_id: u64,
+    pub reason: String,
+    pub invalidated_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct InvalidatedProposalsResponse {
+    pub proposals: Vec<InvalidatedProposal>,
+}
+
 // LockEntryWithPower is a LockEntry with the current voting power of the sender
 // attached. It is used to enrich query responses where the
 // lockups are returned with the current voting power of the lockup.
+// The current voting power is the power the lockup votes with in the current round, i.e. its power
+// at the end of the round. If the interpolate_lock_power is set, the interpolated voting power is
+// the power of the lockup at the time of the query, otherwise it equals the current voting power.
 #[cw_serde]
 pub struct LockEntryWithPower {
     pub lock_entry: LockEntry,
     pub current_voting_power: Uint128,
+    pub interpolated_voting_power: Uint128,
+    pub note: Option<String>,
 }
 
 // PerTrancheLockupInfo is used to store the lockup information for a specific tranche.
@@ -151,6 +428,8 @@ pub struct PerTrancheLockupInfo {
     // In particular, if the lockup is blocked from voting in the current round (because it voted for a
     // proposal with a long deployment duration in a previous round), this will be None.
     pub current_voted_on_proposal: Option<u64>,
+    // Whether the owner explicitly abstained from voting with the lockup in the current round.
+    pub abstaining: bool,
 }
 
 // LockupWithPerTrancheInfo is used to store the lockup information for a specific lockup,
@@ -164,6 +443,7 @@ pub struct LockupWithPerTrancheInfo {
 #[cw_serde]
 pub struct AllUserLockupsResponse {
     pub lockups: Vec<LockEntryWithPower>,
+    pub next_key: Option<u64>,
 }
 
 // This is necessary because otherwise, cosmwasm-ts-codegen does not generate SpecificUserLockupsResponse
@@ -177,6 +457,7 @@ pub struct SpecificUserLockupsResponse {
 #[cw_serde]
 pub struct AllUserLockupsWithTrancheInfosResponse {
     pub lockups_with_per_tranche_infos: Vec<LockupWithPerTrancheInfo>,
+    pub next_key: Option<u64>,
 }
 
 // This is necessary because otherwise, cosmwasm-ts-codegen does not generate SpecificUserLockupsWithTrancheInfosResponse
@@ -189,11 +470,15 @@ pub struct SpecificUserLockupsWithTrancheInfosResponse {
 #[cw_serde]
 pub struct ExpiredUserLockupsResponse {
     pub lockups: Vec<LockEntry>,
+    pub next_key: Option<u64>,
 }
 
+// The voting power is the power the user votes with in the current round, while the interpolated voting
+// power is the power at the time of the query, which differs from it only if the interpolate_lock_power is set.
 #[cw_serde]
 pub struct UserVotingPowerResponse {
     pub voting_power: u128,
+    pub interpolated_voting_power: u128,
 }
 
 #[cw_serde]
@@ -201,6 +486,21 @@ pub struct UserVotesResponse {
     pub votes: Vec<VoteWithPower>,
 }
 
+// The power is the voting power of the lockup in the given round, before the owner power scaling
+// of the tranche is applied. It is zero if the validator of the lockup dropped out from the top N.
+#[cw_serde]
+pub struct ProposalVoter {
+    pub lock_id: u64,
+    pub owner: Addr,
+    pub power: Decimal,
+}
+
+#[cw_serde]
+pub struct ProposalVotersResponse {
+    pub voters: Vec<ProposalVoter>,
+    pub next_key: Option<u64>,
+}
+
 #[cw_serde]
 pub struct CurrentRoundResponse {
     pub round_id: u64,
@@ -212,19 +512,50 @@ pub struct RoundEndResponse {
     pub round_end: Timestamp,
 }
 
+// Tributes for the proposals of a round can only be added before the tribute_cutoff.
+#[cw_serde]
+pub struct RoundCutoffsResponse {
+    pub round_id: u64,
+    pub tribute_cutoff: Timestamp,
+    pub round_end: Timestamp,
+}
+
+// The total voting power is the power of the round at its end, against which the votes are tallied, while the
+// interpolated total voting power is the power at the time of the query, which differs from it only if the
+// interpolate_lock_power is set.
 #[cw_serde]
 pub struct RoundTotalVotingPowerResponse {
     pub total_voting_power: Uint128,
+    pub interpolated_total_voting_power: Uint128,
 }
 
 #[cw_serde]
+// The tribute weight of the proposal is its power plus the tribute weight bonus of the votes that qualified for it.
+// The capped power is the power of the proposal that counts towards its score, which is lower than
+// its power only if the proposal has a vote cap and received more power than the cap allows, or if
+// some of its voters are above the vote power cap of the round.
 pub struct ProposalResponse {
     pub proposal: Proposal,
+    pub tribute_weight: Decimal,
+    pub capped_power: Uint128,
 }
 
 #[cw_serde]
+// Proposals are ordered by their score (i.e. capped power) in descending order. Proposals with
+// equal score are ordered by their id in ascending order, so the earlier proposal wins the tie.
+// The ordering_keys hold the keys by which the proposals at the same positions were ordered.
 pub struct TopNProposalsResponse {
     pub proposals: Vec<Proposal>,
+    pub ordering_keys: Vec<ProposalOrderingKey>,
+    // Precise percentages of the total round power received by each of the returned proposals.
+    // The percentage field of the proposals holds the same value, truncated to an integer.
+    pub percentages: Vec<Decimal>,
+}
+
+#[cw_serde]
+pub struct ProposalOrderingKey {
+    pub score: Uint128,
+    pub proposal_id: u64,
 }
 #[cw_serde]
 pub struct WhitelistResponse {
@@ -244,6 +575,7 @@ pub struct TotalLockedTokensResponse {
 #[cw_serde]
 pub struct RoundProposalsResponse {
     pub proposals: Vec<Proposal>,
+    pub next_key: Option<u64>,
 }
 
 // A vector containing tuples, where each tuple contains a validator address
@@ -258,6 +590,195 @@ pub struct ValidatorPowerRatioResponse {
     pub ratio: Decimal,
 }
 
+#[cw_serde]
+pub enum DenomResolution {
+    Lockable {
+        validator: String,
+        power_ratio: Decimal,
+    },
+    // The validator is None if the denom doesn't represent the LSM shares of any validator.
+    NotLockable {
+        validator: Option<String>,
+        reason: String,
+    },
+}
+
+// The resolution only changes when the validator set is updated, so it can be cached
+// by the clients until the end of the given round.
+#[cw_serde]
+pub struct DenomValidatorResponse {
+    pub denom: String,
+    pub round_id: u64,
+    pub resolution: DenomResolution,
+}
+
+// A vector containing tuples, where each tuple contains a validator address
+// and the information about all slashes that were applied to that validator.
+#[cw_serde]
+pub struct ValidatorSlashesResponse {
+    pub slashes: Vec<(String, ValidatorSlashInfo)>,
+}
+
+#[cw_serde]
+pub struct ValidatorLock {
+    pub lock_id: u64,
+    pub owner: Addr,
+}
+
+#[cw_serde]
+pub struct ValidatorLocksResponse {
+    pub locks: Vec<ValidatorLock>,
+    pub next_key: Option<u64>,
+}
+
+// Shows the effect that correcting the power ratio of a validator in a past round would have.
+// Only the proposals whose power would change are listed, out of the page of the round proposals
+// determined by start_after (or start_from) and limit. The next_key is the (tranche_id, proposal_id)
+// of the last proposal in the page, even if its power wouldn't change.
+#[cw_serde]
+pub struct RecomputeRoundTotalsDryRunResponse {
+    pub current_power_ratio: Decimal,
+    pub corrected_power_ratio: Decimal,
+    pub current_round_total_power: Decimal,
+    pub corrected_round_total_power: Decimal,
+    pub proposals: Vec<ProposalPowerDelta>,
+    pub next_key: Option<(u64, u64)>,
+}
+
+#[cw_serde]
+pub struct ProposalPowerDelta {
+    pub tranche_id: u64,
+    pub proposal_id: u64,
+    pub current_power: Uint128,
+    pub corrected_power: Uint128,
+}
+
+#[cw_serde]
+pub struct NotificationPreferencesResponse {
+    pub preferences: Option<NotificationPreferences>,
+}
+
+#[cw_serde]
+pub struct UserNotificationPreferences {
+    pub address: Addr,
+    pub preferences: NotificationPreferences,
+}
+
+#[cw_serde]
+pub struct AllNotificationPreferencesResponse {
+    pub preferences: Vec<UserNotificationPreferences>,
+    pub next_key: Option<String>,
+}
+
+#[cw_serde]
+pub struct TemplateProposal {
+    pub round_id: u64,
+    pub proposal_id: u64,
+}
+
+#[cw_serde]
+pub struct ProposalTemplateResponse {
+    pub template: ProposalTemplate,
+    // proposals created from the template, ordered by the round id
+    pub proposals: Vec<TemplateProposal>,
+}
+
+#[cw_serde]
+pub struct ProposalTemplatesResponse {
+    pub templates: Vec<ProposalTemplate>,
+    pub next_key: Option<u64>,
+}
+
+#[cw_serde]
+pub struct RawStateEntry {
+    pub key: Binary,
+    pub value: Binary,
+}
+
+// The next_key is the key of the last entry, which should be used as start_after to get the next page.
+#[cw_serde]
+pub struct ExportRawStateResponse {
+    pub entries: Vec<RawStateEntry>,
+    pub next_key: Option<Binary>,
+}
+
+// The pruning is None if no data of the round was pruned yet.
+#[cw_serde]
+pub struct RoundDataPruningResponse {
+    pub pruning: Option<RoundDataPruning>,
+}
+
+// The results are None if the round wasn't finalized yet in the given tranche.
+#[cw_serde]
+pub struct RoundResultsResponse {
+    pub results: Option<RoundResults>,
+}
+
+#[cw_serde]
+pub struct AutoRefreshLock {
+    pub lock_id: u64,
+    pub auto_refresh: AutoRefresh,
+}
+
+#[cw_serde]
+pub struct AutoRefreshLocksResponse {
+    pub locks: Vec<AutoRefreshLock>,
+}
+
+#[cw_serde]
+pub struct PendingUnlockEntry {
+    pub lock_id: u64,
+    pub pending_unlock: PendingUnlock,
+}
+
+#[cw_serde]
+pub struct PendingUnlocksResponse {
+    pub pending_unlocks: Vec<PendingUnlockEntry>,
+}
+
+#[cw_serde]
+pub struct VoteDelegation {
+    pub lock_id: u64,
+    pub delegate: Addr,
+}
+
+#[cw_serde]
+pub struct DelegationsForUserResponse {
+    pub delegations: Vec<VoteDelegation>,
+}
+
+#[cw_serde]
+pub struct RelayedVoteNonceResponse {
+    pub next_nonce: u64,
+}
+
+#[cw_serde]
+pub struct VotingExecutorResponse {
+    pub voting_executor: Option<VotingExecutor>,
+}
+
+#[cw_serde]
+pub struct LockOperator {
+    pub lock_id: u64,
+    pub operator: Addr,
+}
+
+#[cw_serde]
+pub struct LockOperatorsResponse {
+    pub operators: Vec<LockOperator>,
+}
+
+#[cw_serde]
+pub struct LockAutoMergeResponse {
+    pub tolerance: Option<u64>,
+}
+
+#[cw_serde]
+pub struct AdminActionsResponse {
+    pub actions: Vec<AdminAction>,
+    pub next_key: Option<u64>,
+}
+
 #[cw_serde]
 pub struct ICQManagersResponse {
     pub managers: Vec<Addr>,
@@ -271,4 +792,34 @@ pub struct LiquidityDeploymentResponse {
 #[cw_serde]
 pub struct RoundTrancheLiquidityDeploymentsResponse {
     pub liquidity_deployments: Vec<LiquidityDeployment>,
+    pub next_key: Option<u64>,
+}
+
+// Participation in a single tranche of a round: the sum of the powers of all proposals
+// in the tranche, and the ratio of that sum to the total voting power of the round.
+#[cw_serde]
+pub struct TrancheParticipation {
+    pub tranche_id: u64,
+    pub voted_power: Uint128,
+    pub participation: Decimal,
+}
+
+#[cw_serde]
+pub struct RoundProtocolStats {
+    pub round_id: u64,
+    pub total_voting_power: Uint128,
+    pub tranches: Vec<TrancheParticipation>,
+    pub unique_voters: u64,
+    pub proposals_count: u64,
+    // sum of the funds deployed for all proposals of the round, per denom
+    pub deployed_funds: Vec<Coin>,
+}
+
+// The statistics are tracked from first_tracked_round_id on. The earlier rounds report no voters,
+// proposals or voted power, since the contract didn't keep statistics at the time.
+#[cw_serde]
+pub struct ProtocolStatsResponse {
+    pub rounds: Vec<RoundProtocolStats>,
+    pub first_tracked_round_id: u64,
+    pub next_key: Option<u64>,
 }
@@ -1,9 +1,12 @@
-use cosmwasm_std::{Decimal, StdError, StdResult, Storage};
+use cosmwasm_std::{Decimal, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::Map;
 
 use crate::{
     lsm_integration::get_validator_power_ratio_for_round,
-    state::{PROPOSAL_TOTAL_MAP, SCALED_PROPOSAL_SHARES_MAP},
+    state::{
+        Proposal, PROPOSAL_BONUS_TOTAL_MAP, PROPOSAL_TOTAL_MAP, SCALED_PROPOSAL_BONUS_SHARES_MAP,
+        SCALED_PROPOSAL_SHARES_MAP,
+    },
 };
 
 pub fn get_total_power_for_proposal(storage: &dyn Storage, prop_id: u64) -> StdResult<Decimal> {
@@ -12,6 +15,62 @@ pub fn get_total_power_for_proposal(storage: &dyn Storage, prop_id: u64) -> StdR
         .unwrap_or(Decimal::zero()))
 }
 
+// Returns the power of the proposal that counts towards its score. The given power that the owners above
+// the vote power cap contribute to the proposal beyond the cap is ignored. If the proposal has a vote cap,
+// any power above the given percentage of the total round power is truncated.
+pub fn get_capped_proposal_power(
+    proposal: &Proposal,
+    vote_power_cap_excess: Decimal,
+    total_round_power: Uint128,
+) -> StdResult<Uint128> {
+    let power = proposal
+        .power
+        .saturating_sub(vote_power_cap_excess.to_uint_floor());
+
+    let Some(max_vote_percentage) = proposal.max_vote_percentage else {
+        return Ok(power);
+    };
+
+    let max_power = Decimal::from_ratio(total_round_power, 100u128)
+        .checked_mul(max_vote_percentage)?
+        .to_uint_floor();
+
+    Ok(power.min(max_power))
+}
+
+// Returns the factor by which the shares of an owner with the given power within a tranche are scaled,
+// so that the owner's power doesn't exceed the given percentage of the total round power. The excess
+// power is ignored, so owners below the cap are not scaled.
+pub fn get_vote_power_cap_factor(
+    owner_power: Decimal,
+    total_round_power: Decimal,
+    max_power_percentage: Decimal,
+) -> StdResult<Decimal> {
+    let max_power = total_round_power
+        .checked_mul(max_power_percentage)?
+        .checked_div(Decimal::from_ratio(100u128, 1u128))
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    if owner_power <= max_power {
+        return Ok(Decimal::one());
+    }
+
+    max_power
+        .checked_div(owner_power)
+        .map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+// Returns the tribute weight bonus of the proposal, i.e. the tribute weight that the votes
+// which qualified for the tranche tribute weight bonus have on top of their power.
+pub fn get_tribute_weight_bonus_for_proposal(
+    storage: &dyn Storage,
+    prop_id: u64,
+) -> StdResult<Decimal> {
+    Ok(PROPOSAL_BONUS_TOTAL_MAP
+        .may_load(storage, prop_id)?
+        .unwrap_or(Decimal::zero()))
+}
+
 pub fn get_validator_shares_for_proposal(
     storage: &dyn Storage,
     prop_id: u64,
@@ -128,6 +187,80 @@ pub fn remove_validator_shares_from_proposal(
     )
 }
 
+// Returns the bonus shares for the given vote shares, if the vote qualified for a tribute weight bonus.
+fn get_bonus_shares(
+    num_shares: Decimal,
+    tribute_weight_multiplier: Option<Decimal>,
+) -> StdResult<Option<Decimal>> {
+    tribute_weight_multiplier
+        .filter(|multiplier| *multiplier > Decimal::one())
+        .map(|multiplier| Ok(num_shares.checked_mul(multiplier - Decimal::one())?))
+        .transpose()
+}
+
+// Adds the shares of a vote to the proposal. If the vote qualified for a tribute weight bonus,
+// the bonus is added to the proposal as well, without changing the proposal power.
+pub fn add_vote_shares_to_proposal(
+    storage: &mut dyn Storage,
+    round_id: u64,
+    prop_id: u64,
+    validator: String,
+    num_shares: Decimal,
+    tribute_weight_multiplier: Option<Decimal>,
+) -> StdResult<()> {
+    add_validator_shares_to_proposal(storage, round_id, prop_id, validator.clone(), num_shares)?;
+
+    if let Some(bonus_shares) = get_bonus_shares(num_shares, tribute_weight_multiplier)? {
+        let power_ratio =
+            get_validator_power_ratio_for_round(storage, round_id, validator.clone())?;
+        add_validator_shares(
+            storage,
+            prop_id,
+            SCALED_PROPOSAL_BONUS_SHARES_MAP,
+            PROPOSAL_BONUS_TOTAL_MAP,
+            validator,
+            bonus_shares,
+            power_ratio,
+        )?;
+    }
+
+    Ok(())
+}
+
+// Removes the shares of a vote from the proposal, together with its tribute weight bonus, if any.
+pub fn remove_vote_shares_from_proposal(
+    storage: &mut dyn Storage,
+    round_id: u64,
+    prop_id: u64,
+    validator: String,
+    num_shares: Decimal,
+    tribute_weight_multiplier: Option<Decimal>,
+) -> StdResult<()> {
+    remove_validator_shares_from_proposal(
+        storage,
+        round_id,
+        prop_id,
+        validator.clone(),
+        num_shares,
+    )?;
+
+    if let Some(bonus_shares) = get_bonus_shares(num_shares, tribute_weight_multiplier)? {
+        let power_ratio =
+            get_validator_power_ratio_for_round(storage, round_id, validator.clone())?;
+        remove_validator_shares(
+            storage,
+            prop_id,
+            SCALED_PROPOSAL_BONUS_SHARES_MAP,
+            PROPOSAL_BONUS_TOTAL_MAP,
+            validator,
+            bonus_shares,
+            power_ratio,
+        )?;
+    }
+
+    Ok(())
+}
+
 // A more gas efficient version of remove_validator_shares_from_proposal
 // when removing shares from multiple validators
 pub fn remove_many_validator_shares_from_proposal(
@@ -218,6 +351,16 @@ pub fn update_power_ratio_for_proposal(
         prop_id,
         SCALED_PROPOSAL_SHARES_MAP,
         PROPOSAL_TOTAL_MAP,
+        validator.clone(),
+        old_power_ratio,
+        new_power_ratio,
+    )?;
+
+    update_power_ratio(
+        storage,
+        prop_id,
+        SCALED_PROPOSAL_BONUS_SHARES_MAP,
+        PROPOSAL_BONUS_TOTAL_MAP,
         validator,
         old_power_ratio,
         new_power_ratio,
@@ -2,7 +2,7 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Coin, Decimal, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
-use crate::msg::LiquidityDeployment;
+use crate::msg::{LiquidityDeployment, NotificationCategory, TributeWeightBonus};
 
 pub const CONSTANTS: Item<Constants> = Item::new("constants");
 
@@ -66,6 +66,30 @@ pub struct Constants {
     pub paused: bool,
     pub max_deployment_duration: u64,
     pub round_lock_power_schedule: RoundLockPowerSchedule,
+    // If set, the lock power scaling factor is linearly interpolated between the entries of
+    // the round_lock_power_schedule. See scale_lockup_power in contract.rs
+    pub interpolate_lock_power: bool,
+    // Duration (in nanoseconds) before the end of each round during which tributes
+    // can no longer be added for the proposals of that round. Zero disables the cutoff.
+    pub tribute_cutoff_duration: u64,
+    // Maximum number of proposals that can be created in a single tranche in each round.
+    // Zero means that the number of proposals is not limited.
+    pub max_proposals_per_tranche_round: u64,
+    // If set, tokens can be locked before the first round starts. See compute_lock_round_id in contract.rs
+    pub allow_pre_launch_locks: bool,
+    // If set, the raw entries of the locks, votes and proposals maps can be exported through the
+    // ExportRawState query, e.g. to rebuild the state for contract redeployments.
+    pub raw_export_enabled: bool,
+    // Duration (in nanoseconds) that the unlocked tokens are held by the contract before they can be
+    // claimed via ClaimUnlocked. Zero disables the cooldown, so the tokens are sent out on unlock.
+    pub unlock_cooldown_duration: u64,
+    // Names of the execute messages that are paused on their own, while the rest of the contract keeps
+    // working. See get_pausable_message_name in contract.rs for the messages that can be paused.
+    pub paused_messages: Vec<String>,
+    // Maximum number of characters in the titles and descriptions of new proposals and proposal templates.
+    // Zero means that the length is not limited.
+    pub max_proposal_title_length: u64,
+    pub max_proposal_description_length: u64,
 }
 
 // the total number of tokens locked in the contract
@@ -73,6 +97,10 @@ pub const LOCKED_TOKENS: Item<u128> = Item::new("locked_tokens");
 
 pub const LOCK_ID: Item<u64> = Item::new("lock_id");
 
+// Set once the whitelist admins finish importing the lockups migrated from another deployment,
+// after which no more lockups can be imported.
+pub const LOCKUPS_IMPORT_FINALIZED: Item<bool> = Item::new("lockups_import_finalized");
+
 // stores the current PROP_ID, in order to ensure that each proposal has a unique ID
 // this is incremented every time a new proposal is created
 pub const PROP_ID: Item<u64> = Item::new("prop_id");
@@ -100,34 +128,68 @@ pub struct Proposal {
     pub percentage: Uint128,
     pub deployment_duration: u64, // number of rounds liquidity is allocated excluding voting round.
     pub minimum_atom_liquidity_request: Uint128,
+    // Set only for proposals created in tranches that use hash-only descriptions. In that case
+    // the description field holds just a short summary, and the full description is stored off-chain.
+    pub description_hash: Option<String>,
+    // Maximum percentage (0-100) of the total round power that counts towards the score of the proposal.
+    // Any power above it is still recorded, but it doesn't help the proposal rank higher.
+    pub max_vote_percentage: Option<Decimal>,
 }
 
 // VOTE_MAP: key((round_id, tranche_id), sender_addr, lock_id) -> Vote
 pub const VOTE_MAP: Map<((u64, u64), Addr, u64), Vote> = Map::new("vote_map");
 
+// Index of the VOTE_MAP entries by the proposal that was voted for, so that the voters of a proposal can be
+// listed without going through all votes of the round and tranche. Updated together with the VOTE_MAP.
+// PROPOSAL_VOTERS: key((round_id, tranche_id), proposal_id, lock_id) -> sender_addr
+pub const PROPOSAL_VOTERS: Map<((u64, u64), u64, u64), Addr> = Map::new("proposal_voters");
+
 // Tracks the next round in which user is allowed to vote with the given lock_id.
 // VOTING_ALLOWED_ROUND: key(tranche_id, lock_id) -> round_id
 pub const VOTING_ALLOWED_ROUND: Map<(u64, u64), u64> = Map::new("voting_allowed_round");
 
+// Lockups whose owners explicitly abstained from voting with them in a round, while reserving the right to
+// vote with them later in the same round. Voting with the lockup in that round removes the abstention.
+// ABSTENTIONS: key((round_id, tranche_id), owner_addr, lock_id) -> abstention_time
+pub const ABSTENTIONS: Map<((u64, u64), Addr, u64), Timestamp> = Map::new("abstentions");
+
 #[cw_serde]
 pub struct Vote {
     pub prop_id: u64,
     // stores the amount of shares of that validator the user voted with
     // (already scaled according to lockup scaling)
     pub time_weighted_shares: (String, Decimal),
+    // multiplier of the tribute weight of the vote, if the lock qualified for the
+    // tribute weight bonus of the tranche when it voted
+    pub tribute_weight_multiplier: Option<Decimal>,
 }
 
 #[cw_serde]
 // VoteWithPower is used to store a vote, where the time_weighted_shares
 // have been resolved to compute the total power of the vote.
+// The tribute weight is the power of the vote, including any tribute weight bonus,
+// and is used to compute the share of the proposal tributes that the voter can claim.
 pub struct VoteWithPower {
     pub prop_id: u64,
     pub power: Decimal,
+    pub tribute_weight: Decimal,
 }
 
-// PROPS_BY_SCORE: key((round_id, tranche_id), score, prop_id) -> prop_id
+// PROPS_BY_SCORE: key((round_id, tranche_id), score, tie_breaker) -> prop_id
+// The tie_breaker is u64::MAX - prop_id, so that when iterating in descending order, proposals
+// with equal score are returned in ascending order of prop_id, i.e. the earlier proposal wins the tie.
+// Use get_props_by_score_key() to build the keys of this map.
 pub const PROPS_BY_SCORE: Map<((u64, u64), u128, u64), u64> = Map::new("props_by_score");
 
+pub fn get_props_by_score_key(
+    round_id: u64,
+    tranche_id: u64,
+    score: Uint128,
+    prop_id: u64,
+) -> ((u64, u64), u128, u64) {
+    ((round_id, tranche_id), score.u128(), u64::MAX - prop_id)
+}
+
 pub const TRANCHE_ID: Item<u64> = Item::new("tranche_id");
 
 // TRANCHE_MAP: key(tranche_id) -> Tranche
@@ -139,6 +201,116 @@ pub struct Tranche {
     pub metadata: String,
 }
 
+// Tranches in which proposals store only a hash of their full description, alongside a short summary.
+// The full description is kept off-chain, and its URI is emitted when the proposal is created.
+// Whitelist admins can enable or disable this mode for each tranche via SetTrancheDescriptionMode.
+// HASH_ONLY_DESCRIPTION_TRANCHES: key(tranche_id) -> bool
+pub const HASH_ONLY_DESCRIPTION_TRANCHES: Map<u64, bool> =
+    Map::new("hash_only_description_tranches");
+
+// Stores the history of the power scaling settings of each tranche. In tranches with quadratic power scaling,
+// the power of each owner within the tranche is the square root of the aggregate power of all the locks they voted with.
+// Changes made by whitelist admins take effect starting from the next round, so that all votes in a round are
+// scaled in the same way. The setting for a round is given by the entry with the largest activation round
+// that is not greater than that round.
+// QUADRATIC_POWER_SCALING: key(tranche_id, activation_round_id) -> quadratic
+pub const QUADRATIC_POWER_SCALING: Map<(u64, u64), bool> = Map::new("quadratic_power_scaling");
+
+// Stores the history of the tribute weight bonus settings of each tranche. Locks that qualify for the bonus
+// when voting get a larger share of the proposal tributes, without getting any additional voting power.
+// As for the power scaling, changes take effect starting from the next round, and the setting for a round
+// is given by the entry with the largest activation round that is not greater than that round.
+// TRIBUTE_WEIGHT_BONUSES: key(tranche_id, activation_round_id) -> bonus (None if disabled)
+pub const TRIBUTE_WEIGHT_BONUSES: Map<(u64, u64), Option<TributeWeightBonus>> =
+    Map::new("tribute_weight_bonuses");
+
+// Stores the history of the vote power cap, which limits the power that the locks of a single owner can
+// contribute within a tranche to a percentage of the total round power. Any power above the cap is ignored
+// when the votes are tallied.
+// As for the power scaling, changes take effect starting from the next round, and the cap for a round
+// is given by the entry with the largest activation round that is not greater than that round.
+// VOTE_POWER_CAPS: key(activation_round_id) -> max_power_percentage (None if disabled)
+pub const VOTE_POWER_CAPS: Map<u64, Option<Decimal>> = Map::new("vote_power_caps");
+
+// In rounds in which the vote power cap applies, stores the power of the votes of each owner within a tranche,
+// computed with the validator power ratios at the time of the last change of the owner's votes. Together with
+// OWNERS_BY_VOTE_POWER, it is used to find the owners whose power is above the cap when the votes are tallied.
+// OWNER_VOTE_POWER: key((round_id, tranche_id), owner_addr) -> power
+pub const OWNER_VOTE_POWER: Map<((u64, u64), Addr), u128> = Map::new("owner_vote_power");
+
+// OWNERS_BY_VOTE_POWER: key((round_id, tranche_id), power, owner_addr) -> bool
+pub const OWNERS_BY_VOTE_POWER: Map<((u64, u64), u128, Addr), bool> =
+    Map::new("owners_by_vote_power");
+
+// Product of all increases of the validator power ratios within each round, i.e. the largest factor by which the
+// power of the votes cast in the round could have grown since they were cast. Since the owner powers stored in
+// OWNERS_BY_VOTE_POWER are not updated when the power ratios change, they are scaled by this factor when looking
+// for the owners that could be above the vote power cap.
+// ROUND_POWER_RATIO_GROWTH: key(round_id) -> growth
+pub const ROUND_POWER_RATIO_GROWTH: Map<u64, Decimal> = Map::new("round_power_ratio_growth");
+
+// Address of the tribute contract, which is asked to claim the tributes of the unlocked locks
+// when the user unlocks tokens with claim_tributes set. Managed by the whitelist admins.
+pub const TRIBUTE_CONTRACT: Item<Addr> = Item::new("tribute_contract");
+
+// Notification preferences that the users opted in with, to be read by off-chain notifier services.
+// The endpoint is an opaque value, e.g. a hash of the notification endpoint or a public key, so that
+// no sensitive information is stored on-chain. Set and removed only by the users themselves.
+// NOTIFICATION_PREFERENCES: key(user_address) -> NotificationPreferences
+pub const NOTIFICATION_PREFERENCES: Map<Addr, NotificationPreferences> =
+    Map::new("notification_preferences");
+
+#[cw_serde]
+pub struct NotificationPreferences {
+    pub endpoint: String,
+    pub categories: Vec<NotificationCategory>,
+    pub updated_at: Timestamp,
+}
+
+// Proposal templates registered by the whitelisted proposers for their recurring deployments. Each round,
+// the template creator can create a new proposal from the template via InstantiateFromTemplate.
+// PROPOSAL_TEMPLATES: key(template_id) -> ProposalTemplate
+pub const PROPOSAL_TEMPLATES: Map<u64, ProposalTemplate> = Map::new("proposal_templates");
+
+// Id to be assigned to the next registered proposal template. Not initialized in instantiate,
+// so it defaults to 0 when not set.
+pub const PROPOSAL_TEMPLATE_ID: Item<u64> = Item::new("proposal_template_id");
+
+// Proposals created from each template, which form the chain of successive proposals of the same deployment.
+// TEMPLATE_PROPOSALS: key(template_id, round_id) -> proposal_id
+pub const TEMPLATE_PROPOSALS: Map<(u64, u64), u64> = Map::new("template_proposals");
+
+#[cw_serde]
+pub struct ProposalTemplate {
+    pub template_id: u64,
+    pub creator: Addr,
+    pub tranche_id: u64,
+    pub title: String,
+    pub description: String,
+    pub deployment_duration: u64,
+    pub minimum_atom_liquidity_request: Uint128,
+    pub description_hash: Option<String>,
+    pub description_uri: Option<String>,
+}
+
+// Overrides of the max_proposals_per_tranche_round limit for specific rounds and tranches,
+// set by the whitelist admins via SetProposalLimitOverride.
+// PROPOSAL_LIMIT_OVERRIDES: key(round_id, tranche_id) -> max_proposals
+pub const PROPOSAL_LIMIT_OVERRIDES: Map<(u64, u64), u64> = Map::new("proposal_limit_overrides");
+
+// Proposals that were invalidated by the whitelist admins, e.g. because they turned out to be fraudulent.
+// Invalidated proposals are removed from PROPS_BY_SCORE and can't receive any new votes. Locks that voted
+// for an invalidated proposal are allowed to vote again right away, regardless of VOTING_ALLOWED_ROUND.
+// INVALIDATED_PROPOSALS: key(round_id, tranche_id, proposal_id) -> InvalidatedProposalInfo
+pub const INVALIDATED_PROPOSALS: Map<(u64, u64, u64), InvalidatedProposalInfo> =
+    Map::new("invalidated_proposals");
+
+#[cw_serde]
+pub struct InvalidatedProposalInfo {
+    pub reason: String,
+    pub invalidated_at: Timestamp,
+}
+
 // The initial whitelist is set upon contract instantiation.
 // It can be updated by anyone on the WHITELIST_ADMINS list
 // via the update_whitelist message.
@@ -186,6 +358,22 @@ pub const VALIDATORS_STORE_INITIALIZED: Map<u64, bool> = Map::new("round_store_i
 pub const SCALED_ROUND_POWER_SHARES_MAP: Map<(u64, String), Decimal> =
     Map::new("scaled_round_power_shares");
 
+// Checkpoint of the total voting power of each round, i.e. the sum of the SCALED_ROUND_POWER_SHARES_MAP
+// shares of each validator in the round multiplied by its power ratio in that round. Updated whenever the
+// shares or the power ratios change, so that the total power can be read without going over all validators.
+// Rounds whose validator store hasn't been initialized yet have no entry, and their total power is zero.
+// TOTAL_ROUND_POWER: key(round_id) -> total_power
+pub const TOTAL_ROUND_POWER: Map<u64, Decimal> = Map::new("total_round_power");
+
+// If the interpolate_lock_power is set, the same time-scaled shares and total power are also stored as of
+// the start of each round, so that the power within the round can be interpolated between the two.
+// SCALED_ROUND_START_POWER_SHARES_MAP: key(round_id, validator_address) -> number_of_shares
+pub const SCALED_ROUND_START_POWER_SHARES_MAP: Map<(u64, String), Decimal> =
+    Map::new("scaled_round_start_power_shares");
+
+// TOTAL_ROUND_START_POWER: key(round_id) -> total_power
+pub const TOTAL_ROUND_START_POWER: Map<u64, Decimal> = Map::new("total_round_start_power");
+
 // The following two store fields are supposed to be kept in sync,
 // i.e. whenever the shares of a proposal (or the power ratio of a validator)
 // get updated, the total power of the proposal should be updated as well.
@@ -199,6 +387,55 @@ pub const SCALED_PROPOSAL_SHARES_MAP: Map<(u64, String), Decimal> =
 // PROPOSAL_TOTAL_MAP: key(proposal_id) -> total_power
 pub const PROPOSAL_TOTAL_MAP: Map<u64, Decimal> = Map::new("proposal_power_total");
 
+// The following two store fields hold the tribute weight bonus of the votes that qualified for it.
+// They are kept separately from the two maps above, so that the bonus doesn't affect the proposal power,
+// and are kept in sync in the same way. The bonus shares of a vote are its shares multiplied by
+// (tribute_weight_multiplier - 1), so that the tribute weight of a proposal is its power plus its bonus.
+// SCALED_PROPOSAL_BONUS_SHARES_MAP: key(proposal_id, validator_address) -> number_of_bonus_shares
+pub const SCALED_PROPOSAL_BONUS_SHARES_MAP: Map<(u64, String), Decimal> =
+    Map::new("scaled_proposal_bonus_shares");
+
+// PROPOSAL_BONUS_TOTAL_MAP: key(proposal_id) -> total_tribute_weight_bonus
+pub const PROPOSAL_BONUS_TOTAL_MAP: Map<u64, Decimal> = Map::new("proposal_bonus_total");
+
+// Slashes of validators are detected by observing a decrease of the validator power ratio in the
+// results of the interchain queries. Since the power of all locks holding the shares of that validator
+// is derived from the power ratio, the power of those locks gets reduced by the same fraction.
+// This map keeps track of all slashes applied to each validator over the lifetime of the contract.
+// VALIDATOR_SLASHES: key(validator_address) -> ValidatorSlashInfo
+pub const VALIDATOR_SLASHES: Map<String, ValidatorSlashInfo> = Map::new("validator_slashes");
+#[cw_serde]
+pub struct ValidatorSlashInfo {
+    // fraction of the validator power that was lost due to all slashes, compounded
+    pub cumulative_slash_fraction: Decimal,
+    pub slashes_count: u64,
+    pub last_slash_round_id: u64,
+}
+
+// Index of the locks by the validator whose shares they hold, so that the locks affected by a slash of that
+// validator can be listed. Updated whenever a lock is created or unlocked. The locks created before the index
+// was introduced are added to it by the whitelist admins via IndexValidatorLocks.
+// VALIDATOR_LOCKS: key(validator_address, lock_id) -> owner
+pub const VALIDATOR_LOCKS: Map<(String, u64), Addr> = Map::new("validator_locks");
+
+// Reverse of the VALIDATOR_LOCKS index, used to remove a lock from it once the lock is unlocked.
+// LOCK_VALIDATORS: key(lock_id) -> validator_address
+pub const LOCK_VALIDATORS: Map<u64, String> = Map::new("lock_validators");
+
+// Corrections of a validator power ratio in a round that has already ended, started by the whitelist admins
+// via RecomputeRoundTotals. The corrected ratio is written into VALIDATORS_INFO right away, while the powers
+// of the round proposals are recomputed in batches. An entry is removed once all proposals have been processed.
+// POWER_RATIO_CORRECTIONS: key(round_id, validator_address) -> PowerRatioCorrection
+pub const POWER_RATIO_CORRECTIONS: Map<(u64, String), PowerRatioCorrection> =
+    Map::new("power_ratio_corrections");
+#[cw_serde]
+pub struct PowerRatioCorrection {
+    pub old_power_ratio: Decimal,
+    pub corrected_power_ratio: Decimal,
+    // (tranche_id, proposal_id) of the last proposal whose power was recomputed
+    pub last_processed_proposal: Option<(u64, u64)>,
+}
+
 // Stores the accounts that can attempt to create ICQs without sending funds to the contract
 // in the same message, which will then implicitly be paid for by the contract.
 // These accounts can also withdraw native tokens (but not voting tokens locked by users)
@@ -228,3 +465,151 @@ impl ValidatorInfo {
 // LIQUIDITY_DEPLOYMENTS_MAP: key(round_id, tranche_id, prop_id) -> deployment
 pub const LIQUIDITY_DEPLOYMENTS_MAP: Map<(u64, u64, u64), LiquidityDeployment> =
     Map::new("liquidity_deployments_map");
+
+// Aggregated statistics for each round. These are updated incrementally as proposals are created,
+// votes are cast and liquidity deployments are entered, so that they never need to be recomputed
+// by scanning the per-round maps. Rounds before ROUND_STATS_FIRST_ROUND have no entry in this map,
+// or an incomplete one if liquidity deployments were entered for them afterwards.
+// ROUND_STATS: key(round_id) -> RoundStats
+pub const ROUND_STATS: Map<u64, RoundStats> = Map::new("round_stats");
+
+// The first round whose statistics are complete. Contracts instantiated with the round statistics
+// track them from the first round on, so this is only set by the migration that introduced them.
+pub const ROUND_STATS_FIRST_ROUND: Item<u64> = Item::new("round_stats_first_round");
+#[cw_serde]
+#[derive(Default)]
+pub struct RoundStats {
+    pub unique_voters: u64,
+    pub proposals_count: u64,
+    pub deployed_funds: Vec<Coin>,
+}
+
+// Tracks which addresses have votes in a round, in any tranche, so that each voter is counted only once,
+// and is no longer counted once all of its votes in the round are removed.
+// ROUND_VOTERS: key(round_id, voter_addr) -> bool
+pub const ROUND_VOTERS: Map<(u64, Addr), bool> = Map::new("round_voters");
+
+// Stores the sum of the powers of all proposals in a given round and tranche.
+// It is kept in sync with the power field of each Proposal in the PROPOSAL_MAP.
+// ROUND_TRANCHE_VOTED_POWER: key(round_id, tranche_id) -> voted_power
+pub const ROUND_TRANCHE_VOTED_POWER: Map<(u64, u64), Uint128> =
+    Map::new("round_tranche_voted_power");
+
+// Progress of the pruning of the data of rounds that ended long ago, started by the whitelist admins
+// via PruneRoundData. Pruned rounds keep their proposals, results and statistics, but lose their votes,
+// abstentions, voters, proposal rankings, validator set and the validator shares of the round and its proposals.
+// ROUND_DATA_PRUNING: key(round_id) -> RoundDataPruning
+pub const ROUND_DATA_PRUNING: Map<u64, RoundDataPruning> = Map::new("round_data_pruning");
+#[cw_serde]
+#[derive(Default)]
+pub struct RoundDataPruning {
+    pub removed_entries: u64,
+    pub completed: bool,
+    // (tranche_id, proposal_id) of the last proposal whose shares were removed
+    pub last_processed_proposal: Option<(u64, u64)>,
+    // id of the last proposal template whose proposal in the round was removed
+    pub last_processed_template: Option<u64>,
+}
+
+// Results of the rounds that were finalized via FinalizeRound, after they ended. Holds the best ranked
+// proposals at the time of the finalization, so that they don't change if the proposal powers are later
+// recomputed, e.g. due to a power ratio correction.
+// ROUND_RESULTS: key(round_id, tranche_id) -> RoundResults
+pub const ROUND_RESULTS: Map<(u64, u64), RoundResults> = Map::new("round_results");
+#[cw_serde]
+pub struct RoundResults {
+    pub finalized_at: Timestamp,
+    pub total_round_power: Uint128,
+    // Ordered the same way as the proposals returned by the TopNProposals query.
+    pub proposals: Vec<FinalizedProposal>,
+}
+
+#[cw_serde]
+pub struct FinalizedProposal {
+    pub proposal_id: u64,
+    pub power: Uint128,
+    pub score: Uint128,
+    pub percentage: Decimal,
+}
+
+// Append-only journal of the executions of the admin-privileged messages, kept so that the multisig signers
+// and auditors don't need to reconstruct it from the events. Only the most recent MAX_ADMIN_ACTIONS_LOG_ENTRIES
+// entries are kept, the older ones are removed as the new ones get recorded.
+// ADMIN_ACTIONS_LOG: key(action_id) -> AdminAction
+pub const ADMIN_ACTIONS_LOG: Map<u64, AdminAction> = Map::new("admin_actions_log");
+#[cw_serde]
+pub struct AdminAction {
+    pub action_id: u64,
+    pub actor: Addr,
+    pub action_type: String,
+    // hex encoded SHA-256 hash of the JSON encoded execute message
+    pub params_hash: String,
+    pub height: u64,
+    pub timestamp: Timestamp,
+}
+
+// Id to be assigned to the next recorded admin action. Not initialized in instantiate,
+// so it defaults to 0 when not set.
+pub const ADMIN_ACTION_ID: Item<u64> = Item::new("admin_action_id");
+
+// Lockups whose lock end is automatically extended once per round via ProcessAutoRefresh, so that their remaining
+// lock duration stays the same as when the auto refresh was enabled. Removed once the lock owner disables it.
+// AUTO_REFRESH_LOCKS: key(sender_addr, lock_id) -> AutoRefresh
+pub const AUTO_REFRESH_LOCKS: Map<(Addr, u64), AutoRefresh> = Map::new("auto_refresh_locks");
+
+// Index of the AUTO_REFRESH_LOCKS entries by the round in which they were last refreshed, so that ProcessAutoRefresh
+// only goes through the lockups that weren't refreshed in the current round yet. Updated together with the
+// AUTO_REFRESH_LOCKS.
+// AUTO_REFRESH_QUEUE: key(last_refresh_round, sender_addr, lock_id) -> bool
+pub const AUTO_REFRESH_QUEUE: Map<(u64, Addr, u64), bool> = Map::new("auto_refresh_queue");
+#[cw_serde]
+pub struct AutoRefresh {
+    pub lock_duration: u64,
+    pub last_refresh_round: u64,
+}
+
+// Tokens of the unlocked lockups that are held by the contract until their unlock cooldown passes.
+// PENDING_UNLOCKS: key(owner_addr, lock_id) -> PendingUnlock
+pub const PENDING_UNLOCKS: Map<(Addr, u64), PendingUnlock> = Map::new("pending_unlocks");
+#[cw_serde]
+pub struct PendingUnlock {
+    pub recipient: Addr,
+    pub funds: Coin,
+    pub claimable_at: Timestamp,
+}
+
+// Delegates appointed by the lock owners to vote with their lockups via VoteOnBehalf. The lock owner can still
+// vote with the delegated lockups themselves, and can revoke the delegation at any time.
+// VOTE_DELEGATIONS: key(owner_addr, lock_id) -> delegate_addr
+pub const VOTE_DELEGATIONS: Map<(Addr, u64), Addr> = Map::new("vote_delegations");
+
+// Operators appointed by the lock owners to manage the participation of their lockups, e.g. a hot key managing
+// the lockups owned by a cold key. Operators can vote with the lockups and refresh them, but never unlock them.
+// LOCK_OPERATORS: key(owner_addr, lock_id) -> operator_addr
+pub const LOCK_OPERATORS: Map<(Addr, u64), Addr> = Map::new("lock_operators");
+
+// Short notes that the lock owners attach to their lockups, e.g. to keep track of what each lockup is for
+// across devices. Kept apart from the LOCKS_MAP, so that the notes are only loaded when requested.
+// LOCK_NOTES: key(owner_addr, lock_id) -> note
+pub const LOCK_NOTES: Map<(Addr, u64), String> = Map::new("lock_notes");
+
+// Users that opted into merging their new locks into the existing lockups of the same denom, instead of creating
+// a new lockup each time. A new lock is merged if its lock end is within the tolerance from the existing lock end.
+// LOCK_AUTO_MERGE_TOLERANCES: key(owner_addr) -> tolerance (in nanoseconds)
+pub const LOCK_AUTO_MERGE_TOLERANCES: Map<Addr, u64> = Map::new("lock_auto_merge_tolerances");
+
+// Executors registered by the lock owners, typically contracts such as DAO treasuries, to vote with all of
+// their lockups via VoteOnBehalf. The executor can't make the lockups vote for more than the given number
+// of proposals in a single round.
+// VOTING_EXECUTORS: key(owner_addr) -> VotingExecutor
+pub const VOTING_EXECUTORS: Map<Addr, VotingExecutor> = Map::new("voting_executors");
+
+// Nonce that the next vote intent of the voter relayed via RelayedVote must have. It is incremented
+// each time a vote intent of the voter is relayed, so that the signed intents can't be replayed.
+// RELAYED_VOTE_NONCES: key(voter_addr) -> next_nonce
+pub const RELAYED_VOTE_NONCES: Map<Addr, u64> = Map::new("relayed_vote_nonces");
+#[cw_serde]
+pub struct VotingExecutor {
+    pub executor: Addr,
+    pub max_proposals_per_round: u64,
+}
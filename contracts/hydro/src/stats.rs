@@ -0,0 +1,134 @@
+use cosmwasm_std::{Addr, Coin, Order, StdResult, Storage, Uint128};
+
+use crate::state::{
+    RoundStats, ROUND_STATS, ROUND_TRANCHE_VOTED_POWER, ROUND_VOTERS, TRANCHE_MAP, VOTE_MAP,
+};
+
+pub fn get_round_stats(storage: &dyn Storage, round_id: u64) -> StdResult<RoundStats> {
+    Ok(ROUND_STATS.may_load(storage, round_id)?.unwrap_or_default())
+}
+
+pub fn get_round_tranche_voted_power(
+    storage: &dyn Storage,
+    round_id: u64,
+    tranche_id: u64,
+) -> StdResult<Uint128> {
+    Ok(ROUND_TRANCHE_VOTED_POWER
+        .may_load(storage, (round_id, tranche_id))?
+        .unwrap_or_default())
+}
+
+pub fn increment_round_proposals_count(storage: &mut dyn Storage, round_id: u64) -> StdResult<()> {
+    let mut round_stats = get_round_stats(storage, round_id)?;
+    round_stats.proposals_count += 1;
+    ROUND_STATS.save(storage, round_id, &round_stats)
+}
+
+// Registers that the given address voted in the round. The number of unique voters
+// is only increased the first time the address votes in the round, regardless of the tranche.
+pub fn record_round_voter(storage: &mut dyn Storage, round_id: u64, voter: &Addr) -> StdResult<()> {
+    if ROUND_VOTERS.has(storage, (round_id, voter.clone())) {
+        return Ok(());
+    }
+
+    ROUND_VOTERS.save(storage, (round_id, voter.clone()), &true)?;
+
+    let mut round_stats = get_round_stats(storage, round_id)?;
+    round_stats.unique_voters += 1;
+    ROUND_STATS.save(storage, round_id, &round_stats)
+}
+
+// Must be called after some votes of the given address in the round are removed. If the address has no votes
+// left in the round, in any tranche, it is no longer counted among the unique voters of the round.
+pub fn remove_round_voter(storage: &mut dyn Storage, round_id: u64, voter: &Addr) -> StdResult<()> {
+    if !ROUND_VOTERS.has(storage, (round_id, voter.clone())) {
+        return Ok(());
+    }
+
+    let tranche_ids = TRANCHE_MAP
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    for tranche_id in tranche_ids {
+        if VOTE_MAP
+            .prefix(((round_id, tranche_id), voter.clone()))
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+        {
+            return Ok(());
+        }
+    }
+
+    ROUND_VOTERS.remove(storage, (round_id, voter.clone()));
+
+    let mut round_stats = get_round_stats(storage, round_id)?;
+    round_stats.unique_voters = round_stats.unique_voters.saturating_sub(1);
+    ROUND_STATS.save(storage, round_id, &round_stats)
+}
+
+// Must be called every time the power of some proposal changes, in order to keep
+// the total voted power of the round and tranche in sync with the proposal powers.
+pub fn update_round_tranche_voted_power(
+    storage: &mut dyn Storage,
+    round_id: u64,
+    tranche_id: u64,
+    old_proposal_power: Uint128,
+    new_proposal_power: Uint128,
+) -> StdResult<()> {
+    if old_proposal_power == new_proposal_power {
+        return Ok(());
+    }
+
+    let voted_power = get_round_tranche_voted_power(storage, round_id, tranche_id)?;
+    let voted_power = voted_power
+        .checked_add(new_proposal_power)?
+        .saturating_sub(old_proposal_power);
+
+    ROUND_TRANCHE_VOTED_POWER.save(storage, (round_id, tranche_id), &voted_power)
+}
+
+pub fn add_round_deployed_funds(
+    storage: &mut dyn Storage,
+    round_id: u64,
+    funds: &[Coin],
+) -> StdResult<()> {
+    let mut round_stats = get_round_stats(storage, round_id)?;
+
+    for coin in funds {
+        match round_stats
+            .deployed_funds
+            .iter_mut()
+            .find(|deployed| deployed.denom == coin.denom)
+        {
+            Some(deployed) => deployed.amount = deployed.amount.checked_add(coin.amount)?,
+            None => round_stats.deployed_funds.push(coin.clone()),
+        }
+    }
+
+    ROUND_STATS.save(storage, round_id, &round_stats)
+}
+
+pub fn remove_round_deployed_funds(
+    storage: &mut dyn Storage,
+    round_id: u64,
+    funds: &[Coin],
+) -> StdResult<()> {
+    let mut round_stats = get_round_stats(storage, round_id)?;
+
+    for coin in funds {
+        if let Some(deployed) = round_stats
+            .deployed_funds
+            .iter_mut()
+            .find(|deployed| deployed.denom == coin.denom)
+        {
+            deployed.amount = deployed.amount.saturating_sub(coin.amount);
+        }
+    }
+
+    round_stats
+        .deployed_funds
+        .retain(|deployed| !deployed.amount.is_zero());
+
+    ROUND_STATS.save(storage, round_id, &round_stats)
+}
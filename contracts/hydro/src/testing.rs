@@ -2,30 +2,56 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::contract::{
-    get_vote_for_update, query_current_round_id, query_tranches, query_user_votes, query_whitelist,
-    query_whitelist_admins, MAX_LOCK_ENTRIES,
+    get_vote_for_update, query, query_admin_actions, query_auto_refresh_locks,
+    query_current_round_id, query_delegations_for_user, query_hash_only_description_tranches,
+    query_invalidated_proposals, query_lock_operators, query_pending_unlocks, query_proposal_slots,
+    query_proposal_template, query_proposal_templates, query_proposal_text_limits,
+    query_round_data_pruning, query_round_results, query_tranche_power_scaling,
+    query_tranche_tribute_weight_bonus, query_tranches, query_tribute_contract, query_user_votes,
+    query_vote_power_cap, query_voting_executor, query_whitelist, query_whitelist_admins, reply,
+    scale_lockup_power, MAX_ADMIN_ACTIONS_LOG_ENTRIES, MAX_DESCRIPTION_SUMMARY_LENGTH,
+    MAX_LOCK_ENTRIES, MAX_LOCK_NOTE_LENGTH, ROUND_DATA_RETENTION_ROUNDS,
+    TRIBUTE_CLAIM_RETENTION_ROUNDS,
 };
-use crate::msg::{ProposalToLockups, TrancheInfo};
-use crate::state::{LockEntry, RoundLockPowerSchedule, Vote, VOTE_MAP};
-use crate::testing_lsm_integration::set_validator_infos_for_round;
+use crate::msg::{
+    LockVote, LockupImport, ProposalToLockups, TrancheInfo, TributeExecuteMsg, TributeWeightBonus,
+    VoteIntent,
+};
+use crate::query::{
+    AllUserLockupsResponse, AutoRefreshLock, LockAutoMergeResponse, LockOperator,
+    PendingUnlockEntry, ProposalOrderingKey, QueryMsg, SpecificUserLockupsWithTrancheInfosResponse,
+    TemplateProposal, VoteDelegation,
+};
+use crate::state::{
+    AdminAction, AutoRefresh, LockEntry, PendingUnlock, RoundDataPruning, RoundLockPowerSchedule,
+    Vote, VotingExecutor, AUTO_REFRESH_QUEUE, PROPOSAL_VOTERS, ROUND_VOTERS,
+    SCALED_PROPOSAL_SHARES_MAP, VALIDATORS_INFO, VALIDATORS_PER_ROUND,
+    VALIDATORS_STORE_INITIALIZED, VOTE_MAP,
+};
+use crate::testing_lsm_integration::{set_validator_infos_for_round, set_validator_power_ratio};
 use crate::testing_mocks::{
     denom_trace_grpc_query_mock, mock_dependencies, no_op_grpc_query_mock, MockQuerier,
 };
+use crate::validators_icqs::CLAIM_TRIBUTES_REPLY_ID;
 use crate::{
     contract::{
         compute_current_round_id, execute, instantiate, query_all_user_lockups, query_constants,
         query_proposal, query_round_total_power, query_round_tranche_proposals,
-        query_top_n_proposals,
+        query_specific_user_lockups_with_tranche_infos, query_top_n_proposals,
+        query_user_voting_power,
     },
     msg::{ExecuteMsg, InstantiateMsg},
 };
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
 use cosmwasm_std::{
-    BankMsg, CosmosMsg, Decimal, Deps, DepsMut, MessageInfo, OwnedDeps, Timestamp, Uint128,
+    attr, from_json, to_json_binary, to_json_vec, BankMsg, Binary, ContractResult, CosmosMsg,
+    Decimal, Deps, DepsMut, Env, HexBinary, MessageInfo, Order, OwnedDeps, Reply, ReplyOn,
+    SubMsgResult, SystemResult, Timestamp, Uint128, WasmMsg, WasmQuery,
 };
 use cosmwasm_std::{Coin, StdError, StdResult};
 use neutron_sdk::bindings::query::NeutronQuery;
 use proptest::prelude::*;
+use sha2::{Digest, Sha256};
 
 pub const VALIDATOR_1: &str = "cosmosvaloper157v7tczs40axfgejp2m43kwuzqe0wsy0rv8puv";
 pub const VALIDATOR_2: &str = "cosmosvaloper140l6y2gp3gxvay6qtn70re7z2s0gn57zfd832j";
@@ -102,6 +128,8 @@ pub fn get_default_instantiate_msg(mock_api: &MockApi) -> InstantiateMsg {
         icq_managers: vec![user_address],
         max_deployment_duration: 12,
         round_lock_power_schedule: get_default_power_schedule_vec(),
+        interpolate_lock_power: false,
+        allow_pre_launch_locks: false,
     }
 }
 
@@ -205,7 +233,14 @@ fn lock_tokens_basic_test() {
     let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
     assert!(res.is_ok());
 
-    let res = query_all_user_lockups(deps.as_ref(), env.clone(), info.sender.to_string(), 0, 2000);
+    let res = query_all_user_lockups(
+        deps.as_ref(),
+        env.clone(),
+        info.sender.to_string(),
+        0,
+        None,
+        2000,
+    );
     assert!(res.is_ok());
     let res = res.unwrap();
     assert_eq!(2, res.lockups.len());
@@ -245,6 +280,240 @@ fn lock_tokens_basic_test() {
     assert_eq!(4500, lockup.current_voting_power.u128());
 }
 
+#[test]
+fn lock_tokens_multiple_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([
+            (IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string()),
+            (IBC_DENOM_2.to_string(), VALIDATOR_2_LST_DENOM_1.to_string()),
+        ]),
+    );
+
+    let user_address = "addr0000";
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, user_address, &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    let res = set_validator_infos_for_round(
+        &mut deps.storage,
+        0,
+        vec![VALIDATOR_1.to_string(), VALIDATOR_2.to_string()],
+    );
+    assert!(res.is_ok());
+
+    let msg = ExecuteMsg::LockTokensMultiple {
+        lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Must provide at least one coin to lock"));
+
+    // a coin of an unsupported denom makes the whole message fail
+    let invalid_info = get_message_info(
+        &deps.api,
+        user_address,
+        &[
+            Coin::new(1000u64, "invalid_denom".to_string()),
+            Coin::new(1000u64, IBC_DENOM_1.to_string()),
+        ],
+    );
+    let res = execute(deps.as_mut(), env.clone(), invalid_info, msg.clone());
+    assert!(res.unwrap_err().to_string().contains("validating denom"));
+
+    let multiple_info = get_message_info(
+        &deps.api,
+        user_address,
+        &[
+            Coin::new(1000u64, IBC_DENOM_1.to_string()),
+            Coin::new(2000u64, IBC_DENOM_2.to_string()),
+        ],
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        multiple_info.clone(),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("lock_ids", "0,1")));
+
+    let res = query_all_user_lockups(
+        deps.as_ref(),
+        env.clone(),
+        info.sender.to_string(),
+        0,
+        None,
+        2000,
+    )
+    .unwrap();
+    assert_eq!(2, res.lockups.len());
+
+    for (lock_id, lockup) in res.lockups.iter().enumerate() {
+        assert_eq!(lock_id as u64, lockup.lock_entry.lock_id);
+        assert_eq!(multiple_info.funds[lock_id], lockup.lock_entry.funds);
+        assert_eq!(env.block.time, lockup.lock_entry.lock_start);
+        assert_eq!(
+            env.block.time.plus_nanos(THREE_MONTHS_IN_NANO_SECONDS),
+            lockup.lock_entry.lock_end
+        );
+    }
+    // tokens locked for three epochs get 1.5 times their amount as power
+    assert_eq!(1500, res.lockups[0].current_voting_power.u128());
+    assert_eq!(3000, res.lockups[1].current_voting_power.u128());
+
+    // fill up the locks of the user, so that only a single lock can still be created
+    let single_info = get_message_info(
+        &deps.api,
+        user_address,
+        &[Coin::new(10u64, IBC_DENOM_1.to_string())],
+    );
+    for _ in 0..MAX_LOCK_ENTRIES - 3 {
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), single_info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let res = execute(deps.as_mut(), env.clone(), multiple_info, msg.clone());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("User has too many locks"));
+
+    let res = execute(deps.as_mut(), env.clone(), single_info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+}
+
+#[test]
+fn auto_refresh_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let user_address = "addr0000";
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, user_address, &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    for lock_duration in [THREE_MONTHS_IN_NANO_SECONDS, ONE_MONTH_IN_NANO_SECONDS] {
+        let info = get_message_info(
+            &deps.api,
+            user_address,
+            &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens { lock_duration };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // only the lock owner can enable the auto refresh
+    let msg = ExecuteMsg::SetAutoRefresh {
+        lock_ids: vec![0],
+        enabled: true,
+    };
+    let other_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(deps.as_mut(), env.clone(), other_info, msg.clone());
+    assert!(res.is_err());
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_auto_refresh_locks(deps.as_ref(), info.sender.to_string()).unwrap();
+    assert_eq!(
+        vec![AutoRefreshLock {
+            lock_id: 0,
+            auto_refresh: AutoRefresh {
+                lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+                last_refresh_round: 0,
+            },
+        }],
+        res.locks
+    );
+
+    // the lock was already refreshed in the current round
+    let process_msg = ExecuteMsg::ProcessAutoRefresh { limit: 10 };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        process_msg.clone(),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("refreshed_lock_ids", "")));
+
+    // in the next round, anyone can refresh the lock, and only the auto refreshed lock gets extended
+    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS + 1);
+    let other_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        process_msg.clone(),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("refreshed_lock_ids", "0")));
+
+    let res = execute(deps.as_mut(), env.clone(), other_info, process_msg).unwrap();
+    assert!(res.attributes.contains(&attr("refreshed_lock_ids", "")));
+
+    let res = query_all_user_lockups(
+        deps.as_ref(),
+        env.clone(),
+        info.sender.to_string(),
+        0,
+        None,
+        2000,
+    )
+    .unwrap();
+    assert_eq!(
+        env.block.time.plus_nanos(THREE_MONTHS_IN_NANO_SECONDS),
+        res.lockups[0].lock_entry.lock_end
+    );
+    assert_eq!(1500, res.lockups[0].current_voting_power.u128());
+    assert_eq!(
+        mock_env().block.time.plus_nanos(ONE_MONTH_IN_NANO_SECONDS),
+        res.lockups[1].lock_entry.lock_end
+    );
+
+    let res = query_auto_refresh_locks(deps.as_ref(), info.sender.to_string()).unwrap();
+    assert_eq!(1, res.locks[0].auto_refresh.last_refresh_round);
+
+    // expired locks can't be auto refreshed
+    env.block.time = mock_env()
+        .block
+        .time
+        .plus_nanos(ONE_MONTH_IN_NANO_SECONDS + 1);
+    let msg = ExecuteMsg::SetAutoRefresh {
+        lock_ids: vec![1],
+        enabled: true,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.unwrap_err().to_string().contains("Lock 1 has expired"));
+
+    let msg = ExecuteMsg::SetAutoRefresh {
+        lock_ids: vec![0],
+        enabled: false,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_auto_refresh_locks(deps.as_ref(), info.sender.to_string()).unwrap();
+    assert!(res.locks.is_empty());
+    assert!(AUTO_REFRESH_QUEUE.is_empty(&deps.storage));
+}
+
 #[test]
 fn unlock_tokens_basic_test() {
     let user_address = "addr0000";
@@ -255,7 +524,7 @@ fn unlock_tokens_basic_test() {
         HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
     let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[user_token.clone()]);
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token));
     let msg = get_default_instantiate_msg(&deps.api);
 
     let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
@@ -279,7 +548,10 @@ fn unlock_tokens_basic_test() {
         deps.as_mut(),
         env.clone(),
         info.clone(),
-        ExecuteMsg::UnlockTokens { lock_ids: None },
+        ExecuteMsg::UnlockTokens {
+            lock_ids: None,
+            claim_tributes: None,
+        },
     );
     assert!(res.is_ok());
 
@@ -293,7 +565,10 @@ fn unlock_tokens_basic_test() {
         deps.as_mut(),
         env.clone(),
         info.clone(),
-        ExecuteMsg::UnlockTokens { lock_ids: None },
+        ExecuteMsg::UnlockTokens {
+            lock_ids: None,
+            claim_tributes: None,
+        },
     );
     assert!(res.is_ok());
 
@@ -327,7 +602,7 @@ fn unlock_specific_tokens_test() {
         HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
     let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[user_token.clone()]);
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token));
     let msg = get_default_instantiate_msg(&deps.api);
 
     let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
@@ -369,6 +644,7 @@ fn unlock_specific_tokens_test() {
     // First attempt: unlock locks 1 and 4
     let unlock_msg = ExecuteMsg::UnlockTokens {
         lock_ids: Some(vec![lock_ids[0], lock_ids[3]]),
+        claim_tributes: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), unlock_msg);
     assert!(res.is_ok());
@@ -413,6 +689,7 @@ fn unlock_specific_tokens_test() {
     // Second attempt: unlock locks 2 and 3
     let unlock_msg = ExecuteMsg::UnlockTokens {
         lock_ids: Some(vec![lock_ids[1], lock_ids[2]]),
+        claim_tributes: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), unlock_msg);
     assert!(res.is_ok());
@@ -457,6 +734,7 @@ fn unlock_specific_tokens_test() {
     // Third attempt: try to unlock lock 2 again (should succeed but unlock nothing)
     let unlock_msg = ExecuteMsg::UnlockTokens {
         lock_ids: Some(vec![lock_ids[1]]),
+        claim_tributes: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), unlock_msg);
     assert!(res.is_ok());
@@ -477,622 +755,867 @@ fn unlock_specific_tokens_test() {
 }
 
 #[test]
-fn create_proposal_basic_test() {
-    let user_address = "addr0000";
+fn unlock_tokens_to_test() {
     let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
 
-    let (mut deps, mut env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[user_token.clone()]);
-    let instantiate_message = get_default_instantiate_msg(&deps.api);
-
-    let res = instantiate(
-        deps.as_mut(),
-        env.clone(),
-        info.clone(),
-        instantiate_message.clone(),
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", std::slice::from_ref(&user_token));
+    let msg = get_default_instantiate_msg(&deps.api);
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
-    let msg1 = ExecuteMsg::CreateProposal {
-        round_id: None,
-        tranche_id: 1,
-        title: "proposal title 1".to_string(),
-        description: "proposal description 1".to_string(),
-        deployment_duration: 1,
-        minimum_atom_liquidity_request: Uint128::zero(),
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg1.clone());
-    assert!(res.is_ok());
+    for _ in 0..2 {
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
 
-    let msg2 = ExecuteMsg::CreateProposal {
-        round_id: None,
-        tranche_id: 1,
-        title: "proposal title 2".to_string(),
-        description: "proposal description 2".to_string(),
-        deployment_duration: 1,
-        minimum_atom_liquidity_request: Uint128::zero(),
+    env.block.time = env.block.time.plus_nanos(ONE_MONTH_IN_NANO_SECONDS + 1);
+
+    let recipient = get_address_as_str(&deps.api, "cold_wallet");
+    env.contract.address = deps.api.addr_make("hydro");
+    let unlock_msg = |recipient: String, confirm_recipient: bool| ExecuteMsg::UnlockTokensTo {
+        lock_ids: vec![0],
+        recipient,
+        confirm_recipient,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg2.clone());
-    assert!(res.is_ok());
 
-    let expected_round_id = 0;
-    let res = query_round_tranche_proposals(deps.as_ref(), expected_round_id, 1, 0, 3000);
-    assert!(res.is_ok(), "error: {:?}", res);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        unlock_msg(recipient.clone(), false),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Recipient of the unlocked tokens must be confirmed"));
 
-    let res = res.unwrap();
-    assert_eq!(2, res.proposals.len());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        unlock_msg("invalid".to_string(), true),
+    );
+    assert!(res.is_err());
 
-    let proposal = &res.proposals[0];
-    assert_eq!(expected_round_id, proposal.round_id);
-    assert_eq!(0, proposal.power.u128());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        unlock_msg(env.contract.address.to_string(), true),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Unlocked tokens can't be sent to the Hydro contract"));
 
-    let proposal = &res.proposals[1];
-    assert_eq!(expected_round_id, proposal.round_id);
-    assert_eq!(0, proposal.power.u128());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        unlock_msg(recipient.clone(), true),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("sender", info.sender.to_string())));
+    assert!(res
+        .attributes
+        .contains(&attr("recipient", recipient.clone())));
+    assert!(res.attributes.contains(&attr("unlocked_lock_ids", "0")));
+    assert_eq!(1, res.messages.len());
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient,
+            amount: vec![user_token],
+        }),
+        res.messages[0].msg
+    );
 
-    // assert that the proposals are not added to top N proposals
-    // immediately upon creation, as their voting power is 0
-    let res = query_top_n_proposals(deps.as_ref(), expected_round_id, 1, 2);
-    assert!(res.is_ok(), "error: {:?}", res);
+    // only the given lock was unlocked
+    let res =
+        query_all_user_lockups(deps.as_ref(), env, info.sender.to_string(), 0, None, 10).unwrap();
+    assert_eq!(1, res.lockups.len());
+    assert_eq!(1, res.lockups[0].lock_entry.lock_id);
+}
 
-    let res = res.unwrap();
-    assert_eq!(0, res.proposals.len());
+#[test]
+fn unlock_cooldown_test() {
+    let user_address = "addr0000";
+    let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
 
-    // create a proposal in a future round; this should work
-    let msg3 = ExecuteMsg::CreateProposal {
-        round_id: Some(5),
-        tranche_id: 1,
-        title: "proposal title 3".to_string(),
-        description: "proposal description 3".to_string(),
-        deployment_duration: 1,
-        minimum_atom_liquidity_request: Uint128::zero(),
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg3.clone());
-    assert!(res.is_ok());
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token));
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![info.sender.to_string()];
 
-    let res = query_round_tranche_proposals(deps.as_ref(), 5, 1, 0, 3000);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
 
-    assert!(res.is_ok(), "error: {:?}", res);
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
 
-    let res = res.unwrap();
-    assert_eq!(1, res.proposals.len());
+    let cooldown = ONE_DAY_IN_NANO_SECONDS;
+    let msg = ExecuteMsg::UpdateConfig {
+        max_locked_tokens: None,
+        max_deployment_duration: None,
+        tribute_cutoff_duration: None,
+        max_proposals_per_tranche_round: None,
+        raw_export_enabled: None,
+        unlock_cooldown_duration: Some(cooldown),
+        max_validator_shares_participating: None,
+        max_proposal_title_length: None,
+        max_proposal_description_length: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // advance time to round 1
-    env.block.time = env
-        .block
-        .time
-        .plus_nanos(instantiate_message.round_length + 1);
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // create a proposal in a past round; this should fail
-    let msg4 = ExecuteMsg::CreateProposal {
-        round_id: Some(0),
-        tranche_id: 1,
-        title: "proposal title 4".to_string(),
-        description: "proposal description 4".to_string(),
-        deployment_duration: 1,
-        minimum_atom_liquidity_request: Uint128::zero(),
+    env.block.time = env.block.time.plus_nanos(ONE_MONTH_IN_NANO_SECONDS + 1);
+
+    // the unlocked tokens are held by the contract until the cooldown passes
+    let unlock_msg = ExecuteMsg::UnlockTokens {
+        lock_ids: None,
+        claim_tributes: None,
     };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), unlock_msg).unwrap();
+    assert!(res.messages.is_empty());
 
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg4.clone());
+    let res = query_all_user_lockups(
+        deps.as_ref(),
+        env.clone(),
+        info.sender.to_string(),
+        0,
+        None,
+        2000,
+    )
+    .unwrap();
+    assert!(res.lockups.is_empty());
 
-    assert!(res.is_err());
+    let res = query_pending_unlocks(deps.as_ref(), info.sender.to_string()).unwrap();
+    assert_eq!(
+        vec![PendingUnlockEntry {
+            lock_id: 0,
+            pending_unlock: PendingUnlock {
+                recipient: info.sender.clone(),
+                funds: user_token.clone(),
+                claimable_at: env.block.time.plus_nanos(cooldown),
+            },
+        }],
+        res.pending_unlocks
+    );
+
+    let claim_msg = ExecuteMsg::ClaimUnlocked {};
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), claim_msg.clone());
     assert!(res
-        .err()
-        .unwrap()
+        .unwrap_err()
         .to_string()
-        .contains("cannot create a proposal in a round that ended in the past"),);
-}
+        .contains("No pending unlocks can be claimed yet"));
 
-#[test]
-fn vote_basic_test() {
-    vote_test_with_start_time(mock_env().block.time, 0);
+    env.block.time = env.block.time.plus_nanos(cooldown);
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), claim_msg.clone()).unwrap();
+    assert_eq!(1, res.messages.len());
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![user_token],
+        }),
+        res.messages[0].msg
+    );
+
+    let res = query_pending_unlocks(deps.as_ref(), info.sender.to_string()).unwrap();
+    assert!(res.pending_unlocks.is_empty());
+
+    let res = execute(deps.as_mut(), env.clone(), info, claim_msg);
+    assert!(res.is_err());
 }
 
-// If user already voted for only one proposal in the given round and tranche, and then locks new tokens or
-// refreshes the existing lock, the voting power on that proposal should get updated accordingly. However,
-// if user voted for proposal that requires liquidity deployment for multiple rounds, but the newly created
-// lock entry doesn't span long enough, then the voting power on such proposal should not be updated.
 #[test]
-fn proposal_power_change_on_lock_and_refresh_test() {
-    let user_address = "addr0000";
-    let user_token1 = Coin::new(1000u64, IBC_DENOM_1.to_string());
-    let user_token2 = Coin::new(1000u64, IBC_DENOM_2.to_string());
-
+fn unlock_tokens_with_claim_tributes_test() {
     let grpc_query = denom_trace_grpc_query_mock(
         "transfer/channel-0".to_string(),
-        HashMap::from([
-            (IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string()),
-            (IBC_DENOM_2.to_string(), VALIDATOR_2_LST_DENOM_1.to_string()),
-        ]),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
     let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[user_token1.clone()]);
-
+    let info = get_message_info(&deps.api, "addr0000", &[]);
     let mut msg = get_default_instantiate_msg(&deps.api);
-    msg.lock_epoch_length = TWO_WEEKS_IN_NANO_SECONDS;
-    // add another tranche
-    msg.tranches.push(TrancheInfo {
-        name: "tranche 2".to_string(),
-        metadata: "tranche 2 metadata".to_string(),
-    });
-
-    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
-
-    let res = set_validator_infos_for_round(
-        deps.as_mut().storage,
-        0,
-        vec![VALIDATOR_1.to_string(), VALIDATOR_2.to_string()],
-    );
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
-    // advance the chain by 1000 nano seconds to simulate locking during the round
-    env.block.time = env.block.time.plus_nanos(1000);
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
 
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
+    let tranche_id = 1;
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
-    assert!(res.is_ok());
-
-    let prop_infos = vec![
-        (
-            1,
-            "proposal title 1".to_string(),
-            "proposal description 1".to_string(),
-        ),
-        (
-            2,
-            "proposal title 2".to_string(),
-            "proposal description 2".to_string(),
-        ),
-        (
-            2,
-            "proposal title 3".to_string(),
-            "proposal description 3".to_string(),
-        ),
-    ];
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    for prop_info in prop_infos {
-        let msg = ExecuteMsg::CreateProposal {
-            round_id: None,
-            tranche_id: prop_info.0,
-            title: prop_info.1,
-            description: prop_info.2,
-            deployment_duration: 1,
-            minimum_atom_liquidity_request: Uint128::zero(),
+    // lock tokens twice and vote with both locks in round 0
+    let lock_info = get_message_info(&deps.api, "addr0000", &[Coin::new(1000u64, IBC_DENOM_1)]);
+    for _ in 0..2 {
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
         };
-
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-        assert!(res.is_ok());
+        let res = execute(deps.as_mut(), env.clone(), lock_info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
     }
 
-    let first_round_id = 0;
-    let second_round_id = 1;
-
-    let first_tranche_id = 1;
-    let second_tranche_id = 2;
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![0, 1],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    let first_proposal_id = 0;
-    let second_proposal_id = 1;
-    let third_proposal_id = 2;
-    let fourth_proposal_id = 3;
-    let fifth_proposal_id = 4;
+    env.block.time = env.block.time.plus_nanos(ONE_MONTH_IN_NANO_SECONDS + 1);
 
-    let first_lockup_id = 0;
-    let second_lockup_id = 1;
-    let third_lockup_id = 2;
-    let fourth_lockup_id = 3;
+    // without the tribute contract, the tokens are unlocked and the claim is skipped
+    let msg = ExecuteMsg::UnlockTokens {
+        lock_ids: Some(vec![0]),
+        claim_tributes: Some(true),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(1, res.messages.len());
+    assert!(res
+        .attributes
+        .contains(&attr("claim_tributes", "skipped_no_tribute_contract")));
 
-    // lock additional 1000 tokens before voting and verify this has no effect on proposals power
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
+    // only whitelist admins can set the tribute contract
+    let tribute_contract = deps.api.addr_make("tribute");
+    let msg = ExecuteMsg::SetTributeContract {
+        address: Some(tribute_contract.to_string()),
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
-    assert!(res.is_ok());
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(deps.as_mut(), env.clone(), non_admin_info, msg.clone());
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
 
-    let mut expected_voting_power = 0u128;
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        first_tranche_id,
-        first_proposal_id,
-        expected_voting_power,
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(
+        Some(tribute_contract.clone()),
+        query_tribute_contract(deps.as_ref())
+            .unwrap()
+            .tribute_contract
     );
 
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        second_proposal_id,
-        expected_voting_power,
-    );
+    // the tribute contract is asked to claim the tributes for the vote of the unlocked lock
+    let msg = ExecuteMsg::UnlockTokens {
+        lock_ids: Some(vec![1]),
+        claim_tributes: Some(true),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(2, res.messages.len());
 
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        third_proposal_id,
-        expected_voting_power,
+    let submsg = &res.messages[1];
+    assert_eq!(CLAIM_TRIBUTES_REPLY_ID, submsg.id);
+    assert_eq!(ReplyOn::Error, submsg.reply_on);
+    assert_eq!(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: tribute_contract.to_string(),
+            msg: to_json_binary(&TributeExecuteMsg::ClaimForLocks {
+                voter_address: info.sender.to_string(),
+                lock_votes: vec![LockVote {
+                    lock_id: 1,
+                    round_id: 0,
+                    tranche_id,
+                    proposal_id: 0,
+                }],
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        submsg.msg
     );
 
-    // vote for the first proposal in tranche 1
-    let msg = ExecuteMsg::Vote {
-        tranche_id: first_tranche_id,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: first_proposal_id,
-            lock_ids: vec![first_lockup_id, second_lockup_id],
-        }],
+    // a failed claim doesn't revert the unlock
+    let reply_msg = Reply {
+        id: CLAIM_TRIBUTES_REPLY_ID,
+        payload: submsg.payload.clone(),
+        gas_used: 0,
+        result: SubMsgResult::Err("out of gas".to_string()),
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    assert!(res.attributes.contains(&attr("error", "out of gas")));
+    assert!(res
+        .attributes
+        .contains(&attr("voter", info.sender.to_string())));
+}
 
-    // verify users vote for the first proposal in tranche 1
-    expected_voting_power = 2000u128;
+#[test]
+fn create_proposal_basic_test() {
+    let user_address = "addr0000";
+    let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
 
-    let res = query_user_votes(
-        deps.as_ref(),
-        first_round_id,
-        first_tranche_id,
-        info.sender.to_string(),
-    );
-    assert!(res.is_ok(), "error: {:?}", res);
-    assert_eq!(first_proposal_id, res.unwrap().votes[0].prop_id);
+    let (mut deps, mut env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token));
+    let instantiate_message = get_default_instantiate_msg(&deps.api);
 
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        first_tranche_id,
-        first_proposal_id,
-        expected_voting_power,
+    let res = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_message.clone(),
     );
+    assert!(res.is_ok());
 
-    // vote for the second proposal in tranche 2
-    let msg = ExecuteMsg::Vote {
-        tranche_id: second_tranche_id,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: second_proposal_id,
-            lock_ids: vec![first_lockup_id, second_lockup_id],
-        }],
+    let msg1 = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 1,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg1.clone());
     assert!(res.is_ok());
 
-    // verify users vote for the second proposal in tranche 2
-    let res = query_user_votes(
-        deps.as_ref(),
-        first_round_id,
-        second_tranche_id,
-        info.sender.to_string(),
-    );
+    let msg2 = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 1,
+        title: "proposal title 2".to_string(),
+        description: "proposal description 2".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg2.clone());
+    assert!(res.is_ok());
+
+    let expected_round_id = 0;
+    let res = query_round_tranche_proposals(deps.as_ref(), expected_round_id, 1, 0, None, 3000);
     assert!(res.is_ok(), "error: {:?}", res);
-    assert_eq!(second_proposal_id, res.unwrap().votes[0].prop_id);
 
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        second_proposal_id,
-        expected_voting_power,
-    );
+    let res = res.unwrap();
+    assert_eq!(2, res.proposals.len());
 
-    // verify that the proposal that user didn't vote for is unaffected
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        third_proposal_id,
-        0,
-    );
+    let proposal = &res.proposals[0];
+    assert_eq!(expected_round_id, proposal.round_id);
+    assert_eq!(0, proposal.power.u128());
 
-    // lock additional 1000 tokens and verify that the voting power gets updated on both proposals
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
+    let proposal = &res.proposals[1];
+    assert_eq!(expected_round_id, proposal.round_id);
+    assert_eq!(0, proposal.power.u128());
+
+    // assert that the proposals are not added to top N proposals
+    // immediately upon creation, as their voting power is 0
+    let res = query_top_n_proposals(deps.as_ref(), expected_round_id, 1, 2);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = res.unwrap();
+    assert_eq!(0, res.proposals.len());
+
+    // create a proposal in a future round; this should work
+    let msg3 = ExecuteMsg::CreateProposal {
+        round_id: Some(5),
+        tranche_id: 1,
+        title: "proposal title 3".to_string(),
+        description: "proposal description 3".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
-    // lock LSM token that user already locked before
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg3.clone());
     assert!(res.is_ok());
 
-    expected_voting_power = 3000u128;
+    let res = query_round_tranche_proposals(deps.as_ref(), 5, 1, 0, None, 3000);
 
-    // verify that the voting power increased for the first proposal
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        first_tranche_id,
-        first_proposal_id,
-        expected_voting_power,
-    );
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // verify that the voting power increased for the second proposal
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        second_proposal_id,
-        expected_voting_power,
-    );
+    let res = res.unwrap();
+    assert_eq!(1, res.proposals.len());
 
-    // verify that the proposal that user didn't vote for is unaffected
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        third_proposal_id,
-        0,
-    );
+    // advance time to round 1
+    env.block.time = env
+        .block
+        .time
+        .plus_nanos(instantiate_message.round_length + 1);
 
-    // lock 1000 of a different LSM token
-    let info = get_message_info(&deps.api, user_address, &[user_token2.clone()]);
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
+    // create a proposal in a past round; this should fail
+    let msg4 = ExecuteMsg::CreateProposal {
+        round_id: Some(0),
+        tranche_id: 1,
+        title: "proposal title 4".to_string(),
+        description: "proposal description 4".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
 
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
-    assert!(res.is_ok());
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg4.clone());
 
-    expected_voting_power = 4000u128;
+    assert!(res.is_err());
+    assert!(res
+        .err()
+        .unwrap()
+        .to_string()
+        .contains("cannot create a proposal in a round that ended in the past"),);
+}
 
-    // verify that the voting power increased for the first proposal
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        first_tranche_id,
-        first_proposal_id,
-        expected_voting_power,
-    );
+#[test]
+fn create_proposal_hash_only_description_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let mut instantiate_message = get_default_instantiate_msg(&deps.api);
+    instantiate_message.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
 
-    // verify that the voting power increased for the second proposal
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        second_proposal_id,
-        expected_voting_power,
+    let res = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_message,
     );
+    assert!(res.is_ok());
 
-    // verify that the proposal that user didn't vote for is unaffected
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        third_proposal_id,
-        0,
+    let tranche_id = 1;
+    let description_hash =
+        "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08".to_string();
+    let description_uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    let create_proposal_msg =
+        |description: &str, description_hash: Option<String>, description_uri: Option<&str>| {
+            ExecuteMsg::CreateProposal {
+                round_id: None,
+                tranche_id,
+                title: "proposal title".to_string(),
+                description: description.to_string(),
+                deployment_duration: 1,
+                minimum_atom_liquidity_request: Uint128::zero(),
+                description_hash,
+                description_uri: description_uri.map(|uri| uri.to_string()),
+                max_vote_percentage: None,
+            }
+        };
+
+    // description hash is not allowed in tranches that store full descriptions on-chain
+    let msg = create_proposal_msg(
+        "summary",
+        Some(description_hash.clone()),
+        Some(description_uri),
     );
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("can only be provided for tranches that use hash-only descriptions"));
 
-    // refresh first lockup
-    let msg = ExecuteMsg::RefreshLockDuration {
-        lock_ids: vec![first_lockup_id],
-        lock_duration: 3 * TWO_WEEKS_IN_NANO_SECONDS,
+    // only whitelist admins can change the description mode of a tranche
+    let msg = ExecuteMsg::SetTrancheDescriptionMode {
+        tranche_id,
+        hash_only: true,
     };
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(deps.as_mut(), env.clone(), non_admin_info, msg.clone());
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
 
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
-    assert!(res.is_ok());
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    expected_voting_power = 4500u128;
+    let res = query_hash_only_description_tranches(deps.as_ref()).unwrap();
+    assert_eq!(vec![tranche_id], res.tranche_ids);
 
-    // verify that the voting power increased for the first proposal
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        first_tranche_id,
-        first_proposal_id,
-        expected_voting_power,
+    // hash and URI are required in hash-only tranches
+    let msg = create_proposal_msg("summary", None, None);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("must be provided for tranches that use hash-only descriptions"));
+
+    // hash must be a hex encoded SHA-256 hash
+    let msg = create_proposal_msg("summary", Some("abc".to_string()), Some(description_uri));
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("must be a hex encoded SHA-256 hash"));
+
+    // summary must be short
+    let long_summary = "a".repeat(MAX_DESCRIPTION_SUMMARY_LENGTH + 1);
+    let msg = create_proposal_msg(
+        &long_summary,
+        Some(description_hash.clone()),
+        Some(description_uri),
     );
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("must not be longer than"));
 
-    // verify that the voting power increased for the second proposal
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        second_proposal_id,
-        expected_voting_power,
+    let msg = create_proposal_msg(
+        "summary",
+        Some(description_hash.clone()),
+        Some(description_uri),
     );
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert!(res
+        .unwrap()
+        .attributes
+        .contains(&attr("proposal_description_uri", description_uri)));
 
-    // verify that the proposal that user didn't vote for is unaffected
-    assert_proposal_voting_power(
-        &deps,
-        first_round_id,
-        second_tranche_id,
-        third_proposal_id,
-        0,
+    let proposal = query_proposal(deps.as_ref(), 0, tranche_id, 0)
+        .unwrap()
+        .proposal;
+    assert_eq!("summary", proposal.description);
+    assert_eq!(
+        Some(description_hash.to_lowercase()),
+        proposal.description_hash
     );
 
-    // advance the chain by two weeks to move to the next round
-    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS);
+    // disabling the hash-only mode restores the on-chain descriptions
+    let msg = ExecuteMsg::SetTrancheDescriptionMode {
+        tranche_id,
+        hash_only: false,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // create a new proposal in this round
-    let msg = ExecuteMsg::CreateProposal {
+    let res = query_hash_only_description_tranches(deps.as_ref()).unwrap();
+    assert!(res.tranche_ids.is_empty());
+
+    let msg = create_proposal_msg("full description", None, None);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let proposal = query_proposal(deps.as_ref(), 0, tranche_id, 1)
+        .unwrap()
+        .proposal;
+    assert_eq!(None, proposal.description_hash);
+}
+
+#[test]
+fn create_proposal_text_limits_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let mut instantiate_message = get_default_instantiate_msg(&deps.api);
+    instantiate_message.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+
+    let res = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_message,
+    );
+    assert!(res.is_ok());
+
+    let create_proposal_msg = |title: &str, description: &str| ExecuteMsg::CreateProposal {
         round_id: None,
-        tranche_id: first_tranche_id,
-        title: "proposal title 4".to_string(),
-        description: "proposal description 4".to_string(),
+        tranche_id: 1,
+        title: title.to_string(),
+        description: description.to_string(),
         deployment_duration: 1,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
 
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
+    // the lengths are not limited by default
+    let res = query_proposal_text_limits(deps.as_ref()).unwrap();
+    assert_eq!(None, res.max_title_length);
+    assert_eq!(None, res.max_description_length);
+    assert_eq!(
+        MAX_DESCRIPTION_SUMMARY_LENGTH as u64,
+        res.max_description_summary_length
+    );
 
-    // vote for the fourth proposal in tranche 1
-    let msg = ExecuteMsg::Vote {
-        tranche_id: first_tranche_id,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: fourth_proposal_id,
-            lock_ids: vec![
-                first_lockup_id,
-                second_lockup_id,
-                third_lockup_id,
-                fourth_lockup_id,
-            ],
-        }],
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
+    let msg = create_proposal_msg(&"t".repeat(100), &"d".repeat(1000));
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // verify users vote for the fourth proposal in tranche 1
-    expected_voting_power = 1250u128;
-
-    let res = query_user_votes(
-        deps.as_ref(),
-        second_round_id,
-        first_tranche_id,
-        info.sender.to_string(),
-    );
+    let msg = ExecuteMsg::UpdateConfig {
+        max_locked_tokens: None,
+        max_deployment_duration: None,
+        tribute_cutoff_duration: None,
+        max_proposals_per_tranche_round: None,
+        raw_export_enabled: None,
+        unlock_cooldown_duration: None,
+        max_validator_shares_participating: None,
+        max_proposal_title_length: Some(10),
+        max_proposal_description_length: Some(20),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok(), "error: {:?}", res);
-    assert_eq!(fourth_proposal_id, res.unwrap().votes[0].prop_id);
 
-    assert_proposal_voting_power(
-        &deps,
-        second_round_id,
-        first_tranche_id,
-        fourth_proposal_id,
-        expected_voting_power,
-    );
+    let res = query_proposal_text_limits(deps.as_ref()).unwrap();
+    assert_eq!(Some(10), res.max_title_length);
+    assert_eq!(Some(20), res.max_description_length);
 
-    // refresh first lockup
-    let msg = ExecuteMsg::RefreshLockDuration {
-        lock_ids: vec![first_lockup_id],
-        lock_duration: 3 * TWO_WEEKS_IN_NANO_SECONDS,
-    };
+    // the lengths are counted in characters, after trimming the whitespace
+    let msg = create_proposal_msg(" ééééééééé ", "ü\n\tdescription");
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
+    let msg = create_proposal_msg("long title!", "description");
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
-    assert!(res.is_ok());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Proposal title must not be longer than 10 characters"));
 
-    expected_voting_power = 1500u128;
+    let msg = create_proposal_msg("title", &"d".repeat(21));
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Proposal description must not be longer than 20 characters"));
 
-    // verify that the voting power increased for the fourth proposal
-    assert_proposal_voting_power(
-        &deps,
-        second_round_id,
-        first_tranche_id,
-        fourth_proposal_id,
-        expected_voting_power,
-    );
+    // control characters are rejected, besides the line feeds and tabs in descriptions
+    let msg = create_proposal_msg("ti\ntle", "description");
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Proposal title must not contain control characters"));
 
-    // create a new (fifth) proposal that requires liquidity for 3 rounds
-    let msg = ExecuteMsg::CreateProposal {
-        round_id: None,
-        tranche_id: first_tranche_id,
-        title: "proposal title 5".to_string(),
-        description: "proposal description 5".to_string(),
-        deployment_duration: 3,
+    let msg = create_proposal_msg("title", "descr\u{1b}[2J");
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Proposal description must not contain control characters"));
+
+    // the same limits apply to the proposal templates
+    let msg = ExecuteMsg::RegisterProposalTemplate {
+        tranche_id: 1,
+        title: "long title!".to_string(),
+        description: "description".to_string(),
+        deployment_duration: 1,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
     };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Proposal title must not be longer than 10 characters"));
+}
 
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+#[test]
+fn proposal_templates_test() {
+    let user_address = "addr0000";
+    let (mut deps, mut env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, user_address, &[]);
+    let instantiate_message = get_default_instantiate_msg(&deps.api);
+
+    let res = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_message.clone(),
+    );
     assert!(res.is_ok());
 
-    // switch vote to the fifth proposal in tranche 1
-    let msg = ExecuteMsg::Vote {
-        tranche_id: first_tranche_id,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: fifth_proposal_id,
-            lock_ids: vec![
-                // only the first lockup has some power in second round
-                first_lockup_id,
-            ],
-        }],
+    let register_msg = ExecuteMsg::RegisterProposalTemplate {
+        tranche_id: 1,
+        title: " recurring deployment ".to_string(),
+        description: "recurring deployment description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::new(100),
+        description_hash: None,
+        description_uri: None,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
 
-    // verify users vote for the fifth proposal in tranche 1
-    expected_voting_power = 1500u128;
+    // only the whitelisted accounts can register templates
+    let other_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        register_msg.clone(),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
 
-    let res = query_user_votes(
-        deps.as_ref(),
-        second_round_id,
-        first_tranche_id,
-        info.sender.to_string(),
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), register_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // only the template creator can create proposals from it
+    let instantiate_msg = ExecuteMsg::InstantiateFromTemplate { template_id: 0 };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        instantiate_msg.clone(),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_msg.clone(),
     );
     assert!(res.is_ok(), "error: {:?}", res);
-    assert_eq!(fifth_proposal_id, res.unwrap().votes[0].prop_id);
 
-    assert_proposal_voting_power(
-        &deps,
-        second_round_id,
-        first_tranche_id,
-        fifth_proposal_id,
-        expected_voting_power,
+    let proposal = query_proposal(deps.as_ref(), 0, 1, 0).unwrap().proposal;
+    assert_eq!("recurring deployment", proposal.title);
+    assert_eq!(Uint128::new(100), proposal.minimum_atom_liquidity_request);
+
+    // a template can be used only once per round
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_msg.clone(),
     );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Proposal from template 0 was already created in round 0"));
 
-    // lock more tokens for one round and verify that the fifth proposal power
-    // didn't change since the lock doesn't span long enough to be allowed to
-    // vote for this proposal.
-    let info = get_message_info(&deps.api, user_address, &[user_token1.clone()]);
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
+    // create an unrelated proposal, then use the template again in the next round
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: Some(1),
+        tranche_id: 1,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    assert_proposal_voting_power(
-        &deps,
-        second_round_id,
-        first_tranche_id,
-        fifth_proposal_id,
-        expected_voting_power,
-    );
-}
+    env.block.time = env
+        .block
+        .time
+        .plus_nanos(instantiate_message.round_length + 1);
 
-#[test]
-fn past_start_time_test() {
-    // check behaviour starting one round before the start
-    vote_test_with_start_time(
-        // make the first round start slightly more than one epoch length in the past
-        mock_env()
-            .block
-            .time
-            .minus_nanos(TWO_WEEKS_IN_NANO_SECONDS + ONE_DAY_IN_NANO_SECONDS),
-        1,
-    );
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), instantiate_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+    let res = res.unwrap();
+    assert!(res.attributes.contains(&attr("predecessor_round_id", "0")));
+    assert!(res
+        .attributes
+        .contains(&attr("predecessor_proposal_id", "0")));
 
-    // check behaviour starting with the first round not done yet
-    vote_test_with_start_time(
-        // make the first round start slightly less than one epoch length in the past
-        mock_env()
-            .block
-            .time
-            .minus_nanos(TWO_WEEKS_IN_NANO_SECONDS - ONE_DAY_IN_NANO_SECONDS),
-        0, // round_id should be 0 because we are still during the first round
+    let res = query_proposal_template(deps.as_ref(), 0).unwrap();
+    assert_eq!(
+        get_address_as_str(&deps.api, user_address),
+        res.template.creator.to_string()
     );
-
-    // check behaviour starting in round 100
-    vote_test_with_start_time(
-        // make the first round start slightly more than 100 epochs in the past
-        mock_env()
-            .block
-            .time
-            .minus_nanos(TWO_WEEKS_IN_NANO_SECONDS * 100 + ONE_DAY_IN_NANO_SECONDS),
-        100,
+    assert_eq!(
+        vec![
+            TemplateProposal {
+                round_id: 0,
+                proposal_id: 0,
+            },
+            TemplateProposal {
+                round_id: 1,
+                proposal_id: 2,
+            },
+        ],
+        res.proposals
     );
+
+    // only the creator or a whitelist admin can remove the template
+    let remove_msg = ExecuteMsg::RemoveProposalTemplate { template_id: 0 };
+    let res = execute(deps.as_mut(), env.clone(), other_info, remove_msg.clone());
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(deps.as_mut(), env.clone(), info, remove_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_proposal_templates(deps.as_ref(), 0, None, 10).unwrap();
+    assert!(res.templates.is_empty());
+
+    // proposals created from the removed template are kept
+    assert!(query_proposal(deps.as_ref(), 1, 1, 2).is_ok());
 }
 
-// Locks tokens, creates two proposals, then votes for one, and switches the vote to the other.
-// It will set the start time of the contract to the specified time, and will use the specified
-// round id to query proposals and votes.
-fn vote_test_with_start_time(start_time: Timestamp, current_round_id: u64) {
+#[test]
+fn vote_basic_test() {
+    vote_test_with_start_time(mock_env().block.time, 0);
+}
+
+// If user already voted for only one proposal in the given round and tranche, and then locks new tokens or
+// refreshes the existing lock, the voting power on that proposal should get updated accordingly. However,
+// if user voted for proposal that requires liquidity deployment for multiple rounds, but the newly created
+// lock entry doesn't span long enough, then the voting power on such proposal should not be updated.
+#[test]
+fn proposal_power_change_on_lock_and_refresh_test() {
     let user_address = "addr0000";
-    let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
+    let user_token1 = Coin::new(1000u64, IBC_DENOM_1.to_string());
+    let user_token2 = Coin::new(1000u64, IBC_DENOM_2.to_string());
 
     let grpc_query = denom_trace_grpc_query_mock(
         "transfer/channel-0".to_string(),
-        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+        HashMap::from([
+            (IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string()),
+            (IBC_DENOM_2.to_string(), VALIDATOR_2_LST_DENOM_1.to_string()),
+        ]),
     );
     let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[user_token.clone()]);
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token1));
+
     let mut msg = get_default_instantiate_msg(&deps.api);
-    msg.first_round_start = start_time;
+    msg.lock_epoch_length = TWO_WEEKS_IN_NANO_SECONDS;
+    // add another tranche
+    msg.tranches.push(TrancheInfo {
+        name: "tranche 2".to_string(),
+        metadata: "tranche 2 metadata".to_string(),
+    });
 
     let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+    let res = set_validator_infos_for_round(
+        deps.as_mut().storage,
+        0,
+        vec![VALIDATOR_1.to_string(), VALIDATOR_2.to_string()],
+    );
+    assert!(res.is_ok());
+
+    // advance the chain by 1000 nano seconds to simulate locking during the round
+    env.block.time = env.block.time.plus_nanos(1000);
 
-    // lock some tokens to get voting power
     let msg = ExecuteMsg::LockTokens {
-        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
@@ -1104,10 +1627,15 @@ fn vote_test_with_start_time(start_time: Timestamp, current_round_id: u64) {
             "proposal description 1".to_string(),
         ),
         (
-            1,
+            2,
             "proposal title 2".to_string(),
             "proposal description 2".to_string(),
         ),
+        (
+            2,
+            "proposal title 3".to_string(),
+            "proposal description 3".to_string(),
+        ),
     ];
 
     for prop_info in prop_infos {
@@ -1118,365 +1646,427 @@ fn vote_test_with_start_time(start_time: Timestamp, current_round_id: u64) {
             description: prop_info.2,
             deployment_duration: 1,
             minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
         };
 
         let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
         assert!(res.is_ok());
     }
 
-    // vote for the first proposal
+    let first_round_id = 0;
+    let second_round_id = 1;
+
+    let first_tranche_id = 1;
+    let second_tranche_id = 2;
+
     let first_proposal_id = 0;
+    let second_proposal_id = 1;
+    let third_proposal_id = 2;
+    let fourth_proposal_id = 3;
+    let fifth_proposal_id = 4;
+
+    let first_lockup_id = 0;
+    let second_lockup_id = 1;
+    let third_lockup_id = 2;
+    let fourth_lockup_id = 3;
+
+    // lock additional 1000 tokens before voting and verify this has no effect on proposals power
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let mut expected_voting_power = 0u128;
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        first_tranche_id,
+        first_proposal_id,
+        expected_voting_power,
+    );
+
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        second_proposal_id,
+        expected_voting_power,
+    );
+
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        third_proposal_id,
+        expected_voting_power,
+    );
+
+    // vote for the first proposal in tranche 1
     let msg = ExecuteMsg::Vote {
-        tranche_id: 1,
+        tranche_id: first_tranche_id,
         proposals_votes: vec![ProposalToLockups {
             proposal_id: first_proposal_id,
-            lock_ids: vec![0],
+            lock_ids: vec![first_lockup_id, second_lockup_id],
         }],
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // verify users vote for the first proposal
-    let round_id = current_round_id;
-    let tranche_id = 1;
+    // verify users vote for the first proposal in tranche 1
+    expected_voting_power = 2000u128;
 
-    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
+    let res = query_user_votes(
+        deps.as_ref(),
+        first_round_id,
+        first_tranche_id,
+        info.sender.to_string(),
+    );
     assert!(res.is_ok(), "error: {:?}", res);
     assert_eq!(first_proposal_id, res.unwrap().votes[0].prop_id);
 
-    let res = query_proposal(deps.as_ref(), round_id, tranche_id, first_proposal_id);
-    assert!(res.is_ok());
-    assert_eq!(
-        info.funds[0].amount.u128(),
-        res.unwrap().proposal.power.u128()
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        first_tranche_id,
+        first_proposal_id,
+        expected_voting_power,
     );
 
-    // switch vote to the second proposal
-    let second_proposal_id = 1;
+    // vote for the second proposal in tranche 2
     let msg = ExecuteMsg::Vote {
-        tranche_id: 1,
+        tranche_id: second_tranche_id,
         proposals_votes: vec![ProposalToLockups {
             proposal_id: second_proposal_id,
-            lock_ids: vec![0],
+            lock_ids: vec![first_lockup_id, second_lockup_id],
         }],
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok(), "error: {:?}", res);
-
-    // verify users vote for the second proposal
-    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
     assert!(res.is_ok());
-    assert_eq!(second_proposal_id, res.unwrap().votes[0].prop_id);
 
-    let res = query_proposal(deps.as_ref(), round_id, tranche_id, second_proposal_id);
-    assert!(res.is_ok());
-    assert_eq!(
-        info.funds[0].amount.u128(),
-        res.unwrap().proposal.power.u128()
+    // verify users vote for the second proposal in tranche 2
+    let res = query_user_votes(
+        deps.as_ref(),
+        first_round_id,
+        second_tranche_id,
+        info.sender.to_string(),
     );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(second_proposal_id, res.unwrap().votes[0].prop_id);
 
-    // verify that the vote for the first proposal was removed
-    let res = query_proposal(deps.as_ref(), round_id, tranche_id, first_proposal_id);
-    assert!(res.is_ok());
-    assert_eq!(0, res.unwrap().proposal.power.u128());
-
-    // advance the chain by two weeks + 1 nano second to move to the next round and try to unlock tokens
-    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS + 1);
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        second_proposal_id,
+        expected_voting_power,
+    );
 
-    let res = execute(
-        deps.as_mut(),
-        env.clone(),
-        info.clone(),
-        ExecuteMsg::UnlockTokens { lock_ids: None },
+    // verify that the proposal that user didn't vote for is unaffected
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        third_proposal_id,
+        0,
     );
 
-    // user voted for a proposal in previous round, but can unlock tokens
+    // lock additional 1000 tokens and verify that the voting power gets updated on both proposals
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
+    };
+    // lock LSM token that user already locked before
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
-}
 
-// vote_extended_proposals_test tests that a vote is rejected if the round where votes
-// are possible is not reached yet and the vote is granted if it is done in the last round
-// of an extended proposal
-//
-// Test comprises 2 scenarios
-//  * A: fails to vote due to ongoing proposal user voted already for.
-//  * B: vote for new proposal succeeds as 'extended proposal' the user voted for is in last round.
-//
-// - round 0: user votes for extended proposal p(2)
-// - round 1: user tries to vote for p(3) but fails [scenario A]
-// - round 3: user is in last round of p(2) and votes successfully for p(4) [scenario B]
-//
-//  | round 0 | round 1 | round 2 | round 3 | round 4 |
-//  |  p(1)   |  end    |         |         |         |
-//  |  p(2)   |  ----   | -----   |  end    |         |
-//  |  p(3)   |  ----   | -----   |  end    |         |
-//  |         |  p(4)   | end     |         |         |
-//  |         |         |         |  p(5)   | end     |
-//
-#[test]
-fn vote_extended_proposals_test() {
-    let user_address = "addr0000";
-    let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
+    expected_voting_power = 3000u128;
 
-    let grpc_query = denom_trace_grpc_query_mock(
-        "transfer/channel-0".to_string(),
-        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    // verify that the voting power increased for the first proposal
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        first_tranche_id,
+        first_proposal_id,
+        expected_voting_power,
     );
-    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[user_token.clone()]);
-    let mut init_params = get_default_instantiate_msg(&deps.api);
-    init_params.first_round_start = env.block.time;
-    init_params.round_length = ONE_MONTH_IN_NANO_SECONDS;
 
-    let res = instantiate(
-        deps.as_mut(),
-        env.clone(),
-        info.clone(),
-        init_params.clone(),
+    // verify that the voting power increased for the second proposal
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        second_proposal_id,
+        expected_voting_power,
     );
-    assert!(res.is_ok());
-
-    set_default_validator_for_rounds(deps.as_mut(), 0, 5);
 
-    // advance the env time to simulate ongoing round
-    env.block.time = env.block.time.plus_hours(1);
+    // verify that the proposal that user didn't vote for is unaffected
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        third_proposal_id,
+        0,
+    );
 
-    // create a lock that will have power long enough to vote for the 'long lasting' proposal
+    // lock 1000 of a different LSM token
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token2));
     let msg = ExecuteMsg::LockTokens {
-        lock_duration: 6 * ONE_MONTH_IN_NANO_SECONDS,
+        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
     };
 
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
-    // create one more lock that will not be allowed to vote for the 'long lasting' proposal
-    // since it will have 0 power at the end of the round that precedes the round in which
-    // the liquidity should be returned
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: 2 * ONE_MONTH_IN_NANO_SECONDS,
+    expected_voting_power = 4000u128;
+
+    // verify that the voting power increased for the first proposal
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        first_tranche_id,
+        first_proposal_id,
+        expected_voting_power,
+    );
+
+    // verify that the voting power increased for the second proposal
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        second_proposal_id,
+        expected_voting_power,
+    );
+
+    // verify that the proposal that user didn't vote for is unaffected
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        third_proposal_id,
+        0,
+    );
+
+    // refresh first lockup
+    let msg = ExecuteMsg::RefreshLockDuration {
+        lock_ids: vec![first_lockup_id],
+        lock_duration: 3 * TWO_WEEKS_IN_NANO_SECONDS,
     };
 
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
-    let round_id = 0;
-    let tranche_id = 1;
+    expected_voting_power = 4500u128;
 
-    let first_lock_id = 0;
-    let second_lock_id = 1;
+    // verify that the voting power increased for the first proposal
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        first_tranche_id,
+        first_proposal_id,
+        expected_voting_power,
+    );
 
-    let second_proposal_id = 1;
-    let third_proposal_id = 2;
-    let fourth_proposal_id = 3;
-    let fifth_proposal_id = 4;
-
-    let prop_infos = vec![
-        // proposal p(1)  with deployment period of 1 round
-        (
-            "proposal title 1".to_string(),
-            "proposal description 1".to_string(),
-            1,
-        ),
-        // proposal p(2) with deployment period of 3 rounds
-        (
-            "proposal title 2".to_string(),
-            "proposal description 2".to_string(),
-            3,
-        ),
-        // proposal p(3) with deployment period of 3 rounds
-        (
-            "proposal title 3".to_string(),
-            "proposal description 3".to_string(),
-            3,
-        ),
-    ];
+    // verify that the voting power increased for the second proposal
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        second_proposal_id,
+        expected_voting_power,
+    );
 
-    for prop_info in &prop_infos {
-        let msg = ExecuteMsg::CreateProposal {
-            round_id: None,
-            tranche_id,
-            title: prop_info.0.clone(),
-            description: prop_info.1.clone(),
-            deployment_duration: prop_info.2,
-            minimum_atom_liquidity_request: Uint128::zero(),
-        };
+    // verify that the proposal that user didn't vote for is unaffected
+    assert_proposal_voting_power(
+        &deps,
+        first_round_id,
+        second_tranche_id,
+        third_proposal_id,
+        0,
+    );
 
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-        assert!(res.is_ok());
-    }
+    // advance the chain by two weeks to move to the next round
+    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS);
 
-    // vote for the third proposals p(3)
-    let msg = ExecuteMsg::Vote {
-        tranche_id,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: third_proposal_id,
-            lock_ids: vec![first_lock_id],
-        }],
+    // create a new proposal in this round
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: first_tranche_id,
+        title: "proposal title 4".to_string(),
+        description: "proposal description 4".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
+
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // check that users voted for the third proposal
-    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
-    assert!(res.is_ok(), "error: {:?}", res);
-    assert_eq!(third_proposal_id, res.unwrap().votes[0].prop_id);
-
-    // switch vote from the third proposal p(3) to the second proposals p(2)
+    // vote for the fourth proposal in tranche 1
     let msg = ExecuteMsg::Vote {
-        tranche_id,
+        tranche_id: first_tranche_id,
         proposals_votes: vec![ProposalToLockups {
-            proposal_id: second_proposal_id,
-            lock_ids: vec![first_lock_id],
+            proposal_id: fourth_proposal_id,
+            lock_ids: vec![
+                first_lockup_id,
+                second_lockup_id,
+                third_lockup_id,
+                fourth_lockup_id,
+            ],
         }],
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // check that users voted for the second proposal
-    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
-    assert!(res.is_ok(), "error: {:?}", res);
-    let user_vote = res.unwrap().votes[0].clone();
-    assert_eq!(second_proposal_id, user_vote.prop_id);
-
-    // save vote power for future verification
-    let old_vote_power = user_vote.power;
+    // verify users vote for the fourth proposal in tranche 1
+    expected_voting_power = 1250u128;
 
-    // vote for second proposal p(2) with lock that doesn't span long enough
-    let msg = ExecuteMsg::Vote {
-        tranche_id,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: second_proposal_id,
-            lock_ids: vec![second_lock_id],
-        }],
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
+    let res = query_user_votes(
+        deps.as_ref(),
+        second_round_id,
+        first_tranche_id,
+        info.sender.to_string(),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(fourth_proposal_id, res.unwrap().votes[0].prop_id);
 
-    let mut second_lock_skipped = false;
-    for attribute in res.unwrap().attributes {
-        if attribute.key.eq("locks_skipped")
-            && attribute.value.contains(&second_lock_id.to_string())
-        {
-            second_lock_skipped = true;
-            break;
-        }
-    }
-    assert!(
-        second_lock_skipped,
-        "lock with ID {} should be skipped, but it wasn't",
-        second_lock_id
+    assert_proposal_voting_power(
+        &deps,
+        second_round_id,
+        first_tranche_id,
+        fourth_proposal_id,
+        expected_voting_power,
     );
 
-    // verify that user's vote didn't change
-    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
-    assert!(res.is_ok(), "error: {:?}", res);
-    let user_vote = res.unwrap().votes[0].clone();
-    assert_eq!(second_proposal_id, user_vote.prop_id);
-    assert_eq!(old_vote_power, user_vote.power);
+    // refresh first lockup
+    let msg = ExecuteMsg::RefreshLockDuration {
+        lock_ids: vec![first_lockup_id],
+        lock_duration: 3 * TWO_WEEKS_IN_NANO_SECONDS,
+    };
 
-    // advance the chain by one round length to move to round 1
-    env.block.time = env.block.time.plus_nanos(init_params.round_length);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
 
-    // cross check that the current round is round 1
-    let resp = query_current_round_id(deps.as_ref(), env.clone());
-    assert!(resp.is_ok());
+    expected_voting_power = 1500u128;
 
-    assert_eq!(
-        1,
-        resp.unwrap().round_id,
-        "expected to reach round 1 (round after voting)",
+    // verify that the voting power increased for the fourth proposal
+    assert_proposal_voting_power(
+        &deps,
+        second_round_id,
+        first_tranche_id,
+        fourth_proposal_id,
+        expected_voting_power,
     );
 
-    // create new proposal p(4) (successor of p(1))
+    // create a new (fifth) proposal that requires liquidity for 3 rounds
     let msg = ExecuteMsg::CreateProposal {
         round_id: None,
-        tranche_id,
-        title: prop_infos[0].0.clone(),
-        description: prop_infos[0].1.clone(),
-        deployment_duration: prop_infos[0].2,
+        tranche_id: first_tranche_id,
+        title: "proposal title 5".to_string(),
+        description: "proposal description 5".to_string(),
+        deployment_duration: 3,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
+
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // check that voting for p(4), one round after voting for 'long lasting' proposal fails
+    // switch vote to the fifth proposal in tranche 1
     let msg = ExecuteMsg::Vote {
-        tranche_id,
+        tranche_id: first_tranche_id,
         proposals_votes: vec![ProposalToLockups {
-            proposal_id: fourth_proposal_id,
-            lock_ids: vec![first_lock_id],
+            proposal_id: fifth_proposal_id,
+            lock_ids: vec![
+                // only the first lockup has some power in second round
+                first_lockup_id,
+            ],
         }],
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(
-        res.is_err(),
-        "voting in the round after voting for 'long lasting' proposal should fail"
-    );
+    assert!(res.is_ok());
 
-    // advance to the last round of chain #rounds - current round
-    let remaining_rounds = prop_infos[1].2 - 1;
-    env.block.time = env
-        .block
-        .time
-        .plus_nanos(remaining_rounds * init_params.round_length);
+    // verify users vote for the fifth proposal in tranche 1
+    expected_voting_power = 1500u128;
 
-    // check that this is the round in which the proposal 1 ends
-    let resp = query_current_round_id(deps.as_ref(), env.clone());
-    assert!(resp.is_ok());
+    let res = query_user_votes(
+        deps.as_ref(),
+        second_round_id,
+        first_tranche_id,
+        info.sender.to_string(),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(fifth_proposal_id, res.unwrap().votes[0].prop_id);
 
-    let round_no = resp.unwrap().round_id;
-    assert_eq!(
-        3,
-        round_no,
-        "expected to reach round {:?}, sitting in {:?}",
-        prop_infos[0].2 - 1,
-        round_id
+    assert_proposal_voting_power(
+        &deps,
+        second_round_id,
+        first_tranche_id,
+        fifth_proposal_id,
+        expected_voting_power,
     );
 
-    // create new proposal p(5), successor of p(4)
-    let msg = ExecuteMsg::CreateProposal {
-        round_id: None,
-        tranche_id,
-        title: prop_infos[0].0.clone(),
-        description: prop_infos[0].1.clone(),
-        deployment_duration: prop_infos[0].2,
-        minimum_atom_liquidity_request: Uint128::zero(),
+    // lock more tokens for one round and verify that the fifth proposal power
+    // didn't change since the lock doesn't span long enough to be allowed to
+    // vote for this proposal.
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token1));
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: TWO_WEEKS_IN_NANO_SECONDS,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // check that voting for p(5) in round 3 (when the 'long lasting' proposal ends) passes
-    let msg = ExecuteMsg::Vote {
-        tranche_id,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: fifth_proposal_id,
-            lock_ids: vec![first_lock_id],
-        }],
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(
-        res.is_ok(),
-        "voting in the round in which the 'long lasting' proposal is ending failed"
+    assert_proposal_voting_power(
+        &deps,
+        second_round_id,
+        first_tranche_id,
+        fifth_proposal_id,
+        expected_voting_power,
     );
+}
 
-    let res = query_user_votes(deps.as_ref(), round_no, tranche_id, info.sender.to_string());
-    assert!(
-        res.is_ok(),
-        "querying vote for round {:?} failed {:?}",
-        round_no,
-        res
+#[test]
+fn past_start_time_test() {
+    // check behaviour starting one round before the start
+    vote_test_with_start_time(
+        // make the first round start slightly more than one epoch length in the past
+        mock_env()
+            .block
+            .time
+            .minus_nanos(TWO_WEEKS_IN_NANO_SECONDS + ONE_DAY_IN_NANO_SECONDS),
+        1,
+    );
+
+    // check behaviour starting with the first round not done yet
+    vote_test_with_start_time(
+        // make the first round start slightly less than one epoch length in the past
+        mock_env()
+            .block
+            .time
+            .minus_nanos(TWO_WEEKS_IN_NANO_SECONDS - ONE_DAY_IN_NANO_SECONDS),
+        0, // round_id should be 0 because we are still during the first round
+    );
+
+    // check behaviour starting in round 100
+    vote_test_with_start_time(
+        // make the first round start slightly more than 100 epochs in the past
+        mock_env()
+            .block
+            .time
+            .minus_nanos(TWO_WEEKS_IN_NANO_SECONDS * 100 + ONE_DAY_IN_NANO_SECONDS),
+        100,
     );
-    assert_eq!(fifth_proposal_id, res.unwrap().votes[0].prop_id);
 }
 
-// Test case:
-//      1. User votes with 1-round-long-lock for proposal with deployment_duration = 1
-//      2. User votes with the same lock, but for proposal with deployment_duration = 3
-//         (no vote gets created since it is a short lock; old vote gets deleted)
-//      3. User votes for proposal from step #1 again
-//         (or any other with deployment_duration that it should be allowed to vote)
-#[test]
-fn switch_vote_between_short_and_long_props_test() {
+// Locks tokens, creates two proposals, then votes for one, and switches the vote to the other.
+// It will set the start time of the contract to the specified time, and will use the specified
+// round id to query proposals and votes.
+fn vote_test_with_start_time(start_time: Timestamp, current_round_id: u64) {
     let user_address = "addr0000";
     let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
 
@@ -1485,31 +2075,16 @@ fn switch_vote_between_short_and_long_props_test() {
         HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
     let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[user_token.clone()]);
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token));
     let mut msg = get_default_instantiate_msg(&deps.api);
-    msg.round_length = ONE_MONTH_IN_NANO_SECONDS;
+    msg.first_round_start = start_time;
 
     let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    let current_round_id = 0;
-    let tranche_id = 1;
-
-    let first_proposal_id = 0;
-    let second_proposal_id = 1;
-
-    let first_lock_id = 0;
-
-    let res = set_validator_infos_for_round(
-        &mut deps.storage,
-        current_round_id,
-        vec![VALIDATOR_1.to_string()],
-    );
-    assert!(res.is_ok());
-
-    env.block.time = env.block.time.plus_hours(12);
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
 
-    // lock some tokens for one round to get voting power
+    // lock some tokens to get voting power
     let msg = ExecuteMsg::LockTokens {
         lock_duration: ONE_MONTH_IN_NANO_SECONDS,
     };
@@ -1518,25 +2093,28 @@ fn switch_vote_between_short_and_long_props_test() {
 
     let prop_infos = vec![
         (
+            1,
             "proposal title 1".to_string(),
             "proposal description 1".to_string(),
-            1,
         ),
         (
+            1,
             "proposal title 2".to_string(),
             "proposal description 2".to_string(),
-            3,
         ),
     ];
 
     for prop_info in prop_infos {
         let msg = ExecuteMsg::CreateProposal {
             round_id: None,
-            tranche_id: 1,
-            title: prop_info.0,
-            description: prop_info.1,
-            deployment_duration: prop_info.2,
+            tranche_id: prop_info.0,
+            title: prop_info.1,
+            description: prop_info.2,
+            deployment_duration: 1,
             minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
         };
 
         let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
@@ -1544,32 +2122,26 @@ fn switch_vote_between_short_and_long_props_test() {
     }
 
     // vote for the first proposal
+    let first_proposal_id = 0;
     let msg = ExecuteMsg::Vote {
         tranche_id: 1,
         proposals_votes: vec![ProposalToLockups {
             proposal_id: first_proposal_id,
-            lock_ids: vec![first_lock_id],
+            lock_ids: vec![0],
         }],
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
     // verify users vote for the first proposal
-    let res = query_user_votes(
-        deps.as_ref(),
-        current_round_id,
-        tranche_id,
-        info.sender.to_string(),
-    );
+    let round_id = current_round_id;
+    let tranche_id = 1;
+
+    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
     assert!(res.is_ok(), "error: {:?}", res);
     assert_eq!(first_proposal_id, res.unwrap().votes[0].prop_id);
 
-    let res = query_proposal(
-        deps.as_ref(),
-        current_round_id,
-        tranche_id,
-        first_proposal_id,
-    );
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, first_proposal_id);
     assert!(res.is_ok());
     assert_eq!(
         info.funds[0].amount.u128(),
@@ -1577,64 +2149,72 @@ fn switch_vote_between_short_and_long_props_test() {
     );
 
     // switch vote to the second proposal
+    let second_proposal_id = 1;
     let msg = ExecuteMsg::Vote {
         tranche_id: 1,
         proposals_votes: vec![ProposalToLockups {
             proposal_id: second_proposal_id,
-            lock_ids: vec![first_lock_id],
+            lock_ids: vec![0],
         }],
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok(), "error: {:?}", res);
 
-    // no vote for second proposal will be created since the lock doesn't span long enough
-    let res = query_user_votes(
-        deps.as_ref(),
-        current_round_id,
-        tranche_id,
-        info.sender.to_string(),
-    );
-    assert!(res.is_err());
-
-    let msg = ExecuteMsg::Vote {
-        tranche_id: 1,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: first_proposal_id,
-            lock_ids: vec![first_lock_id],
-        }],
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    // verify users vote for the second proposal
+    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
     assert!(res.is_ok());
+    assert_eq!(second_proposal_id, res.unwrap().votes[0].prop_id);
 
-    // verify users vote for the first proposal
-    let res = query_user_votes(
-        deps.as_ref(),
-        current_round_id,
-        tranche_id,
-        info.sender.to_string(),
-    );
-    assert!(res.is_ok(), "error: {:?}", res);
-    assert_eq!(first_proposal_id, res.unwrap().votes[0].prop_id);
-
-    let res = query_proposal(
-        deps.as_ref(),
-        current_round_id,
-        tranche_id,
-        first_proposal_id,
-    );
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, second_proposal_id);
     assert!(res.is_ok());
     assert_eq!(
         info.funds[0].amount.u128(),
         res.unwrap().proposal.power.u128()
     );
+
+    // verify that the vote for the first proposal was removed
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, first_proposal_id);
+    assert!(res.is_ok());
+    assert_eq!(0, res.unwrap().proposal.power.u128());
+
+    // advance the chain by two weeks + 1 nano second to move to the next round and try to unlock tokens
+    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS + 1);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::UnlockTokens {
+            lock_ids: None,
+            claim_tributes: None,
+        },
+    );
+
+    // user voted for a proposal in previous round, but can unlock tokens
+    assert!(res.is_ok());
 }
 
-// Test case:
-//      1. User locks tokens and votes for some proposal with longer deployment duration
-//      2. User locks more tokens, which automatically votes for proposal from step #1
-//      3. When the next round starts, user tries to vote for some proposal with the lockup created in step #2
+// vote_extended_proposals_test tests that a vote is rejected if the round where votes
+// are possible is not reached yet and the vote is granted if it is done in the last round
+// of an extended proposal
+//
+// Test comprises 2 scenarios
+//  * A: fails to vote due to ongoing proposal user voted already for.
+//  * B: vote for new proposal succeeds as 'extended proposal' the user voted for is in last round.
+//
+// - round 0: user votes for extended proposal p(2)
+// - round 1: user tries to vote for p(3) but fails [scenario A]
+// - round 3: user is in last round of p(2) and votes successfully for p(4) [scenario B]
+//
+//  | round 0 | round 1 | round 2 | round 3 | round 4 |
+//  |  p(1)   |  end    |         |         |         |
+//  |  p(2)   |  ----   | -----   |  end    |         |
+//  |  p(3)   |  ----   | -----   |  end    |         |
+//  |         |  p(4)   | end     |         |         |
+//  |         |         |         |  p(5)   | end     |
+//
 #[test]
-fn disable_voting_in_next_round_with_auto_voted_lock_test() {
+fn vote_extended_proposals_test() {
     let user_address = "addr0000";
     let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
 
@@ -1643,797 +2223,3956 @@ fn disable_voting_in_next_round_with_auto_voted_lock_test() {
         HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
     let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[user_token.clone()]);
-    let mut instantiate_msg = get_default_instantiate_msg(&deps.api);
-    instantiate_msg.round_length = ONE_MONTH_IN_NANO_SECONDS;
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token));
+    let mut init_params = get_default_instantiate_msg(&deps.api);
+    init_params.first_round_start = env.block.time;
+    init_params.round_length = ONE_MONTH_IN_NANO_SECONDS;
 
     let res = instantiate(
         deps.as_mut(),
         env.clone(),
         info.clone(),
-        instantiate_msg.clone(),
+        init_params.clone(),
     );
     assert!(res.is_ok());
 
-    let current_round_id = 0;
-    let tranche_id = 1;
-
-    let first_proposal_id = 0;
-    let second_proposal_id = 1;
-
-    let first_lock_id = 0;
-    let second_lock_id = 1;
+    set_default_validator_for_rounds(deps.as_mut(), 0, 5);
 
-    let res = set_validator_infos_for_round(
-        &mut deps.storage,
-        current_round_id,
-        vec![VALIDATOR_1.to_string()],
-    );
-    assert!(res.is_ok());
+    // advance the env time to simulate ongoing round
+    env.block.time = env.block.time.plus_hours(1);
 
-    // lock some tokens to get voting power
+    // create a lock that will have power long enough to vote for the 'long lasting' proposal
     let msg = ExecuteMsg::LockTokens {
-        lock_duration: 12 * ONE_MONTH_IN_NANO_SECONDS,
+        lock_duration: 6 * ONE_MONTH_IN_NANO_SECONDS,
     };
+
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
-    let msg = ExecuteMsg::CreateProposal {
-        round_id: None,
-        tranche_id,
-        title: "proposal title 1".to_string(),
-        description: "proposal description 1".to_string(),
-        deployment_duration: 6,
-        minimum_atom_liquidity_request: Uint128::zero(),
+    // create one more lock that will not be allowed to vote for the 'long lasting' proposal
+    // since it will have 0 power at the end of the round that precedes the round in which
+    // the liquidity should be returned
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: 2 * ONE_MONTH_IN_NANO_SECONDS,
     };
 
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
-    // vote for the first proposal
-    let msg = ExecuteMsg::Vote {
-        tranche_id,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: first_proposal_id,
-            lock_ids: vec![first_lock_id],
-        }],
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
+    let round_id = 0;
+    let tranche_id = 1;
 
-    // verify users vote for the first proposal
-    let res = query_user_votes(
-        deps.as_ref(),
-        current_round_id,
-        tranche_id,
-        info.sender.to_string(),
-    );
-    assert!(res.is_ok(), "error: {:?}", res);
-    assert_eq!(first_proposal_id, res.unwrap().votes[0].prop_id);
+    let first_lock_id = 0;
+    let second_lock_id = 1;
 
-    let res = query_proposal(
-        deps.as_ref(),
-        current_round_id,
-        tranche_id,
-        first_proposal_id,
-    );
-    assert!(res.is_ok());
+    let second_proposal_id = 1;
+    let third_proposal_id = 2;
+    let fourth_proposal_id = 3;
+    let fifth_proposal_id = 4;
 
-    let expected_proposal_power = 4 * info.funds[0].amount.u128();
-    assert_eq!(expected_proposal_power, res.unwrap().proposal.power.u128());
+    let prop_infos = vec![
+        // proposal p(1)  with deployment period of 1 round
+        (
+            "proposal title 1".to_string(),
+            "proposal description 1".to_string(),
+            1,
+        ),
+        // proposal p(2) with deployment period of 3 rounds
+        (
+            "proposal title 2".to_string(),
+            "proposal description 2".to_string(),
+            3,
+        ),
+        // proposal p(3) with deployment period of 3 rounds
+        (
+            "proposal title 3".to_string(),
+            "proposal description 3".to_string(),
+            3,
+        ),
+    ];
 
-    // lock 1000 more tokens and verify that voting power on first proposal increases
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: 12 * ONE_MONTH_IN_NANO_SECONDS,
+    for prop_info in &prop_infos {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: prop_info.0.clone(),
+            description: prop_info.1.clone(),
+            deployment_duration: prop_info.2,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+        assert!(res.is_ok());
+    }
+
+    // vote for the third proposals p(3)
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: third_proposal_id,
+            lock_ids: vec![first_lock_id],
+        }],
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    let res = query_proposal(
-        deps.as_ref(),
-        current_round_id,
+    // check that users voted for the third proposal
+    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(third_proposal_id, res.unwrap().votes[0].prop_id);
+
+    // switch vote from the third proposal p(3) to the second proposals p(2)
+    let msg = ExecuteMsg::Vote {
         tranche_id,
-        first_proposal_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: second_proposal_id,
+            lock_ids: vec![first_lock_id],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // check that users voted for the second proposal
+    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
+    assert!(res.is_ok(), "error: {:?}", res);
+    let user_vote = res.unwrap().votes[0].clone();
+    assert_eq!(second_proposal_id, user_vote.prop_id);
+
+    // save vote power for future verification
+    let old_vote_power = user_vote.power;
+
+    // vote for second proposal p(2) with lock that doesn't span long enough
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: second_proposal_id,
+            lock_ids: vec![second_lock_id],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    let mut second_lock_skipped = false;
+    for attribute in res.unwrap().attributes {
+        if attribute.key.eq("locks_skipped")
+            && attribute.value.contains(&second_lock_id.to_string())
+        {
+            second_lock_skipped = true;
+            break;
+        }
+    }
+    assert!(
+        second_lock_skipped,
+        "lock with ID {} should be skipped, but it wasn't",
+        second_lock_id
+    );
+
+    // verify that user's vote didn't change
+    let res = query_user_votes(deps.as_ref(), round_id, tranche_id, info.sender.to_string());
+    assert!(res.is_ok(), "error: {:?}", res);
+    let user_vote = res.unwrap().votes[0].clone();
+    assert_eq!(second_proposal_id, user_vote.prop_id);
+    assert_eq!(old_vote_power, user_vote.power);
+
+    // advance the chain by one round length to move to round 1
+    env.block.time = env.block.time.plus_nanos(init_params.round_length);
+
+    // cross check that the current round is round 1
+    let resp = query_current_round_id(deps.as_ref(), env.clone());
+    assert!(resp.is_ok());
+
+    assert_eq!(
+        1,
+        resp.unwrap().round_id,
+        "expected to reach round 1 (round after voting)",
     );
+
+    // create new proposal p(4) (successor of p(1))
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: prop_infos[0].0.clone(),
+        description: prop_infos[0].1.clone(),
+        deployment_duration: prop_infos[0].2,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // 2 locks, both 1000 tokens, locked for 12 rounds (4x multiplier)
-    let expected_proposal_power = 2 * 4 * info.funds[0].amount.u128();
-    assert_eq!(expected_proposal_power, res.unwrap().proposal.power.u128());
+    // check that voting for p(4), one round after voting for 'long lasting' proposal fails
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: fourth_proposal_id,
+            lock_ids: vec![first_lock_id],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(
+        res.is_err(),
+        "voting in the round after voting for 'long lasting' proposal should fail"
+    );
 
-    // advance the chain to move to the next round
+    // advance to the last round of chain #rounds - current round
+    let remaining_rounds = prop_infos[1].2 - 1;
     env.block.time = env
         .block
         .time
-        .plus_nanos(instantiate_msg.round_length)
-        .plus_days(1);
+        .plus_nanos(remaining_rounds * init_params.round_length);
 
-    // submit new proposal
+    // check that this is the round in which the proposal 1 ends
+    let resp = query_current_round_id(deps.as_ref(), env.clone());
+    assert!(resp.is_ok());
+
+    let round_no = resp.unwrap().round_id;
+    assert_eq!(
+        3,
+        round_no,
+        "expected to reach round {:?}, sitting in {:?}",
+        prop_infos[0].2 - 1,
+        round_id
+    );
+
+    // create new proposal p(5), successor of p(4)
     let msg = ExecuteMsg::CreateProposal {
         round_id: None,
         tranche_id,
-        title: "proposal title 2".to_string(),
-        description: "proposal description 2".to_string(),
-        deployment_duration: 6,
+        title: prop_infos[0].0.clone(),
+        description: prop_infos[0].1.clone(),
+        deployment_duration: prop_infos[0].2,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
-
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // try to vote for the second proposal with the second lock id (should not be allowed)
+    // check that voting for p(5) in round 3 (when the 'long lasting' proposal ends) passes
     let msg = ExecuteMsg::Vote {
         tranche_id,
         proposals_votes: vec![ProposalToLockups {
-            proposal_id: second_proposal_id,
-            lock_ids: vec![second_lock_id],
+            proposal_id: fifth_proposal_id,
+            lock_ids: vec![first_lock_id],
         }],
     };
-
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_err());
-    assert!(res
-        .unwrap_err()
-        .to_string()
-        .contains("Not allowed to vote with lock_id 1 in tranche 1. Cannot vote again with this lock_id until round 6."));
+    assert!(
+        res.is_ok(),
+        "voting in the round in which the 'long lasting' proposal is ending failed"
+    );
+
+    let res = query_user_votes(deps.as_ref(), round_no, tranche_id, info.sender.to_string());
+    assert!(
+        res.is_ok(),
+        "querying vote for round {:?} failed {:?}",
+        round_no,
+        res
+    );
+    assert_eq!(fifth_proposal_id, res.unwrap().votes[0].prop_id);
 }
 
 #[test]
-fn multi_tranches_test() {
+fn vote_delegation_test() {
     let grpc_query = denom_trace_grpc_query_mock(
         "transfer/channel-0".to_string(),
         HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
     let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(
-        &deps.api,
-        "addr0000",
-        &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
-    );
-    let mut msg = get_default_instantiate_msg(&deps.api);
-    msg.tranches = vec![
-        TrancheInfo {
-            name: "tranche 1".to_string(),
-            metadata: "tranche 1 metadata".to_string(),
-        },
-        TrancheInfo {
-            name: "tranche 2".to_string(),
-            metadata: "tranche 2 metadata".to_string(),
-        },
-    ];
-
-    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    let owner_info = get_message_info(&deps.api, "addr0000", &[]);
+    let delegate_info = get_message_info(&deps.api, "addr0001", &[]);
+    let other_info = get_message_info(&deps.api, "addr0002", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), owner_info.clone(), msg);
     assert!(res.is_ok());
 
     set_default_validator_for_rounds(deps.as_mut(), 0, 100);
 
-    // create two proposals for tranche 1
-    let msg1 = ExecuteMsg::CreateProposal {
+    let tranche_id = 1;
+    let msg = ExecuteMsg::CreateProposal {
         round_id: None,
-        tranche_id: 1,
-        title: "proposal title 1".to_string(),
-        description: "proposal description 1".to_string(),
+        tranche_id,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
         deployment_duration: 1,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg1.clone());
-    assert!(res.is_ok());
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    let msg2 = ExecuteMsg::CreateProposal {
-        round_id: None,
-        tranche_id: 1,
-        title: "proposal title 2".to_string(),
-        description: "proposal description 2".to_string(),
-        deployment_duration: 1,
-        minimum_atom_liquidity_request: Uint128::zero(),
+    let info = get_message_info(
+        &deps.api,
+        "addr0000",
+        &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+    );
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg2.clone());
-    assert!(res.is_ok());
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // create two proposals for tranche 2
-    let msg3 = ExecuteMsg::CreateProposal {
+    let vote_msg = ExecuteMsg::VoteOnBehalf {
+        owner: owner_info.sender.to_string(),
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![0],
+        }],
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        delegate_info.clone(),
+        vote_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Voting rights of lock 0 are not delegated to"));
+
+    // the voting rights can only be delegated by the lock owner, and not to the lock owner
+    let delegate_msg = ExecuteMsg::DelegateVotingRights {
+        lock_ids: vec![0],
+        delegate: delegate_info.sender.to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        delegate_msg.clone(),
+    );
+    assert!(res.is_err());
+
+    let msg = ExecuteMsg::DelegateVotingRights {
+        lock_ids: vec![0],
+        delegate: owner_info.sender.to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Can't delegate voting rights to the lock owner"));
+
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), delegate_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_delegations_for_user(deps.as_ref(), owner_info.sender.to_string()).unwrap();
+    assert_eq!(
+        vec![VoteDelegation {
+            lock_id: 0,
+            delegate: delegate_info.sender.clone(),
+        }],
+        res.delegations
+    );
+
+    // only the appointed delegate can vote on behalf of the owner
+    let res = execute(deps.as_mut(), env.clone(), other_info, vote_msg.clone());
+    assert!(res.is_err());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        delegate_info.clone(),
+        vote_msg.clone(),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("delegate", delegate_info.sender.to_string())));
+
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(1000), res.proposal.power);
+
+    let res =
+        query_user_votes(deps.as_ref(), 0, tranche_id, owner_info.sender.to_string()).unwrap();
+    assert_eq!(1, res.votes.len());
+
+    let msg = ExecuteMsg::RevokeDelegation { lock_ids: vec![0] };
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_delegations_for_user(deps.as_ref(), owner_info.sender.to_string()).unwrap();
+    assert!(res.delegations.is_empty());
+
+    let res = execute(deps.as_mut(), env.clone(), delegate_info, vote_msg);
+    assert!(res.is_err());
+}
+
+#[test]
+fn voting_executor_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let owner_info = get_message_info(&deps.api, "addr0000", &[]);
+    let executor_info = get_message_info(&deps.api, "addr0001", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    for i in 0..2 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+
+        let info = get_message_info(
+            &deps.api,
+            "addr0000",
+            &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let msg = ExecuteMsg::SetVotingExecutor {
+        executor: executor_info.sender.to_string(),
+        max_proposals_per_round: 0,
+    };
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_err());
+
+    let msg = ExecuteMsg::SetVotingExecutor {
+        executor: executor_info.sender.to_string(),
+        max_proposals_per_round: 1,
+    };
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_voting_executor(deps.as_ref(), owner_info.sender.to_string()).unwrap();
+    assert_eq!(
+        Some(VotingExecutor {
+            executor: executor_info.sender.clone(),
+            max_proposals_per_round: 1,
+        }),
+        res.voting_executor
+    );
+
+    let vote_msg = |proposal_id: u64, lock_id: u64| ExecuteMsg::VoteOnBehalf {
+        owner: owner_info.sender.to_string(),
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id,
+            lock_ids: vec![lock_id],
+        }],
+    };
+
+    // the executor can vote with any lockup of the owner, without per-lock delegation
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        executor_info.clone(),
+        vote_msg(0, 0),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("executor", executor_info.sender.to_string())));
+
+    // but the lockups of the owner can't end up voting for more proposals than allowed
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        executor_info.clone(),
+        vote_msg(1, 1),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("can vote for at most 1 proposals per round"));
+
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 1).unwrap();
+    assert_eq!(Uint128::zero(), res.proposal.power);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        executor_info.clone(),
+        vote_msg(0, 1),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(2000), res.proposal.power);
+
+    let msg = ExecuteMsg::RemoveVotingExecutor {};
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_voting_executor(deps.as_ref(), owner_info.sender.to_string()).unwrap();
+    assert!(res.voting_executor.is_none());
+
+    let res = execute(deps.as_mut(), env.clone(), executor_info, vote_msg(0, 0));
+    assert!(res.is_err());
+}
+
+#[test]
+fn abstain_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    let msg = ExecuteMsg::CreateProposal {
         round_id: None,
-        tranche_id: 2,
-        title: "proposal title 3".to_string(),
-        description: "proposal description 3".to_string(),
+        tranche_id,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
         deployment_duration: 1,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg3.clone());
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    for _ in 0..2 {
+        let info = get_message_info(
+            &deps.api,
+            "addr0000",
+            &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // only the owner of the lockup can abstain with it
+    let other_info = get_message_info(&deps.api, "addr0001", &[]);
+    let msg = ExecuteMsg::Abstain {
+        tranche_id,
+        lock_ids: vec![0],
+    };
+    let res = execute(deps.as_mut(), env.clone(), other_info, msg);
+    assert!(res.is_err());
+
+    let msg = ExecuteMsg::Abstain {
+        tranche_id,
+        lock_ids: vec![0, 1],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert!(res.attributes.contains(&attr("lock_ids", "0,1")));
+
+    let abstaining_flags = |deps: Deps<NeutronQuery>| -> Vec<bool> {
+        query_specific_user_lockups_with_tranche_infos(
+            deps,
+            env.clone(),
+            info.sender.to_string(),
+            vec![0, 1],
+        )
+        .unwrap()
+        .lockups_with_per_tranche_infos
+        .iter()
+        .map(|lockup| {
+            lockup
+                .per_tranche_info
+                .iter()
+                .find(|tranche_info| tranche_info.tranche_id == tranche_id)
+                .unwrap()
+                .abstaining
+        })
+        .collect()
+    };
+    assert_eq!(vec![true, true], abstaining_flags(deps.as_ref()));
+
+    // voting with an abstaining lockup clears the abstention
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![0],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(vec![false, true], abstaining_flags(deps.as_ref()));
+
+    // a lockup that already voted can not abstain
+    let msg = ExecuteMsg::Abstain {
+        tranche_id,
+        lock_ids: vec![0],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Lock 0 already voted in round 0 and tranche 1"));
+
+    // only the delegates, operators and voting executors of the owner can abstain on their behalf
+    let other_info = get_message_info(&deps.api, "addr0001", &[]);
+    let abstain_on_behalf_msg = ExecuteMsg::AbstainOnBehalf {
+        owner: info.sender.to_string(),
+        tranche_id,
+        lock_ids: vec![1],
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        abstain_on_behalf_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Voting rights of lock 1 are not delegated"));
+
+    let msg = ExecuteMsg::DelegateVotingRights {
+        lock_ids: vec![1],
+        delegate: other_info.sender.to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        abstain_on_behalf_msg.clone(),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("delegate", other_info.sender.to_string())));
+
+    // once the lockups expire before the round end, they have no vote left to reserve
+    let mut env = env;
+    env.block.time = env.block.time.plus_nanos(2 * TWO_WEEKS_IN_NANO_SECONDS + 1);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info,
+        abstain_on_behalf_msg,
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Lock 1 has no voting power in round 2"));
+}
+
+#[test]
+fn unvote_all_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
-    let msg4 = ExecuteMsg::CreateProposal {
-        round_id: None,
-        tranche_id: 2,
-        title: "proposal title 4".to_string(),
-        description: "proposal description 4".to_string(),
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    for i in 0..2 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    for _ in 0..3 {
+        let info = get_message_info(
+            &deps.api,
+            "addr0000",
+            &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // nothing to remove before voting
+    let msg = ExecuteMsg::UnvoteAll {
+        tranche_id,
+        owner: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("No votes to remove in round 0 and tranche 1"));
+
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![
+            ProposalToLockups {
+                proposal_id: 0,
+                lock_ids: vec![0, 1],
+            },
+            ProposalToLockups {
+                proposal_id: 1,
+                lock_ids: vec![2],
+            },
+        ],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(2000), res.proposal.power);
+
+    let msg = ExecuteMsg::UnvoteAll {
+        tranche_id,
+        owner: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    for (lock_id, proposal_id) in [(0, "0"), (1, "0"), (2, "1")] {
+        assert!(res.attributes.contains(&attr(
+            format!("lock_id_{}_old_proposal_id", lock_id),
+            proposal_id
+        )));
+    }
+    assert!(res.attributes.contains(&attr("proposal_ids", "0,1")));
+
+    for proposal_id in 0..2 {
+        let res = query_proposal(deps.as_ref(), 0, tranche_id, proposal_id).unwrap();
+        assert_eq!(Uint128::zero(), res.proposal.power);
+    }
+    assert!(VOTE_MAP
+        .prefix(((0, tranche_id), info.sender.clone()))
+        .range(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+
+    // the lockups can vote again in the same round
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 1,
+            lock_ids: vec![0, 1],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 1).unwrap();
+    assert_eq!(Uint128::new(2000), res.proposal.power);
+
+    // the votes of the owner can be removed on its behalf only if all of its voted lockups are delegated
+    let delegate_info = get_message_info(&deps.api, "addr0001", &[]);
+    let unvote_on_behalf_msg = ExecuteMsg::UnvoteAll {
+        tranche_id,
+        owner: Some(info.sender.to_string()),
+    };
+
+    let msg = ExecuteMsg::DelegateVotingRights {
+        lock_ids: vec![0],
+        delegate: delegate_info.sender.to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        delegate_info.clone(),
+        unvote_on_behalf_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Voting rights of lock 1 are not delegated"));
+
+    let msg = ExecuteMsg::DelegateVotingRights {
+        lock_ids: vec![1],
+        delegate: delegate_info.sender.to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        delegate_info.clone(),
+        unvote_on_behalf_msg,
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("delegate", delegate_info.sender.to_string())));
+    assert!(res.attributes.contains(&attr("proposal_ids", "1")));
+
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 1).unwrap();
+    assert_eq!(Uint128::zero(), res.proposal.power);
+}
+
+#[test]
+fn lock_operator_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let owner_info = get_message_info(&deps.api, "addr0000", &[]);
+    let operator_info = get_message_info(&deps.api, "addr0001", &[]);
+    let other_info = get_message_info(&deps.api, "addr0002", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let info = get_message_info(
+        &deps.api,
+        "addr0000",
+        &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+    );
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // the operator can only be appointed by the lock owner, and can't be the lock owner
+    let set_operator_msg = ExecuteMsg::SetLockOperator {
+        lock_ids: vec![0],
+        operator: Some(operator_info.sender.to_string()),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        set_operator_msg.clone(),
+    );
+    assert!(res.is_err());
+
+    let msg = ExecuteMsg::SetLockOperator {
+        lock_ids: vec![0],
+        operator: Some(owner_info.sender.to_string()),
+    };
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Lock owner can't be the operator of their own locks"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner_info.clone(),
+        set_operator_msg,
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_lock_operators(deps.as_ref(), owner_info.sender.to_string()).unwrap();
+    assert_eq!(
+        vec![LockOperator {
+            lock_id: 0,
+            operator: operator_info.sender.clone(),
+        }],
+        res.operators
+    );
+
+    // the operator can vote with the lockup
+    let vote_msg = ExecuteMsg::VoteOnBehalf {
+        owner: owner_info.sender.to_string(),
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![0],
+        }],
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        vote_msg.clone(),
+    );
+    assert!(res.is_err());
+
+    let res = execute(deps.as_mut(), env.clone(), operator_info.clone(), vote_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(1000), res.proposal.power);
+
+    // the operator can refresh the lockup
+    let refresh_msg = ExecuteMsg::RefreshLockDurationOnBehalf {
+        owner: owner_info.sender.to_string(),
+        lock_ids: vec![0],
+        lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info.clone(),
+        refresh_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("is not the operator of lock 0"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        operator_info.clone(),
+        refresh_msg.clone(),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("operator", operator_info.sender.to_string())));
+
+    let res = query_all_user_lockups(
+        deps.as_ref(),
+        env.clone(),
+        owner_info.sender.to_string(),
+        0,
+        None,
+        2000,
+    )
+    .unwrap();
+    assert_eq!(
+        env.block.time.plus_nanos(THREE_MONTHS_IN_NANO_SECONDS),
+        res.lockups[0].lock_entry.lock_end
+    );
+
+    // once removed, the operator can't manage the lockup anymore
+    let msg = ExecuteMsg::SetLockOperator {
+        lock_ids: vec![0],
+        operator: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_lock_operators(deps.as_ref(), owner_info.sender.to_string()).unwrap();
+    assert!(res.operators.is_empty());
+
+    let res = execute(deps.as_mut(), env.clone(), operator_info, refresh_msg);
+    assert!(res.is_err());
+}
+
+#[test]
+fn lock_notes_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let owner_info = get_message_info(&deps.api, "addr0000", &[]);
+    let other_info = get_message_info(&deps.api, "addr0001", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), owner_info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    for _ in 0..2 {
+        let info = get_message_info(
+            &deps.api,
+            "addr0000",
+            &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let set_note_msg = |note: &str| ExecuteMsg::SetLockNote {
+        lock_id: 0,
+        note: Some(note.to_string()),
+    };
+
+    // only the lock owner can attach notes to the lockup
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info,
+        set_note_msg("treasury: ops budget Q3"),
+    );
+    assert!(res.is_err());
+
+    let long_note = "a".repeat(MAX_LOCK_NOTE_LENGTH + 1);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner_info.clone(),
+        set_note_msg(&long_note),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Lock note must not be longer than"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner_info.clone(),
+        set_note_msg("treasury\u{0}"),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Lock note must not contain control characters"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner_info.clone(),
+        set_note_msg(" treasury: ops budget Q3 "),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let owner = owner_info.sender.to_string();
+    let get_notes = |deps: Deps<NeutronQuery>, include_metadata: Option<bool>| {
+        let msg = QueryMsg::AllUserLockups {
+            address: owner.clone(),
+            start_from: 0,
+            start_after: None,
+            limit: 10,
+            include_metadata,
+        };
+        from_json::<AllUserLockupsResponse>(query(deps, env.clone(), msg).unwrap())
+            .unwrap()
+            .lockups
+            .into_iter()
+            .map(|lockup| lockup.note)
+            .collect::<Vec<Option<String>>>()
+    };
+
+    // the notes are only returned when requested
+    assert_eq!(vec![None, None], get_notes(deps.as_ref(), None));
+    assert_eq!(
+        vec![Some("treasury: ops budget Q3".to_string()), None],
+        get_notes(deps.as_ref(), Some(true))
+    );
+
+    let msg = QueryMsg::SpecificUserLockupsWithTrancheInfos {
+        address: get_address_as_str(&deps.api, "addr0000"),
+        lock_ids: vec![0],
+        include_metadata: Some(true),
+    };
+    let res: SpecificUserLockupsWithTrancheInfosResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        Some("treasury: ops budget Q3".to_string()),
+        res.lockups_with_per_tranche_infos[0].lock_with_power.note
+    );
+
+    // an empty note removes the note from the lockup
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner_info.clone(),
+        set_note_msg(""),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(vec![None, None], get_notes(deps.as_ref(), Some(true)));
+}
+
+#[test]
+fn lock_auto_merge_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let one_day = ONE_MONTH_IN_NANO_SECONDS / 30;
+    let address = info.sender.to_string();
+    let query_tolerance = |deps: Deps<NeutronQuery>| {
+        from_json::<LockAutoMergeResponse>(
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::LockAutoMerge {
+                    address: address.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .tolerance
+    };
+    let lock_info = get_message_info(&deps.api, "addr0000", &[Coin::new(500u64, IBC_DENOM_1)]);
+    let lock_tokens = |deps: DepsMut<NeutronQuery>, env: &Env, lock_duration: u64| {
+        execute(
+            deps,
+            env.clone(),
+            lock_info.clone(),
+            ExecuteMsg::LockTokens { lock_duration },
+        )
+        .unwrap()
+    };
+    let get_lockups = |deps: Deps<NeutronQuery>, env: &Env| {
+        query_all_user_lockups(deps, env.clone(), info.sender.to_string(), 0, None, 100)
+            .unwrap()
+            .lockups
+    };
+
+    // the tolerance can't be longer than the lock epoch
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::SetLockAutoMerge {
+            tolerance: Some(ONE_MONTH_IN_NANO_SECONDS + 1),
+        },
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Lock auto merge tolerance must not be longer than the lock epoch length"));
+
+    assert_eq!(None, query_tolerance(deps.as_ref()));
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::SetLockAutoMerge {
+            tolerance: Some(one_day),
+        },
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(Some(one_day), query_tolerance(deps.as_ref()));
+
+    // the first lock creates a new lockup, which then votes for the proposal
+    let res = lock_tokens(deps.as_mut(), &env, ONE_MONTH_IN_NANO_SECONDS);
+    assert!(!res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "merged_into_lock_id"));
+
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![0],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // a lock ending within the tolerance is merged into the existing lockup, together with its votes
+    env.block.time = env.block.time.plus_nanos(one_day / 2);
+    let res = lock_tokens(deps.as_mut(), &env, ONE_MONTH_IN_NANO_SECONDS);
+    assert!(res.attributes.contains(&attr("merged_into_lock_id", "0")));
+
+    let lockups = get_lockups(deps.as_ref(), &env);
+    assert_eq!(1, lockups.len());
+    assert_eq!(1000, lockups[0].lock_entry.funds.amount.u128());
+    assert_eq!(
+        env.block.time.plus_nanos(ONE_MONTH_IN_NANO_SECONDS),
+        lockups[0].lock_entry.lock_end
+    );
+    assert_eq!(1000, lockups[0].current_voting_power.u128());
+
+    let proposal = query_proposal(deps.as_ref(), 0, tranche_id, 0)
+        .unwrap()
+        .proposal;
+    assert_eq!(Uint128::new(1000), proposal.power);
+    assert_eq!(
+        Uint128::new(1000),
+        query_round_total_power(deps.as_ref(), env.clone(), 0)
+            .unwrap()
+            .total_voting_power
+    );
+
+    // a lock ending outside of the tolerance creates a new lockup
+    let res = lock_tokens(deps.as_mut(), &env, 3 * ONE_MONTH_IN_NANO_SECONDS);
+    assert!(res.attributes.contains(&attr("lock_id", "1")));
+    assert_eq!(2, get_lockups(deps.as_ref(), &env).len());
+
+    // the batch locks are merged in the same way
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        lock_info.clone(),
+        ExecuteMsg::LockTokensMultiple {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        },
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("lock_ids", "0")));
+    assert!(res.attributes.contains(&attr("merged_into_lock_ids", "0")));
+
+    let lockups = get_lockups(deps.as_ref(), &env);
+    assert_eq!(2, lockups.len());
+    assert_eq!(1500, lockups[0].lock_entry.funds.amount.u128());
+
+    // the merged tokens add to the vote of lockup 0, next to the vote of lockup 1 that followed it
+    assert_eq!(
+        Uint128::new(2250),
+        query_proposal(deps.as_ref(), 0, tranche_id, 0)
+            .unwrap()
+            .proposal
+            .power
+    );
+
+    // once disabled, the new locks are no longer merged
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::SetLockAutoMerge { tolerance: None },
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(None, query_tolerance(deps.as_ref()));
+
+    let res = lock_tokens(deps.as_mut(), &env, ONE_MONTH_IN_NANO_SECONDS);
+    assert!(res.attributes.contains(&attr("lock_id", "2")));
+    assert_eq!(3, get_lockups(deps.as_ref(), &env).len());
+}
+
+#[test]
+fn import_lockups_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let admin_info = get_message_info(&deps.api, "addr0000", &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let lock_start = env.block.time.plus_nanos(1);
+    env.block.time = env.block.time.plus_nanos(ONE_DAY_IN_NANO_SECONDS);
+
+    let user1 = get_address_as_str(&deps.api, "addr0001");
+    let user2 = get_address_as_str(&deps.api, "addr0002");
+    let entries = vec![
+        LockupImport {
+            owner: user1.clone(),
+            funds: Coin::new(1000u64, IBC_DENOM_1),
+            lock_start,
+            lock_end: env.block.time.plus_nanos(ONE_MONTH_IN_NANO_SECONDS),
+        },
+        LockupImport {
+            owner: user2.clone(),
+            funds: Coin::new(2000u64, IBC_DENOM_1),
+            lock_start,
+            lock_end: env.block.time.plus_nanos(3 * ONE_MONTH_IN_NANO_SECONDS),
+        },
+    ];
+    let import_msg = |entries: Vec<LockupImport>, finalize: bool| ExecuteMsg::ImportLockups {
+        entries,
+        finalize,
+    };
+    let api = deps.api;
+    let funds_info = |sender: &str, amount: u64| {
+        get_message_info(&api, sender, &[Coin::new(amount, IBC_DENOM_1)])
+    };
+
+    // only the whitelist admins can import lockups
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        funds_info("addr0001", 3000),
+        import_msg(entries.clone(), false),
+    );
+    assert!(res.is_err());
+
+    // the sent funds must cover exactly the imported lockups
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        funds_info("addr0000", 2999),
+        import_msg(entries.clone(), false),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Sent funds must match the total amount of the imported lockups"));
+
+    // imported lockups can't end later than the longest lock duration from now
+    let mut too_long_entry = entries[0].clone();
+    too_long_entry.lock_end = env.block.time.plus_nanos(13 * ONE_MONTH_IN_NANO_SECONDS);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        funds_info("addr0000", 1000),
+        import_msg(vec![too_long_entry], false),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("must not end later than the longest lock duration from now"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        funds_info("addr0000", 3000),
+        import_msg(entries.clone(), false),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let lockups = query_all_user_lockups(deps.as_ref(), env.clone(), user1, 0, None, 10)
+        .unwrap()
+        .lockups;
+    assert_eq!(1, lockups.len());
+    assert_eq!(entries[0].funds, lockups[0].lock_entry.funds);
+    assert_eq!(lock_start, lockups[0].lock_entry.lock_start);
+    assert_eq!(entries[0].lock_end, lockups[0].lock_entry.lock_end);
+    assert_eq!(1000, lockups[0].current_voting_power.u128());
+
+    let user2_power = query_all_user_lockups(deps.as_ref(), env.clone(), user2, 0, None, 10)
+        .unwrap()
+        .lockups[0]
+        .current_voting_power;
+    assert_eq!(
+        Uint128::new(1000) + user2_power,
+        query_round_total_power(deps.as_ref(), env.clone(), 0)
+            .unwrap()
+            .total_voting_power
+    );
+
+    // once finalized, no more lockups can be imported
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        funds_info("addr0000", 1000),
+        import_msg(vec![entries[0].clone()], true),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        funds_info("addr0000", 1000),
+        import_msg(vec![entries[0].clone()], false),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Lockups import was already finalized"));
+}
+
+// Test case:
+//      1. User votes with 1-round-long-lock for proposal with deployment_duration = 1
+//      2. User votes with the same lock, but for proposal with deployment_duration = 3
+//         (no vote gets created since it is a short lock; old vote gets deleted)
+//      3. User votes for proposal from step #1 again
+//         (or any other with deployment_duration that it should be allowed to vote)
+#[test]
+fn switch_vote_between_short_and_long_props_test() {
+    let user_address = "addr0000";
+    let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
+
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token));
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.round_length = ONE_MONTH_IN_NANO_SECONDS;
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    let current_round_id = 0;
+    let tranche_id = 1;
+
+    let first_proposal_id = 0;
+    let second_proposal_id = 1;
+
+    let first_lock_id = 0;
+
+    let res = set_validator_infos_for_round(
+        &mut deps.storage,
+        current_round_id,
+        vec![VALIDATOR_1.to_string()],
+    );
+    assert!(res.is_ok());
+
+    env.block.time = env.block.time.plus_hours(12);
+
+    // lock some tokens for one round to get voting power
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let prop_infos = vec![
+        (
+            "proposal title 1".to_string(),
+            "proposal description 1".to_string(),
+            1,
+        ),
+        (
+            "proposal title 2".to_string(),
+            "proposal description 2".to_string(),
+            3,
+        ),
+    ];
+
+    for prop_info in prop_infos {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id: 1,
+            title: prop_info.0,
+            description: prop_info.1,
+            deployment_duration: prop_info.2,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+        assert!(res.is_ok());
+    }
+
+    // vote for the first proposal
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: first_proposal_id,
+            lock_ids: vec![first_lock_id],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // verify users vote for the first proposal
+    let res = query_user_votes(
+        deps.as_ref(),
+        current_round_id,
+        tranche_id,
+        info.sender.to_string(),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(first_proposal_id, res.unwrap().votes[0].prop_id);
+
+    let res = query_proposal(
+        deps.as_ref(),
+        current_round_id,
+        tranche_id,
+        first_proposal_id,
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        info.funds[0].amount.u128(),
+        res.unwrap().proposal.power.u128()
+    );
+
+    // switch vote to the second proposal
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: second_proposal_id,
+            lock_ids: vec![first_lock_id],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // no vote for second proposal will be created since the lock doesn't span long enough
+    let res = query_user_votes(
+        deps.as_ref(),
+        current_round_id,
+        tranche_id,
+        info.sender.to_string(),
+    );
+    assert!(res.is_err());
+
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: first_proposal_id,
+            lock_ids: vec![first_lock_id],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // verify users vote for the first proposal
+    let res = query_user_votes(
+        deps.as_ref(),
+        current_round_id,
+        tranche_id,
+        info.sender.to_string(),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(first_proposal_id, res.unwrap().votes[0].prop_id);
+
+    let res = query_proposal(
+        deps.as_ref(),
+        current_round_id,
+        tranche_id,
+        first_proposal_id,
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        info.funds[0].amount.u128(),
+        res.unwrap().proposal.power.u128()
+    );
+}
+
+// Test case:
+//      1. User locks tokens and votes for some proposal with longer deployment duration
+//      2. User locks more tokens, which automatically votes for proposal from step #1
+//      3. When the next round starts, user tries to vote for some proposal with the lockup created in step #2
+#[test]
+fn disable_voting_in_next_round_with_auto_voted_lock_test() {
+    let user_address = "addr0000";
+    let user_token = Coin::new(1000u64, IBC_DENOM_1.to_string());
+
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, user_address, std::slice::from_ref(&user_token));
+    let mut instantiate_msg = get_default_instantiate_msg(&deps.api);
+    instantiate_msg.round_length = ONE_MONTH_IN_NANO_SECONDS;
+
+    let res = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_msg.clone(),
+    );
+    assert!(res.is_ok());
+
+    let current_round_id = 0;
+    let tranche_id = 1;
+
+    let first_proposal_id = 0;
+    let second_proposal_id = 1;
+
+    let first_lock_id = 0;
+    let second_lock_id = 1;
+
+    let res = set_validator_infos_for_round(
+        &mut deps.storage,
+        current_round_id,
+        vec![VALIDATOR_1.to_string()],
+    );
+    assert!(res.is_ok());
+
+    // lock some tokens to get voting power
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: 12 * ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        deployment_duration: 6,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // vote for the first proposal
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: first_proposal_id,
+            lock_ids: vec![first_lock_id],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // verify users vote for the first proposal
+    let res = query_user_votes(
+        deps.as_ref(),
+        current_round_id,
+        tranche_id,
+        info.sender.to_string(),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(first_proposal_id, res.unwrap().votes[0].prop_id);
+
+    let res = query_proposal(
+        deps.as_ref(),
+        current_round_id,
+        tranche_id,
+        first_proposal_id,
+    );
+    assert!(res.is_ok());
+
+    let expected_proposal_power = 4 * info.funds[0].amount.u128();
+    assert_eq!(expected_proposal_power, res.unwrap().proposal.power.u128());
+
+    // lock 1000 more tokens and verify that voting power on first proposal increases
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: 12 * ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let res = query_proposal(
+        deps.as_ref(),
+        current_round_id,
+        tranche_id,
+        first_proposal_id,
+    );
+    assert!(res.is_ok());
+
+    // 2 locks, both 1000 tokens, locked for 12 rounds (4x multiplier)
+    let expected_proposal_power = 2 * 4 * info.funds[0].amount.u128();
+    assert_eq!(expected_proposal_power, res.unwrap().proposal.power.u128());
+
+    // advance the chain to move to the next round
+    env.block.time = env
+        .block
+        .time
+        .plus_nanos(instantiate_msg.round_length)
+        .plus_days(1);
+
+    // submit new proposal
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: "proposal title 2".to_string(),
+        description: "proposal description 2".to_string(),
+        deployment_duration: 6,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // try to vote for the second proposal with the second lock id (should not be allowed)
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: second_proposal_id,
+            lock_ids: vec![second_lock_id],
+        }],
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_err());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Not allowed to vote with lock_id 1 in tranche 1. Cannot vote again with this lock_id until round 6."));
+}
+
+#[test]
+fn multi_tranches_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(
+        &deps.api,
+        "addr0000",
+        &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+    );
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.tranches = vec![
+        TrancheInfo {
+            name: "tranche 1".to_string(),
+            metadata: "tranche 1 metadata".to_string(),
+        },
+        TrancheInfo {
+            name: "tranche 2".to_string(),
+            metadata: "tranche 2 metadata".to_string(),
+        },
+    ];
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    // create two proposals for tranche 1
+    let msg1 = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 1,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg1.clone());
+    assert!(res.is_ok());
+
+    let msg2 = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 1,
+        title: "proposal title 2".to_string(),
+        description: "proposal description 2".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg2.clone());
+    assert!(res.is_ok());
+
+    // create two proposals for tranche 2
+    let msg3 = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 2,
+        title: "proposal title 3".to_string(),
+        description: "proposal description 3".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg3.clone());
+    assert!(res.is_ok());
+
+    let msg4 = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 2,
+        title: "proposal title 4".to_string(),
+        description: "proposal description 4".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg4.clone());
+    assert!(res.is_ok());
+
+    // vote with user 1
+    // lock some tokens to get voting power
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let user1_lock_id1 = 0;
+    let user2_lock_id1 = 1;
+    let user3_lock_id1 = 2;
+
+    // vote for the first proposal of tranche 1
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![user1_lock_id1],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // vote for the first proposal of tranche 2
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 2,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 2,
+            lock_ids: vec![user1_lock_id1],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // vote for the second proposal of tranche 2 with a different user, who also locks more toekns
+    let info2 = get_message_info(
+        &deps.api,
+        "addr0001",
+        &[Coin::new(2000u64, IBC_DENOM_1.to_string())],
+    );
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
+    assert!(res.is_ok());
+
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 2,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 2,
+            lock_ids: vec![user2_lock_id1],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // vote for the so-far unvoted proposals with a new user with just 1 token
+    let info3 = get_message_info(
+        &deps.api,
+        "addr0002",
+        &[Coin::new(1u64, IBC_DENOM_1.to_string())],
+    );
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info3.clone(), msg);
+    assert!(res.is_ok());
+
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 1,
+            lock_ids: vec![user3_lock_id1],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info3.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 2,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 3,
+            lock_ids: vec![user3_lock_id1],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info3.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // query voting powers
+    // top proposals for tranche 1
+    // (round 0, tranche 1, show 2 proposals)
+    let res = query_top_n_proposals(deps.as_ref(), 0, 1, 2);
+    assert!(
+        res.is_ok(),
+        "error when querying top n proposals: {:?}",
+        res
+    );
+    let res = res.unwrap().proposals;
+    // check that there are two proposals
+    assert_eq!(2, res.len(), "expected 2 proposals, got {:?}", res);
+    // check that the voting power of the first proposal is 1000
+    assert_eq!(1000, res[0].power.u128());
+    // check that the voting power of the second proposal is 0
+    assert_eq!(1, res[1].power.u128());
+
+    // top proposals for tranche 2
+    // (round 0, tranche 2, show 2 proposals)
+    let res = query_top_n_proposals(deps.as_ref(), 0, 2, 2);
+    assert!(res.is_ok());
+    let res = res.unwrap().proposals;
+    // check that there are two proposals
+    assert_eq!(2, res.len(), "expected 2 proposals, got {:?}", res);
+    // check that the voting power of the first proposal is 3000
+    assert_eq!(3000, res[0].power.u128());
+    // check that the voting power of the second proposal is 0
+    assert_eq!(1, res[1].power.u128());
+}
+
+#[test]
+fn top_n_proposals_tie_break_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    for i in 0..3 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // proposals 1 and 2 get the same power, which is larger than the power of proposal 0
+    for (lock_id, &(proposal_id, amount)) in [(2, 2000u64), (0, 1000u64), (1, 2000u64)]
+        .iter()
+        .enumerate()
+    {
+        let info = get_message_info(&deps.api, "addr0000", &[Coin::new(amount, IBC_DENOM_1)]);
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+
+        let msg = ExecuteMsg::Vote {
+            tranche_id,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id,
+                lock_ids: vec![lock_id as u64],
+            }],
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // the earlier proposal wins the tie, regardless of the order in which the votes were cast
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 3).unwrap();
+    let proposal_ids: Vec<u64> = res.proposals.iter().map(|p| p.proposal_id).collect();
+    assert_eq!(vec![1, 2, 0], proposal_ids);
+    assert_eq!(
+        vec![
+            ProposalOrderingKey {
+                score: Uint128::new(2000),
+                proposal_id: 1,
+            },
+            ProposalOrderingKey {
+                score: Uint128::new(2000),
+                proposal_id: 2,
+            },
+            ProposalOrderingKey {
+                score: Uint128::new(1000),
+                proposal_id: 0,
+            },
+        ],
+        res.ordering_keys
+    );
+
+    // querying only the top proposal returns the earlier one of the tied proposals
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 1).unwrap();
+    assert_eq!(1, res.proposals.len());
+    assert_eq!(1, res.proposals[0].proposal_id);
+}
+
+#[test]
+fn top_n_proposals_percentage_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    for i in 0..3 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // each of the proposals gets a third of the total round power
+    for proposal_id in 0..3 {
+        let info = get_message_info(&deps.api, "addr0000", &[Coin::new(1000u64, IBC_DENOM_1)]);
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+
+        let msg = ExecuteMsg::Vote {
+            tranche_id,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id,
+                lock_ids: vec![proposal_id],
+            }],
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 3).unwrap();
+    assert_eq!(
+        vec![Decimal::from_str("33.333333333333333333").unwrap(); 3],
+        res.percentages
+    );
+    for proposal in res.proposals {
+        assert_eq!(Uint128::new(33), proposal.percentage);
+    }
+}
+
+#[test]
+fn proposal_vote_cap_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![info.sender.to_string()];
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    let create_proposal_msg =
+        |i: u64, max_vote_percentage: Option<Decimal>| ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage,
+        };
+
+    for invalid_cap in [Decimal::zero(), Decimal::percent(10100)] {
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            create_proposal_msg(0, Some(invalid_cap)),
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Maximum vote percentage must be greater than 0 and at most 100"));
+    }
+
+    // the first proposal can count at most 20% of the total round power towards its score
+    for (i, max_vote_percentage) in [Some(Decimal::percent(2000)), None, None]
+        .iter()
+        .enumerate()
+    {
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            create_proposal_msg(i as u64, *max_vote_percentage),
+        );
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    for (proposal_id, amount) in [(0, 3000u64), (1, 1500), (2, 500)] {
+        let info = get_message_info(&deps.api, "addr0000", &[Coin::new(amount, IBC_DENOM_1)]);
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+
+        let msg = ExecuteMsg::Vote {
+            tranche_id,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id,
+                lock_ids: vec![proposal_id],
+            }],
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // the power above 20% of the total round power of 5000 doesn't count towards the score
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(3000), res.proposal.power);
+    assert_eq!(Uint128::new(1000), res.capped_power);
+
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 1).unwrap();
+    assert_eq!(1, res.proposals[0].proposal_id);
+
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 3).unwrap();
+    assert_eq!(
+        vec![(1, 1500u128), (0, 1000), (2, 500)],
+        res.ordering_keys
+            .iter()
+            .map(|key| (key.proposal_id, key.score.u128()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Uint128::new(3000), res.proposals[1].power);
+
+    // the percentages follow the capped scores rather than the powers
+    assert_eq!(
+        vec![
+            Decimal::percent(3000),
+            Decimal::percent(2000),
+            Decimal::percent(1000)
+        ],
+        res.percentages
+    );
+    assert_eq!(Uint128::new(20), res.proposals[1].percentage);
+
+    // only whitelist admins can change the vote cap of a proposal
+    let remove_cap_msg = ExecuteMsg::SetProposalVoteCap {
+        round_id: 0,
+        tranche_id,
+        proposal_id: 0,
+        max_vote_percentage: None,
+    };
+    let other_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        other_info,
+        remove_cap_msg.clone(),
+    );
+    assert!(res.is_err());
+
+    let res = execute(deps.as_mut(), env.clone(), info, remove_cap_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 1).unwrap();
+    assert_eq!(0, res.proposals[0].proposal_id);
+    assert_eq!(Uint128::new(3000), res.ordering_keys[0].score);
+}
+
+#[test]
+fn test_query_round_tranche_proposals_pagination() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(
+        &deps.api,
+        "addr0000",
+        &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+    );
+    let msg = get_default_instantiate_msg(&deps.api);
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    // Create multiple proposals
+    let num_proposals = 5;
+    for i in 0..num_proposals {
+        let create_proposal_msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id: 1,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            create_proposal_msg,
+        )
+        .unwrap();
+    }
+
+    // Define test cases for start_after and limit with expected results
+    let test_cases = vec![
+        ((0, 2), vec![0, 1]), // Start from the beginning and get 2 elements -> we expect element 0 and 1
+        ((0, 2), vec![0, 1]), // Start from the beginning and get 2 elements -> we expect element 0 and 1
+        ((2, 2), vec![2, 3]), // Start from the second element, limit 2 -> we expect element 2 and 3
+        ((4, 2), vec![4]),    // Start from the last element, limit 2 -> we expect element 4
+        ((0, 5), vec![0, 1, 2, 3, 4]), // get the whole list -> we expect all elements
+        ((0, 10), vec![0, 1, 2, 3, 4]), // get the whole list and the limit is even bigger -> we expect all elements
+        ((2, 5), vec![2, 3, 4]), // Start from the middle, limit 5 -> we expect elements 2, 3, and 4
+        ((4, 5), vec![4]),       // Start from the end, limit 5 -> we expect element 4
+        ((5, 2), vec![]),        // start after the list is over -> we expect an empty list
+        ((0, 0), vec![]),        // limit to 0 -> we expect an empty list
+    ];
+
+    // Test pagination for different start_after and limit values
+    for ((start_after, limit), expected_proposals) in test_cases {
+        let response =
+            query_round_tranche_proposals(deps.as_ref(), 0, 1, start_after, None, limit).unwrap();
+
+        // Check that pagination works correctly
+        let proposals = response.proposals;
+        assert_eq!(proposals.len(), expected_proposals.len());
+        for (proposal, expected_proposal) in proposals.iter().zip(expected_proposals.iter()) {
+            assert_eq!(
+                proposal.title,
+                format!("proposal title {}", *expected_proposal)
+            );
+        }
+    }
+}
+
+#[test]
+fn duplicate_tranche_name_test() {
+    // try to instantiate the contract with two tranches with the same name
+    // this should fail
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.tranches = vec![
+        TrancheInfo {
+            name: "tranche 1".to_string(),
+            metadata: "tranche 1 metadata".to_string(),
+        },
+        TrancheInfo {
+            name: "tranche 1".to_string(),
+            metadata: "tranche 2 metadata".to_string(),
+        },
+    ];
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_err());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .to_lowercase()
+        .contains("duplicate tranche name"));
+}
+
+#[test]
+fn add_edit_tranche_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let admin_info = get_message_info(&deps.api, "addr0000", &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.tranches = vec![
+        TrancheInfo {
+            name: "tranche 1".to_string(),
+            metadata: "tranche 1 metadata".to_string(),
+        },
+        TrancheInfo {
+            name: "tranche 2".to_string(),
+            metadata: "tranche 2 metadata".to_string(),
+        },
+    ];
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let tranches = query_tranches(deps.as_ref());
+    assert_eq!(tranches.unwrap().tranches.len(), 2);
+
+    // verify that only whitelist admins can add new tranches
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let msg = ExecuteMsg::AddTranche {
+        tranche: TrancheInfo {
+            name: "tranche 2".to_string(),
+            metadata: "tranche 2 metadata".to_string(),
+        },
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), non_admin_info.clone(), msg);
+    assert!(res.is_err());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .to_lowercase()
+        .contains("unauthorized"));
+
+    // verify that the new tranche name must be unique
+    let msg = ExecuteMsg::AddTranche {
+        tranche: TrancheInfo {
+            name: "tranche 2".to_string(),
+            metadata: "tranche 3 metadata".to_string(),
+        },
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_err());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .to_lowercase()
+        .contains("tranche with the given name already exists"));
+
+    // verify that a valid new tranche can be added
+    let new_tranche_name = String::from("tranche 3");
+    let new_tranche_metadata = String::from("tranche 3 metadata");
+
+    let msg = ExecuteMsg::AddTranche {
+        tranche: TrancheInfo {
+            name: new_tranche_name.clone(),
+            metadata: new_tranche_metadata.clone(),
+        },
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_ok());
+
+    let tranches = query_tranches(deps.as_ref()).unwrap().tranches;
+    assert_eq!(tranches.len(), 3);
+
+    let new_tranche = tranches[2].clone();
+    assert_eq!(new_tranche.id, 3);
+    assert_eq!(new_tranche.name, new_tranche_name);
+    assert_eq!(new_tranche.metadata, new_tranche_metadata);
+
+    // verify that only whitelist admins can edit tranches
+    let msg = ExecuteMsg::EditTranche {
+        tranche_id: 3,
+        tranche_name: Some("tranche 3".to_string()),
+        tranche_metadata: Some("tranche 3 metadata".to_string()),
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), non_admin_info, msg.clone());
+    assert!(res.is_err());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .to_lowercase()
+        .contains("unauthorized"));
+
+    // verify that tranche name and metadata gets updated
+    let updated_tranche_name = "tranche 3 updated".to_string();
+    let updated_tranche_metadata = "tranche 3 metadata updated".to_string();
+    let msg = ExecuteMsg::EditTranche {
+        tranche_id: 3,
+        tranche_name: Some(updated_tranche_name.clone()),
+        tranche_metadata: Some(updated_tranche_metadata.clone()),
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_ok());
+
+    let tranches = query_tranches(deps.as_ref()).unwrap().tranches;
+    assert_eq!(tranches.len(), 3);
+
+    let updated_tranche = tranches[2].clone();
+    assert_eq!(updated_tranche.id, 3);
+    assert_eq!(updated_tranche.name, updated_tranche_name);
+    assert_eq!(updated_tranche.metadata, updated_tranche_metadata);
+}
+
+#[test]
+fn test_round_id_computation() {
+    let test_cases: Vec<(u64, u64, u64, StdResult<u64>)> = vec![
+        (
+            0,     // contract start time
+            1000,  // round length
+            500,   // current time
+            Ok(0), // expected round_id
+        ),
+        (
+            1000,  // contract start time
+            1000,  // round length
+            1500,  // current time
+            Ok(0), // expected round_id
+        ),
+        (
+            0,     // contract start time
+            1000,  // round length
+            2500,  // current time
+            Ok(2), // expected round_id
+        ),
+        (
+            0,     // contract start time
+            2000,  // round length
+            6000,  // current time
+            Ok(3), // expected round_id
+        ),
+        (
+            10000, // contract start time
+            5000,  // round length
+            12000, // current time
+            Ok(0), // expected round_id
+        ),
+        (
+            3000,                                                              // contract start time
+            1000,                                                              // round length
+            2000,                                                              // current time
+            Err(StdError::generic_err("The first round has not started yet")), // expected error
+        ),
+    ];
+
+    for (contract_start_time, round_length, current_time, expected_round_id) in test_cases {
+        // instantiate the contract
+        let mut deps = mock_dependencies(no_op_grpc_query_mock());
+        let mut msg = get_default_instantiate_msg(&deps.api);
+        msg.round_length = round_length;
+        msg.first_round_start = Timestamp::from_nanos(contract_start_time);
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_nanos(contract_start_time);
+        let info = get_message_info(&deps.api, "addr0000", &[]);
+        let _ = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+
+        // set the time to the current time
+        env.block.time = Timestamp::from_nanos(current_time);
+
+        let constants = query_constants(deps.as_ref());
+        assert!(constants.is_ok());
+
+        let round_id = compute_current_round_id(&env, &constants.unwrap().constants);
+        assert_eq!(expected_round_id, round_id);
+    }
+}
+
+#[test]
+fn total_voting_power_tracking_test() {
+    let user_address = "addr0000";
+
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, user_address, &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+
+    // align round length with lock epoch length for easier calculations
+    msg.round_length = ONE_MONTH_IN_NANO_SECONDS;
+
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg.clone());
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let info1 = get_message_info(
+        &deps.api,
+        user_address,
+        &[Coin::new(10u64, IBC_DENOM_1.to_string())],
+    );
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info1.clone(), msg);
+    assert!(res.is_ok());
+
+    // user locks 10 tokens for one month, so it will have 1x voting power in the first round only
+    let expected_total_voting_powers = [(0, 10), (1, 0)];
+    verify_expected_voting_power(deps.as_ref(), &expected_total_voting_powers);
+
+    // advance the chain by 10 days and have user lock more tokens
+    env.block.time = env.block.time.plus_nanos(10 * ONE_DAY_IN_NANO_SECONDS);
+
+    let info2 = get_message_info(
+        &deps.api,
+        user_address,
+        &[Coin::new(20u64, IBC_DENOM_1.to_string())],
+    );
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
+    assert!(res.is_ok());
+
+    // user locks 20 additional tokens for three months, so the expectation is:
+    // round:         0      1       2       3
+    // power:       10+30   0+25    0+20    0+0
+    let expected_total_voting_powers = [(0, 40), (1, 25), (2, 20), (3, 0)];
+    verify_expected_voting_power(deps.as_ref(), &expected_total_voting_powers);
+
+    // advance the chain by 25 more days to move to round 1 and have user refresh second lockup to 6 months
+    env.block.time = env.block.time.plus_nanos(25 * ONE_DAY_IN_NANO_SECONDS);
+
+    let info3 = get_message_info(&deps.api, user_address, &[]);
+    let msg = ExecuteMsg::RefreshLockDuration {
+        lock_ids: vec![1],
+        lock_duration: 2 * THREE_MONTHS_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info3.clone(), msg);
+    assert!(res.is_ok());
+
+    // user relocks second lockup worth 20 tokens for six months in round 1, so the expectation is (note that round 0 is not affected):
+    // round:         0       1       2       3       4       5       6       7
+    // power:       10+30    0+40    0+40    0+40    0+30    0+25    0+20    0+0
+    let expected_total_voting_powers = [
+        (0, 40),
+        (1, 40),
+        (2, 40),
+        (3, 40),
+        (4, 30),
+        (5, 25),
+        (6, 20),
+        (7, 0),
+    ];
+    verify_expected_voting_power(deps.as_ref(), &expected_total_voting_powers);
+
+    // advance the chain by 5 more days and have user lock 50 more tokens for three months
+    env.block.time = env.block.time.plus_nanos(5 * ONE_DAY_IN_NANO_SECONDS);
+
+    let info2 = get_message_info(
+        &deps.api,
+        user_address,
+        &[Coin::new(50u64, IBC_DENOM_1.to_string())],
+    );
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
+    assert!(res.is_ok());
+
+    // user locks 50 additional tokens in round 1 for three months, so the expectation is (note that round 0 is not affected):
+    // round:         0        1          2          3          4         5         6         7
+    // power:       10+30    0+40+75    0+40+62    0+40+50    0+30+0    0+25+0    0+20+0    0+0+0
+    let expected_total_voting_powers = [
+        (0, 40),
+        (1, 115),
+        (2, 102),
+        (3, 90),
+        (4, 30),
+        (5, 25),
+        (6, 20),
+        (7, 0),
+    ];
+    verify_expected_voting_power(deps.as_ref(), &expected_total_voting_powers);
+}
+
+fn verify_expected_voting_power(deps: Deps<NeutronQuery>, expected_powers: &[(u64, u128)]) {
+    for expected_power in expected_powers {
+        let res = query_round_total_power(deps, mock_env(), expected_power.0);
+
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(expected_power.1, res.total_voting_power.u128());
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(100))] // set the number of test cases to run
+    #[test]
+    fn relock_proptest(old_lock_remaining_time: u64, new_lock_duration: u8) {
+        let grpc_query = denom_trace_grpc_query_mock(
+            "transfer/channel-0".to_string(),
+            HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+        );
+
+        let (mut deps, mut env) = (
+            mock_dependencies(grpc_query),
+            mock_env(),
+        );
+        let info = get_message_info(&deps.api, "addr0001", &[Coin::new(1000u64, IBC_DENOM_1.to_string())]);
+        let msg = get_default_instantiate_msg(&deps.api);
+
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+        assert!(res.is_ok());
+
+        set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+        // get the new lock duration
+        // list of plausible values, plus a value that should give an error every time (0)
+        let possible_lock_durations = [0, ONE_MONTH_IN_NANO_SECONDS, ONE_MONTH_IN_NANO_SECONDS * 3, ONE_MONTH_IN_NANO_SECONDS * 6, ONE_MONTH_IN_NANO_SECONDS * 12];
+        let new_lock_duration = possible_lock_durations[new_lock_duration as usize % possible_lock_durations.len()];
+
+        // old lock remaining time must be at most 12 months, so we take the modulo
+        let old_lock_remaining_time = old_lock_remaining_time % (ONE_MONTH_IN_NANO_SECONDS * 12);
+
+        // lock the tokens for 12 months
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS * 12,
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok());
+
+        // set the time so that old_lock_remaining_time remains on the old lock
+        env.block.time = env.block.time.plus_nanos(12 * ONE_MONTH_IN_NANO_SECONDS - old_lock_remaining_time);
+
+        // try to refresh the lock duration as a different user
+        let info2 = get_message_info(&deps.api, "addr0002", &[]);
+        let msg = ExecuteMsg::RefreshLockDuration {
+            lock_ids: vec![0],
+            lock_duration: new_lock_duration,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
+
+        // different user cannot refresh the lock
+        assert!(res.is_err(), "different user should not be able to refresh the lock: {:?}", res);
+
+        // refresh the lock duration
+        let info = get_message_info(&deps.api, "addr0001", &[]);
+        let msg = ExecuteMsg::RefreshLockDuration {
+            lock_ids: vec![0],
+            lock_duration: new_lock_duration,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+
+        // if we try to refresh the lock with a duration of 0, it should fail
+        if new_lock_duration == 0 {
+            assert!(res.is_err());
+            return Ok(()); // end the test
+        }
+
+        // if we tried to make the lock_end sooner, it should fail
+        if new_lock_duration < old_lock_remaining_time {
+            assert!(res.is_err());
+            return Ok(()); // end the test
+        }
+
+        // otherwise, succeed
+        assert!(res.is_ok());
+    }
+}
+
+#[test]
+fn quadratic_power_scaling_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let admin_info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+
+    // only whitelist admins can change the power scaling of a tranche
+    let scaling_msg = ExecuteMsg::SetTranchePowerScaling {
+        tranche_id,
+        quadratic: true,
+    };
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        non_admin_info,
+        scaling_msg.clone(),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), scaling_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // the change takes effect starting from the next round
+    let res = query_tranche_power_scaling(deps.as_ref(), tranche_id, 0).unwrap();
+    assert!(!res.quadratic);
+    let res = query_tranche_power_scaling(deps.as_ref(), tranche_id, 1).unwrap();
+    assert!(res.quadratic);
+
+    env.block.time = env.block.time.plus_nanos(msg.round_length);
+    let round_id = 1;
+
+    for i in 0..2 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let lock_msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let lock_tokens = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+                       sender: &str,
+                       amount: u64| {
+        let info = get_message_info(&deps.api, sender, &[Coin::new(amount, IBC_DENOM_1)]);
+        let res = execute(deps.as_mut(), env.clone(), info, lock_msg.clone());
+        assert!(res.is_ok(), "error: {:?}", res);
+    };
+
+    // user 1 gets locks 0 and 1, user 2 gets lock 2
+    lock_tokens(&mut deps, "addr0000", 1000);
+    lock_tokens(&mut deps, "addr0000", 1000);
+    lock_tokens(&mut deps, "addr0001", 100);
+
+    let vote = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+                sender: &str,
+                proposal_id: u64,
+                lock_ids: Vec<u64>| {
+        let info = get_message_info(&deps.api, sender, &[]);
+        let msg = ExecuteMsg::Vote {
+            tranche_id,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id,
+                lock_ids,
+            }],
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    };
+    let proposal_power = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+                          proposal_id: u64| {
+        query_proposal(deps.as_ref(), round_id, tranche_id, proposal_id)
+            .unwrap()
+            .proposal
+            .power
+            .u128()
+    };
+
+    // aggregate power of user 1 is 2000, so the proposal gets ceil(sqrt(2000)) = 45
+    vote(&mut deps, "addr0000", 0, vec![0, 1]);
+    assert_eq!(45, proposal_power(&deps, 0));
+
+    vote(&mut deps, "addr0001", 1, vec![2]);
+    assert_eq!(10, proposal_power(&deps, 1));
+
+    // a new lock automatically votes for the only proposal user 1 voted for, so the
+    // aggregate power of user 1 becomes 4000 and the proposal gets ceil(sqrt(4000)) = 64
+    lock_tokens(&mut deps, "addr0000", 2000);
+    assert_eq!(64, proposal_power(&deps, 0));
+
+    // after switching the vote of the new lock, each lock of user 1 is still scaled down by
+    // sqrt(4000)/4000, so the first proposal gets ceil(2000 * sqrt(4000)/4000) = 32, and the
+    // second one gets ceil(10 + 2000 * sqrt(4000)/4000) = 42
+    vote(&mut deps, "addr0000", 1, vec![3]);
+    assert_eq!(32, proposal_power(&deps, 0));
+    assert_eq!(42, proposal_power(&deps, 1));
+
+    // the total power of user 1 across both proposals is sqrt(4000)
+    let res = query_user_votes(
+        deps.as_ref(),
+        round_id,
+        tranche_id,
+        get_address_as_str(&deps.api, "addr0000"),
+    )
+    .unwrap();
+    let user_power = res
+        .votes
+        .iter()
+        .fold(Decimal::zero(), |acc, vote| acc + vote.power);
+    assert_eq!(64, user_power.to_uint_ceil().u128());
+}
+
+#[test]
+fn vote_power_cap_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let admin_info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+
+    // only whitelist admins can set the vote power cap
+    let cap_msg = ExecuteMsg::SetVotePowerCap {
+        max_power_percentage: Some(Decimal::from_ratio(20u128, 1u128)),
+    };
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(deps.as_mut(), env.clone(), non_admin_info, cap_msg.clone());
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        ExecuteMsg::SetVotePowerCap {
+            max_power_percentage: Some(Decimal::zero()),
+        },
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Maximum power percentage must be greater than 0 and at most 100"));
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), cap_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // the cap applies starting from the next round
+    let res = query_vote_power_cap(deps.as_ref(), 0).unwrap();
+    assert!(res.max_power_percentage.is_none());
+    let res = query_vote_power_cap(deps.as_ref(), 1).unwrap();
+    assert_eq!(
+        Some(Decimal::from_ratio(20u128, 1u128)),
+        res.max_power_percentage
+    );
+
+    env.block.time = env.block.time.plus_nanos(msg.round_length);
+    let round_id = 1;
+
+    for i in 0..2 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // user 1 gets locks 0 and 1, the other users get locks 2, 3 and 4, for a total round power of 5000
+    for sender in ["addr0000", "addr0000", "addr0001", "addr0002", "addr0003"] {
+        let info = get_message_info(&deps.api, sender, &[Coin::new(1000u64, IBC_DENOM_1)]);
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let vote = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+                sender: &str,
+                proposal_id: u64,
+                lock_ids: Vec<u64>| {
+        let info = get_message_info(&deps.api, sender, &[]);
+        let msg = ExecuteMsg::Vote {
+            tranche_id,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id,
+                lock_ids,
+            }],
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    };
+    let proposal_power = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+                          proposal_id: u64| {
+        let res = query_proposal(deps.as_ref(), round_id, tranche_id, proposal_id).unwrap();
+        (res.proposal.power.u128(), res.capped_power.u128())
+    };
+
+    // splitting the power across multiple locks doesn't help, since the cap applies to the aggregate
+    // power of the owner: user 1 has 2000 power, but only 20% of 5000 counts
+    vote(&mut deps, "addr0000", 0, vec![0, 1]);
+    assert_eq!((2000, 1000), proposal_power(&deps, 0));
+
+    // owners below the cap are not affected
+    vote(&mut deps, "addr0001", 1, vec![2]);
+    assert_eq!((1000, 1000), proposal_power(&deps, 1));
+
+    // after switching the vote of one of the locks, both locks of user 1 are still scaled down by half
+    vote(&mut deps, "addr0000", 1, vec![1]);
+    assert_eq!((1000, 500), proposal_power(&deps, 0));
+    assert_eq!((2000, 1500), proposal_power(&deps, 1));
+
+    let res = query_top_n_proposals(deps.as_ref(), round_id, tranche_id, 2).unwrap();
+    assert_eq!(
+        vec![Uint128::new(1500), Uint128::new(500)],
+        res.ordering_keys
+            .iter()
+            .map(|ordering_key| ordering_key.score)
+            .collect::<Vec<Uint128>>()
+    );
+
+    // the cap follows the total round power at the time of the tally, without user 1 voting again:
+    // with a total round power of 7500, user 1 can contribute 1500 power
+    for _ in 0..5 {
+        let info = get_message_info(&deps.api, "addr0004", &[Coin::new(500u64, IBC_DENOM_1)]);
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+    assert_eq!((1000, 750), proposal_power(&deps, 0));
+    assert_eq!((2000, 1750), proposal_power(&deps, 1));
+
+    // once user 1 removes the votes, the rest of the votes are not capped anymore
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::UnvoteAll {
+            tranche_id,
+            owner: None,
+        },
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!((0, 0), proposal_power(&deps, 0));
+    assert_eq!((1000, 1000), proposal_power(&deps, 1));
+
+    // the cap can be removed, starting from the next round
+    let msg = ExecuteMsg::SetVotePowerCap {
+        max_power_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), admin_info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_vote_power_cap(deps.as_ref(), 1).unwrap();
+    assert!(res.max_power_percentage.is_some());
+    let res = query_vote_power_cap(deps.as_ref(), 2).unwrap();
+    assert!(res.max_power_percentage.is_none());
+}
+
+#[test]
+fn vote_power_cap_power_ratio_increase_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([
+            (IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string()),
+            (IBC_DENOM_2.to_string(), VALIDATOR_2_LST_DENOM_1.to_string()),
+        ]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let admin_info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    let round_id = 1;
+    let tranche_id = 1;
+
+    for round_id in 0..=round_id {
+        set_validator_power_ratio(deps.as_mut().storage, round_id, VALIDATOR_1, Decimal::one());
+        set_validator_power_ratio(
+            deps.as_mut().storage,
+            round_id,
+            VALIDATOR_2,
+            Decimal::percent(50),
+        );
+    }
+
+    let msg_cap = ExecuteMsg::SetVotePowerCap {
+        max_power_percentage: Some(Decimal::from_ratio(20u128, 1u128)),
+    };
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg_cap);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    env.block.time = env.block.time.plus_nanos(msg.round_length);
+
+    let msg_create_proposal = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
         deployment_duration: 1,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg4.clone());
-    assert!(res.is_ok());
-
-    // vote with user 1
-    // lock some tokens to get voting power
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
-    assert!(res.is_ok());
+    let res = execute(deps.as_mut(), env.clone(), admin_info, msg_create_proposal);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    let user1_lock_id1 = 0;
-    let user2_lock_id1 = 1;
-    let user3_lock_id1 = 2;
+    // user 1 locks with validator 2, which has half the power ratio of validator 1, so it has 500 power
+    // out of the total round power of 3500, below the cap of 700
+    for (sender, denom) in [
+        ("addr0000", IBC_DENOM_2),
+        ("addr0001", IBC_DENOM_1),
+        ("addr0002", IBC_DENOM_1),
+        ("addr0003", IBC_DENOM_1),
+    ] {
+        let info = get_message_info(&deps.api, sender, &[Coin::new(1000u64, denom)]);
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
 
-    // vote for the first proposal of tranche 1
+    let info = get_message_info(&deps.api, "addr0000", &[]);
     let msg = ExecuteMsg::Vote {
-        tranche_id: 1,
+        tranche_id,
         proposals_votes: vec![ProposalToLockups {
             proposal_id: 0,
-            lock_ids: vec![user1_lock_id1],
+            lock_ids: vec![0],
         }],
     };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // vote for the first proposal of tranche 2
-    let msg = ExecuteMsg::Vote {
-        tranche_id: 2,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: 2,
-            lock_ids: vec![user1_lock_id1],
-        }],
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_ok());
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(500), res.proposal.power);
+    assert_eq!(Uint128::new(500), res.capped_power);
 
-    // vote for the second proposal of tranche 2 with a different user, who also locks more toekns
-    let info2 = get_message_info(
-        &deps.api,
-        "addr0001",
-        &[Coin::new(2000u64, IBC_DENOM_1.to_string())],
+    // once the power ratio of validator 2 doubles, user 1 has 1000 power out of 4000, above the cap of 800,
+    // even though the power stored when user 1 voted was below it
+    set_validator_power_ratio(deps.as_mut().storage, round_id, VALIDATOR_2, Decimal::one());
+
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(1000), res.proposal.power);
+    assert_eq!(Uint128::new(800), res.capped_power);
+
+    let res = query_top_n_proposals(deps.as_ref(), round_id, tranche_id, 1).unwrap();
+    assert_eq!(Uint128::new(800), res.ordering_keys[0].score);
+}
+
+#[test]
+fn tribute_weight_bonus_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
-    };
-    let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
-    assert!(res.is_ok());
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let admin_info = get_message_info(&deps.api, "addr0000", &[]);
 
-    let msg = ExecuteMsg::Vote {
-        tranche_id: 2,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: 2,
-            lock_ids: vec![user2_lock_id1],
-        }],
-    };
-    let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg.clone());
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // vote for the so-far unvoted proposals with a new user with just 1 token
-    let info3 = get_message_info(
-        &deps.api,
-        "addr0002",
-        &[Coin::new(1u64, IBC_DENOM_1.to_string())],
-    );
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    let bonus = TributeWeightBonus {
+        min_lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        multiplier: Decimal::from_str("2").unwrap(),
     };
-    let res = execute(deps.as_mut(), env.clone(), info3.clone(), msg);
-    assert!(res.is_ok());
 
-    let msg = ExecuteMsg::Vote {
-        tranche_id: 1,
-        proposals_votes: vec![ProposalToLockups {
-            proposal_id: 1,
-            lock_ids: vec![user3_lock_id1],
-        }],
+    // only whitelist admins can change the tribute weight bonus of a tranche
+    let bonus_msg = ExecuteMsg::SetTrancheTributeWeightBonus {
+        tranche_id,
+        bonus: Some(bonus.clone()),
     };
-    let res = execute(deps.as_mut(), env.clone(), info3.clone(), msg.clone());
-    assert!(res.is_ok());
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        non_admin_info,
+        bonus_msg.clone(),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    // the multiplier must be greater than 1
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        ExecuteMsg::SetTrancheTributeWeightBonus {
+            tranche_id,
+            bonus: Some(TributeWeightBonus {
+                min_lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+                multiplier: Decimal::one(),
+            }),
+        },
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("multiplier must be greater than 1"));
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), bonus_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // the change takes effect starting from the next round
+    let res = query_tranche_tribute_weight_bonus(deps.as_ref(), tranche_id, 0).unwrap();
+    assert_eq!(None, res.bonus);
+    let res = query_tranche_tribute_weight_bonus(deps.as_ref(), tranche_id, 1).unwrap();
+    assert_eq!(Some(bonus), res.bonus);
+
+    env.block.time = env.block.time.plus_nanos(msg.round_length);
+    let round_id = 1;
+
+    for i in 0..2 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // lock 0 remains locked for less than a month after the round end, while lock 1 qualifies for the bonus
+    for (sender, lock_duration) in [
+        ("addr0000", ONE_MONTH_IN_NANO_SECONDS),
+        ("addr0001", THREE_MONTHS_IN_NANO_SECONDS),
+    ] {
+        let info = get_message_info(&deps.api, sender, &[Coin::new(1000u64, IBC_DENOM_1)]);
+        let msg = ExecuteMsg::LockTokens { lock_duration };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    for (sender, proposal_id, lock_id) in [("addr0000", 0, 0), ("addr0001", 1, 1)] {
+        let info = get_message_info(&deps.api, sender, &[]);
+        let msg = ExecuteMsg::Vote {
+            tranche_id,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id,
+                lock_ids: vec![lock_id],
+            }],
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // the vote without the bonus has the tribute weight equal to its power
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, 0).unwrap();
+    assert_eq!(1000, res.proposal.power.u128());
+    assert_eq!(Decimal::from_str("1000").unwrap(), res.tribute_weight);
 
+    let res = query_user_votes(
+        deps.as_ref(),
+        round_id,
+        tranche_id,
+        get_address_as_str(&deps.api, "addr0000"),
+    )
+    .unwrap();
+    assert_eq!(res.votes[0].power, res.votes[0].tribute_weight);
+
+    // the vote with the bonus has its tribute weight doubled, while its power is unchanged
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, 1).unwrap();
+    assert_eq!(1500, res.proposal.power.u128());
+    assert_eq!(Decimal::from_str("3000").unwrap(), res.tribute_weight);
+
+    let res = query_user_votes(
+        deps.as_ref(),
+        round_id,
+        tranche_id,
+        get_address_as_str(&deps.api, "addr0001"),
+    )
+    .unwrap();
+    assert_eq!(Decimal::from_str("1500").unwrap(), res.votes[0].power);
+    assert_eq!(
+        Decimal::from_str("3000").unwrap(),
+        res.votes[0].tribute_weight
+    );
+
+    // the bonus is removed from the proposal together with the vote
+    let info = get_message_info(&deps.api, "addr0001", &[]);
     let msg = ExecuteMsg::Vote {
-        tranche_id: 2,
+        tranche_id,
         proposals_votes: vec![ProposalToLockups {
-            proposal_id: 3,
-            lock_ids: vec![user3_lock_id1],
+            proposal_id: 0,
+            lock_ids: vec![1],
         }],
     };
-    let res = execute(deps.as_mut(), env.clone(), info3.clone(), msg.clone());
-    assert!(res.is_ok());
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // query voting powers
-    // top proposals for tranche 1
-    // (round 0, tranche 1, show 2 proposals)
-    let res = query_top_n_proposals(deps.as_ref(), 0, 1, 2);
-    assert!(
-        res.is_ok(),
-        "error when querying top n proposals: {:?}",
-        res
-    );
-    let res = res.unwrap().proposals;
-    // check that there are two proposals
-    assert_eq!(2, res.len(), "expected 2 proposals, got {:?}", res);
-    // check that the voting power of the first proposal is 1000
-    assert_eq!(1000, res[0].power.u128());
-    // check that the voting power of the second proposal is 0
-    assert_eq!(1, res[1].power.u128());
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, 1).unwrap();
+    assert_eq!(0, res.proposal.power.u128());
+    assert_eq!(Decimal::zero(), res.tribute_weight);
 
-    // top proposals for tranche 2
-    // (round 0, tranche 2, show 2 proposals)
-    let res = query_top_n_proposals(deps.as_ref(), 0, 2, 2);
-    assert!(res.is_ok());
-    let res = res.unwrap().proposals;
-    // check that there are two proposals
-    assert_eq!(2, res.len(), "expected 2 proposals, got {:?}", res);
-    // check that the voting power of the first proposal is 3000
-    assert_eq!(3000, res[0].power.u128());
-    // check that the voting power of the second proposal is 0
-    assert_eq!(1, res[1].power.u128());
+    let res = query_proposal(deps.as_ref(), round_id, tranche_id, 0).unwrap();
+    assert_eq!(2500, res.proposal.power.u128());
+    assert_eq!(Decimal::from_str("4000").unwrap(), res.tribute_weight);
 }
 
 #[test]
-fn test_query_round_tranche_proposals_pagination() {
-    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
-    let info = get_message_info(
-        &deps.api,
-        "addr0000",
-        &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+fn invalidate_proposal_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
-    let msg = get_default_instantiate_msg(&deps.api);
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let admin_info = get_message_info(&deps.api, "addr0000", &[]);
 
-    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg.clone());
     assert!(res.is_ok());
 
-    // Create multiple proposals
-    let num_proposals = 5;
-    for i in 0..num_proposals {
-        let create_proposal_msg = ExecuteMsg::CreateProposal {
-            round_id: None,
-            tranche_id: 1,
-            title: format!("proposal title {}", i),
-            description: format!("proposal description {}", i),
-            deployment_duration: 1,
-            minimum_atom_liquidity_request: Uint128::zero(),
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+
+    let create_proposal =
+        |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+         env: &Env,
+         deployment_duration: u64| {
+            let msg = ExecuteMsg::CreateProposal {
+                round_id: None,
+                tranche_id,
+                title: "proposal title".to_string(),
+                description: "proposal description".to_string(),
+                deployment_duration,
+                minimum_atom_liquidity_request: Uint128::zero(),
+                description_hash: None,
+                description_uri: None,
+                max_vote_percentage: None,
+            };
+            let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+            assert!(res.is_ok(), "error: {:?}", res);
         };
-        let _ = execute(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            create_proposal_msg,
-        )
-        .unwrap();
+    let vote = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+                env: &Env,
+                sender: &str,
+                proposal_id: u64,
+                lock_ids: Vec<u64>| {
+        let info = get_message_info(&deps.api, sender, &[]);
+        let msg = ExecuteMsg::Vote {
+            tranche_id,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id,
+                lock_ids,
+            }],
+        };
+        execute(deps.as_mut(), env.clone(), info, msg)
+    };
+
+    // proposals 0 and 1 are deployed for 3 rounds
+    create_proposal(&mut deps, &env, 3);
+    create_proposal(&mut deps, &env, 3);
+
+    // user 1 gets lock 0, user 2 gets lock 1
+    for sender in ["addr0001", "addr0002"] {
+        let info = get_message_info(&deps.api, sender, &[Coin::new(1000u64, IBC_DENOM_1)]);
+        let lock_msg = ExecuteMsg::LockTokens {
+            lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, lock_msg);
+        assert!(res.is_ok(), "error: {:?}", res);
     }
 
-    // Define test cases for start_after and limit with expected results
-    let test_cases = vec![
-        ((0, 2), vec![0, 1]), // Start from the beginning and get 2 elements -> we expect element 0 and 1
-        ((0, 2), vec![0, 1]), // Start from the beginning and get 2 elements -> we expect element 0 and 1
-        ((2, 2), vec![2, 3]), // Start from the second element, limit 2 -> we expect element 2 and 3
-        ((4, 2), vec![4]),    // Start from the last element, limit 2 -> we expect element 4
-        ((0, 5), vec![0, 1, 2, 3, 4]), // get the whole list -> we expect all elements
-        ((0, 10), vec![0, 1, 2, 3, 4]), // get the whole list and the limit is even bigger -> we expect all elements
-        ((2, 5), vec![2, 3, 4]), // Start from the middle, limit 5 -> we expect elements 2, 3, and 4
-        ((4, 5), vec![4]),       // Start from the end, limit 5 -> we expect element 4
-        ((5, 2), vec![]),        // start after the list is over -> we expect an empty list
-        ((0, 0), vec![]),        // limit to 0 -> we expect an empty list
-    ];
+    let res = vote(&mut deps, &env, "addr0001", 0, vec![0]);
+    assert!(res.is_ok(), "error: {:?}", res);
+    let res = vote(&mut deps, &env, "addr0002", 1, vec![1]);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let invalidate_msg = |round_id: u64, reason: &str| ExecuteMsg::InvalidateProposal {
+        round_id,
+        tranche_id,
+        proposal_id: 0,
+        reason: reason.to_string(),
+    };
+
+    // only whitelist admins can invalidate proposals
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        non_admin_info,
+        invalidate_msg(0, "fraudulent proposal"),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        invalidate_msg(0, " "),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Reason for invalidating the proposal must be provided"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        invalidate_msg(1, "fraudulent proposal"),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Only proposals from the current round can be invalidated"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        invalidate_msg(0, "fraudulent proposal"),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        invalidate_msg(0, "fraudulent proposal"),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("has already been invalidated"));
+
+    let res = query_invalidated_proposals(deps.as_ref(), 0, tranche_id).unwrap();
+    assert_eq!(1, res.proposals.len());
+    assert_eq!(0, res.proposals[0].proposal_id);
+    assert_eq!("fraudulent proposal", res.proposals[0].reason);
 
-    // Test pagination for different start_after and limit values
-    for ((start_after, limit), expected_proposals) in test_cases {
-        let response =
-            query_round_tranche_proposals(deps.as_ref(), 0, 1, start_after, limit).unwrap();
+    // the invalidated proposal is no longer among the top proposals
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 2).unwrap();
+    assert_eq!(
+        vec![1],
+        res.proposals
+            .iter()
+            .map(|proposal| proposal.proposal_id)
+            .collect::<Vec<u64>>()
+    );
 
-        // Check that pagination works correctly
-        let proposals = response.proposals;
-        assert_eq!(proposals.len(), expected_proposals.len());
-        for (proposal, expected_proposal) in proposals.iter().zip(expected_proposals.iter()) {
-            assert_eq!(
-                proposal.title,
-                format!("proposal title {}", *expected_proposal)
-            );
-        }
-    }
-}
+    // the invalidated proposal can't receive any new votes
+    let res = vote(&mut deps, &env, "addr0002", 0, vec![1]);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("has been invalidated and can't be voted for"));
 
-#[test]
-fn duplicate_tranche_name_test() {
-    // try to instantiate the contract with two tranches with the same name
-    // this should fail
-    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
-    let info = get_message_info(&deps.api, "addr0000", &[]);
-    let mut msg = get_default_instantiate_msg(&deps.api);
-    msg.tranches = vec![
-        TrancheInfo {
-            name: "tranche 1".to_string(),
-            metadata: "tranche 1 metadata".to_string(),
-        },
-        TrancheInfo {
-            name: "tranche 1".to_string(),
-            metadata: "tranche 2 metadata".to_string(),
-        },
-    ];
+    // a new lock doesn't automatically vote for the invalidated proposal
+    let info = get_message_info(&deps.api, "addr0001", &[Coin::new(1000u64, IBC_DENOM_1)]);
+    let lock_msg = ExecuteMsg::LockTokens {
+        lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, lock_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+    // the power of the invalidated proposal is still the one given by lock 0 (1000 tokens scaled by 1.5)
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(1500), res.proposal.power);
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 2).unwrap();
+    assert_eq!(1, res.proposals.len());
 
-    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert!(res.is_err());
+    // in the next round, the lock that voted for the invalidated proposal can vote right away,
+    // while the lock that voted for the other proposal still has to wait until the deployment ends
+    env.block.time = env.block.time.plus_nanos(msg.round_length);
+    create_proposal(&mut deps, &env, 1);
+
+    let res = vote(&mut deps, &env, "addr0001", 2, vec![0]);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = vote(&mut deps, &env, "addr0002", 2, vec![1]);
     assert!(res
         .unwrap_err()
         .to_string()
-        .to_lowercase()
-        .contains("duplicate tranche name"));
+        .contains("Cannot vote again with this lock_id until round 3"));
 }
 
 #[test]
-fn add_edit_tranche_test() {
-    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+fn prune_round_data_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
     let admin_info = get_message_info(&deps.api, "addr0000", &[]);
+
     let mut msg = get_default_instantiate_msg(&deps.api);
-    msg.tranches = vec![
-        TrancheInfo {
-            name: "tranche 1".to_string(),
-            metadata: "tranche 1 metadata".to_string(),
-        },
-        TrancheInfo {
-            name: "tranche 2".to_string(),
-            metadata: "tranche 2 metadata".to_string(),
-        },
-    ];
     msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg.clone());
+    assert!(res.is_ok());
 
-    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    let msg_create_proposal = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        msg_create_proposal,
+    );
     assert!(res.is_ok(), "error: {:?}", res);
 
-    let tranches = query_tranches(deps.as_ref());
-    assert_eq!(tranches.unwrap().tranches.len(), 2);
+    let info = get_message_info(&deps.api, "addr0001", &[Coin::new(1000u64, IBC_DENOM_1)]);
+    let lock_msg = ExecuteMsg::LockTokens {
+        lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), lock_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // verify that only whitelist admins can add new tranches
-    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
-    let msg = ExecuteMsg::AddTranche {
-        tranche: TrancheInfo {
-            name: "tranche 2".to_string(),
-            metadata: "tranche 2 metadata".to_string(),
-        },
+    let vote_msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![0],
+        }],
     };
+    let res = execute(deps.as_mut(), env.clone(), info, vote_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    let res = execute(deps.as_mut(), env.clone(), non_admin_info.clone(), msg);
-    assert!(res.is_err());
+    let prune_msg = |limit: u32| ExecuteMsg::PruneRoundData { round_id: 0, limit };
+
+    // the data of the most recent rounds is retained
+    env.block.time = env
+        .block
+        .time
+        .plus_nanos(ROUND_DATA_RETENTION_ROUNDS * TWO_WEEKS_IN_NANO_SECONDS);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        prune_msg(10),
+    );
     assert!(res
         .unwrap_err()
         .to_string()
-        .to_lowercase()
-        .contains("unauthorized"));
+        .contains("Data of round 0 is still retained"));
 
-    // verify that the new tranche name must be unique
-    let msg = ExecuteMsg::AddTranche {
-        tranche: TrancheInfo {
-            name: "tranche 2".to_string(),
-            metadata: "tranche 3 metadata".to_string(),
-        },
-    };
+    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS);
 
-    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
-    assert!(res.is_err());
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(deps.as_mut(), env.clone(), non_admin_info, prune_msg(10));
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), prune_msg(0));
     assert!(res
         .unwrap_err()
         .to_string()
-        .to_lowercase()
-        .contains("tranche with the given name already exists"));
-
-    // verify that a valid new tranche can be added
-    let new_tranche_name = String::from("tranche 3");
-    let new_tranche_metadata = String::from("tranche 3 metadata");
+        .contains("Limit must be greater than zero"));
 
-    let msg = ExecuteMsg::AddTranche {
-        tranche: TrancheInfo {
-            name: new_tranche_name.clone(),
-            metadata: new_tranche_metadata.clone(),
-        },
+    // rounds that still hold tributes can't be pruned
+    let tribute_contract = deps.api.addr_make("tribute");
+    let msg = ExecuteMsg::SetTributeContract {
+        address: Some(tribute_contract.to_string()),
     };
-
     let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
-    assert!(res.is_ok());
-
-    let tranches = query_tranches(deps.as_ref()).unwrap().tranches;
-    assert_eq!(tranches.len(), 3);
-
-    let new_tranche = tranches[2].clone();
-    assert_eq!(new_tranche.id, 3);
-    assert_eq!(new_tranche.name, new_tranche_name);
-    assert_eq!(new_tranche.metadata, new_tranche_metadata);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    // verify that only whitelist admins can edit tranches
-    let msg = ExecuteMsg::EditTranche {
-        tranche_id: 3,
-        tranche_name: Some("tranche 3".to_string()),
-        tranche_metadata: Some("tranche 3 metadata".to_string()),
+    let mock_round_tributes = |refunded: bool| {
+        move |_: &WasmQuery| {
+            SystemResult::Ok(ContractResult::Ok(Binary::from(
+                format!(
+                    r#"{{"tributes":[{{"round_id":0,"tranche_id":1,"proposal_id":0,"tribute_id":0,"refunded":{}}}]}}"#,
+                    refunded
+                )
+                .as_bytes(),
+            )))
+        }
     };
+    deps.querier = deps.querier.with_wasm_handler(mock_round_tributes(false));
 
-    let res = execute(deps.as_mut(), env.clone(), non_admin_info, msg.clone());
-    assert!(res.is_err());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        prune_msg(10),
+    );
     assert!(res
         .unwrap_err()
         .to_string()
-        .to_lowercase()
-        .contains("unauthorized"));
-
-    // verify that tranche name and metadata gets updated
-    let updated_tranche_name = "tranche 3 updated".to_string();
-    let updated_tranche_metadata = "tranche 3 metadata updated".to_string();
-    let msg = ExecuteMsg::EditTranche {
-        tranche_id: 3,
-        tranche_name: Some(updated_tranche_name.clone()),
-        tranche_metadata: Some(updated_tranche_metadata.clone()),
-    };
+        .contains("Round 0 still holds tribute 0"));
 
-    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
-    assert!(res.is_ok());
+    // once the tributes are refunded, the round can be pruned in batches
+    deps.querier = deps.querier.with_wasm_handler(mock_round_tributes(true));
 
-    let tranches = query_tranches(deps.as_ref()).unwrap().tranches;
-    assert_eq!(tranches.len(), 3);
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), prune_msg(2)).unwrap();
+    assert!(res.attributes.contains(&attr("removed_entries", "2")));
+    assert!(res.attributes.contains(&attr("completed", "false")));
 
-    let updated_tranche = tranches[2].clone();
-    assert_eq!(updated_tranche.id, 3);
-    assert_eq!(updated_tranche.name, updated_tranche_name);
-    assert_eq!(updated_tranche.metadata, updated_tranche_metadata);
-}
+    let res = query_round_data_pruning(deps.as_ref(), 0).unwrap();
+    assert_eq!(
+        Some(RoundDataPruning {
+            removed_entries: 2,
+            completed: false,
+            last_processed_proposal: None,
+            last_processed_template: None,
+        }),
+        res.pruning
+    );
 
-#[test]
-fn test_round_id_computation() {
-    let test_cases: Vec<(u64, u64, u64, StdResult<u64>)> = vec![
-        (
-            0,     // contract start time
-            1000,  // round length
-            500,   // current time
-            Ok(0), // expected round_id
-        ),
-        (
-            1000,  // contract start time
-            1000,  // round length
-            1500,  // current time
-            Ok(0), // expected round_id
-        ),
-        (
-            0,     // contract start time
-            1000,  // round length
-            2500,  // current time
-            Ok(2), // expected round_id
-        ),
-        (
-            0,     // contract start time
-            2000,  // round length
-            6000,  // current time
-            Ok(3), // expected round_id
-        ),
-        (
-            10000, // contract start time
-            5000,  // round length
-            12000, // current time
-            Ok(0), // expected round_id
-        ),
-        (
-            3000,                                                              // contract start time
-            1000,                                                              // round length
-            2000,                                                              // current time
-            Err(StdError::generic_err("The first round has not started yet")), // expected error
-        ),
-    ];
+    // the tributes are only checked before the pruning of the round starts
+    deps.querier = deps.querier.with_wasm_handler(mock_round_tributes(false));
 
-    for (contract_start_time, round_length, current_time, expected_round_id) in test_cases {
-        // instantiate the contract
-        let mut deps = mock_dependencies(no_op_grpc_query_mock());
-        let mut msg = get_default_instantiate_msg(&deps.api);
-        msg.round_length = round_length;
-        msg.first_round_start = Timestamp::from_nanos(contract_start_time);
+    // the visited proposal counts towards the limit, next to its removed shares and the round voter
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), prune_msg(3)).unwrap();
+    assert!(res.attributes.contains(&attr("removed_entries", "3")));
+    assert!(res.attributes.contains(&attr("completed", "false")));
 
-        let mut env = mock_env();
-        env.block.time = Timestamp::from_nanos(contract_start_time);
-        let info = get_message_info(&deps.api, "addr0000", &[]);
-        let _ = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    let res = query_round_data_pruning(deps.as_ref(), 0).unwrap();
+    assert_eq!(
+        Some(RoundDataPruning {
+            removed_entries: 5,
+            completed: false,
+            last_processed_proposal: Some((tranche_id, 0)),
+            last_processed_template: None,
+        }),
+        res.pruning
+    );
 
-        // set the time to the current time
-        env.block.time = Timestamp::from_nanos(current_time);
+    // the pruning resumes after the last visited proposal
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        prune_msg(100),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("removed_entries", "3")));
+    assert!(res.attributes.contains(&attr("completed", "true")));
+
+    let res = query_round_data_pruning(deps.as_ref(), 0).unwrap();
+    assert_eq!(
+        Some(RoundDataPruning {
+            removed_entries: 8,
+            completed: true,
+            last_processed_proposal: Some((tranche_id, 0)),
+            last_processed_template: None,
+        }),
+        res.pruning
+    );
 
-        let constants = query_constants(deps.as_ref());
-        assert!(constants.is_ok());
+    assert!(VOTE_MAP
+        .sub_prefix((0, tranche_id))
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+    let res = query_top_n_proposals(deps.as_ref(), 0, tranche_id, 1).unwrap();
+    assert!(res.proposals.is_empty());
+    assert!(VALIDATORS_PER_ROUND
+        .sub_prefix(0)
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+
+    assert!(PROPOSAL_VOTERS
+        .sub_prefix((0, tranche_id))
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+    assert!(ROUND_VOTERS
+        .prefix(0)
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+    assert!(VALIDATORS_INFO
+        .prefix(0)
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+    assert!(SCALED_PROPOSAL_SHARES_MAP
+        .prefix(0)
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+
+    // proposals of the pruned round are kept
+    assert!(query_proposal(deps.as_ref(), 0, tranche_id, 0).is_ok());
+
+    // the validator set of the current round was initialized before the older ones were pruned
+    let current_round_id = query_current_round_id(deps.as_ref(), env.clone())
+        .unwrap()
+        .round_id;
+    assert!(VALIDATORS_STORE_INITIALIZED
+        .load(&deps.storage, current_round_id)
+        .unwrap());
 
-        let round_id = compute_current_round_id(&env, &constants.unwrap().constants);
-        assert_eq!(expected_round_id, round_id);
-    }
+    // once the tribute claims of a round expire, it can be pruned even if it still holds tributes
+    env.block.time = env
+        .block
+        .time
+        .plus_nanos(TRIBUTE_CLAIM_RETENTION_ROUNDS * TWO_WEEKS_IN_NANO_SECONDS);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        ExecuteMsg::PruneRoundData {
+            round_id: 1,
+            limit: 100,
+        },
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("completed", "true")));
 }
 
 #[test]
-fn total_voting_power_tracking_test() {
-    let user_address = "addr0000";
-
+fn finalize_round_test() {
     let grpc_query = denom_trace_grpc_query_mock(
         "transfer/channel-0".to_string(),
         HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
     );
     let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info = get_message_info(&deps.api, user_address, &[]);
-    let mut msg = get_default_instantiate_msg(&deps.api);
-
-    // align round length with lock epoch length for easier calculations
-    msg.round_length = ONE_MONTH_IN_NANO_SECONDS;
-
-    let res = instantiate(deps.as_mut(), env.clone(), info, msg.clone());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
     set_default_validator_for_rounds(deps.as_mut(), 0, 100);
 
-    let info1 = get_message_info(
-        &deps.api,
-        user_address,
-        &[Coin::new(10u64, IBC_DENOM_1.to_string())],
-    );
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
-    };
-    let res = execute(deps.as_mut(), env.clone(), info1.clone(), msg);
-    assert!(res.is_ok());
-
-    // user locks 10 tokens for one month, so it will have 1x voting power in the first round only
-    let expected_total_voting_powers = [(0, 10), (1, 0)];
-    verify_expected_voting_power(deps.as_ref(), &expected_total_voting_powers);
+    let tranche_id = 1;
+    for i in 0..3 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
 
-    // advance the chain by 10 days and have user lock more tokens
-    env.block.time = env.block.time.plus_nanos(10 * ONE_DAY_IN_NANO_SECONDS);
+    for amount in [1000u64, 3000u64] {
+        let info = get_message_info(
+            &deps.api,
+            "addr0001",
+            &[Coin::new(amount, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
 
-    let info2 = get_message_info(
-        &deps.api,
-        user_address,
-        &[Coin::new(20u64, IBC_DENOM_1.to_string())],
-    );
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+    let voter_info = get_message_info(&deps.api, "addr0001", &[]);
+    let msg = ExecuteMsg::Vote {
+        tranche_id,
+        proposals_votes: vec![
+            ProposalToLockups {
+                proposal_id: 0,
+                lock_ids: vec![0],
+            },
+            ProposalToLockups {
+                proposal_id: 2,
+                lock_ids: vec![1],
+            },
+        ],
     };
-    let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
-    assert!(res.is_ok());
-
-    // user locks 20 additional tokens for three months, so the expectation is:
-    // round:         0      1       2       3
-    // power:       10+30   0+25    0+20    0+0
-    let expected_total_voting_powers = [(0, 40), (1, 25), (2, 20), (3, 0)];
-    verify_expected_voting_power(deps.as_ref(), &expected_total_voting_powers);
-
-    // advance the chain by 25 more days to move to round 1 and have user refresh second lockup to 6 months
-    env.block.time = env.block.time.plus_nanos(25 * ONE_DAY_IN_NANO_SECONDS);
+    let res = execute(deps.as_mut(), env.clone(), voter_info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
 
-    let info3 = get_message_info(&deps.api, user_address, &[]);
-    let msg = ExecuteMsg::RefreshLockDuration {
-        lock_ids: vec![1],
-        lock_duration: 2 * THREE_MONTHS_IN_NANO_SECONDS,
+    let finalize_msg = ExecuteMsg::FinalizeRound {
+        round_id: 0,
+        tranche_id,
     };
-    let res = execute(deps.as_mut(), env.clone(), info3.clone(), msg);
-    assert!(res.is_ok());
 
-    // user relocks second lockup worth 20 tokens for six months in round 1, so the expectation is (note that round 0 is not affected):
-    // round:         0       1       2       3       4       5       6       7
-    // power:       10+30    0+40    0+40    0+40    0+30    0+25    0+20    0+0
-    let expected_total_voting_powers = [
-        (0, 40),
-        (1, 40),
-        (2, 40),
-        (3, 40),
-        (4, 30),
-        (5, 25),
-        (6, 20),
-        (7, 0),
-    ];
-    verify_expected_voting_power(deps.as_ref(), &expected_total_voting_powers);
+    // the round can't be finalized before it ends
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        finalize_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Round 0 can't be finalized before it ends"));
+    assert!(query_round_results(deps.as_ref(), 0, tranche_id)
+        .unwrap()
+        .results
+        .is_none());
 
-    // advance the chain by 5 more days and have user lock 50 more tokens for three months
-    env.block.time = env.block.time.plus_nanos(5 * ONE_DAY_IN_NANO_SECONDS);
+    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS + 1);
 
-    let info2 = get_message_info(
-        &deps.api,
-        user_address,
-        &[Coin::new(50u64, IBC_DENOM_1.to_string())],
+    // anyone can finalize the round once it ended
+    let anyone_info = get_message_info(&deps.api, "addr0002", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        anyone_info.clone(),
+        finalize_msg.clone(),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("total_round_power", "4000")));
+    assert!(res.attributes.contains(&attr("proposal_ids", "2,0")));
+
+    let results = query_round_results(deps.as_ref(), 0, tranche_id)
+        .unwrap()
+        .results
+        .unwrap();
+    assert_eq!(env.block.time, results.finalized_at);
+    assert_eq!(Uint128::new(4000), results.total_round_power);
+    // proposals that received no votes aren't ranked
+    assert_eq!(
+        vec![2, 0],
+        results
+            .proposals
+            .iter()
+            .map(|proposal| proposal.proposal_id)
+            .collect::<Vec<u64>>()
+    );
+    assert_eq!(Uint128::new(3000), results.proposals[0].power);
+    assert_eq!(Uint128::new(1000), results.proposals[1].power);
+    assert_eq!(
+        Decimal::from_ratio(75u128, 1u128),
+        results.proposals[0].percentage
     );
-    let msg = ExecuteMsg::LockTokens {
-        lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
-    };
-    let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
-    assert!(res.is_ok());
-
-    // user locks 50 additional tokens in round 1 for three months, so the expectation is (note that round 0 is not affected):
-    // round:         0        1          2          3          4         5         6         7
-    // power:       10+30    0+40+75    0+40+62    0+40+50    0+30+0    0+25+0    0+20+0    0+0+0
-    let expected_total_voting_powers = [
-        (0, 40),
-        (1, 115),
-        (2, 102),
-        (3, 90),
-        (4, 30),
-        (5, 25),
-        (6, 20),
-        (7, 0),
-    ];
-    verify_expected_voting_power(deps.as_ref(), &expected_total_voting_powers);
-}
 
-fn verify_expected_voting_power(deps: Deps<NeutronQuery>, expected_powers: &[(u64, u128)]) {
-    for expected_power in expected_powers {
-        let res = query_round_total_power(deps, expected_power.0);
+    // the results can only be recorded once
+    let res = execute(deps.as_mut(), env.clone(), anyone_info, finalize_msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Round 0 was already finalized in tranche 1"));
 
-        assert!(res.is_ok());
-        let res = res.unwrap();
-        assert_eq!(expected_power.1, res.total_voting_power.u128());
-    }
+    // the round can't be finalized in a tranche that doesn't exist
+    let msg = ExecuteMsg::FinalizeRound {
+        round_id: 0,
+        tranche_id: 5,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Tranche does not exist"));
 }
 
-proptest! {
-    #![proptest_config(ProptestConfig::with_cases(100))] // set the number of test cases to run
-    #[test]
-    fn relock_proptest(old_lock_remaining_time: u64, new_lock_duration: u8) {
+#[test]
+fn pre_launch_locks_test() {
+    for allow_pre_launch_locks in [false, true] {
+        println!(
+            "running test case: allow_pre_launch_locks = {}",
+            allow_pre_launch_locks
+        );
+
         let grpc_query = denom_trace_grpc_query_mock(
             "transfer/channel-0".to_string(),
             HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
         );
+        let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+        let info = get_message_info(&deps.api, "addr0000", &[]);
 
-        let (mut deps, mut env) = (
-            mock_dependencies(grpc_query),
-            mock_env(),
-        );
-        let info = get_message_info(&deps.api, "addr0001", &[Coin::new(1000u64, IBC_DENOM_1.to_string())]);
-        let msg = get_default_instantiate_msg(&deps.api);
-
+        let mut msg = get_default_instantiate_msg(&deps.api);
+        msg.first_round_start = env.block.time.plus_nanos(2 * ONE_MONTH_IN_NANO_SECONDS);
+        msg.allow_pre_launch_locks = allow_pre_launch_locks;
         let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
         assert!(res.is_ok());
 
         set_default_validator_for_rounds(deps.as_mut(), 0, 100);
 
-        // get the new lock duration
-        // list of plausible values, plus a value that should give an error every time (0)
-        let possible_lock_durations = [0, ONE_MONTH_IN_NANO_SECONDS, ONE_MONTH_IN_NANO_SECONDS * 3, ONE_MONTH_IN_NANO_SECONDS * 6, ONE_MONTH_IN_NANO_SECONDS * 12];
-        let new_lock_duration = possible_lock_durations[new_lock_duration as usize % possible_lock_durations.len()];
+        let lock_msg = |lock_duration| ExecuteMsg::LockTokens { lock_duration };
+        let lock_info = get_message_info(&deps.api, "addr0000", &[Coin::new(1000u64, IBC_DENOM_1)]);
 
-        // old lock remaining time must be at most 12 months, so we take the modulo
-        let old_lock_remaining_time = old_lock_remaining_time % (ONE_MONTH_IN_NANO_SECONDS * 12);
+        if !allow_pre_launch_locks {
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                lock_info,
+                lock_msg(6 * ONE_MONTH_IN_NANO_SECONDS),
+            );
+            assert!(res
+                .unwrap_err()
+                .to_string()
+                .contains("The first round has not started yet"));
+            continue;
+        }
 
-        // lock the tokens for 12 months
-        let msg = ExecuteMsg::LockTokens {
-            lock_duration: ONE_MONTH_IN_NANO_SECONDS * 12,
-        };
+        // pre-launch locks must give voting power at least in round 0
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            lock_info.clone(),
+            lock_msg(ONE_MONTH_IN_NANO_SECONDS),
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("must not expire before the end of round 0"));
 
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
-        assert!(res.is_ok());
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            lock_info,
+            lock_msg(6 * ONE_MONTH_IN_NANO_SECONDS),
+        );
+        assert!(res.is_ok(), "error: {:?}", res);
 
-        // set the time so that old_lock_remaining_time remains on the old lock
-        env.block.time = env.block.time.plus_nanos(12 * ONE_MONTH_IN_NANO_SECONDS - old_lock_remaining_time);
+        // at the end of round 0 the lock has 3.5 months left, so it gets the
+        // power scaling factor of 2 defined for 6 months in the schedule
+        let round_0_power = query_round_total_power(deps.as_ref(), env.clone(), 0)
+            .unwrap()
+            .total_voting_power;
+        assert_eq!(Uint128::new(2000), round_0_power);
 
-        // try to refresh the lock duration as a different user
-        let info2 = get_message_info(&deps.api, "addr0002", &[]);
-        let msg = ExecuteMsg::RefreshLockDuration {
-            lock_ids: vec![0],
-            lock_duration: new_lock_duration,
+        // proposals can't be created and votes can't be cast before the first round starts
+        let proposal_msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id: 1,
+            title: "proposal title".to_string(),
+            description: "proposal description".to_string(),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
         };
-        let res = execute(deps.as_mut(), env.clone(), info2.clone(), msg);
-
-        // different user cannot refresh the lock
-        assert!(res.is_err(), "different user should not be able to refresh the lock: {:?}", res);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            proposal_msg.clone(),
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("The first round has not started yet"));
 
-        // refresh the lock duration
-        let info = get_message_info(&deps.api, "addr0001", &[]);
-        let msg = ExecuteMsg::RefreshLockDuration {
-            lock_ids: vec![0],
-            lock_duration: new_lock_duration,
+        let vote_msg = ExecuteMsg::Vote {
+            tranche_id: 1,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id: 0,
+                lock_ids: vec![0],
+            }],
         };
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), vote_msg.clone());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("The first round has not started yet"));
+
+        // once the first round starts, the pre-launch lock can vote with its full power
+        env.block.time = msg.first_round_start;
+        assert_eq!(
+            0,
+            compute_current_round_id(&env, &query_constants(deps.as_ref()).unwrap().constants)
+                .unwrap()
+        );
 
-        // if we try to refresh the lock with a duration of 0, it should fail
-        if new_lock_duration == 0 {
-            assert!(res.is_err());
-            return Ok(()); // end the test
-        }
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), proposal_msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), vote_msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+
+        let res = query_proposal(deps.as_ref(), 0, 1, 0).unwrap();
+        assert_eq!(round_0_power, res.proposal.power);
+    }
+}
+
+#[test]
+fn scale_lockup_power_interpolation_test() {
+    let round_lock_power_schedule = get_default_power_schedule();
+    let raw_power = Uint128::new(1000);
+
+    // (lockup_time, expected_stepwise_power, expected_interpolated_power)
+    let test_cases = vec![
+        (ONE_MONTH_IN_NANO_SECONDS / 2, 1000, 1000),
+        (ONE_MONTH_IN_NANO_SECONDS, 1000, 1000),
+        (3 * ONE_MONTH_IN_NANO_SECONDS / 2, 1250, 1125),
+        (2 * ONE_MONTH_IN_NANO_SECONDS, 1250, 1250),
+        (9 * ONE_MONTH_IN_NANO_SECONDS / 2, 2000, 1750),
+        (9 * ONE_MONTH_IN_NANO_SECONDS, 4000, 3000),
+        (12 * ONE_MONTH_IN_NANO_SECONDS, 4000, 4000),
+        (24 * ONE_MONTH_IN_NANO_SECONDS, 4000, 4000),
+    ];
+
+    for (lockup_time, expected_stepwise_power, expected_interpolated_power) in test_cases {
+        let stepwise_power = scale_lockup_power(
+            &round_lock_power_schedule,
+            ONE_MONTH_IN_NANO_SECONDS,
+            lockup_time,
+            raw_power,
+            false,
+        );
+        assert_eq!(expected_stepwise_power, stepwise_power.u128());
+
+        let interpolated_power = scale_lockup_power(
+            &round_lock_power_schedule,
+            ONE_MONTH_IN_NANO_SECONDS,
+            lockup_time,
+            raw_power,
+            true,
+        );
+        assert_eq!(expected_interpolated_power, interpolated_power.u128());
+    }
+}
 
-        // if we tried to make the lock_end sooner, it should fail
-        if new_lock_duration < old_lock_remaining_time {
-            assert!(res.is_err());
-            return Ok(()); // end the test
+#[test]
+fn scale_lockup_power_longer_than_schedule_test() {
+    let round_lock_power_schedule = get_default_power_schedule();
+
+    // lockups longer than the longest schedule entry get the largest multiplier applied to the whole raw power
+    for raw_power in [1u128, 1000, 1_000_000_000_000] {
+        for interpolate_lock_power in [false, true] {
+            let power = scale_lockup_power(
+                &round_lock_power_schedule,
+                ONE_MONTH_IN_NANO_SECONDS,
+                13 * ONE_MONTH_IN_NANO_SECONDS,
+                Uint128::new(raw_power),
+                interpolate_lock_power,
+            );
+            assert_eq!(4 * raw_power, power.u128());
         }
-
-        // otherwise, succeed
-        assert!(res.is_ok());
     }
 }
 
@@ -2494,7 +6233,10 @@ fn test_too_many_locks() {
 
     // now test that the first user can unlock tokens after we have passed enough time so that they are unlocked
     env.block.time = env.block.time.plus_nanos(ONE_MONTH_IN_NANO_SECONDS + 1);
-    let unlock_msg = ExecuteMsg::UnlockTokens { lock_ids: None };
+    let unlock_msg = ExecuteMsg::UnlockTokens {
+        lock_ids: None,
+        claim_tributes: None,
+    };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), unlock_msg.clone());
     assert!(res.is_ok());
 
@@ -2574,7 +6316,10 @@ fn max_locked_tokens_test() {
         deps.as_mut(),
         env.clone(),
         info.clone(),
-        ExecuteMsg::UnlockTokens { lock_ids: None },
+        ExecuteMsg::UnlockTokens {
+            lock_ids: None,
+            claim_tributes: None,
+        },
     );
     assert!(res.is_ok());
 
@@ -2592,6 +6337,13 @@ fn max_locked_tokens_test() {
     let update_max_locked_tokens_msg = ExecuteMsg::UpdateConfig {
         max_locked_tokens: Some(3000),
         max_deployment_duration: None,
+        tribute_cutoff_duration: None,
+        max_proposals_per_tranche_round: None,
+        raw_export_enabled: None,
+        unlock_cooldown_duration: None,
+        max_validator_shares_participating: None,
+        max_proposal_title_length: None,
+        max_proposal_description_length: None,
     };
     let res = execute(
         deps.as_mut(),
@@ -2654,11 +6406,70 @@ fn contract_pausing_test() {
     // verify that no action can be executed while the contract is paused
     let msgs = vec![
         ExecuteMsg::LockTokens { lock_duration: 0 },
+        ExecuteMsg::LockTokensMultiple { lock_duration: 0 },
+        ExecuteMsg::SetAutoRefresh {
+            lock_ids: vec![0],
+            enabled: true,
+        },
+        ExecuteMsg::ProcessAutoRefresh { limit: 1 },
+        ExecuteMsg::DelegateVotingRights {
+            lock_ids: vec![0],
+            delegate: whitelist_admin.to_string(),
+        },
+        ExecuteMsg::RevokeDelegation { lock_ids: vec![0] },
+        ExecuteMsg::SetVotingExecutor {
+            executor: whitelist_admin.to_string(),
+            max_proposals_per_round: 1,
+        },
+        ExecuteMsg::RemoveVotingExecutor {},
+        ExecuteMsg::Abstain {
+            tranche_id: 0,
+            lock_ids: vec![0],
+        },
+        ExecuteMsg::AbstainOnBehalf {
+            owner: whitelist_admin.to_string(),
+            tranche_id: 0,
+            lock_ids: vec![0],
+        },
+        ExecuteMsg::UnvoteAll {
+            tranche_id: 0,
+            owner: None,
+        },
+        ExecuteMsg::RelayedVote {
+            vote_intent: VoteIntent {
+                voter: "addr0000".to_string(),
+                round_id: 0,
+                tranche_id: 0,
+                proposals_votes: vec![],
+                nonce: 0,
+                expiry: Timestamp::from_seconds(0),
+            },
+            pubkey: Binary::default(),
+            signature: Binary::default(),
+        },
+        ExecuteMsg::SetLockOperator {
+            lock_ids: vec![0],
+            operator: None,
+        },
+        ExecuteMsg::SetLockNote {
+            lock_id: 0,
+            note: None,
+        },
+        ExecuteMsg::SetLockAutoMerge { tolerance: None },
+        ExecuteMsg::RefreshLockDurationOnBehalf {
+            owner: whitelist_admin.to_string(),
+            lock_ids: vec![0],
+            lock_duration: 0,
+        },
         ExecuteMsg::RefreshLockDuration {
             lock_ids: vec![0],
             lock_duration: 0,
         },
-        ExecuteMsg::UnlockTokens { lock_ids: None },
+        ExecuteMsg::UnlockTokens {
+            lock_ids: None,
+            claim_tributes: None,
+        },
+        ExecuteMsg::ClaimUnlocked {},
         ExecuteMsg::CreateProposal {
             round_id: None,
             tranche_id: 0,
@@ -2666,6 +6477,9 @@ fn contract_pausing_test() {
             description: "".to_string(),
             deployment_duration: 1,
             minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
         },
         ExecuteMsg::Vote {
             tranche_id: 0,
@@ -2674,6 +6488,14 @@ fn contract_pausing_test() {
                 lock_ids: vec![0],
             }],
         },
+        ExecuteMsg::VoteOnBehalf {
+            owner: whitelist_admin.to_string(),
+            tranche_id: 0,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id: 0,
+                lock_ids: vec![0],
+            }],
+        },
         ExecuteMsg::AddAccountToWhitelist {
             address: whitelist_admin.to_string(),
         },
@@ -2683,6 +6505,13 @@ fn contract_pausing_test() {
         ExecuteMsg::UpdateConfig {
             max_locked_tokens: None,
             max_deployment_duration: None,
+            tribute_cutoff_duration: None,
+            max_proposals_per_tranche_round: None,
+            raw_export_enabled: None,
+            unlock_cooldown_duration: None,
+            max_validator_shares_participating: None,
+            max_proposal_title_length: None,
+            max_proposal_description_length: None,
         },
         ExecuteMsg::Pause {},
         ExecuteMsg::AddTranche {
@@ -2696,6 +6525,12 @@ fn contract_pausing_test() {
             tranche_name: Some(String::new()),
             tranche_metadata: Some(String::new()),
         },
+        ExecuteMsg::SetProposalVoteCap {
+            round_id: 0,
+            tranche_id: 0,
+            proposal_id: 0,
+            max_vote_percentage: None,
+        },
         ExecuteMsg::CreateICQsForValidators { validators: vec![] },
         ExecuteMsg::AddICQManager {
             address: whitelist_admin.to_string(),
@@ -2721,6 +6556,23 @@ fn contract_pausing_test() {
             tranche_id: 0,
             proposal_id: 0,
         },
+        ExecuteMsg::FinalizeRound {
+            round_id: 0,
+            tranche_id: 0,
+        },
+        ExecuteMsg::SetVotePowerCap {
+            max_power_percentage: None,
+        },
+        ExecuteMsg::IndexValidatorLocks {
+            start_after: None,
+            limit: 1,
+        },
+        ExecuteMsg::PauseMessages {
+            names: vec!["LockTokens".to_string()],
+        },
+        ExecuteMsg::UnpauseMessages {
+            names: vec!["LockTokens".to_string()],
+        },
     ];
 
     for msg in msgs {
@@ -2730,6 +6582,109 @@ fn contract_pausing_test() {
     }
 }
 
+#[test]
+fn pause_messages_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let user_info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let whitelist_admin = "addr0001";
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, whitelist_admin)];
+    let res = instantiate(deps.as_mut(), env.clone(), user_info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let lock_info = get_message_info(
+        &deps.api,
+        "addr0000",
+        &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+    );
+    let lock_msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let pause_msg = ExecuteMsg::PauseMessages {
+        names: vec!["LockTokens".to_string()],
+    };
+
+    // verify that non-privileged user can not pause messages
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        user_info.clone(),
+        pause_msg.clone(),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    // admin messages and unknown messages can't be paused
+    let admin_info = get_message_info(&deps.api, whitelist_admin, &[]);
+    for name in [
+        "PauseMessages",
+        "UnpauseMessages",
+        "UpdateConfig",
+        "lock_tokens",
+    ] {
+        let msg = ExecuteMsg::PauseMessages {
+            names: vec![name.to_string()],
+        };
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains(&format!("Message {} can't be paused", name)));
+    }
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), pause_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let constants = query_constants(deps.as_ref()).unwrap().constants;
+    assert!(!constants.paused);
+    assert_eq!(vec!["LockTokens".to_string()], constants.paused_messages);
+
+    // locking is paused, while the other messages keep working
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        lock_info.clone(),
+        lock_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Message LockTokens is paused"));
+
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 1,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), user_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // after unpausing, the tokens can be locked again
+    let msg = ExecuteMsg::UnpauseMessages {
+        names: vec!["LockTokens".to_string()],
+    };
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let constants = query_constants(deps.as_ref()).unwrap().constants;
+    assert!(constants.paused_messages.is_empty());
+
+    let res = execute(deps.as_mut(), env.clone(), lock_info, lock_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+}
+
 // This test verifies that only whitelisted addresses can submit proposals
 #[test]
 pub fn whitelist_proposal_submission_test() {
@@ -2752,6 +6707,9 @@ pub fn whitelist_proposal_submission_test() {
         description: "proposal description".to_string(),
         deployment_duration: 1,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
 
     let res = execute(
@@ -2805,6 +6763,217 @@ pub fn whitelist_proposal_submission_test() {
     assert!(res.is_ok(), "error: {:?}", res);
 }
 
+#[test]
+fn admin_actions_log_test() {
+    let (mut deps, mut env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let whitelist_admin = "addr0001";
+
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, whitelist_admin)];
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let whitelist_msg = ExecuteMsg::AddAccountToWhitelist {
+        address: get_address_as_str(&deps.api, "addr0002"),
+    };
+
+    // failed admin actions and the actions that don't require admin privileges are not recorded
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        whitelist_msg.clone(),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let proposal_msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 1,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, proposal_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_admin_actions(deps.as_ref(), None, 10).unwrap();
+    assert!(res.actions.is_empty());
+
+    let admin_info = get_message_info(&deps.api, whitelist_admin, &[]);
+    env.block.height += 1;
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        whitelist_msg.clone(),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_admin_actions(deps.as_ref(), None, 10).unwrap();
+    assert_eq!(
+        vec![AdminAction {
+            action_id: 0,
+            actor: admin_info.sender.clone(),
+            action_type: "add_account_to_whitelist".to_string(),
+            params_hash: HexBinary::from(
+                Sha256::digest(to_json_vec(&whitelist_msg).unwrap()).as_slice()
+            )
+            .to_hex(),
+            height: env.block.height,
+            timestamp: env.block.time,
+        }],
+        res.actions
+    );
+
+    // once the log is full, the oldest actions get removed
+    for max_proposals in 0..MAX_ADMIN_ACTIONS_LOG_ENTRIES {
+        let msg = ExecuteMsg::SetProposalLimitOverride {
+            round_id: 0,
+            tranche_id: 1,
+            max_proposals: Some(max_proposals),
+        };
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let res = query_admin_actions(deps.as_ref(), None, 2).unwrap();
+    assert_eq!(
+        vec![1, 2],
+        res.actions
+            .iter()
+            .map(|action| action.action_id)
+            .collect::<Vec<u64>>()
+    );
+    assert_eq!("set_proposal_limit_override", res.actions[0].action_type);
+
+    let res =
+        query_admin_actions(deps.as_ref(), Some(MAX_ADMIN_ACTIONS_LOG_ENTRIES - 1), 10).unwrap();
+    assert_eq!(1, res.actions.len());
+    assert_eq!(MAX_ADMIN_ACTIONS_LOG_ENTRIES, res.actions[0].action_id);
+}
+
+#[test]
+fn proposal_limit_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let admin_info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_ok());
+
+    let tranche_id = 1;
+    let create_proposal =
+        |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+         round_id: Option<u64>| {
+            let msg = ExecuteMsg::CreateProposal {
+                round_id,
+                tranche_id,
+                title: "proposal title".to_string(),
+                description: "proposal description".to_string(),
+                deployment_duration: 1,
+                minimum_atom_liquidity_request: Uint128::zero(),
+                description_hash: None,
+                description_uri: None,
+                max_vote_percentage: None,
+            };
+            execute(deps.as_mut(), env.clone(), admin_info.clone(), msg)
+        };
+
+    // by default, the number of proposals is not limited
+    let res = query_proposal_slots(deps.as_ref(), 0, tranche_id).unwrap();
+    assert_eq!(None, res.max_proposals);
+    assert_eq!(0, res.proposals_count);
+    assert_eq!(None, res.remaining_slots);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        max_locked_tokens: None,
+        max_deployment_duration: None,
+        tribute_cutoff_duration: None,
+        max_proposals_per_tranche_round: Some(2),
+        raw_export_enabled: None,
+        unlock_cooldown_duration: None,
+        max_validator_shares_participating: None,
+        max_proposal_title_length: None,
+        max_proposal_description_length: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    for _ in 0..2 {
+        let res = create_proposal(&mut deps, None);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let res = create_proposal(&mut deps, None);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Maximum number of proposals (2) reached for round 0 and tranche 1"));
+
+    let res = query_proposal_slots(deps.as_ref(), 0, tranche_id).unwrap();
+    assert_eq!(Some(2), res.max_proposals);
+    assert_eq!(2, res.proposals_count);
+    assert_eq!(Some(0), res.remaining_slots);
+
+    // only whitelist admins can override the limit
+    let override_msg = |max_proposals| ExecuteMsg::SetProposalLimitOverride {
+        round_id: 0,
+        tranche_id,
+        max_proposals,
+    };
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        non_admin_info,
+        override_msg(Some(3)),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        override_msg(Some(3)),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = create_proposal(&mut deps, None);
+    assert!(res.is_ok(), "error: {:?}", res);
+    let res = create_proposal(&mut deps, None);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Maximum number of proposals (3) reached for round 0 and tranche 1"));
+
+    // the override only applies to the given round
+    let res = query_proposal_slots(deps.as_ref(), 1, tranche_id).unwrap();
+    assert_eq!(Some(2), res.max_proposals);
+    assert_eq!(Some(2), res.remaining_slots);
+    let res = create_proposal(&mut deps, Some(1));
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // after removing the override, the default limit applies again
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        override_msg(None),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_proposal_slots(deps.as_ref(), 0, tranche_id).unwrap();
+    assert_eq!(Some(2), res.max_proposals);
+    assert_eq!(3, res.proposals_count);
+    assert_eq!(Some(0), res.remaining_slots);
+}
+
 fn assert_proposal_voting_power(
     deps: &OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
     round_id: u64,
@@ -3073,6 +7242,7 @@ fn test_refresh_multiple_locks() {
                 env.clone(),
                 get_address_as_str(&deps.api, sender),
                 0,
+                None,
                 100,
             )
             .unwrap()
@@ -3113,10 +7283,12 @@ fn test_get_vote_for_update() {
     let vote_1 = Vote {
         prop_id: prop_id_1,
         time_weighted_shares: (validator_1.clone(), Decimal::one()),
+        tribute_weight_multiplier: None,
     };
     let vote_2 = Vote {
         prop_id: prop_id_2,
         time_weighted_shares: (validator_2.clone(), Decimal::one()),
+        tribute_weight_multiplier: None,
     };
 
     let lock_entry_1 = LockEntry {
@@ -3224,3 +7396,69 @@ fn test_get_vote_for_update() {
         }
     }
 }
+
+#[test]
+fn interpolate_lock_power_within_round_test() {
+    let user_address = "addr0000";
+    for interpolate_lock_power in [false, true] {
+        let grpc_query = denom_trace_grpc_query_mock(
+            "transfer/channel-0".to_string(),
+            HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+        );
+        let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+        let info = get_message_info(&deps.api, user_address, &[]);
+        let mut msg = get_default_instantiate_msg(&deps.api);
+        msg.round_length = ONE_MONTH_IN_NANO_SECONDS;
+        msg.interpolate_lock_power = interpolate_lock_power;
+
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+        assert!(res.is_ok());
+
+        set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+        let info = get_message_info(
+            &deps.api,
+            user_address,
+            &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: THREE_MONTHS_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+
+        // at the end of round 0 the lock has 2 months left (1.25x), and at its start 3 months (1.5x);
+        // at the end of round 1 it has 1 month left (1x)
+        let round_end_powers = [1250, 1250, 1000, 1000];
+        let interpolated_powers = if interpolate_lock_power {
+            [1500, 1375, 1250, 1125]
+        } else {
+            round_end_powers
+        };
+
+        let first_round_start = env.block.time;
+        for (i, (round_end_power, interpolated_power)) in round_end_powers
+            .iter()
+            .zip(interpolated_powers.iter())
+            .enumerate()
+        {
+            env.block.time = first_round_start.plus_nanos(i as u64 * ONE_MONTH_IN_NANO_SECONDS / 2);
+            let round_id = i as u64 / 2;
+
+            // the votes are tallied with the round end power, so the interpolated power is reported separately
+            let user_power =
+                query_user_voting_power(deps.as_ref(), env.clone(), info.sender.to_string())
+                    .unwrap();
+            assert_eq!(*round_end_power, user_power.voting_power);
+            assert_eq!(*interpolated_power, user_power.interpolated_voting_power);
+
+            let total_power =
+                query_round_total_power(deps.as_ref(), env.clone(), round_id).unwrap();
+            assert_eq!(*round_end_power, total_power.total_voting_power.u128());
+            assert_eq!(
+                *interpolated_power,
+                total_power.interpolated_total_voting_power.u128()
+            );
+        }
+    }
+}
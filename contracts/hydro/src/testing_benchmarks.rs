@@ -0,0 +1,222 @@
+use std::{cell::Cell, collections::HashMap, marker::PhantomData};
+
+use cosmwasm_std::{
+    testing::{mock_env, MockApi, MockQuerier as BaseMockQuerier, MockStorage},
+    Coin, Order, OwnedDeps, Record, Storage, Uint128,
+};
+use neutron_sdk::bindings::query::NeutronQuery;
+use serde::Deserialize;
+
+use crate::{
+    contract::{execute, instantiate, query_all_user_lockups},
+    msg::{ExecuteMsg, ProposalToLockups},
+    testing::{
+        get_default_instantiate_msg, get_message_info, set_default_validator_for_rounds,
+        IBC_DENOM_1, ONE_MONTH_IN_NANO_SECONDS, VALIDATOR_1_LST_DENOM_1,
+    },
+    testing_mocks::{denom_trace_grpc_query_mock, MockQuerier},
+};
+
+// Storage operation counts are used as a deterministic proxy for the gas consumed by the contract,
+// since the gas costs of the hot paths are dominated by the storage accesses.
+// The baseline numbers are kept in benchmarks/baseline.json. If a change makes some operation
+// more expensive by more than REGRESSION_THRESHOLD_PERCENT, the corresponding test fails.
+// If the increase is intended (or a change made some operation cheaper), update the baseline.
+const BASELINE: &str = include_str!("../benchmarks/baseline.json");
+const REGRESSION_THRESHOLD_PERCENT: u64 = 10;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+struct StorageOpCounts {
+    reads: u64,
+    writes: u64,
+    removes: u64,
+    // number of entries returned by all range iterations
+    iterated: u64,
+}
+
+// Wraps the MockStorage and counts all storage operations performed on it.
+#[derive(Default)]
+struct CountingStorage {
+    storage: MockStorage,
+    reads: Cell<u64>,
+    writes: Cell<u64>,
+    removes: Cell<u64>,
+    iterated: Cell<u64>,
+}
+
+impl CountingStorage {
+    fn reset(&self) {
+        self.reads.set(0);
+        self.writes.set(0);
+        self.removes.set(0);
+        self.iterated.set(0);
+    }
+
+    fn counts(&self) -> StorageOpCounts {
+        StorageOpCounts {
+            reads: self.reads.get(),
+            writes: self.writes.get(),
+            removes: self.removes.get(),
+            iterated: self.iterated.get(),
+        }
+    }
+}
+
+impl Storage for CountingStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.reads.set(self.reads.get() + 1);
+        self.storage.get(key)
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+        Box::new(
+            self.storage
+                .range(start, end, order)
+                .inspect(move |_| self.iterated.set(self.iterated.get() + 1)),
+        )
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.writes.set(self.writes.get() + 1);
+        self.storage.set(key, value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.removes.set(self.removes.get() + 1);
+        self.storage.remove(key)
+    }
+}
+
+type BenchmarkDeps = OwnedDeps<CountingStorage, MockApi, MockQuerier, NeutronQuery>;
+
+fn setup_benchmark_deps() -> BenchmarkDeps {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let mut deps = OwnedDeps {
+        storage: CountingStorage::default(),
+        api: MockApi::default(),
+        querier: MockQuerier::new(BaseMockQuerier::new(&[]), grpc_query),
+        custom_query_type: PhantomData,
+    };
+
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+    assert!(res.is_ok(), "instantiating contract: {:?}", res);
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    deps
+}
+
+fn lock_tokens(deps: &mut BenchmarkDeps, num_locks: u64) {
+    let info = get_message_info(
+        &deps.api,
+        "addr0000",
+        &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+    );
+    for _ in 0..num_locks {
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(res.is_ok(), "locking tokens: {:?}", res);
+    }
+}
+
+// Compares the measured counts against the baseline for the given benchmark.
+fn check_against_baseline(benchmark: &str, measured: StorageOpCounts) {
+    let baselines: HashMap<String, StorageOpCounts> =
+        serde_json_wasm::from_str(BASELINE).expect("failed to parse the benchmarks baseline");
+    let baseline = baselines
+        .get(benchmark)
+        .unwrap_or_else(|| panic!("no baseline found for benchmark {}", benchmark));
+
+    let metrics = [
+        ("reads", baseline.reads, measured.reads),
+        ("writes", baseline.writes, measured.writes),
+        ("removes", baseline.removes, measured.removes),
+        ("iterated", baseline.iterated, measured.iterated),
+    ];
+    for (metric, baseline_count, measured_count) in metrics {
+        let allowed = baseline_count * (100 + REGRESSION_THRESHOLD_PERCENT) / 100;
+        assert!(
+            measured_count <= allowed,
+            "benchmark {} regressed: {} {} exceeds the baseline {} by more than {}%; measured: {:?}",
+            benchmark,
+            measured_count,
+            metric,
+            baseline_count,
+            REGRESSION_THRESHOLD_PERCENT,
+            measured
+        );
+    }
+}
+
+#[test]
+fn benchmark_lock_tokens() {
+    let mut deps = setup_benchmark_deps();
+    lock_tokens(&mut deps, 10);
+
+    deps.storage.reset();
+    lock_tokens(&mut deps, 1);
+
+    check_against_baseline("lock_tokens", deps.storage.counts());
+}
+
+#[test]
+fn benchmark_vote_with_50_locks() {
+    let mut deps = setup_benchmark_deps();
+    lock_tokens(&mut deps, 50);
+
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 1,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert!(res.is_ok(), "creating proposal: {:?}", res);
+
+    deps.storage.reset();
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: (0..50).collect(),
+        }],
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    assert!(res.is_ok(), "voting: {:?}", res);
+
+    check_against_baseline("vote_with_50_locks", deps.storage.counts());
+}
+
+#[test]
+fn benchmark_query_all_user_lockups_with_100_locks() {
+    let mut deps = setup_benchmark_deps();
+    lock_tokens(&mut deps, 100);
+
+    let address = get_message_info(&deps.api, "addr0000", &[])
+        .sender
+        .to_string();
+
+    deps.storage.reset();
+    let res = query_all_user_lockups(deps.as_ref(), mock_env(), address, 0, None, 100).unwrap();
+    assert_eq!(100, res.lockups.len());
+
+    check_against_baseline("query_all_user_lockups_100_locks", deps.storage.counts());
+}
@@ -50,6 +50,8 @@ mod tests {
             description: "description1".to_string(),
             minimum_atom_liquidity_request: Uint128::zero(),
             deployment_duration: 1,
+            description_hash: None,
+            max_vote_percentage: None,
         };
         PROPOSAL_MAP
             .save(deps.as_mut().storage, (0, 1, proposal_id), &proposal)
@@ -157,6 +159,8 @@ mod tests {
             description: "description1".to_string(),
             deployment_duration: 1,
             minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            max_vote_percentage: None,
         };
         PROPOSAL_MAP
             .save(
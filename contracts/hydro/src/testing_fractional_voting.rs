@@ -64,7 +64,11 @@ impl FractionalVotingTestCase {
         lock_epoch_length: u64,
     ) {
         for lockup in lockups {
-            let info = get_message_info(&deps.api, self.voter_address, &[lockup.token.clone()]);
+            let info = get_message_info(
+                &deps.api,
+                self.voter_address,
+                std::slice::from_ref(&lockup.token),
+            );
             let msg = ExecuteMsg::LockTokens {
                 lock_duration: lock_epoch_length,
             };
@@ -456,6 +460,9 @@ fn fractional_voting_test() {
                 description: "proposal description 1".to_string(),
                 minimum_atom_liquidity_request: Uint128::zero(),
                 deployment_duration: 1,
+                description_hash: None,
+                description_uri: None,
+                max_vote_percentage: None,
             },
             ExecuteMsg::CreateProposal {
                 round_id: None,
@@ -464,6 +471,9 @@ fn fractional_voting_test() {
                 description: "proposal description 2".to_string(),
                 minimum_atom_liquidity_request: Uint128::zero(),
                 deployment_duration: 1,
+                description_hash: None,
+                description_uri: None,
+                max_vote_percentage: None,
             },
         ];
 
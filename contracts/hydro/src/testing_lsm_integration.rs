@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use cosmos_sdk_proto::prost::Message;
 use cosmwasm_std::{
-    testing::mock_env, BankMsg, Binary, Coin, CosmosMsg, Decimal, DepsMut, Env, StdError,
+    testing::mock_env, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, StdError,
     StdResult, Storage, SystemError, SystemResult, Timestamp, Uint128,
 };
 use neutron_sdk::{
@@ -13,16 +13,25 @@ use neutron_sdk::{
 use neutron_std::types::ibc::applications::transfer::v1::QueryDenomTraceResponse;
 
 use crate::{
-    contract::{execute, instantiate, query_round_tranche_proposals, query_top_n_proposals, sudo},
+    contract::{
+        execute, instantiate, query_recompute_round_totals_dry_run, query_round_tranche_proposals,
+        query_top_n_proposals, query_validator_locks, sudo,
+    },
     lsm_integration::{
         get_total_power_for_round, get_validator_power_ratio_for_round,
+        recompute_total_power_for_round, remove_lock_from_validator_index,
         update_scores_due_to_power_ratio_change, validate_denom,
     },
     msg::{ExecuteMsg, ProposalToLockups},
-    state::{ValidatorInfo, VALIDATORS_INFO, VALIDATORS_PER_ROUND, VALIDATORS_STORE_INITIALIZED},
+    query::ProposalPowerDelta,
+    state::{
+        ValidatorInfo, POWER_RATIO_CORRECTIONS, VALIDATORS_INFO, VALIDATORS_PER_ROUND,
+        VALIDATORS_STORE_INITIALIZED,
+    },
+    stats::get_round_tranche_voted_power,
     testing::{
-        get_default_instantiate_msg, get_default_power_schedule, get_message_info,
-        set_default_validator_for_rounds, IBC_DENOM_1, IBC_DENOM_2, IBC_DENOM_3,
+        get_address_as_str, get_default_instantiate_msg, get_default_power_schedule,
+        get_message_info, set_default_validator_for_rounds, IBC_DENOM_1, IBC_DENOM_2, IBC_DENOM_3,
         ONE_DAY_IN_NANO_SECONDS, ONE_MONTH_IN_NANO_SECONDS, VALIDATOR_1, VALIDATOR_1_LST_DENOM_1,
         VALIDATOR_2, VALIDATOR_2_LST_DENOM_1, VALIDATOR_3, VALIDATOR_3_LST_DENOM_1,
     },
@@ -47,6 +56,15 @@ fn get_default_constants() -> crate::state::Constants {
         icq_update_period: 100,
         max_deployment_duration: 12,
         round_lock_power_schedule: get_default_power_schedule(),
+        interpolate_lock_power: false,
+        tribute_cutoff_duration: 0,
+        max_proposals_per_tranche_round: 0,
+        allow_pre_launch_locks: false,
+        raw_export_enabled: false,
+        unlock_cooldown_duration: 0,
+        paused_messages: vec![],
+        max_proposal_title_length: 0,
+        max_proposal_description_length: 0,
     }
 }
 
@@ -407,7 +425,7 @@ fn lock_tokens_with_multiple_denoms() {
         );
 
         for fund in case.funds.iter() {
-            let info = get_message_info(&deps.api, "addr0001", &[fund.clone()]);
+            let info = get_message_info(&deps.api, "addr0001", std::slice::from_ref(fund));
             let msg = ExecuteMsg::LockTokens {
                 lock_duration: case.lock_duration,
             };
@@ -488,7 +506,10 @@ fn unlock_tokens_multiple_denoms() {
         deps.as_mut(),
         env.clone(),
         info.clone(),
-        ExecuteMsg::UnlockTokens { lock_ids: None },
+        ExecuteMsg::UnlockTokens {
+            lock_ids: None,
+            claim_tributes: None,
+        },
     );
     assert!(res.is_ok(), "unlocking tokens: {:?}", res);
 
@@ -517,6 +538,116 @@ fn unlock_tokens_multiple_denoms() {
     }
 }
 
+fn get_validator_lock_ids(deps: Deps<NeutronQuery>, validator: &str) -> Vec<u64> {
+    query_validator_locks(deps, validator.to_string(), None, 100)
+        .unwrap()
+        .locks
+        .iter()
+        .map(|lock| lock.lock_id)
+        .collect()
+}
+
+#[test]
+fn validator_locks_index_test() {
+    let user_address = "addr0000";
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([
+            (IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string()),
+            (IBC_DENOM_2.to_string(), VALIDATOR_2_LST_DENOM_1.to_string()),
+        ]),
+    );
+
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, user_address, &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, user_address)];
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "instantiating contract: {:?}", res);
+
+    set_validators_constant_power_ratios_for_rounds(
+        deps.as_mut(),
+        0,
+        100,
+        vec![VALIDATOR_1.to_string(), VALIDATOR_2.to_string()],
+        vec![Decimal::one(), Decimal::one()],
+    );
+
+    // lock 0 and lock 2 hold the shares of validator 1, lock 1 holds the shares of validator 2
+    for (denom, lock_duration) in [
+        (IBC_DENOM_1, ONE_MONTH_IN_NANO_SECONDS),
+        (IBC_DENOM_2, ONE_MONTH_IN_NANO_SECONDS),
+        (IBC_DENOM_1, 3 * ONE_MONTH_IN_NANO_SECONDS),
+    ] {
+        let info = get_message_info(
+            &deps.api,
+            user_address,
+            &[Coin::new(1000u64, denom.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens { lock_duration };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "locking tokens: {:?}", res);
+    }
+
+    assert_eq!(
+        vec![0, 2],
+        get_validator_lock_ids(deps.as_ref(), VALIDATOR_1)
+    );
+    assert_eq!(vec![1], get_validator_lock_ids(deps.as_ref(), VALIDATOR_2));
+
+    let res = query_validator_locks(deps.as_ref(), VALIDATOR_1.to_string(), None, 1).unwrap();
+    assert_eq!(info.sender, res.locks[0].owner);
+    assert_eq!(Some(0), res.next_key);
+
+    // unlocked locks are removed from the index
+    env.block.time = env.block.time.plus_nanos(ONE_MONTH_IN_NANO_SECONDS + 1);
+    let msg = ExecuteMsg::UnlockTokens {
+        lock_ids: Some(vec![0]),
+        claim_tributes: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "unlocking tokens: {:?}", res);
+    assert_eq!(vec![2], get_validator_lock_ids(deps.as_ref(), VALIDATOR_1));
+
+    // simulate locks that were created before the index was introduced
+    remove_lock_from_validator_index(&mut deps.storage, 1).unwrap();
+    remove_lock_from_validator_index(&mut deps.storage, 2).unwrap();
+    assert!(get_validator_lock_ids(deps.as_ref(), VALIDATOR_1).is_empty());
+
+    let index_msg = |start_after: Option<(String, u64)>, limit: u32| {
+        ExecuteMsg::IndexValidatorLocks { start_after, limit }
+    };
+
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        non_admin_info,
+        index_msg(None, 10),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), index_msg(None, 1)).unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::Attribute::new("completed", "false")));
+    assert_eq!(vec![1], get_validator_lock_ids(deps.as_ref(), VALIDATOR_2));
+    assert!(get_validator_lock_ids(deps.as_ref(), VALIDATOR_1).is_empty());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        index_msg(Some((info.sender.to_string(), 1)), 10),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::Attribute::new("completed", "true")));
+    assert_eq!(vec![2], get_validator_lock_ids(deps.as_ref(), VALIDATOR_1));
+}
+
 #[test]
 fn unlock_tokens_multiple_users() {
     let user1_address = "addr0001";
@@ -530,8 +661,8 @@ fn unlock_tokens_multiple_users() {
     );
 
     let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
-    let info1 = get_message_info(&deps.api, user1_address, &[user1_token.clone()]);
-    let info2 = get_message_info(&deps.api, user2_address, &[user2_token.clone()]);
+    let info1 = get_message_info(&deps.api, user1_address, std::slice::from_ref(&user1_token));
+    let info2 = get_message_info(&deps.api, user2_address, std::slice::from_ref(&user2_token));
     let msg = get_default_instantiate_msg(&deps.api);
 
     let res = instantiate(deps.as_mut(), env.clone(), info1.clone(), msg.clone());
@@ -564,7 +695,10 @@ fn unlock_tokens_multiple_users() {
         deps.as_mut(),
         env.clone(),
         info1.clone(),
-        ExecuteMsg::UnlockTokens { lock_ids: None },
+        ExecuteMsg::UnlockTokens {
+            lock_ids: None,
+            claim_tributes: None,
+        },
     );
     assert!(res.is_ok(), "unlocking tokens: {:?}", res);
 
@@ -590,7 +724,10 @@ fn unlock_tokens_multiple_users() {
         deps.as_mut(),
         env.clone(),
         info2.clone(),
-        ExecuteMsg::UnlockTokens { lock_ids: None },
+        ExecuteMsg::UnlockTokens {
+            lock_ids: None,
+            claim_tributes: None,
+        },
     );
     assert!(res.is_ok());
 
@@ -681,6 +818,9 @@ fn lock_tokens_multiple_validators_and_vote() {
         description: "proposal description 1".to_string(),
         minimum_atom_liquidity_request: Uint128::zero(),
         deployment_duration: 1,
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg1.clone());
     assert!(res.is_ok());
@@ -692,6 +832,9 @@ fn lock_tokens_multiple_validators_and_vote() {
         description: "proposal description 2".to_string(),
         minimum_atom_liquidity_request: Uint128::zero(),
         deployment_duration: 1,
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg2.clone());
     assert!(res.is_ok());
@@ -710,7 +853,7 @@ fn lock_tokens_multiple_validators_and_vote() {
     // check proposals
     {
         // Check the proposal scores
-        let proposals = query_round_tranche_proposals(deps.as_ref(), 0, 1, 0, 100);
+        let proposals = query_round_tranche_proposals(deps.as_ref(), 0, 1, 0, None, 100);
 
         // unwrap the proposals
         let proposals = proposals.unwrap();
@@ -766,6 +909,254 @@ fn lock_tokens_multiple_validators_and_vote() {
     }
 }
 
+// Checks that a power ratio that was recorded wrongly during a past round can be corrected
+// in batches, and that the dry run query shows the effect of the correction beforehand.
+#[test]
+fn recompute_round_totals_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([
+            (IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string()),
+            (IBC_DENOM_2.to_string(), VALIDATOR_2_LST_DENOM_1.to_string()),
+            (IBC_DENOM_3.to_string(), VALIDATOR_3_LST_DENOM_1.to_string()),
+        ]),
+    );
+
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let mut info = get_message_info(&deps.api, "addr0000", &[]);
+    let mut instantiate_msg = get_default_instantiate_msg(&deps.api);
+    instantiate_msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+
+    let res = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_msg.clone(),
+    );
+    assert!(res.is_ok(), "instantiating contract: {:?}", res);
+
+    set_validators_constant_power_ratios_for_rounds(
+        deps.as_mut(),
+        0,
+        100,
+        vec![
+            VALIDATOR_1.to_string(),
+            VALIDATOR_2.to_string(),
+            VALIDATOR_3.to_string(),
+        ],
+        vec![Decimal::one(), Decimal::percent(95), Decimal::percent(60)],
+    );
+
+    for token in [
+        Coin::new(1000u64, IBC_DENOM_1.to_string()),
+        Coin::new(2000u64, IBC_DENOM_2.to_string()),
+        Coin::new(3000u64, IBC_DENOM_3.to_string()),
+    ] {
+        info.funds = vec![token];
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "locking tokens: {:?}", res);
+    }
+    info.funds = vec![];
+
+    for i in 0..2 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id: 1,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            minimum_atom_liquidity_request: Uint128::zero(),
+            deployment_duration: 1,
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "creating proposal: {:?}", res);
+    }
+
+    // proposal 0 gets 2000 * 0.95 = 1900 power, proposal 1 gets 1000 * 1 + 3000 * 0.6 = 2800 power
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![
+            ProposalToLockups {
+                proposal_id: 0,
+                lock_ids: vec![1],
+            },
+            ProposalToLockups {
+                proposal_id: 1,
+                lock_ids: vec![0, 2],
+            },
+        ],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "voting: {:?}", res);
+
+    let recompute_msg = |corrected_ratio: Decimal| ExecuteMsg::RecomputeRoundTotals {
+        round_id: 0,
+        validator: VALIDATOR_1.to_string(),
+        corrected_ratio,
+        limit: 1,
+    };
+
+    // the round has not ended yet
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        recompute_msg(Decimal::percent(50)),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Power ratio can only be corrected for rounds that have ended"));
+
+    env.block.time = env.block.time.plus_nanos(instantiate_msg.round_length + 1);
+
+    // only whitelist admins can correct power ratios
+    let non_admin_info = get_message_info(&deps.api, "addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        non_admin_info,
+        recompute_msg(Decimal::percent(50)),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = query_recompute_round_totals_dry_run(
+        deps.as_ref(),
+        env.clone(),
+        0,
+        VALIDATOR_1.to_string(),
+        Decimal::percent(50),
+        0,
+        None,
+        100,
+    )
+    .unwrap();
+    assert_eq!(Decimal::one(), res.current_power_ratio);
+    assert_eq!(
+        Decimal::from_ratio(4700u128, 1u128),
+        res.current_round_total_power
+    );
+    assert_eq!(
+        Decimal::from_ratio(4200u128, 1u128),
+        res.corrected_round_total_power
+    );
+    assert_eq!(
+        vec![ProposalPowerDelta {
+            tranche_id: 1,
+            proposal_id: 1,
+            current_power: Uint128::new(2800),
+            corrected_power: Uint128::new(2300),
+        }],
+        res.proposals
+    );
+
+    // the first batch only processes proposal 0, which has no shares of validator 1
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        recompute_msg(Decimal::percent(50)),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::Attribute::new("completed", "false")));
+    assert!(POWER_RATIO_CORRECTIONS.has(&deps.storage, (0, VALIDATOR_1.to_string())));
+    assert_eq!(
+        Uint128::new(4200),
+        get_total_power_for_round(deps.as_ref(), 0)
+            .unwrap()
+            .to_uint_floor()
+    );
+
+    // the incrementally updated checkpoint matches the one computed from scratch
+    let checkpoint = get_total_power_for_round(deps.as_ref(), 0).unwrap();
+    recompute_total_power_for_round(&mut deps.storage, 0).unwrap();
+    assert_eq!(
+        checkpoint,
+        get_total_power_for_round(deps.as_ref(), 0).unwrap()
+    );
+
+    // the dry run still shows the pending change of proposal 1
+    let res = query_recompute_round_totals_dry_run(
+        deps.as_ref(),
+        env.clone(),
+        0,
+        VALIDATOR_1.to_string(),
+        Decimal::percent(50),
+        0,
+        None,
+        100,
+    )
+    .unwrap();
+    assert_eq!(Decimal::percent(50), res.current_power_ratio);
+    assert_eq!(
+        res.current_round_total_power,
+        res.corrected_round_total_power
+    );
+    assert_eq!(1, res.proposals.len());
+    assert_eq!(Uint128::new(2300), res.proposals[0].corrected_power);
+
+    // a different ratio can't be used while the correction is in progress
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        recompute_msg(Decimal::percent(40)),
+    );
+    assert!(res.unwrap_err().to_string().contains("already in progress"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        recompute_msg(Decimal::percent(50)),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::Attribute::new("completed", "true")));
+    assert!(!POWER_RATIO_CORRECTIONS.has(&deps.storage, (0, VALIDATOR_1.to_string())));
+
+    let proposals = query_round_tranche_proposals(deps.as_ref(), 0, 1, 0, None, 100).unwrap();
+    assert_eq!(1900, proposals.proposals[0].power.u128());
+    assert_eq!(2300, proposals.proposals[1].power.u128());
+
+    let top_proposals = query_top_n_proposals(deps.as_ref(), 0, 1, 2).unwrap();
+    assert_eq!(1, top_proposals.proposals[0].proposal_id);
+    assert_eq!(
+        Uint128::new(4200),
+        get_round_tranche_voted_power(&deps.storage, 0, 1).unwrap()
+    );
+
+    let res = query_recompute_round_totals_dry_run(
+        deps.as_ref(),
+        env.clone(),
+        0,
+        VALIDATOR_1.to_string(),
+        Decimal::percent(50),
+        0,
+        None,
+        100,
+    )
+    .unwrap();
+    assert!(res.proposals.is_empty());
+
+    // the ratio is already corrected
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        recompute_msg(Decimal::percent(50)),
+    );
+    assert!(res.unwrap_err().to_string().contains("is already"));
+}
+
 struct ValidatorSetInitializationTestCase {
     description: String,
     message: ExecuteMsg,
@@ -791,6 +1182,9 @@ fn validator_set_initialization_test() {
                 description: "proposal description".to_string(),
                 minimum_atom_liquidity_request: Uint128::zero(),
                 deployment_duration: 1,
+                description_hash: None,
+                description_uri: None,
+                max_vote_percentage: None,
             },
         },
         ValidatorSetInitializationTestCase {
@@ -874,6 +1268,9 @@ fn validator_set_initialization_test() {
             description: "proposal description".to_string(),
             minimum_atom_liquidity_request: Uint128::zero(),
             deployment_duration: 1,
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
         };
 
         let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
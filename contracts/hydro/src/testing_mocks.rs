@@ -7,7 +7,7 @@ use cosmwasm_std::{
         MockApi, MockQuerier as BaseMockQuerier, MockQuerierCustomHandlerResult, MockStorage,
     },
     Binary, Coin, ContractResult, GrpcQuery, OwnedDeps, Querier, QuerierResult, QueryRequest,
-    SystemError, SystemResult,
+    SystemError, SystemResult, WasmQuery,
 };
 use neutron_sdk::{
     bindings::{
@@ -63,6 +63,15 @@ impl MockQuerier {
 
         self
     }
+
+    pub fn with_wasm_handler<WH>(mut self, handler: WH) -> Self
+    where
+        WH: Fn(&WasmQuery) -> QuerierResult + 'static,
+    {
+        self.base_querier.update_wasm(handler);
+
+        self
+    }
 }
 
 // Overrides raw_query() to support gRPC queries. If the QueryRequest is
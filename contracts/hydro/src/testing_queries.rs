@@ -2,17 +2,24 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::contract::{
-    compute_current_round_id, query_all_user_lockups, query_all_user_lockups_with_tranche_infos,
-    query_specific_user_lockups, query_specific_user_lockups_with_tranche_infos, query_user_votes,
-    scale_lockup_power,
+    compute_current_round_id, query_all_notification_preferences, query_all_user_lockups,
+    query_all_user_lockups_with_tranche_infos, query_can_lock_denom_at_round, query_capabilities,
+    query_denom_validator, query_export_raw_state, query_notification_preferences,
+    query_proposal_voters, query_protocol_stats, query_round_cutoffs,
+    query_round_tranche_liquidity_deployments, query_specific_user_lockups,
+    query_specific_user_lockups_with_tranche_infos, query_user_votes, scale_lockup_power,
 };
-use crate::msg::ProposalToLockups;
+use crate::msg::{LiquidityDeployment, NotificationCategory, ProposalToLockups};
+use crate::query::{DenomResolution, RawStateMap, TrancheCapabilities};
 use crate::state::{
-    RoundLockPowerSchedule, ValidatorInfo, Vote, CONSTANTS, VALIDATORS_INFO, VOTE_MAP,
+    NotificationPreferences, Proposal, RoundLockPowerSchedule, ValidatorInfo, Vote, CONSTANTS,
+    LIQUIDITY_DEPLOYMENTS_MAP, PROPOSAL_MAP, VALIDATORS_INFO, VOTE_MAP,
 };
 use crate::testing::{
-    get_default_instantiate_msg, get_message_info, set_default_validator_for_rounds, IBC_DENOM_1,
-    ONE_MONTH_IN_NANO_SECONDS, VALIDATOR_1, VALIDATOR_1_LST_DENOM_1, VALIDATOR_2, VALIDATOR_3,
+    get_address_as_str, get_default_instantiate_msg, get_message_info,
+    set_default_validator_for_rounds, IBC_DENOM_1, IBC_DENOM_2, ONE_MONTH_IN_NANO_SECONDS,
+    TWO_WEEKS_IN_NANO_SECONDS, VALIDATOR_1, VALIDATOR_1_LST_DENOM_1, VALIDATOR_2,
+    VALIDATOR_2_LST_DENOM_1, VALIDATOR_3,
 };
 use crate::testing_lsm_integration::set_validator_power_ratio;
 use crate::testing_mocks::{
@@ -23,11 +30,12 @@ use crate::{
     msg::ExecuteMsg,
     state::LockEntry,
 };
+use cosmwasm_std::{attr, Addr, Decimal, StdError, StdResult, Uint128};
 use cosmwasm_std::{
+    from_json,
     testing::{mock_env, MockApi, MockStorage},
-    Coin, Env, OwnedDeps,
+    Coin, Deps, Env, OwnedDeps, Storage,
 };
-use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Uint128};
 use neutron_sdk::bindings::query::NeutronQuery;
 
 #[test]
@@ -121,6 +129,9 @@ fn query_user_lockups_test() {
         description: "proposal description 1".to_string(),
         deployment_duration: 1,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg1.clone());
     assert!(res.is_ok());
@@ -133,6 +144,9 @@ fn query_user_lockups_test() {
         description: "proposal description 2".to_string(),
         deployment_duration: 3,
         minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg2.clone());
     assert!(res.is_ok());
@@ -165,6 +179,7 @@ fn query_user_lockups_test() {
         env.clone(),
         info.sender.to_string(),
         0,
+        None,
         2000,
     );
     assert!(res.is_ok());
@@ -205,6 +220,7 @@ fn query_user_lockups_test() {
             ONE_MONTH_IN_NANO_SECONDS,
             3 * ONE_MONTH_IN_NANO_SECONDS,
             Uint128::new(second_lockup_amount),
+            false,
         )
         .u128(),
         res.lockups_with_per_tranche_infos[1]
@@ -279,6 +295,7 @@ fn query_user_lockups_test() {
         env.clone(),
         info.sender.to_string(),
         0,
+        None,
         2000,
     );
     assert!(all_lockups.is_ok());
@@ -321,6 +338,7 @@ fn query_user_lockups_test() {
             ONE_MONTH_IN_NANO_SECONDS,
             2 * ONE_MONTH_IN_NANO_SECONDS,
             Uint128::new(second_lockup_amount),
+            false,
         )
         .u128()
             / 2, // adjusted for the 50% power ratio,
@@ -367,6 +385,7 @@ fn query_user_lockups_test() {
         env.clone(),
         info.sender.to_string(),
         0,
+        None,
         2000,
     );
 
@@ -410,15 +429,24 @@ fn query_user_lockups_test() {
     );
 
     // unlock the tokens and verify that the user doesn't have any expired lockups after that
-    let msg = ExecuteMsg::UnlockTokens { lock_ids: None };
+    let msg = ExecuteMsg::UnlockTokens {
+        lock_ids: None,
+        claim_tributes: None,
+    };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
     assert!(res.is_ok());
 
     let expired_lockups = get_expired_user_lockups(&deps, env.clone(), info.sender.to_string());
     assert_eq!(0, expired_lockups.len());
 
-    let all_lockups =
-        query_all_user_lockups(deps.as_ref(), env.clone(), info.sender.to_string(), 0, 2000);
+    let all_lockups = query_all_user_lockups(
+        deps.as_ref(),
+        env.clone(),
+        info.sender.to_string(),
+        0,
+        None,
+        2000,
+    );
     assert!(all_lockups.is_ok());
 
     let all_lockups = all_lockups.unwrap();
@@ -546,6 +574,7 @@ fn query_user_votes_test() {
                             VALIDATOR_1.to_string(),
                             Decimal::from_ratio(500u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
                 VoteToCreate {
@@ -558,6 +587,7 @@ fn query_user_votes_test() {
                             VALIDATOR_2.to_string(),
                             Decimal::from_ratio(300u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
                 VoteToCreate {
@@ -570,6 +600,7 @@ fn query_user_votes_test() {
                             VALIDATOR_2.to_string(),
                             Decimal::from_ratio(700u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
             ],
@@ -618,6 +649,7 @@ fn query_user_votes_test() {
                             VALIDATOR_1.to_string(),
                             Decimal::from_ratio(500u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
                 VoteToCreate {
@@ -630,6 +662,7 @@ fn query_user_votes_test() {
                             VALIDATOR_2.to_string(),
                             Decimal::from_ratio(500u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
                 VoteToCreate {
@@ -642,6 +675,7 @@ fn query_user_votes_test() {
                             VALIDATOR_2.to_string(),
                             Decimal::from_ratio(700u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
             ],
@@ -688,6 +722,7 @@ fn query_user_votes_test() {
                             VALIDATOR_1.to_string(),
                             Decimal::from_ratio(500u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
                 VoteToCreate {
@@ -700,6 +735,7 @@ fn query_user_votes_test() {
                             VALIDATOR_2.to_string(),
                             Decimal::from_ratio(500u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
             ],
@@ -731,6 +767,7 @@ fn query_user_votes_test() {
                             VALIDATOR_1.to_string(),
                             Decimal::from_ratio(500u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
                 VoteToCreate {
@@ -743,6 +780,7 @@ fn query_user_votes_test() {
                             VALIDATOR_2.to_string(),
                             Decimal::from_ratio(300u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
                 VoteToCreate {
@@ -755,6 +793,7 @@ fn query_user_votes_test() {
                             VALIDATOR_2.to_string(),
                             Decimal::from_ratio(700u128, Uint128::one()),
                         ),
+                        tribute_weight_multiplier: None,
                     },
                 },
             ],
@@ -848,6 +887,466 @@ fn query_user_votes_test() {
     }
 }
 
+#[test]
+fn query_round_cutoffs_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let mut instantiate_msg = get_default_instantiate_msg(&deps.api);
+    instantiate_msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg);
+    assert!(res.is_ok());
+
+    let round_id = 1;
+    let round_end = env.block.time.plus_nanos(2 * TWO_WEEKS_IN_NANO_SECONDS);
+
+    // by default, tributes can be added until the round ends
+    let res = query_round_cutoffs(deps.as_ref(), round_id).unwrap();
+    assert_eq!(round_end, res.round_end);
+    assert_eq!(round_end, res.tribute_cutoff);
+
+    // tribute cutoff duration must be shorter than the round length
+    let update_config_msg = |tribute_cutoff_duration| ExecuteMsg::UpdateConfig {
+        max_locked_tokens: None,
+        max_deployment_duration: None,
+        tribute_cutoff_duration: Some(tribute_cutoff_duration),
+        max_proposals_per_tranche_round: None,
+        raw_export_enabled: None,
+        unlock_cooldown_duration: None,
+        max_validator_shares_participating: None,
+        max_proposal_title_length: None,
+        max_proposal_description_length: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        update_config_msg(TWO_WEEKS_IN_NANO_SECONDS),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("must be shorter than the round length"));
+
+    let tribute_cutoff_duration = TWO_WEEKS_IN_NANO_SECONDS / 14;
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        update_config_msg(tribute_cutoff_duration),
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_round_cutoffs(deps.as_ref(), round_id).unwrap();
+    assert_eq!(round_end, res.round_end);
+    assert_eq!(
+        round_end.minus_nanos(tribute_cutoff_duration),
+        res.tribute_cutoff
+    );
+}
+
+#[test]
+fn query_notification_preferences_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let instantiate_msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info, instantiate_msg);
+    assert!(res.is_ok());
+
+    let set_preferences =
+        |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+         sender: &str,
+         endpoint: &str,
+         categories: Vec<NotificationCategory>| {
+            let info = get_message_info(&deps.api, sender, &[]);
+            let msg = ExecuteMsg::SetNotificationPreferences {
+                endpoint: endpoint.to_string(),
+                categories,
+            };
+            execute(deps.as_mut(), env.clone(), info, msg)
+        };
+
+    // the endpoint and at least one category must be provided
+    let res = set_preferences(
+        &mut deps,
+        "addr0000",
+        " ",
+        vec![NotificationCategory::LockExpiry],
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Notification endpoint must be between 1 and"));
+    let res = set_preferences(&mut deps, "addr0000", "pubkey0", vec![]);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("At least one notification category must be provided"));
+
+    // duplicate categories are removed
+    let res = set_preferences(
+        &mut deps,
+        "addr0000",
+        "pubkey0",
+        vec![
+            NotificationCategory::ProposalWon,
+            NotificationCategory::LockExpiry,
+            NotificationCategory::ProposalWon,
+        ],
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = set_preferences(
+        &mut deps,
+        "addr0001",
+        "pubkey1",
+        vec![NotificationCategory::TributeAvailable],
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let user_0 = get_address_as_str(&deps.api, "addr0000");
+    let res = query_notification_preferences(deps.as_ref(), user_0.clone()).unwrap();
+    assert_eq!(
+        Some(NotificationPreferences {
+            endpoint: "pubkey0".to_string(),
+            categories: vec![
+                NotificationCategory::LockExpiry,
+                NotificationCategory::ProposalWon,
+            ],
+            updated_at: env.block.time,
+        }),
+        res.preferences
+    );
+
+    // all preferences are returned when no category is given
+    let res = query_all_notification_preferences(deps.as_ref(), None, 0, None, 10).unwrap();
+    assert_eq!(2, res.preferences.len());
+
+    let res = query_all_notification_preferences(deps.as_ref(), None, 1, None, 10).unwrap();
+    assert_eq!(1, res.preferences.len());
+
+    let res = query_all_notification_preferences(
+        deps.as_ref(),
+        Some(NotificationCategory::TributeAvailable),
+        0,
+        None,
+        10,
+    )
+    .unwrap();
+    assert_eq!(1, res.preferences.len());
+    assert_eq!(
+        get_address_as_str(&deps.api, "addr0001"),
+        res.preferences[0].address.to_string()
+    );
+
+    // users can remove their preferences
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::RemoveNotificationPreferences {},
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let res = query_notification_preferences(deps.as_ref(), user_0).unwrap();
+    assert_eq!(None, res.preferences);
+
+    let res = query_all_notification_preferences(deps.as_ref(), None, 0, None, 10).unwrap();
+    assert_eq!(1, res.preferences.len());
+}
+
+#[test]
+fn query_proposal_voters_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let user1 = "addr0000";
+    let user2 = "addr0001";
+    let info = get_message_info(&deps.api, user1, &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    // user1 gets locks 0, 1 and 2, while user2 gets lock 3
+    for user in [user1, user1, user1, user2] {
+        let info = get_message_info(
+            &deps.api,
+            user,
+            &[Coin::new(1000u64, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    for i in 0..2 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id: 1,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let vote = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
+                user: &str,
+                proposals_votes: Vec<(u64, Vec<u64>)>| {
+        let msg = ExecuteMsg::Vote {
+            tranche_id: 1,
+            proposals_votes: proposals_votes
+                .into_iter()
+                .map(|(proposal_id, lock_ids)| ProposalToLockups {
+                    proposal_id,
+                    lock_ids,
+                })
+                .collect(),
+        };
+        let info = get_message_info(&deps.api, user, &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    };
+    let get_voters = |deps: Deps<NeutronQuery>, proposal_id: u64| {
+        query_proposal_voters(deps, 0, 1, proposal_id, None, 10)
+            .unwrap()
+            .voters
+            .into_iter()
+            .map(|voter| (voter.lock_id, voter.owner.to_string()))
+            .collect::<Vec<(u64, String)>>()
+    };
+
+    vote(&mut deps, user1, vec![(0, vec![0, 1]), (1, vec![2])]);
+    vote(&mut deps, user2, vec![(0, vec![3])]);
+
+    let user1_addr = deps.api.addr_make(user1);
+    let user2_addr = deps.api.addr_make(user2);
+
+    // the voters are paginated by the lock id
+    let res = query_proposal_voters(deps.as_ref(), 0, 1, 0, None, 2).unwrap();
+    assert_eq!(2, res.voters.len());
+    assert_eq!(0, res.voters[0].lock_id);
+    assert_eq!(user1_addr, res.voters[0].owner);
+    assert_eq!(Decimal::from_ratio(1000u128, 1u128), res.voters[0].power);
+    assert_eq!(Some(1), res.next_key);
+
+    let res = query_proposal_voters(deps.as_ref(), 0, 1, 0, res.next_key, 2).unwrap();
+    assert_eq!(1, res.voters.len());
+    assert_eq!(3, res.voters[0].lock_id);
+    assert_eq!(user2_addr, res.voters[0].owner);
+    assert_eq!(None, res.next_key);
+
+    // switching the vote of a lock moves it to the voters of the other proposal
+    vote(&mut deps, user1, vec![(1, vec![1])]);
+    assert_eq!(
+        vec![(0, user1_addr.to_string()), (3, user2_addr.to_string())],
+        get_voters(deps.as_ref(), 0)
+    );
+    assert_eq!(
+        vec![(1, user1_addr.to_string()), (2, user1_addr.to_string())],
+        get_voters(deps.as_ref(), 1)
+    );
+
+    // unvoting removes the locks from the voters
+    let msg = ExecuteMsg::UnvoteAll {
+        tranche_id: 1,
+        owner: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+    assert_eq!(
+        vec![(3, user2_addr.to_string())],
+        get_voters(deps.as_ref(), 0)
+    );
+    assert!(get_voters(deps.as_ref(), 1).is_empty());
+}
+
+#[test]
+fn query_cursor_pagination_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env, info, msg);
+    assert!(res.is_ok());
+
+    let (round_id, tranche_id) = (0, 1);
+    let save_deployment = |storage: &mut dyn Storage, proposal_id: u64| {
+        let deployment = LiquidityDeployment {
+            round_id,
+            tranche_id,
+            proposal_id,
+            destinations: vec![],
+            deployed_funds: vec![],
+            funds_before_deployment: vec![],
+            total_rounds: 1,
+            remaining_rounds: 1,
+        };
+        LIQUIDITY_DEPLOYMENTS_MAP
+            .save(storage, (round_id, tranche_id, proposal_id), &deployment)
+            .unwrap();
+    };
+    for proposal_id in 0..5 {
+        save_deployment(&mut deps.storage, proposal_id);
+    }
+
+    let get_page = |deps: Deps<NeutronQuery>, start_from: u64, start_after: Option<u64>| {
+        query_round_tranche_liquidity_deployments(
+            deps,
+            round_id,
+            tranche_id,
+            start_from,
+            start_after,
+            2,
+        )
+        .unwrap()
+        .liquidity_deployments
+        .into_iter()
+        .map(|deployment| deployment.proposal_id)
+        .collect::<Vec<u64>>()
+    };
+
+    assert_eq!(vec![0, 1], get_page(deps.as_ref(), 0, None));
+
+    // an entry of the first page gets removed before the second page is loaded
+    LIQUIDITY_DEPLOYMENTS_MAP.remove(&mut deps.storage, (round_id, tranche_id, 0));
+
+    // the offset-based pagination skips an entry, while the cursor-based one doesn't
+    assert_eq!(vec![3, 4], get_page(deps.as_ref(), 2, None));
+    assert_eq!(vec![2, 3], get_page(deps.as_ref(), 0, Some(1)));
+
+    // start_after takes precedence over start_from
+    assert_eq!(vec![2, 3], get_page(deps.as_ref(), 3, Some(1)));
+
+    // entries added after the cursor are picked up by the next page
+    save_deployment(&mut deps.storage, 10);
+    assert_eq!(vec![4, 10], get_page(deps.as_ref(), 0, Some(3)));
+    assert!(get_page(deps.as_ref(), 0, Some(10)).is_empty());
+
+    // a full page returns the key of its last entry as the cursor of the next page
+    let res =
+        query_round_tranche_liquidity_deployments(deps.as_ref(), round_id, tranche_id, 0, None, 2)
+            .unwrap();
+    assert_eq!(Some(2), res.next_key);
+    let res = query_round_tranche_liquidity_deployments(
+        deps.as_ref(),
+        round_id,
+        tranche_id,
+        0,
+        res.next_key,
+        2,
+    )
+    .unwrap();
+    assert_eq!(Some(4), res.next_key);
+
+    // the last page holds fewer entries than the limit and doesn't return a cursor
+    let res = query_round_tranche_liquidity_deployments(
+        deps.as_ref(),
+        round_id,
+        tranche_id,
+        0,
+        res.next_key,
+        2,
+    )
+    .unwrap();
+    assert_eq!(1, res.liquidity_deployments.len());
+    assert_eq!(None, res.next_key);
+}
+
+#[test]
+fn query_export_raw_state_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let mut instantiate_msg = get_default_instantiate_msg(&deps.api);
+    instantiate_msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg);
+    assert!(res.is_ok());
+
+    for i in 0..3 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id: 1,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // the export is disabled by default
+    let res = query_export_raw_state(deps.as_ref(), RawStateMap::Proposals, None, 10);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Raw state export is not enabled"));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        max_locked_tokens: None,
+        max_deployment_duration: None,
+        tribute_cutoff_duration: None,
+        max_proposals_per_tranche_round: None,
+        raw_export_enabled: Some(true),
+        unlock_cooldown_duration: None,
+        max_validator_shares_participating: None,
+        max_proposal_title_length: None,
+        max_proposal_description_length: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let first_page =
+        query_export_raw_state(deps.as_ref(), RawStateMap::Proposals, None, 2).unwrap();
+    assert_eq!(2, first_page.entries.len());
+    assert_eq!(Some(first_page.entries[1].key.clone()), first_page.next_key);
+
+    let second_page = query_export_raw_state(
+        deps.as_ref(),
+        RawStateMap::Proposals,
+        first_page.next_key,
+        2,
+    )
+    .unwrap();
+    assert_eq!(1, second_page.entries.len());
+    assert_eq!(None, second_page.next_key);
+
+    // the raw entries can be used to rebuild the proposals in the same order
+    let proposals = first_page
+        .entries
+        .iter()
+        .chain(second_page.entries.iter())
+        .map(|entry| from_json::<Proposal>(&entry.value).unwrap())
+        .collect::<Vec<Proposal>>();
+    for (proposal_id, proposal) in proposals.iter().enumerate() {
+        assert_eq!(proposal_id as u64, proposal.proposal_id);
+        assert_eq!(
+            *proposal,
+            PROPOSAL_MAP
+                .load(&deps.storage, (0, 1, proposal_id as u64))
+                .unwrap()
+        );
+    }
+
+    let res = query_export_raw_state(deps.as_ref(), RawStateMap::Votes, None, 10).unwrap();
+    assert!(res.entries.is_empty());
+}
+
 fn get_expired_user_lockups(
     deps: &OwnedDeps<MockStorage, MockApi, MockQuerier, NeutronQuery>,
     env: Env,
@@ -858,6 +1357,7 @@ fn get_expired_user_lockups(
         env.clone(),
         user_address.to_string(),
         0,
+        None,
         2000,
     );
     assert!(res.is_ok());
@@ -876,3 +1376,376 @@ fn get_user_voting_power(
 
     res.unwrap().voting_power
 }
+
+#[test]
+fn query_protocol_stats_test() {
+    let user_address_1 = "addr0000";
+    let user_address_2 = "addr0001";
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, user_address_1, &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, user_address_1)];
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    env.block.time = env.block.time.plus_days(1);
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    // both users lock tokens, which gives them voting power equal to the locked amount
+    for (user_address, amount) in [(user_address_1, 1000u128), (user_address_2, 2000u128)] {
+        let info = get_message_info(
+            &deps.api,
+            user_address,
+            &[Coin::new(amount, IBC_DENOM_1.to_string())],
+        );
+        let msg = ExecuteMsg::LockTokens {
+            lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    for i in 0..2 {
+        let msg = ExecuteMsg::CreateProposal {
+            round_id: None,
+            tranche_id: 1,
+            title: format!("proposal title {}", i),
+            description: format!("proposal description {}", i),
+            deployment_duration: 1,
+            minimum_atom_liquidity_request: Uint128::zero(),
+            description_hash: None,
+            description_uri: None,
+            max_vote_percentage: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // only the first user votes, and then switches the vote to the other proposal
+    for proposal_id in [0, 1] {
+        let msg = ExecuteMsg::Vote {
+            tranche_id: 1,
+            proposals_votes: vec![ProposalToLockups {
+                proposal_id,
+                lock_ids: vec![0],
+            }],
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    let stats = query_protocol_stats(deps.as_ref(), env.clone(), None, 100).unwrap();
+    assert_eq!(1, stats.rounds.len());
+
+    let round_stats = &stats.rounds[0];
+    assert_eq!(0, round_stats.round_id);
+    assert_eq!(Uint128::new(3000), round_stats.total_voting_power);
+    assert_eq!(1, round_stats.unique_voters);
+    assert_eq!(2, round_stats.proposals_count);
+    assert!(round_stats.deployed_funds.is_empty());
+    assert_eq!(1, round_stats.tranches.len());
+    assert_eq!(Uint128::new(1000), round_stats.tranches[0].voted_power);
+    assert_eq!(
+        Decimal::from_ratio(1u128, 3u128),
+        round_stats.tranches[0].participation
+    );
+
+    // the second user votes as well, so every locked token participates
+    let info_2 = get_message_info(&deps.api, user_address_2, &[]);
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![1],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info_2, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let deployment_msg = ExecuteMsg::AddLiquidityDeployment {
+        round_id: 0,
+        tranche_id: 1,
+        proposal_id: 0,
+        destinations: vec![],
+        deployed_funds: vec![Coin::new(500u128, "uatom")],
+        funds_before_deployment: vec![],
+        total_rounds: 1,
+        remaining_rounds: 0,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), deployment_msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let stats = query_protocol_stats(deps.as_ref(), env.clone(), None, 100).unwrap();
+    let round_stats = &stats.rounds[0];
+    assert_eq!(2, round_stats.unique_voters);
+    assert_eq!(Uint128::new(3000), round_stats.tranches[0].voted_power);
+    assert_eq!(Decimal::one(), round_stats.tranches[0].participation);
+    assert_eq!(
+        vec![Coin::new(500u128, "uatom")],
+        round_stats.deployed_funds
+    );
+
+    // removing the deployment also removes its funds from the statistics
+    let msg = ExecuteMsg::RemoveLiquidityDeployment {
+        round_id: 0,
+        tranche_id: 1,
+        proposal_id: 0,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // a user that removes all of its votes in the round is no longer counted among its voters
+    let info_2 = get_message_info(&deps.api, user_address_2, &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info_2,
+        ExecuteMsg::UnvoteAll {
+            tranche_id: 1,
+            owner: None,
+        },
+    );
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let stats = query_protocol_stats(deps.as_ref(), env.clone(), None, 100).unwrap();
+    assert_eq!(1, stats.rounds[0].unique_voters);
+    assert_eq!(Uint128::new(1000), stats.rounds[0].tranches[0].voted_power);
+
+    // the same applies if the user votes again, but the lock can't vote for the new proposal,
+    // since it doesn't remain locked for the whole deployment duration
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id: 1,
+        title: "proposal title 2".to_string(),
+        description: "proposal description 2".to_string(),
+        deployment_duration: 12,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let msg = ExecuteMsg::Vote {
+        tranche_id: 1,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 2,
+            lock_ids: vec![0],
+        }],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert!(res.attributes.contains(&attr("locks_skipped", "0")));
+
+    let stats = query_protocol_stats(deps.as_ref(), env.clone(), None, 100).unwrap();
+    assert_eq!(0, stats.rounds[0].unique_voters);
+    assert_eq!(Uint128::zero(), stats.rounds[0].tranches[0].voted_power);
+
+    // statistics are returned for every round up to the current one
+    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS);
+    let stats = query_protocol_stats(deps.as_ref(), env.clone(), None, 100).unwrap();
+    assert_eq!(2, stats.rounds.len());
+    assert_eq!(0, stats.first_tracked_round_id);
+    assert_eq!(None, stats.next_key);
+    assert!(stats.rounds[0].deployed_funds.is_empty());
+    assert_eq!(0, stats.rounds[1].unique_voters);
+    assert_eq!(0, stats.rounds[1].proposals_count);
+
+    // the rounds can be paged through
+    let stats = query_protocol_stats(deps.as_ref(), env.clone(), None, 1).unwrap();
+    assert_eq!(1, stats.rounds.len());
+    assert_eq!(0, stats.rounds[0].round_id);
+    assert_eq!(Some(0), stats.next_key);
+
+    let stats = query_protocol_stats(deps.as_ref(), env.clone(), stats.next_key, 1).unwrap();
+    assert_eq!(1, stats.rounds.len());
+    assert_eq!(1, stats.rounds[0].round_id);
+
+    let stats = query_protocol_stats(deps.as_ref(), env.clone(), Some(1), 1).unwrap();
+    assert!(stats.rounds.is_empty());
+    assert_eq!(None, stats.next_key);
+}
+
+#[test]
+fn query_capabilities_test() {
+    let user_address = "addr0000";
+    let (mut deps, mut env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, user_address, &[]);
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, user_address)];
+
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let capabilities = query_capabilities(deps.as_ref(), env.clone()).unwrap();
+    assert_eq!(capabilities.contract_name, crate::contract::CONTRACT_NAME);
+    assert_eq!(
+        capabilities.contract_version,
+        crate::contract::CONTRACT_VERSION
+    );
+    assert!(!capabilities.features.tribute_contract_set);
+    assert!(!capabilities.features.unlock_cooldown);
+    assert_eq!(capabilities.limits.max_lock_entries, 100);
+    assert_eq!(
+        capabilities.tranches,
+        vec![TrancheCapabilities {
+            tranche_id: 1,
+            hash_only_descriptions: false,
+            quadratic_power_scaling: false,
+        }]
+    );
+
+    for msg in [
+        ExecuteMsg::SetTrancheDescriptionMode {
+            tranche_id: 1,
+            hash_only: true,
+        },
+        ExecuteMsg::SetTranchePowerScaling {
+            tranche_id: 1,
+            quadratic: true,
+        },
+        ExecuteMsg::UpdateConfig {
+            max_locked_tokens: None,
+            max_deployment_duration: None,
+            tribute_cutoff_duration: None,
+            max_proposals_per_tranche_round: None,
+            raw_export_enabled: None,
+            unlock_cooldown_duration: Some(ONE_MONTH_IN_NANO_SECONDS),
+            max_validator_shares_participating: None,
+            max_proposal_title_length: None,
+            max_proposal_description_length: None,
+        },
+    ] {
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert!(res.is_ok(), "error: {:?}", res);
+    }
+
+    // the hash-only descriptions are enabled right away, while the power scaling
+    // change takes effect starting from the next round
+    let capabilities = query_capabilities(deps.as_ref(), env.clone()).unwrap();
+    assert!(capabilities.features.unlock_cooldown);
+    assert!(capabilities.tranches[0].hash_only_descriptions);
+    assert!(!capabilities.tranches[0].quadratic_power_scaling);
+
+    env.block.time = env.block.time.plus_nanos(TWO_WEEKS_IN_NANO_SECONDS + 1);
+    let capabilities = query_capabilities(deps.as_ref(), env.clone()).unwrap();
+    assert!(capabilities.tranches[0].quadratic_power_scaling);
+}
+
+#[test]
+fn query_denom_validator_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([
+            (IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string()),
+            (IBC_DENOM_2.to_string(), VALIDATOR_2_LST_DENOM_1.to_string()),
+        ]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    // only VALIDATOR_1 is in the validator set
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let res = query_denom_validator(deps.as_ref(), env.clone(), IBC_DENOM_1.to_string()).unwrap();
+    assert_eq!(res.round_id, 0);
+    assert_eq!(
+        res.resolution,
+        DenomResolution::Lockable {
+            validator: VALIDATOR_1.to_string(),
+            power_ratio: Decimal::one(),
+        }
+    );
+
+    let res = query_denom_validator(deps.as_ref(), env.clone(), IBC_DENOM_2.to_string()).unwrap();
+    match res.resolution {
+        DenomResolution::NotLockable { validator, reason } => {
+            assert_eq!(validator, Some(VALIDATOR_2.to_string()));
+            assert!(reason.contains("is not among the top"));
+        }
+        resolution => panic!("unexpected resolution: {:?}", resolution),
+    }
+
+    let res = query_denom_validator(deps.as_ref(), env, "uatom".to_string()).unwrap();
+    assert_eq!(
+        res.resolution,
+        DenomResolution::NotLockable {
+            validator: None,
+            reason: "Generic error: IBC token expected".to_string(),
+        }
+    );
+}
+
+#[test]
+fn query_can_lock_denom_at_round_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([
+            (IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string()),
+            (IBC_DENOM_2.to_string(), VALIDATOR_2_LST_DENOM_1.to_string()),
+        ]),
+    );
+    let (mut deps, mut env) = (mock_dependencies(grpc_query), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    // VALIDATOR_1 is in the validator set of rounds 0 and 1, and VALIDATOR_2 in the one of round 3,
+    // while round 2 had no activity, so its validator set was never stored
+    set_default_validator_for_rounds(deps.as_mut(), 0, 2);
+    set_validator_power_ratio(deps.as_mut().storage, 3, VALIDATOR_2, Decimal::one());
+
+    env.block.time = env.block.time.plus_nanos(4 * TWO_WEEKS_IN_NANO_SECONDS + 1);
+
+    let can_lock = |denom: &str, round_id: u64| {
+        query_can_lock_denom_at_round(deps.as_ref(), env.clone(), denom.to_string(), round_id)
+    };
+
+    for round_id in [1, 2] {
+        let res = can_lock(IBC_DENOM_1, round_id).unwrap();
+        assert_eq!(res.round_id, round_id);
+        assert_eq!(
+            res.resolution,
+            DenomResolution::Lockable {
+                validator: VALIDATOR_1.to_string(),
+                power_ratio: Decimal::one(),
+            }
+        );
+    }
+
+    let res = can_lock(IBC_DENOM_1, 3).unwrap();
+    match res.resolution {
+        DenomResolution::NotLockable { validator, reason } => {
+            assert_eq!(validator, Some(VALIDATOR_1.to_string()));
+            assert!(reason.contains("is not among the top"));
+            assert!(reason.contains("in round 3"));
+        }
+        resolution => panic!("unexpected resolution: {:?}", resolution),
+    }
+
+    for round_id in [3, 4] {
+        let res = can_lock(IBC_DENOM_2, round_id).unwrap();
+        assert_eq!(
+            res.resolution,
+            DenomResolution::Lockable {
+                validator: VALIDATOR_2.to_string(),
+                power_ratio: Decimal::one(),
+            }
+        );
+    }
+
+    let res = can_lock(IBC_DENOM_1, 5);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Round 5 has not started yet"));
+}
@@ -3,24 +3,27 @@ use std::collections::HashMap;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::Validator as CosmosValidator;
 use cosmos_sdk_proto::prost::Message;
 use cosmwasm_std::{
-    attr, coins, testing::mock_env, Addr, BankMsg, Binary, Coin, Decimal, SubMsg, Uint128,
+    attr, coins, testing::mock_env, to_json_vec, Addr, BankMsg, Binary, Coin, Decimal, MsgResponse,
+    Reply, SubMsg, SubMsgResponse, SubMsgResult, Uint128,
 };
 use neutron_sdk::{
     bindings::types::StorageValue,
     interchain_queries::{types::QueryType, v047::types::STAKING_STORE_KEY},
+    proto_types::neutron::interchainqueries::MsgRegisterInterchainQueryResponse,
     sudo::msg::SudoMsg,
 };
 
 use crate::{
     contract::{
-        execute, instantiate, query_icq_managers, query_validators_info,
-        query_validators_per_round, sudo, NATIVE_TOKEN_DENOM,
+        execute, instantiate, query_icq_managers, query_validator_slashes, query_validators_info,
+        query_validators_per_round, reply, sudo, NATIVE_TOKEN_DENOM,
     },
     error::ContractError,
+    lsm_integration::add_lock_to_validator_index,
     msg::ExecuteMsg,
     state::{
-        ValidatorInfo, QUERY_ID_TO_VALIDATOR, VALIDATORS_INFO, VALIDATORS_PER_ROUND,
-        VALIDATOR_TO_QUERY_ID,
+        ValidatorInfo, ValidatorSlashInfo, QUERY_ID_TO_VALIDATOR, VALIDATORS_INFO,
+        VALIDATORS_PER_ROUND, VALIDATOR_TO_QUERY_ID,
     },
     testing::{
         get_address_as_str, get_default_instantiate_msg, get_message_info, VALIDATOR_1,
@@ -30,7 +33,10 @@ use crate::{
         custom_interchain_query_mock, min_query_deposit_grpc_query_mock, mock_dependencies,
         no_op_grpc_query_mock, ICQMockData,
     },
-    validators_icqs::TOKENS_TO_SHARES_MULTIPLIER,
+    validators_icqs::{
+        ReplyPayload, ReplyPayloadEnvelope, CREATE_VALIDATOR_ICQ_REPLY_ID, LEGACY_REPLY_ID,
+        REMOVE_VALIDATOR_ICQ_REPLY_ID, REPLY_PAYLOAD_VERSION, TOKENS_TO_SHARES_MULTIPLIER,
+    },
 };
 
 struct ICQResultsParseTestCase {
@@ -77,7 +83,7 @@ fn create_interchain_queries_test() {
     // in the msg above there are 2 valid addresses, hence 2 * min_deposit
     let min_deposit_required = Coin::new(2 * min_deposit.amount.u128(), min_deposit.denom.clone());
 
-    let info = get_message_info(&deps.api, "addr0000", &[user_token.clone()]);
+    let info = get_message_info(&deps.api, "addr0000", std::slice::from_ref(&user_token));
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
 
     assert!(
@@ -85,11 +91,126 @@ fn create_interchain_queries_test() {
         .to_string()
         .to_lowercase().contains(format!("insufficient tokens sent to pay for {} interchain queries deposits. sent: {}, required: {}", 2, user_token, min_deposit_required).as_str()));
 
-    let info = get_message_info(&deps.api, "addr0000", &[min_deposit_required.clone()]);
+    let info = get_message_info(
+        &deps.api,
+        "addr0000",
+        std::slice::from_ref(&min_deposit_required),
+    );
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert!(res.is_ok());
     let messages = res.unwrap().messages;
     assert_eq!(messages.len(), 2);
+    assert!(messages
+        .iter()
+        .all(|msg| msg.id == CREATE_VALIDATOR_ICQ_REPLY_ID));
+}
+
+#[test]
+fn reply_payload_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    let build_reply = |id: u64, payload: Vec<u8>, query_id: u64| Reply {
+        id,
+        payload: Binary::from(payload),
+        gas_used: 0,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            #[allow(deprecated)]
+            data: None,
+            msg_responses: vec![MsgResponse {
+                type_url: "/neutron.interchainqueries.MsgRegisterInterchainQueryResponse"
+                    .to_string(),
+                value: Binary::from(
+                    MsgRegisterInterchainQueryResponse { id: query_id }.encode_to_vec(),
+                ),
+            }],
+        }),
+    };
+    let envelope = |version: u32, payload: ReplyPayload| {
+        to_json_vec(&ReplyPayloadEnvelope { version, payload }).unwrap()
+    };
+
+    // (description, reply, expected_error)
+    let test_cases = vec![
+        (
+            "versioned payload with matching reply id",
+            build_reply(
+                CREATE_VALIDATOR_ICQ_REPLY_ID,
+                envelope(
+                    REPLY_PAYLOAD_VERSION,
+                    ReplyPayload::CreateValidatorICQ(VALIDATOR_1.to_string()),
+                ),
+                1,
+            ),
+            None,
+        ),
+        (
+            "unversioned payload sent before the migration",
+            build_reply(
+                LEGACY_REPLY_ID,
+                to_json_vec(&ReplyPayload::CreateValidatorICQ(VALIDATOR_2.to_string())).unwrap(),
+                2,
+            ),
+            None,
+        ),
+        (
+            "payload of a different flow than the reply id",
+            build_reply(
+                CREATE_VALIDATOR_ICQ_REPLY_ID,
+                envelope(REPLY_PAYLOAD_VERSION, ReplyPayload::RemoveValidatorICQ(1)),
+                3,
+            ),
+            Some("Reply payload doesn't match the reply ID: 1"),
+        ),
+        (
+            "unsupported payload version",
+            build_reply(
+                CREATE_VALIDATOR_ICQ_REPLY_ID,
+                envelope(
+                    REPLY_PAYLOAD_VERSION + 1,
+                    ReplyPayload::CreateValidatorICQ(VALIDATOR_3.to_string()),
+                ),
+                4,
+            ),
+            Some("Unsupported reply payload version: 2"),
+        ),
+        (
+            "unversioned payload with a new reply id",
+            build_reply(
+                CREATE_VALIDATOR_ICQ_REPLY_ID,
+                to_json_vec(&ReplyPayload::CreateValidatorICQ(VALIDATOR_3.to_string())).unwrap(),
+                5,
+            ),
+            Some("Error parsing"),
+        ),
+    ];
+
+    for (description, msg, expected_error) in test_cases {
+        println!("running test case: {}", description);
+
+        let res = reply(deps.as_mut(), env.clone(), msg);
+        match expected_error {
+            None => assert!(res.is_ok(), "error: {:?}", res),
+            Some(error) => {
+                let err = res.unwrap_err().to_string();
+                assert!(err.contains(error), "unexpected error: {}", err);
+            }
+        }
+    }
+
+    assert_eq!(
+        VALIDATOR_1.to_string(),
+        QUERY_ID_TO_VALIDATOR.load(&deps.storage, 1).unwrap()
+    );
+    assert_eq!(
+        VALIDATOR_2.to_string(),
+        QUERY_ID_TO_VALIDATOR.load(&deps.storage, 2).unwrap()
+    );
+    assert!(!QUERY_ID_TO_VALIDATOR.has(&deps.storage, 5));
 }
 
 #[test]
@@ -407,8 +528,7 @@ fn icq_results_state_update_test() {
         let current_round = 0u64;
 
         // setup initial validators
-        let mut mock_query_id = 1;
-        for validator in test_case.initial_validators {
+        for (mock_query_id, validator) in (1..).zip(test_case.initial_validators) {
             let res = VALIDATORS_INFO.save(
                 deps.as_mut().storage,
                 (current_round, validator.address.clone()),
@@ -439,8 +559,6 @@ fn icq_results_state_update_test() {
                 &validator.address,
             );
             assert!(res.is_ok());
-
-            mock_query_id += 1;
         }
 
         let msg = SudoMsg::KVQueryResult {
@@ -490,6 +608,132 @@ fn icq_results_state_update_test() {
     }
 }
 
+#[test]
+fn icq_results_validator_slash_test() {
+    let (mut deps, env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg.clone());
+    assert!(res.is_ok());
+
+    let current_round = 0u64;
+    let query_id = 1u64;
+    let validator = ValidatorInfo::new(
+        VALIDATOR_1.to_string(),
+        Uint128::new(300000000),
+        Decimal::one(),
+    );
+
+    VALIDATORS_INFO
+        .save(
+            deps.as_mut().storage,
+            (current_round, validator.address.clone()),
+            &validator,
+        )
+        .unwrap();
+    VALIDATORS_PER_ROUND
+        .save(
+            deps.as_mut().storage,
+            (
+                current_round,
+                validator.delegated_tokens.u128(),
+                validator.address.clone(),
+            ),
+            &validator.address,
+        )
+        .unwrap();
+    VALIDATOR_TO_QUERY_ID
+        .save(deps.as_mut().storage, validator.address.clone(), &query_id)
+        .unwrap();
+    QUERY_ID_TO_VALIDATOR
+        .save(deps.as_mut().storage, query_id, &validator.address)
+        .unwrap();
+
+    // the locks holding the shares of the validator are reported in the slash event
+    let lock_owner = deps.api.addr_make("addr0001");
+    for lock_id in [3, 7] {
+        add_lock_to_validator_index(deps.as_mut().storage, VALIDATOR_1, &lock_owner, lock_id)
+            .unwrap();
+    }
+
+    let shares = Uint128::new(300000000) * TOKENS_TO_SHARES_MULTIPLIER;
+
+    // (tokens, expected slash fraction, expected cumulative slash fraction, expected slashes count)
+    let icq_results = vec![
+        // validator got slashed by 10%
+        (
+            Uint128::new(270000000),
+            Some(Decimal::percent(10)),
+            Decimal::percent(10),
+            1,
+        ),
+        // validator got slashed by another 10%
+        (
+            Uint128::new(243000000),
+            Some(Decimal::percent(10)),
+            Decimal::percent(19),
+            2,
+        ),
+        // power ratio increased- no slash is recorded
+        (Uint128::new(250000000), None, Decimal::percent(19), 2),
+    ];
+
+    for (tokens, expected_slash, expected_cumulative_slash, expected_slashes_count) in icq_results {
+        let mock_validator = get_mock_validator(VALIDATOR_1, tokens, shares);
+        deps.querier = deps
+            .querier
+            .with_custom_handler(custom_interchain_query_mock(HashMap::from([(
+                query_id,
+                ICQMockData {
+                    query_type: QueryType::KV,
+                    should_query_return_error: false,
+                    should_query_result_return_error: false,
+                    kv_results: vec![StorageValue {
+                        storage_prefix: STAKING_STORE_KEY.to_string(),
+                        key: Binary::default(),
+                        value: Binary::from(mock_validator.encode_to_vec()),
+                    }],
+                },
+            )])));
+
+        let res = sudo(
+            deps.as_mut(),
+            env.clone(),
+            SudoMsg::KVQueryResult { query_id },
+        )
+        .unwrap();
+        match expected_slash {
+            Some(slash_fraction) => {
+                assert!(res
+                    .attributes
+                    .contains(&attr("slashed_validator", VALIDATOR_1)));
+                assert!(res
+                    .attributes
+                    .contains(&attr("slash_fraction", slash_fraction.to_string())));
+                assert!(res.attributes.contains(&attr("affected_lock_ids", "3,7")));
+                assert!(res
+                    .attributes
+                    .contains(&attr("affected_lock_ids_truncated", "false")));
+            }
+            None => assert!(res.attributes.is_empty()),
+        }
+
+        let slashes = query_validator_slashes(deps.as_ref()).unwrap().slashes;
+        assert_eq!(
+            slashes,
+            vec![(
+                VALIDATOR_1.to_string(),
+                ValidatorSlashInfo {
+                    cumulative_slash_fraction: expected_cumulative_slash,
+                    slashes_count: expected_slashes_count,
+                    last_slash_round_id: current_round,
+                }
+            )]
+        );
+    }
+}
+
 pub fn get_mock_validator(address: &str, tokens: Uint128, shares: Uint128) -> CosmosValidator {
     CosmosValidator {
         operator_address: address.to_string(),
@@ -537,7 +781,7 @@ fn test_icq_managers_feature() {
         withdraw_msg.clone(),
     );
     match res {
-        Err(ContractError::Unauthorized {}) => {}
+        Err(ContractError::Unauthorized) => {}
         _ => panic!("Expected Unauthorized error"),
     }
 
@@ -618,7 +862,114 @@ fn test_icq_managers_feature() {
         withdraw_msg,
     );
     match res {
-        Err(ContractError::Unauthorized {}) => {}
+        Err(ContractError::Unauthorized) => {}
         _ => panic!("Expected Unauthorized error"),
     }
 }
+
+#[test]
+fn max_validator_shares_participating_update_test() {
+    let (mut deps, mut env) = (mock_dependencies(no_op_grpc_query_mock()), mock_env());
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+
+    let mut msg = get_default_instantiate_msg(&deps.api);
+    msg.max_validator_shares_participating = 3;
+    msg.whitelist_admins = vec![get_address_as_str(&deps.api, "addr0000")];
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert!(res.is_ok());
+
+    let validators = vec![
+        (VALIDATOR_1, Uint128::new(300000000)),
+        (VALIDATOR_2, Uint128::new(250000000)),
+        (VALIDATOR_3, Uint128::new(200000000)),
+    ];
+    for (query_id, (address, tokens)) in (1..).zip(validators) {
+        let validator = ValidatorInfo::new(address.to_string(), tokens, Decimal::one());
+        VALIDATORS_INFO
+            .save(
+                deps.as_mut().storage,
+                (0, validator.address.clone()),
+                &validator,
+            )
+            .unwrap();
+        VALIDATORS_PER_ROUND
+            .save(
+                deps.as_mut().storage,
+                (0, tokens.u128(), validator.address.clone()),
+                &validator.address,
+            )
+            .unwrap();
+        VALIDATOR_TO_QUERY_ID
+            .save(deps.as_mut().storage, validator.address.clone(), &query_id)
+            .unwrap();
+        QUERY_ID_TO_VALIDATOR
+            .save(deps.as_mut().storage, query_id, &validator.address)
+            .unwrap();
+    }
+
+    let update_config_msg = |max_validator_shares_participating| ExecuteMsg::UpdateConfig {
+        max_locked_tokens: None,
+        max_deployment_duration: None,
+        tribute_cutoff_duration: None,
+        max_proposals_per_tranche_round: None,
+        raw_export_enabled: None,
+        unlock_cooldown_duration: None,
+        max_validator_shares_participating: Some(max_validator_shares_participating),
+        max_proposal_title_length: None,
+        max_proposal_description_length: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        update_config_msg(0),
+    );
+    assert!(res.unwrap_err().to_string().contains("greater than zero"));
+
+    let res = execute(deps.as_mut(), env.clone(), info, update_config_msg(2));
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    // the decrease doesn't affect the validators of the current round
+    assert_eq!(
+        3,
+        query_validators_per_round(deps.as_ref(), 0).unwrap().len()
+    );
+
+    // in the next round, the ICQ result of the validator that fell out of the top N
+    // doesn't get it back into the set, and its interchain query gets removed
+    env.block.time = env.block.time.plus_nanos(msg.round_length + 1);
+
+    let tokens = Uint128::new(200000000);
+    let mock_validator =
+        get_mock_validator(VALIDATOR_3, tokens, tokens * TOKENS_TO_SHARES_MULTIPLIER);
+    deps.querier = deps
+        .querier
+        .with_custom_handler(custom_interchain_query_mock(HashMap::from([(
+            3,
+            ICQMockData {
+                query_type: QueryType::KV,
+                should_query_return_error: false,
+                should_query_result_return_error: false,
+                kv_results: vec![StorageValue {
+                    storage_prefix: STAKING_STORE_KEY.to_string(),
+                    key: Binary::default(),
+                    value: Binary::from(mock_validator.encode_to_vec()),
+                }],
+            },
+        )])));
+
+    let res = sudo(deps.as_mut(), env, SudoMsg::KVQueryResult { query_id: 3 }).unwrap();
+    assert_eq!(1, res.messages.len());
+    assert_eq!(REMOVE_VALIDATOR_ICQ_REPLY_ID, res.messages[0].id);
+
+    let round_1_validators = query_validators_per_round(deps.as_ref(), 1).unwrap();
+    assert_eq!(
+        vec![VALIDATOR_1.to_string(), VALIDATOR_2.to_string()],
+        round_1_validators
+            .into_iter()
+            .map(|(_, address)| address)
+            .collect::<Vec<String>>()
+    );
+    assert!(!VALIDATORS_INFO.has(&deps.storage, (1, VALIDATOR_3.to_string())));
+    assert!(VALIDATORS_INFO.has(&deps.storage, (0, VALIDATOR_3.to_string())));
+}
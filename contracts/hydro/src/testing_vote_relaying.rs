@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{attr, testing::mock_env, Binary, Coin, Env, Uint128};
+use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+
+use crate::contract::{execute, instantiate, query_proposal, query_relayed_vote_nonce};
+use crate::msg::{ExecuteMsg, ProposalToLockups, VoteIntent};
+use crate::testing::{
+    get_default_instantiate_msg, get_message_info, set_default_validator_for_rounds, IBC_DENOM_1,
+    ONE_MONTH_IN_NANO_SECONDS, VALIDATOR_1_LST_DENOM_1,
+};
+use crate::testing_mocks::{denom_trace_grpc_query_mock, mock_dependencies};
+use crate::vote_relaying::{derive_address_from_pubkey, get_vote_intent_sign_hash};
+
+fn get_signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_slice(&[seed; 32]).unwrap()
+}
+
+fn get_pubkey(signing_key: &SigningKey) -> Binary {
+    Binary::from(
+        signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec(),
+    )
+}
+
+fn sign_vote_intent(env: &Env, signing_key: &SigningKey, vote_intent: &VoteIntent) -> Binary {
+    let sign_hash = get_vote_intent_sign_hash(env, vote_intent).unwrap();
+    let signature: Signature = signing_key.sign_prehash(&sign_hash).unwrap();
+
+    Binary::from(signature.to_bytes().to_vec())
+}
+
+#[test]
+fn relayed_vote_test() {
+    let grpc_query = denom_trace_grpc_query_mock(
+        "transfer/channel-0".to_string(),
+        HashMap::from([(IBC_DENOM_1.to_string(), VALIDATOR_1_LST_DENOM_1.to_string())]),
+    );
+    let (mut deps, env) = (mock_dependencies(grpc_query), mock_env());
+
+    let voter_key = get_signing_key(1);
+    let voter_pubkey = get_pubkey(&voter_key);
+    let voter = derive_address_from_pubkey(&deps.as_ref(), &voter_pubkey).unwrap();
+    let relayer_info = get_message_info(&deps.api, "addr0001", &[]);
+
+    let info = get_message_info(&deps.api, "addr0000", &[]);
+    let msg = get_default_instantiate_msg(&deps.api);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    set_default_validator_for_rounds(deps.as_mut(), 0, 100);
+
+    let tranche_id = 1;
+    let msg = ExecuteMsg::CreateProposal {
+        round_id: None,
+        tranche_id,
+        title: "proposal title".to_string(),
+        description: "proposal description".to_string(),
+        deployment_duration: 1,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        description_hash: None,
+        description_uri: None,
+        max_vote_percentage: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let mut voter_info = relayer_info.clone();
+    voter_info.sender = voter.clone();
+    voter_info.funds = vec![Coin::new(1000u64, IBC_DENOM_1.to_string())];
+    let msg = ExecuteMsg::LockTokens {
+        lock_duration: ONE_MONTH_IN_NANO_SECONDS,
+    };
+    let res = execute(deps.as_mut(), env.clone(), voter_info, msg);
+    assert!(res.is_ok(), "error: {:?}", res);
+
+    let vote_intent = VoteIntent {
+        voter: voter.to_string(),
+        round_id: 0,
+        tranche_id,
+        proposals_votes: vec![ProposalToLockups {
+            proposal_id: 0,
+            lock_ids: vec![0],
+        }],
+        nonce: 0,
+        expiry: env.block.time.plus_days(1),
+    };
+    let relayed_vote_msg =
+        |vote_intent: &VoteIntent, signing_key: &SigningKey| ExecuteMsg::RelayedVote {
+            vote_intent: vote_intent.clone(),
+            pubkey: voter_pubkey.clone(),
+            signature: sign_vote_intent(&env, signing_key, vote_intent),
+        };
+
+    // the intent must be signed by the voter
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        relayer_info.clone(),
+        relayed_vote_msg(&vote_intent, &get_signing_key(2)),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Invalid vote intent signature"));
+
+    // and the public key must belong to the voter
+    let other_voter_intent = VoteIntent {
+        voter: relayer_info.sender.to_string(),
+        ..vote_intent.clone()
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        relayer_info.clone(),
+        relayed_vote_msg(&other_voter_intent, &voter_key),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Public key doesn't belong to the voter"));
+
+    let expired_intent = VoteIntent {
+        expiry: env.block.time,
+        ..vote_intent.clone()
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        relayer_info.clone(),
+        relayed_vote_msg(&expired_intent, &voter_key),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Vote intent has expired"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        relayer_info.clone(),
+        relayed_vote_msg(&vote_intent, &voter_key),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("relayer", relayer_info.sender.to_string())));
+    assert!(res.attributes.contains(&attr("sender", voter.to_string())));
+
+    let res = query_proposal(deps.as_ref(), 0, tranche_id, 0).unwrap();
+    assert_eq!(Uint128::new(1000), res.proposal.power);
+
+    let res = query_relayed_vote_nonce(deps.as_ref(), voter.to_string()).unwrap();
+    assert_eq!(1, res.next_nonce);
+
+    // the same intent can't be relayed again
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        relayer_info,
+        relayed_vote_msg(&vote_intent, &voter_key),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Invalid vote intent nonce 0; expected nonce 1"));
+}
@@ -1,8 +1,8 @@
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    from_json, to_json_vec, Coin, Decimal, Deps, DepsMut, Env, Order, Reply, Response, StdError,
-    StdResult, SubMsg, Uint128,
+    attr, from_json, to_json_binary, to_json_vec, Addr, Attribute, Coin, Decimal, Deps, DepsMut,
+    Env, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 
 use neutron_sdk::{
@@ -18,12 +18,14 @@ use neutron_std::types::neutron::interchainqueries::InterchainqueriesQuerier;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    contract::{compute_current_round_id, NATIVE_TOKEN_DENOM},
+    contract::{compute_lock_round_id, NATIVE_TOKEN_DENOM},
     error::ContractError,
     lsm_integration::{initialize_validator_store, update_scores_due_to_power_ratio_change},
+    msg::{LockVote, TributeExecuteMsg},
     state::{
-        Constants, ValidatorInfo, CONSTANTS, QUERY_ID_TO_VALIDATOR, VALIDATORS_INFO,
-        VALIDATORS_PER_ROUND, VALIDATOR_TO_QUERY_ID,
+        Constants, ValidatorInfo, ValidatorSlashInfo, CONSTANTS, QUERY_ID_TO_VALIDATOR,
+        SCALED_ROUND_POWER_SHARES_MAP, VALIDATORS_INFO, VALIDATORS_PER_ROUND, VALIDATOR_LOCKS,
+        VALIDATOR_SLASHES, VALIDATOR_TO_QUERY_ID,
     },
 };
 
@@ -31,15 +33,45 @@ use crate::{
 // and was never slashed, 1 Token = Shares / TOKEN_TO_SHARES_MULTIPLIER.
 pub const TOKENS_TO_SHARES_MULTIPLIER: Uint128 = Uint128::new(1_000_000_000_000_000_000);
 
-// SubMsg ID is used so that we can differentiate submessages sent by the smart contract when the Wasm SDK module
-// calls back the reply() function on the smart contract. Since we are using the payload to populate all the data
-// that we need when reply() is called, we don't need to set a unique SubMsg ID and can use 0 for all SubMsgs.
-const UNUSED_MSG_ID: u64 = 0;
+// The maximum number of affected lock ids listed in the slash event, so that a slash of a validator with many
+// locks can't make the handling of the interchain query result run out of gas. The full list of the affected
+// locks can be obtained through the ValidatorLocks query.
+pub const MAX_SLASH_EVENT_LOCK_IDS: usize = 100;
+
+// Each SubMsg sent by the smart contract gets a reply ID that identifies its flow when the Wasm SDK module
+// calls back the reply() function on the smart contract. Any data needed to handle the reply is put into the
+// SubMsg payload, wrapped in a versioned envelope, so that the payload format can change in future versions.
+pub const CREATE_VALIDATOR_ICQ_REPLY_ID: u64 = 1;
+pub const REMOVE_VALIDATOR_ICQ_REPLY_ID: u64 = 2;
+pub const CLAIM_TRIBUTES_REPLY_ID: u64 = 3;
+
+// Gas limit for claiming the tributes on unlock, so that an expensive claim can't consume the gas of the unlock.
+pub const CLAIM_TRIBUTES_GAS_LIMIT: u64 = 2_000_000;
+
+// Previous contract versions used this ID for all SubMsgs, with an unversioned ReplyPayload. It is still
+// handled so that the replies for SubMsgs sent before the contract migration can be processed.
+pub const LEGACY_REPLY_ID: u64 = 0;
+
+pub const REPLY_PAYLOAD_VERSION: u32 = 1;
 
 #[derive(Serialize, Deserialize)]
 pub enum ReplyPayload {
     CreateValidatorICQ(String),
     RemoveValidatorICQ(u64),
+    ClaimTributes(String),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReplyPayloadEnvelope {
+    pub version: u32,
+    pub payload: ReplyPayload,
+}
+
+fn build_reply_payload(payload: ReplyPayload) -> StdResult<Vec<u8>> {
+    to_json_vec(&ReplyPayloadEnvelope {
+        version: REPLY_PAYLOAD_VERSION,
+        payload,
+    })
 }
 
 pub fn build_create_interchain_query_submsg(
@@ -47,25 +79,76 @@ pub fn build_create_interchain_query_submsg(
     validator_address: String,
 ) -> StdResult<SubMsg<NeutronMsg>> {
     Ok(
-        SubMsg::reply_on_success(msg, UNUSED_MSG_ID).with_payload(to_json_vec(
-            &ReplyPayload::CreateValidatorICQ(validator_address),
-        )?),
+        SubMsg::reply_on_success(msg, CREATE_VALIDATOR_ICQ_REPLY_ID).with_payload(
+            build_reply_payload(ReplyPayload::CreateValidatorICQ(validator_address))?,
+        ),
     )
 }
 
 fn build_remove_interchain_query_submsg(query_id: u64) -> StdResult<SubMsg<NeutronMsg>> {
-    Ok(
-        SubMsg::reply_on_success(NeutronMsg::remove_interchain_query(query_id), UNUSED_MSG_ID)
-            .with_payload(to_json_vec(&ReplyPayload::RemoveValidatorICQ(query_id))?),
+    Ok(SubMsg::reply_on_success(
+        NeutronMsg::remove_interchain_query(query_id),
+        REMOVE_VALIDATOR_ICQ_REPLY_ID,
+    )
+    .with_payload(build_reply_payload(ReplyPayload::RemoveValidatorICQ(
+        query_id,
+    ))?))
+}
+
+// Builds the SubMsg that asks the tribute contract to claim the tributes for the votes of the unlocked locks.
+// It only replies on error, and the error is ignored in handle_submsg_reply(), so that the unlock always succeeds.
+pub fn build_claim_tributes_submsg(
+    tribute_contract: Addr,
+    voter_address: String,
+    lock_votes: Vec<LockVote>,
+) -> StdResult<SubMsg<NeutronMsg>> {
+    Ok(SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: tribute_contract.to_string(),
+            msg: to_json_binary(&TributeExecuteMsg::ClaimForLocks {
+                voter_address: voter_address.clone(),
+                lock_votes,
+            })?,
+            funds: vec![],
+        },
+        CLAIM_TRIBUTES_REPLY_ID,
     )
+    .with_gas_limit(CLAIM_TRIBUTES_GAS_LIMIT)
+    .with_payload(build_reply_payload(ReplyPayload::ClaimTributes(
+        voter_address,
+    ))?))
+}
+
+// Extracts the payload from the reply and verifies that it matches the flow given by the reply ID.
+fn parse_reply_payload(msg: &Reply) -> StdResult<ReplyPayload> {
+    if msg.id == LEGACY_REPLY_ID {
+        return from_json(&msg.payload);
+    }
+
+    let envelope: ReplyPayloadEnvelope = from_json(&msg.payload)?;
+    if envelope.version != REPLY_PAYLOAD_VERSION {
+        return Err(StdError::generic_err(format!(
+            "Unsupported reply payload version: {}",
+            envelope.version
+        )));
+    }
+
+    match (msg.id, &envelope.payload) {
+        (CREATE_VALIDATOR_ICQ_REPLY_ID, ReplyPayload::CreateValidatorICQ(_))
+        | (REMOVE_VALIDATOR_ICQ_REPLY_ID, ReplyPayload::RemoveValidatorICQ(_))
+        | (CLAIM_TRIBUTES_REPLY_ID, ReplyPayload::ClaimTributes(_)) => Ok(envelope.payload),
+        _ => Err(StdError::generic_err(format!(
+            "Reply payload doesn't match the reply ID: {}",
+            msg.id
+        ))),
+    }
 }
 
 pub fn handle_submsg_reply(
     deps: DepsMut<NeutronQuery>,
     msg: Reply,
 ) -> Result<Response<NeutronMsg>, ContractError> {
-    // No need to use msg.id to determine what to do, since we can extract everything we need from the msg.payload.
-    let reply_paylod = from_json(&msg.payload)?;
+    let reply_paylod = parse_reply_payload(&msg)?;
     match reply_paylod {
         ReplyPayload::CreateValidatorICQ(validator_address) => {
             let register_query_resp: MsgRegisterInterchainQueryResponse = decode_message_response(
@@ -103,6 +186,13 @@ pub fn handle_submsg_reply(
             QUERY_ID_TO_VALIDATOR.remove(deps.storage, query_id);
             VALIDATOR_TO_QUERY_ID.remove(deps.storage, validator_address);
         }
+        ReplyPayload::ClaimTributes(voter_address) => {
+            // the claim failed, but the tokens still get unlocked
+            return Ok(Response::new()
+                .add_attribute("action", "claim_tributes_on_unlock_failed")
+                .add_attribute("voter", voter_address)
+                .add_attribute("error", msg.result.into_result().err().unwrap_or_default()));
+        }
     }
 
     Ok(Response::default())
@@ -122,7 +212,7 @@ pub fn handle_delivered_interchain_query_result(
         }
     };
     let constants = CONSTANTS.load(deps.storage)?;
-    let current_round = compute_current_round_id(&env, &constants)?;
+    let current_round = compute_lock_round_id(&env, &constants)?;
     initialize_validator_store(deps.storage, current_round)?;
 
     let validator_address = validator.operator_address.clone();
@@ -131,6 +221,7 @@ pub fn handle_delivered_interchain_query_result(
     let new_power_ratio = Decimal::from_ratio(new_tokens * TOKENS_TO_SHARES_MULTIPLIER, new_shares);
 
     let mut submsgs = vec![];
+    let mut attributes = vec![];
 
     let current_validator_info =
         VALIDATORS_INFO.may_load(deps.storage, (current_round, validator_address.clone()))?;
@@ -138,6 +229,17 @@ pub fn handle_delivered_interchain_query_result(
         // If the validator_info is found, it means that it is among the top N for this round.
         // We just need to update its rank and power ratio, if they changed in the meantime.
         Some(validator_info) => {
+            // The power ratio of a validator can only decrease if the validator got slashed.
+            if new_power_ratio < validator_info.power_ratio {
+                attributes = record_validator_slash(
+                    deps.storage,
+                    current_round,
+                    &validator_info.address,
+                    validator_info.power_ratio,
+                    new_power_ratio,
+                )?;
+            }
+
             top_n_validator_update(
                 &mut deps,
                 current_round,
@@ -182,7 +284,73 @@ pub fn handle_delivered_interchain_query_result(
         }
     };
 
-    Ok(Response::default().add_submessages(submsgs))
+    Ok(Response::default()
+        .add_attributes(attributes)
+        .add_submessages(submsgs))
+}
+
+// Records the slash of the given validator, computed from the decrease of its power ratio.
+// The power of the locks holding the shares of this validator is reduced by the same fraction
+// once the new power ratio gets applied to the proposal scores. Returns the attributes that
+// describe the applied slash and the affected locks, to be emitted by the caller.
+fn record_validator_slash(
+    storage: &mut dyn Storage,
+    current_round: u64,
+    validator: &String,
+    old_power_ratio: Decimal,
+    new_power_ratio: Decimal,
+) -> StdResult<Vec<Attribute>> {
+    let remaining_fraction = new_power_ratio / old_power_ratio;
+    let slash_fraction = Decimal::one() - remaining_fraction;
+
+    let slash_info = match VALIDATOR_SLASHES.may_load(storage, validator.clone())? {
+        None => ValidatorSlashInfo {
+            cumulative_slash_fraction: slash_fraction,
+            slashes_count: 1,
+            last_slash_round_id: current_round,
+        },
+        Some(slash_info) => ValidatorSlashInfo {
+            cumulative_slash_fraction: Decimal::one()
+                - (Decimal::one() - slash_info.cumulative_slash_fraction) * remaining_fraction,
+            slashes_count: slash_info.slashes_count + 1,
+            last_slash_round_id: current_round,
+        },
+    };
+    VALIDATOR_SLASHES.save(storage, validator.clone(), &slash_info)?;
+
+    let affected_shares = SCALED_ROUND_POWER_SHARES_MAP
+        .may_load(storage, (current_round, validator.clone()))?
+        .unwrap_or_default();
+
+    let mut affected_lock_ids = VALIDATOR_LOCKS
+        .prefix(validator.clone())
+        .keys(storage, None, None, Order::Ascending)
+        .take(MAX_SLASH_EVENT_LOCK_IDS + 1)
+        .collect::<StdResult<Vec<u64>>>()?;
+    let affected_lock_ids_truncated = affected_lock_ids.len() > MAX_SLASH_EVENT_LOCK_IDS;
+    affected_lock_ids.truncate(MAX_SLASH_EVENT_LOCK_IDS);
+
+    Ok(vec![
+        attr("slashed_validator", validator),
+        attr("slash_fraction", slash_fraction.to_string()),
+        attr(
+            "cumulative_slash_fraction",
+            slash_info.cumulative_slash_fraction.to_string(),
+        ),
+        attr("affected_scaled_shares", affected_shares.to_string()),
+        attr(
+            "affected_lock_ids",
+            affected_lock_ids
+                .iter()
+                .map(|lock_id| lock_id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+        attr(
+            "affected_lock_ids_truncated",
+            affected_lock_ids_truncated.to_string(),
+        ),
+    ])
 }
 
 fn top_n_validator_add(
@@ -302,15 +470,27 @@ fn top_n_validator_remove(
     Ok(())
 }
 
+// Returns the validator with the least delegated tokens in the round, if there are at least
+// max_validator_shares_participating validators in the round, and None otherwise. There can be
+// more validators in the round if max_validator_shares_participating was decreased during the
+// round, since the decrease only applies from the next round.
 fn get_last_validator(
     deps: &mut DepsMut<NeutronQuery>,
     current_round: u64,
     constants: &Constants,
 ) -> Option<(u128, String)> {
+    let is_top_n_full = VALIDATORS_PER_ROUND
+        .sub_prefix(current_round)
+        .keys(deps.storage, None, None, Order::Descending)
+        .nth((constants.max_validator_shares_participating - 1) as usize)
+        .is_some();
+    if !is_top_n_full {
+        return None;
+    }
+
     let last_validator: Vec<(u128, String)> = VALIDATORS_PER_ROUND
         .sub_prefix(current_round)
-        .range(deps.storage, None, None, Order::Descending)
-        .skip((constants.max_validator_shares_participating - 1) as usize)
+        .range(deps.storage, None, None, Order::Ascending)
         .filter(|f| {
             let ok = f.is_ok();
             if !ok {
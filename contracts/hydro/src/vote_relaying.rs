@@ -0,0 +1,75 @@
+use cosmwasm_std::{to_json_vec, Addr, Binary, CanonicalAddr, Deps, Env, StdError, StdResult};
+use neutron_sdk::bindings::query::NeutronQuery;
+use ripemd::Ripemd160;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::msg::VoteIntent;
+
+pub const COMPRESSED_PUBKEY_LENGTH: usize = 33;
+
+// Document signed by the voter. The chain ID and the contract address are included so that
+// the signature can't be replayed against other deployments of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteIntentSignDoc {
+    pub chain_id: String,
+    pub contract: String,
+    pub vote_intent: VoteIntent,
+}
+
+// Returns the hash of the sign doc that the voter needs to sign for the given vote intent to be
+// relayed to the given contract.
+pub fn get_vote_intent_sign_hash(env: &Env, vote_intent: &VoteIntent) -> StdResult<Vec<u8>> {
+    let sign_doc = VoteIntentSignDoc {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        vote_intent: vote_intent.clone(),
+    };
+
+    Ok(Sha256::digest(to_json_vec(&sign_doc)?).to_vec())
+}
+
+// Returns the address of the account with the given secp256k1 public key, derived in the same
+// way as by the Cosmos SDK: ripemd160(sha256(pubkey)).
+pub fn derive_address_from_pubkey(deps: &Deps<NeutronQuery>, pubkey: &Binary) -> StdResult<Addr> {
+    if pubkey.len() != COMPRESSED_PUBKEY_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "Public key must be a compressed secp256k1 key of {} bytes",
+            COMPRESSED_PUBKEY_LENGTH
+        )));
+    }
+
+    let hash = Ripemd160::digest(Sha256::digest(pubkey.as_slice()));
+    deps.api
+        .addr_humanize(&CanonicalAddr::from(hash.as_slice()))
+}
+
+// Verifies that the vote intent was signed by the voter with the given public key, and returns
+// the address of the voter.
+pub fn verify_vote_intent_signature(
+    deps: &Deps<NeutronQuery>,
+    env: &Env,
+    vote_intent: &VoteIntent,
+    pubkey: &Binary,
+    signature: &Binary,
+) -> StdResult<Addr> {
+    let voter = deps.api.addr_validate(&vote_intent.voter)?;
+    if derive_address_from_pubkey(deps, pubkey)? != voter {
+        return Err(StdError::generic_err(format!(
+            "Public key doesn't belong to the voter {}",
+            voter
+        )));
+    }
+
+    let sign_hash = get_vote_intent_sign_hash(env, vote_intent)?;
+    let valid = deps
+        .api
+        .secp256k1_verify(&sign_hash, signature, pubkey)
+        .map_err(|err| StdError::generic_err(format!("Signature verification failed: {}", err)))?;
+    if !valid {
+        return Err(StdError::generic_err("Invalid vote intent signature"));
+    }
+
+    Ok(voter)
+}
@@ -3,10 +3,13 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use tribute::msg::{ExecuteMsg, InstantiateMsg};
+use tribute::msg::{ClaimHookMsg, ExecuteMsg, InstantiateMsg, SwapVenueMsg};
 use tribute::query::{
-    ConfigResponse, HistoricalTributeClaimsResponse, OutstandingTributeClaimsResponse,
-    ProposalTributesResponse, QueryMsg, RoundTributesResponse,
+    AllowedTributeDenomsResponse, ClaimHooksResponse, ConfigResponse, DisputeStatusResponse,
+    HistoricalTributeClaimsResponse, LifetimeTributeClaimsResponse,
+    OutstandingTributeClaimsResponse, ProposalTributesResponse, QueryMsg, RoundTributesResponse,
+    SwapVenuesResponse, TopTributeEarnersResponse, TributeClaimsReportResponse,
+    TributeEscrowResponse, TributeOutcomeResponse, TributeVestingStatusResponse,
 };
 
 fn main() {
@@ -18,10 +21,22 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ClaimHookMsg), &out_dir);
+    export_schema(&schema_for!(SwapVenueMsg), &out_dir);
 
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(ProposalTributesResponse), &out_dir);
     export_schema(&schema_for!(HistoricalTributeClaimsResponse), &out_dir);
     export_schema(&schema_for!(RoundTributesResponse), &out_dir);
     export_schema(&schema_for!(OutstandingTributeClaimsResponse), &out_dir);
+    export_schema(&schema_for!(AllowedTributeDenomsResponse), &out_dir);
+    export_schema(&schema_for!(ClaimHooksResponse), &out_dir);
+    export_schema(&schema_for!(SwapVenuesResponse), &out_dir);
+    export_schema(&schema_for!(DisputeStatusResponse), &out_dir);
+    export_schema(&schema_for!(TributeEscrowResponse), &out_dir);
+    export_schema(&schema_for!(TributeOutcomeResponse), &out_dir);
+    export_schema(&schema_for!(TopTributeEarnersResponse), &out_dir);
+    export_schema(&schema_for!(LifetimeTributeClaimsResponse), &out_dir);
+    export_schema(&schema_for!(TributeVestingStatusResponse), &out_dir);
+    export_schema(&schema_for!(TributeClaimsReportResponse), &out_dir);
 }
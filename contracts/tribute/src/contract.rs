@@ -1,24 +1,37 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::vec;
 
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, Response, StdError, StdResult, Uint128,
+    entry_point, from_json, to_json_binary, to_json_vec, Addr, BankMsg, Binary, Coin, Coins,
+    Decimal, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage,
+    SubMsg, Timestamp, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
-use hydro::msg::LiquidityDeployment;
+use cw_storage_plus::Bound;
+use hydro::contract::{get_next_key, get_pagination_start};
+use hydro::msg::{LiquidityDeployment, LockVote};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg};
+use crate::msg::{ClaimHookMsg, DisputeResolution, ExecuteMsg, InstantiateMsg, SwapVenueMsg};
 use crate::query::{
-    ConfigResponse, HistoricalTributeClaimsResponse, OutstandingTributeClaimsResponse,
-    ProposalTributesResponse, QueryMsg, RoundTributesResponse, TributeClaim,
+    AllowedTributeDenom, AllowedTributeDenomsResponse, ClaimHooksResponse, ConfigResponse,
+    DisputeStatusResponse, HistoricalTributeClaimsResponse, LifetimeTributeClaimsResponse,
+    OutstandingTributeClaimsResponse, ProposalTributesResponse, QueryMsg, RoundTributesResponse,
+    SwapVenue, SwapVenuesResponse, TopTributeEarnersResponse, TributeClaim,
+    TributeClaimsReportEntry, TributeClaimsReportResponse, TributeEarner, TributeEscrowResponse,
+    TributeOutcome, TributeOutcomeResponse, TributeVestingStatusResponse,
 };
 use crate::state::{
-    Config, Tribute, CONFIG, ID_TO_TRIBUTE_MAP, TRIBUTE_CLAIMS, TRIBUTE_ID, TRIBUTE_MAP,
+    Config, Tribute, TributeDisputeResolution, VoidedProposal, ALLOWED_TRIBUTE_DENOMS,
+    CLAIMANTS_BY_AMOUNT, CLAIM_HOOKS, CONFIG, DISPUTE_WINDOWS, ID_TO_TRIBUTE_MAP,
+    LIFETIME_CLAIMED_TOTALS, ROUND_CLAIMED_TOTALS, SWAP_VENUES, TRIBUTE_CLAIMS,
+    TRIBUTE_DISPUTE_RESOLUTIONS, TRIBUTE_ID, TRIBUTE_MAP, VOIDED_PROPOSALS,
 };
 use hydro::query::{
-    CurrentRoundResponse, LiquidityDeploymentResponse, ProposalResponse, QueryMsg as HydroQueryMsg,
-    UserVotesResponse,
+    CurrentRoundResponse, InvalidatedProposalsResponse, LiquidityDeploymentResponse,
+    ProposalResponse, QueryMsg as HydroQueryMsg, RoundCutoffsResponse, UserVotesResponse,
+    WhitelistAdminsResponse,
 };
 use hydro::state::{Proposal, VoteWithPower};
 
@@ -29,6 +42,21 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const DEFAULT_MAX_ENTRIES: usize = 100;
 
+// Maximum number of claim hooks, so that claiming tributes can't become too expensive.
+pub const MAX_CLAIM_HOOKS: usize = 5;
+// Gas limit for the execution of each claim hook, so that a hook can't use up the gas needed for the claim.
+pub const CLAIM_HOOK_GAS_LIMIT: u64 = 500_000;
+pub const CLAIM_HOOK_REPLY_ID: u64 = 1;
+pub const SWAP_REPLY_ID: u64 = 2;
+
+// Attached as the payload of the swap submessage sent by ClaimTributeAs, so that the claimed
+// funds can be sent to the claimant in their original denom if the swap fails.
+#[cw_serde]
+struct SwapFallback {
+    recipient: String,
+    funds: Coin,
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -61,22 +89,99 @@ pub fn execute(
             round_id,
             tranche_id,
             proposal_id,
-        } => add_tribute(deps, env, info, round_id, tranche_id, proposal_id),
+            min_vote_power,
+            vesting_rounds,
+            delivery_deadline_rounds,
+        } => add_tribute(
+            deps,
+            env,
+            info,
+            round_id,
+            tranche_id,
+            proposal_id,
+            min_vote_power,
+            vesting_rounds,
+            delivery_deadline_rounds,
+        ),
         ExecuteMsg::ClaimTribute {
             round_id,
             tranche_id,
             tribute_id,
             voter_address,
-        } => claim_tribute(deps, info, round_id, tranche_id, tribute_id, voter_address),
+        } => claim_tribute(
+            deps,
+            env,
+            info,
+            round_id,
+            tranche_id,
+            tribute_id,
+            voter_address,
+        ),
+        ExecuteMsg::ClaimTributeAs {
+            round_id,
+            tranche_id,
+            tribute_id,
+            ask_denom,
+            min_output,
+        } => claim_tribute_as(
+            deps, env, info, round_id, tranche_id, tribute_id, ask_denom, min_output,
+        ),
+        ExecuteMsg::ClaimForLocks {
+            voter_address,
+            lock_votes,
+        } => claim_for_locks(deps, env, info, voter_address, lock_votes),
         ExecuteMsg::RefundTribute {
             round_id,
             tranche_id,
             proposal_id,
             tribute_id,
-        } => refund_tribute(deps, info, round_id, proposal_id, tranche_id, tribute_id),
+        } => refund_tribute(
+            deps,
+            env,
+            info,
+            round_id,
+            proposal_id,
+            tranche_id,
+            tribute_id,
+        ),
+        ExecuteMsg::RefundUnclaimedTribute {
+            round_id,
+            tranche_id,
+            proposal_id,
+        } => refund_unclaimed_tribute(deps, env, info, round_id, tranche_id, proposal_id),
+        ExecuteMsg::AddAllowedTributeDenom { denom, min_amount } => {
+            add_allowed_tribute_denom(deps, info, denom, min_amount)
+        }
+        ExecuteMsg::RemoveAllowedTributeDenom { denom } => {
+            remove_allowed_tribute_denom(deps, info, denom)
+        }
+        ExecuteMsg::AddClaimHook { address } => add_claim_hook(deps, info, address),
+        ExecuteMsg::RemoveClaimHook { address } => remove_claim_hook(deps, info, address),
+        ExecuteMsg::RegisterSwapVenue {
+            offer_denom,
+            ask_denom,
+            venue,
+        } => register_swap_venue(deps, info, offer_denom, ask_denom, venue),
+        ExecuteMsg::RemoveSwapVenue {
+            offer_denom,
+            ask_denom,
+        } => remove_swap_venue(deps, info, offer_denom, ask_denom),
+        ExecuteMsg::SetDisputeWindow {
+            tranche_id,
+            duration,
+        } => set_dispute_window(deps, info, tranche_id, duration),
+        ExecuteMsg::ResolveTributeDispute {
+            tribute_id,
+            resolution,
+        } => resolve_tribute_dispute(deps, env, info, tribute_id, resolution),
+        ExecuteMsg::SyncProposalOutcomes {
+            round_id,
+            tranche_id,
+        } => sync_proposal_outcomes(deps, env, info, round_id, tranche_id),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn add_tribute(
     deps: DepsMut,
     env: Env,
@@ -84,11 +189,39 @@ fn add_tribute(
     round_id: u64,
     tranche_id: u64,
     proposal_id: u64,
+    min_vote_power: Option<Uint128>,
+    vesting_rounds: Option<u64>,
+    delivery_deadline_rounds: Option<u64>,
 ) -> Result<Response, ContractError> {
     let hydro_contract = CONFIG.load(deps.storage)?.hydro_contract;
 
     // Check that the proposal exists
-    query_proposal(&deps, &hydro_contract, round_id, tranche_id, proposal_id)?;
+    let proposal = query_proposal(&deps, &hydro_contract, round_id, tranche_id, proposal_id)?;
+
+    // Check that the tribute doesn't vest for longer than the proposal gets deployed
+    if let Some(vesting_rounds) = vesting_rounds {
+        if vesting_rounds == 0 || vesting_rounds > proposal.deployment_duration {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Vesting rounds must be between 1 and the proposal deployment duration of {} rounds",
+                proposal.deployment_duration
+            ))));
+        }
+    }
+
+    if delivery_deadline_rounds == Some(0) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Delivery deadline must be at least 1 round",
+        )));
+    }
+
+    // Check that the tribute cutoff for the round hasn't passed yet
+    let round_cutoffs = query_round_cutoffs(&deps, &hydro_contract, round_id)?;
+    if env.block.time >= round_cutoffs.tribute_cutoff {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Tributes can no longer be added for round {}. Tribute cutoff was at: {}",
+            round_id, round_cutoffs.tribute_cutoff
+        ))));
+    }
 
     // Check that the sender has sent funds
     if info.funds.is_empty() {
@@ -104,6 +237,8 @@ fn add_tribute(
         )));
     }
 
+    validate_tribute_funds(&deps, &info.funds[0])?;
+
     // Create tribute in TributeMap
     let tribute_id = TRIBUTE_ID.load(deps.storage)?;
     TRIBUTE_ID.save(deps.storage, &(tribute_id + 1))?;
@@ -117,6 +252,9 @@ fn add_tribute(
         refunded: false,
         creation_time: env.block.time,
         creation_round: query_current_round_id(&deps, &hydro_contract)?,
+        min_vote_power,
+        vesting_rounds,
+        delivery_deadline_rounds,
     };
     TRIBUTE_MAP.save(
         deps.storage,
@@ -125,43 +263,262 @@ fn add_tribute(
     )?;
     ID_TO_TRIBUTE_MAP.save(deps.storage, tribute_id, &tribute)?;
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_attribute("action", "add_tribute")
         .add_attribute("depositor", info.sender.clone())
         .add_attribute("round_id", round_id.to_string())
         .add_attribute("tranche_id", tranche_id.to_string())
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("tribute_id", tribute_id.to_string())
-        .add_attribute("funds", info.funds[0].to_string()))
+        .add_attribute("funds", info.funds[0].to_string());
+
+    if let Some(min_vote_power) = min_vote_power {
+        response = response.add_attribute("min_vote_power", min_vote_power.to_string());
+    }
+
+    if let Some(vesting_rounds) = vesting_rounds {
+        response = response.add_attribute("vesting_rounds", vesting_rounds.to_string());
+    }
+
+    if let Some(delivery_deadline_rounds) = delivery_deadline_rounds {
+        response = response.add_attribute(
+            "delivery_deadline_rounds",
+            delivery_deadline_rounds.to_string(),
+        );
+    }
+
+    Ok(response)
 }
 
 // ClaimTribute(round_id, tranche_id, prop_id, tribute_id, voter_address):
 //     Check that the voter has not already claimed the tribute
+//     Check that the tribute wasn't refunded
 //     Check that the round is ended
+//     Check that the dispute window for the round and tranche has passed
 //     Check that there was a deployment entered for the proposal, and that the proposal received a non-zero amount of funds
 //     Look up voter's vote for the round
 //     Check that the voter voted for the prop
+//     Check that the voter's voting power meets the tribute's minimum, if one is set
 //     Divide voter's vote power by total power voting for the prop to figure out their percentage
 //     Use the voter's percentage to send them the right portion of the tribute
 //     Mark on the voter's vote that they claimed the tribute
 fn claim_tribute(
     deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+    tribute_id: u64,
+    voter_address: String,
+) -> Result<Response, ContractError> {
+    let voter = deps.api.addr_validate(&voter_address)?;
+    let (sent_coin, response, claim_hook_msgs) =
+        record_tribute_claim(deps, &env, info, round_id, tranche_id, tribute_id, &voter)?;
+
+    // Send the tribute to the voter
+    Ok(response
+        .add_message(BankMsg::Send {
+            to_address: voter.to_string(),
+            amount: vec![sent_coin],
+        })
+        .add_submessages(claim_hook_msgs))
+}
+
+// ClaimTributeAs(round_id, tranche_id, tribute_id, ask_denom, min_output):
+//     Validate that a swap venue is registered for the tribute denom and the ask_denom
+//     Claim the tribute for the sender in the same way as ClaimTribute
+//     Send the claimed funds to the swap venue, which sends the swapped funds to the sender
+//     If the swap fails, send the claimed funds to the sender in their original denom (see reply())
+#[allow(clippy::too_many_arguments)]
+fn claim_tribute_as(
+    deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     round_id: u64,
     tranche_id: u64,
     tribute_id: u64,
+    ask_denom: String,
+    min_output: Uint128,
+) -> Result<Response, ContractError> {
+    let voter = info.sender.clone();
+    let tribute_denom = ID_TO_TRIBUTE_MAP
+        .load(deps.storage, tribute_id)?
+        .funds
+        .denom;
+
+    // nothing to swap if the tribute is already in the requested denom
+    if tribute_denom == ask_denom {
+        return claim_tribute(
+            deps,
+            env,
+            info,
+            round_id,
+            tranche_id,
+            tribute_id,
+            voter.to_string(),
+        );
+    }
+
+    let venue = SWAP_VENUES
+        .may_load(deps.storage, (tribute_denom.clone(), ask_denom.clone()))?
+        .ok_or_else(|| {
+            ContractError::Std(StdError::generic_err(format!(
+                "No swap venue registered for swapping {} into {}",
+                tribute_denom, ask_denom
+            )))
+        })?;
+
+    let (sent_coin, response, claim_hook_msgs) =
+        record_tribute_claim(deps, &env, info, round_id, tranche_id, tribute_id, &voter)?;
+
+    let swap_msg = SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: venue.to_string(),
+            msg: to_json_binary(&SwapVenueMsg::Swap {
+                ask_denom: ask_denom.clone(),
+                min_output,
+                recipient: voter.to_string(),
+            })?,
+            funds: vec![sent_coin.clone()],
+        },
+        SWAP_REPLY_ID,
+    )
+    .with_payload(to_json_vec(&SwapFallback {
+        recipient: voter.to_string(),
+        funds: sent_coin,
+    })?);
+
+    Ok(response
+        .add_attribute("ask_denom", ask_denom)
+        .add_attribute("min_output", min_output)
+        .add_attribute("swap_venue", venue)
+        .add_submessage(swap_msg)
+        .add_submessages(claim_hook_msgs))
+}
+
+// ClaimForLocks(voter_address, lock_votes):
+//     Validate that the sender is the Hydro contract
+//     For each proposal that the locks voted for, claim each tribute that the voter hasn't claimed yet
+//     Skip the tributes that can't be claimed, e.g. because the proposal didn't receive a deployment
+//     Send all claimed funds to the voter
+// Since tributes are claimed per voter, the voter receives the full share of each claimed tribute,
+// including the share given by the votes of other locks on the same proposal.
+fn claim_for_locks(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     voter_address: String,
+    lock_votes: Vec<LockVote>,
 ) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.hydro_contract {
+        return Err(ContractError::Unauthorized);
+    }
+
     let voter = deps.api.addr_validate(&voter_address)?;
 
-    // Check that the voter has not already claimed the tribute using the TRIBUTE_CLAIMS map
-    let claim = TRIBUTE_CLAIMS.may_load(deps.storage, (voter.clone(), tribute_id))?;
-    if claim.is_some() {
+    // multiple locks could have voted for the same proposal
+    let voted_proposals: BTreeSet<(u64, u64, u64)> = lock_votes
+        .iter()
+        .map(|lock_vote| {
+            (
+                lock_vote.round_id,
+                lock_vote.tranche_id,
+                lock_vote.proposal_id,
+            )
+        })
+        .collect();
+
+    let mut claimed_funds: BTreeMap<String, Uint128> = BTreeMap::new();
+    let mut claimed_tribute_ids = vec![];
+    let mut claim_hook_msgs = vec![];
+
+    for (round_id, tranche_id, proposal_id) in voted_proposals {
+        let tribute_ids = TRIBUTE_MAP
+            .prefix((round_id, proposal_id))
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|tribute| tribute.map(|(_, tribute_id)| tribute_id))
+            .collect::<StdResult<Vec<u64>>>()?;
+
+        for tribute_id in tribute_ids {
+            if TRIBUTE_CLAIMS.has(deps.storage, (voter.clone(), tribute_id))
+                && ID_TO_TRIBUTE_MAP
+                    .load(deps.storage, tribute_id)?
+                    .vesting_rounds
+                    .is_none()
+            {
+                continue;
+            }
+
+            // record_tribute_claim() doesn't change the state if the tribute can't be claimed
+            let Ok((sent_coin, _, hook_msgs)) = record_tribute_claim(
+                deps.branch(),
+                &env,
+                info.clone(),
+                round_id,
+                tranche_id,
+                tribute_id,
+                &voter,
+            ) else {
+                continue;
+            };
+
+            *claimed_funds.entry(sent_coin.denom).or_default() += sent_coin.amount;
+            claimed_tribute_ids.push(tribute_id.to_string());
+            claim_hook_msgs.extend(hook_msgs);
+        }
+    }
+
+    let funds: Vec<Coin> = claimed_funds
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(denom, amount)| Coin { denom, amount })
+        .collect();
+
+    let mut response = Response::new()
+        .add_attribute("action", "claim_for_locks")
+        .add_attribute("sender", info.sender)
+        .add_attribute("tribute_receiver", voter.clone())
+        .add_attribute("claimed_tribute_ids", claimed_tribute_ids.join(", "));
+
+    if !funds.is_empty() {
+        response = response.add_message(BankMsg::Send {
+            to_address: voter.to_string(),
+            amount: funds,
+        });
+    }
+
+    Ok(response.add_submessages(claim_hook_msgs))
+}
+
+// Checks that the voter can claim the given tribute, marks the tribute as claimed by the voter and
+// returns the claimed funds, together with the response attributes and the claim hook submessages.
+// Sending the claimed funds to the voter is left to the caller.
+fn record_tribute_claim(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+    tribute_id: u64,
+    voter: &Addr,
+) -> Result<(Coin, Response, Vec<SubMsg>), ContractError> {
+    // Check that the voter has not already claimed the tribute using the TRIBUTE_CLAIMS map.
+    // Vesting tributes can be claimed multiple times, as their vested portion grows.
+    let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+    let previous_claim = TRIBUTE_CLAIMS.may_load(deps.storage, (voter.clone(), tribute_id))?;
+    if previous_claim.is_some() && tribute.vesting_rounds.is_none() {
         return Err(ContractError::Std(StdError::generic_err(
             "User has already claimed the tribute",
         )));
     }
 
+    if tribute.refunded {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Tribute was refunded to its depositor",
+        )));
+    }
+
     // Check that the round is ended
     let config = CONFIG.load(deps.storage)?;
     let current_round_id = query_current_round_id(&deps, &config.hydro_contract)?;
@@ -172,7 +529,9 @@ fn claim_tribute(
         )));
     }
 
-    let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+    validate_tribute_location(&tribute, round_id, tranche_id)?;
+
+    validate_distribution_not_frozen(&deps.as_ref(), env, &config, round_id, tranche_id)?;
 
     // Look up voter's votes for the round, error if no votes can be found
     let vote = match query_user_votes(
@@ -194,23 +553,65 @@ fn claim_tribute(
         Some(vote) => vote,
     };
 
+    validate_voter_eligibility(&tribute, vote.power)?;
+
     // make sure that tributes for this proposal are claimable
     get_proposal_tributes_info(&deps.as_ref(), &config, round_id, tranche_id, vote.prop_id)?
         .are_tributes_claimable()?;
 
-    let proposal = get_proposal(&deps.as_ref(), &config, round_id, tranche_id, vote.prop_id)?;
+    let ProposalResponse {
+        proposal,
+        tribute_weight,
+        ..
+    } = get_proposal(&deps.as_ref(), &config, round_id, tranche_id, vote.prop_id)?;
+
+    let share =
+        calculate_voter_claim_amount(tribute.funds.clone(), vote.tribute_weight, tribute_weight)?;
+
+    // For vesting tributes, only the vested part of the share that wasn't claimed before is sent
+    let vested_amount = get_vested_amount(&tribute, share.amount, current_round_id);
+    let claimed_amount = previous_claim
+        .as_ref()
+        .map(|claim| claim.amount)
+        .unwrap_or_default();
+    let sent_coin = Coin {
+        denom: share.denom,
+        amount: vested_amount.saturating_sub(claimed_amount),
+    };
 
-    let sent_coin = calculate_voter_claim_amount(tribute.funds, vote.power, proposal.power)?;
+    if previous_claim.is_some() && sent_coin.amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "User has already claimed the vested portion of the tribute; it fully vests in round {}",
+            get_fully_vested_round(&tribute)
+        ))));
+    }
 
-    // Mark in the TRIBUTE_CLAIMS that the voter has claimed this tribute
+    // Mark in the TRIBUTE_CLAIMS that the voter has claimed this tribute, together with the total amount claimed
     TRIBUTE_CLAIMS.save(
         deps.storage,
         (voter.clone(), tribute_id),
-        &sent_coin.clone(),
+        &Coin {
+            denom: sent_coin.denom.clone(),
+            amount: claimed_amount + sent_coin.amount,
+        },
     )?;
 
-    // Send the tribute to the voter
-    Ok(Response::new()
+    update_claimed_totals(deps.storage, round_id, tranche_id, voter, &sent_coin)?;
+
+    // Notify the registered claim hooks about the claim
+    let claim_hook_msgs = build_claim_hook_submsgs(
+        deps.storage,
+        &ClaimHookMsg::TributeClaimed {
+            claimant: voter.to_string(),
+            round_id,
+            tranche_id,
+            proposal_id: proposal.proposal_id,
+            tribute_id,
+            amount: sent_coin.clone(),
+        },
+    )?;
+
+    let response = Response::new()
         .add_attribute("action", "claim_tribute")
         .add_attribute("sender", info.sender)
         .add_attribute("round_id", round_id.to_string())
@@ -218,21 +619,121 @@ fn claim_tribute(
         .add_attribute("proposal_id", proposal.proposal_id.to_string())
         .add_attribute("tribute_id", tribute_id.to_string())
         .add_attribute("tribute_receiver", voter.clone())
-        .add_attribute("tribute_amount", sent_coin.to_string())
-        .add_message(BankMsg::Send {
-            to_address: voter.to_string(),
-            amount: vec![sent_coin],
-        }))
+        .add_attribute("tribute_amount", sent_coin.to_string());
+
+    Ok((sent_coin, response, claim_hook_msgs))
+}
+
+// Adds the claimed amount to the claimant's running totals for the round and tranche, and for their
+// lifetime, and moves the claimant to the new position in the CLAIMANTS_BY_AMOUNT index.
+fn update_claimed_totals(
+    storage: &mut dyn Storage,
+    round_id: u64,
+    tranche_id: u64,
+    claimant: &Addr,
+    claimed: &Coin,
+) -> StdResult<()> {
+    if claimed.amount.is_zero() {
+        return Ok(());
+    }
+
+    let round_key = (round_id, tranche_id, claimed.denom.clone());
+    let old_total = ROUND_CLAIMED_TOTALS
+        .may_load(storage, (round_key.clone(), claimant.clone()))?
+        .unwrap_or_default();
+    let new_total = old_total.checked_add(claimed.amount)?;
+
+    if !old_total.is_zero() {
+        CLAIMANTS_BY_AMOUNT.remove(
+            storage,
+            (round_key.clone(), old_total.u128(), claimant.clone()),
+        );
+    }
+    CLAIMANTS_BY_AMOUNT.save(
+        storage,
+        (round_key.clone(), new_total.u128(), claimant.clone()),
+        &(),
+    )?;
+    ROUND_CLAIMED_TOTALS.save(storage, (round_key, claimant.clone()), &new_total)?;
+
+    LIFETIME_CLAIMED_TOTALS.update(
+        storage,
+        (claimant.clone(), claimed.denom.clone()),
+        |total| -> StdResult<Uint128> {
+            Ok(total.unwrap_or_default().checked_add(claimed.amount)?)
+        },
+    )?;
+
+    Ok(())
+}
+
+// Returns the part of the given share of the tribute that has vested by the given round. The tribute
+// vests by 1/N of the share for each round that has passed since the end of the tribute's round.
+pub fn get_vested_amount(tribute: &Tribute, share: Uint128, current_round_id: u64) -> Uint128 {
+    match tribute.vesting_rounds {
+        None => share,
+        Some(vesting_rounds) => {
+            let vested_rounds = current_round_id
+                .saturating_sub(tribute.round_id)
+                .min(vesting_rounds);
+            share.multiply_ratio(vested_rounds, vesting_rounds)
+        }
+    }
+}
+
+// Returns the first round in which the whole tribute can be claimed.
+pub fn get_fully_vested_round(tribute: &Tribute) -> u64 {
+    tribute.round_id + tribute.vesting_rounds.unwrap_or(1)
+}
+
+fn validate_voter_eligibility(tribute: &Tribute, vote_power: Decimal) -> Result<(), ContractError> {
+    if let Some(min_vote_power) = tribute.min_vote_power {
+        if vote_power < Decimal::from_ratio(min_vote_power, Uint128::one()) {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "User's voting power is below the minimum of {} required to claim tribute {}",
+                min_vote_power, tribute.tribute_id
+            ))));
+        }
+    }
+
+    Ok(())
+}
+
+// Builds a submessage for each of the registered claim hooks. The submessages only reply on error,
+// and their errors are ignored in reply(), so that a failing hook can't prevent users from claiming tributes.
+fn build_claim_hook_submsgs(
+    storage: &dyn Storage,
+    claim_hook_msg: &ClaimHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(claim_hook_msg)?;
+
+    CLAIM_HOOKS
+        .may_load(storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|hook| {
+            Ok(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: hook.to_string(),
+                    msg: msg.clone(),
+                    funds: vec![],
+                },
+                CLAIM_HOOK_REPLY_ID,
+            )
+            .with_gas_limit(CLAIM_HOOK_GAS_LIMIT)
+            .with_payload(hook.as_bytes().to_vec()))
+        })
+        .collect()
 }
 
+// The tribute is split among the voters proportionally to the tribute weight of their votes,
+// which is equal to their voting power unless the votes qualified for a tribute weight bonus.
 pub fn calculate_voter_claim_amount(
     tribute_funds: Coin,
-    user_voting_power: Decimal,
-    total_proposal_power: Uint128,
+    user_tribute_weight: Decimal,
+    proposal_tribute_weight: Decimal,
 ) -> Result<Coin, ContractError> {
-    let percentage_fraction = match user_voting_power
-        .checked_div(Decimal::from_ratio(total_proposal_power, Uint128::one()))
-    {
+    let percentage_fraction = match user_tribute_weight.checked_div(proposal_tribute_weight) {
         Ok(percentage_fraction) => percentage_fraction,
         Err(_) => {
             return Err(ContractError::Std(StdError::generic_err(
@@ -263,13 +764,16 @@ pub fn calculate_voter_claim_amount(
 }
 
 // RefundTribute(round_id, tranche_id, prop_id, tribute_id):
+//     Check that the tribute belongs to the given round, tranche and proposal
 //     Check that the round is ended
+//     Check that the dispute window for the round and tranche has passed
 //     Check that the prop lost
 //     Check that the sender is the depositor of the tribute
 //     Check that the sender has not already refunded the tribute
 //     Send the tribute back to the sender
 fn refund_tribute(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     round_id: u64,
     proposal_id: u64,
@@ -278,6 +782,16 @@ fn refund_tribute(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
+    // Load the tribute
+    let mut tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+    validate_tribute_location(&tribute, round_id, tranche_id)?;
+    if tribute.proposal_id != proposal_id {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Tribute {} doesn't belong to proposal {}",
+            tribute_id, proposal_id
+        ))));
+    }
+
     // Check that the round is ended by checking that the round_id is less than the current round
     let current_round_id = query_current_round_id(&deps, &config.hydro_contract)?;
     if round_id >= current_round_id {
@@ -286,11 +800,13 @@ fn refund_tribute(
         )));
     }
 
-    get_proposal_tributes_info(&deps.as_ref(), &config, round_id, tranche_id, proposal_id)?
-        .are_tributes_refundable()?;
+    validate_distribution_not_frozen(&deps.as_ref(), &env, &config, round_id, tranche_id)?;
 
-    // Load the tribute
-    let mut tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+    let proposal_info =
+        get_proposal_tributes_info(&deps.as_ref(), &config, round_id, tranche_id, proposal_id)?;
+    if !is_delivery_missed(&tribute, &proposal_info, current_round_id) {
+        proposal_info.are_tributes_refundable()?;
+    }
 
     // Check that the sender is the depositor of the tribute
     if tribute.depositor != info.sender {
@@ -325,15 +841,104 @@ fn refund_tribute(
         }))
 }
 
+// RefundUnclaimedTribute(round_id, tranche_id, prop_id):
+//     Check that the round is ended
+//     Check that the dispute window for the round and tranche has passed
+//     Check that the prop received no votes, so that none of its tributes can ever be claimed
+//     Send each of the tributes on the prop that weren't refunded yet back to its depositor,
+//     aggregating the tributes of each depositor into a single transfer
+fn refund_unclaimed_tribute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let current_round_id = query_current_round_id(&deps, &config.hydro_contract)?;
+    if round_id >= current_round_id {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Round has not ended yet",
+        )));
+    }
+
+    validate_distribution_not_frozen(&deps.as_ref(), &env, &config, round_id, tranche_id)?;
+
+    let proposal =
+        get_proposal(&deps.as_ref(), &config, round_id, tranche_id, proposal_id)?.proposal;
+    if !proposal.power.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Can't refund unclaimed tributes for proposal that received votes",
+        )));
+    }
+
+    let tribute_ids = TRIBUTE_MAP
+        .prefix((round_id, proposal_id))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(_, tribute_id)| tribute_id))
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    let mut refunds: BTreeMap<Addr, Coins> = BTreeMap::new();
+    let mut refunded_tribute_ids = vec![];
+
+    for tribute_id in tribute_ids {
+        let mut tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+        if tribute.refunded || tribute.tranche_id != tranche_id {
+            continue;
+        }
+
+        refunds
+            .entry(tribute.depositor.clone())
+            .or_default()
+            .add(tribute.funds.clone())?;
+        refunded_tribute_ids.push(tribute_id.to_string());
+
+        tribute.refunded = true;
+        ID_TO_TRIBUTE_MAP.save(deps.storage, tribute_id, &tribute)?;
+    }
+
+    if refunded_tribute_ids.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No unclaimed tributes to refund for the proposal",
+        )));
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "refund_unclaimed_tribute")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("refunded_tribute_ids", refunded_tribute_ids.join(","));
+
+    for (depositor, funds) in refunds {
+        response = response.add_message(BankMsg::Send {
+            to_address: depositor.to_string(),
+            amount: funds.into_vec(),
+        });
+    }
+
+    Ok(response)
+}
+
 // Holds information about a proposal: whether the proposal had a liquidity deployment entered,
-// and whether that deployment was for a non-zero amount of funds.
+// whether that deployment was for a non-zero amount of funds, and whether the proposal was voided.
 struct ProposalTributesInfo {
     pub had_deployment_entered: bool,
     pub received_nonzero_funds: bool,
+    pub voided: bool,
 }
 
 impl ProposalTributesInfo {
     fn are_tributes_claimable(&self) -> Result<(), ContractError> {
+        if self.voided {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Tribute not claimable: Proposal was invalidated in Hydro",
+            )));
+        }
+
         if !self.had_deployment_entered {
             return Err(ContractError::Std(StdError::generic_err(
                 "Tribute not claimable: Proposal did not have a liquidity deployment entered",
@@ -350,6 +955,10 @@ impl ProposalTributesInfo {
     }
 
     fn are_tributes_refundable(&self) -> Result<(), ContractError> {
+        if self.voided {
+            return Ok(());
+        }
+
         if !self.had_deployment_entered {
             return Err(ContractError::Std(StdError::generic_err(
                 "Can't refund tribute for proposal that didn't have a liquidity deployment entered",
@@ -366,6 +975,22 @@ impl ProposalTributesInfo {
     }
 }
 
+// Pay-on-delivery tributes become refundable once their delivery deadline passes
+// without a liquidity deployment being entered for the proposal.
+fn is_delivery_missed(
+    tribute: &Tribute,
+    proposal_info: &ProposalTributesInfo,
+    current_round_id: u64,
+) -> bool {
+    match tribute.delivery_deadline_rounds {
+        Some(delivery_deadline_rounds) => {
+            !proposal_info.had_deployment_entered
+                && current_round_id > tribute.round_id + delivery_deadline_rounds
+        }
+        None => false,
+    }
+}
+
 // This function will return an info struct that holds information about the proposal.
 // The info struct will contain information about whether tributes on this proposal are refundable, claimable, or neither.
 fn get_proposal_tributes_info(
@@ -378,6 +1003,7 @@ fn get_proposal_tributes_info(
     let mut info = ProposalTributesInfo {
         had_deployment_entered: false,
         received_nonzero_funds: false,
+        voided: VOIDED_PROPOSALS.has(deps.storage, (round_id, tranche_id, proposal_id)),
     };
 
     // get the liquidity deployments for this proposal
@@ -396,40 +1022,580 @@ fn get_proposal_tributes_info(
     Ok(info)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        QueryMsg::ProposalTributes {
-            round_id,
-            proposal_id,
-            start_from,
-            limit,
-        } => to_json_binary(&query_proposal_tributes(
-            deps,
-            round_id,
-            proposal_id,
-            start_from,
-            limit,
-        )?),
-        QueryMsg::HistoricalTributeClaims {
-            user_address,
-            start_from,
-            limit,
-        } => to_json_binary(&query_historical_tribute_claims(
-            &deps,
-            user_address,
-            start_from,
-            limit,
-        )?),
-        QueryMsg::RoundTributes {
-            round_id,
-            start_from,
-            limit,
-        } => to_json_binary(&query_round_tributes(&deps, round_id, start_from, limit)?),
-        QueryMsg::OutstandingTributeClaims {
-            user_address,
-            round_id,
+// Checks that the tribute is in one of the allowed denoms and that it isn't smaller than
+// the minimum amount for that denom. If no denoms were added to the allowlist, any denom is allowed.
+fn validate_tribute_funds(deps: &DepsMut, funds: &Coin) -> Result<(), ContractError> {
+    if ALLOWED_TRIBUTE_DENOMS.is_empty(deps.storage) {
+        return Ok(());
+    }
+
+    match ALLOWED_TRIBUTE_DENOMS.may_load(deps.storage, funds.denom.clone())? {
+        None => Err(ContractError::Std(StdError::generic_err(format!(
+            "Denom {} is not allowed to be used for tributes",
+            funds.denom
+        )))),
+        Some(min_amount) if funds.amount < min_amount => {
+            Err(ContractError::Std(StdError::generic_err(format!(
+                "Tribute amount {} is below the minimum amount of {} for denom {}",
+                funds.amount, min_amount, funds.denom
+            ))))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+// AddAllowedTributeDenom(denom, min_amount):
+//     Validate that the sender is a whitelist admin in the Hydro contract
+//     Add the denom to the allowlist, or update its minimum amount
+fn add_allowed_tribute_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    min_amount: Uint128,
+) -> Result<Response, ContractError> {
+    validate_sender_is_hydro_whitelist_admin(&deps, &info)?;
+
+    let denom = denom.trim().to_string();
+    if denom.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Denom must not be empty",
+        )));
+    }
+
+    ALLOWED_TRIBUTE_DENOMS.save(deps.storage, denom.clone(), &min_amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_allowed_tribute_denom")
+        .add_attribute("sender", info.sender)
+        .add_attribute("denom", denom)
+        .add_attribute("min_amount", min_amount.to_string()))
+}
+
+// RemoveAllowedTributeDenom(denom):
+//     Validate that the sender is a whitelist admin in the Hydro contract
+//     Remove the denom from the allowlist
+fn remove_allowed_tribute_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    validate_sender_is_hydro_whitelist_admin(&deps, &info)?;
+
+    if !ALLOWED_TRIBUTE_DENOMS.has(deps.storage, denom.clone()) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Denom {} is not in the list of allowed tribute denoms",
+            denom
+        ))));
+    }
+
+    ALLOWED_TRIBUTE_DENOMS.remove(deps.storage, denom.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_allowed_tribute_denom")
+        .add_attribute("sender", info.sender)
+        .add_attribute("denom", denom))
+}
+
+// AddClaimHook(address):
+//     Validate that the sender is a whitelist admin in the Hydro contract
+//     Validate that the hook isn't already registered and that the maximum number of hooks isn't reached
+//     Add the hook contract to the list of claim hooks
+fn add_claim_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    validate_sender_is_hydro_whitelist_admin(&deps, &info)?;
+
+    let hook = deps.api.addr_validate(&address)?;
+    let mut claim_hooks = CLAIM_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+
+    if claim_hooks.contains(&hook) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Claim hook {} is already registered",
+            hook
+        ))));
+    }
+
+    if claim_hooks.len() >= MAX_CLAIM_HOOKS {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Maximum number of claim hooks ({}) reached",
+            MAX_CLAIM_HOOKS
+        ))));
+    }
+
+    claim_hooks.push(hook.clone());
+    CLAIM_HOOKS.save(deps.storage, &claim_hooks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_claim_hook")
+        .add_attribute("sender", info.sender)
+        .add_attribute("claim_hook", hook))
+}
+
+// RemoveClaimHook(address):
+//     Validate that the sender is a whitelist admin in the Hydro contract
+//     Remove the hook contract from the list of claim hooks
+fn remove_claim_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    validate_sender_is_hydro_whitelist_admin(&deps, &info)?;
+
+    let hook = deps.api.addr_validate(&address)?;
+    let mut claim_hooks = CLAIM_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+
+    if !claim_hooks.contains(&hook) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Claim hook {} is not registered",
+            hook
+        ))));
+    }
+
+    claim_hooks.retain(|registered_hook| *registered_hook != hook);
+    CLAIM_HOOKS.save(deps.storage, &claim_hooks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_claim_hook")
+        .add_attribute("sender", info.sender)
+        .add_attribute("claim_hook", hook))
+}
+
+// RegisterSwapVenue(offer_denom, ask_denom, venue):
+//     Validate that the sender is a whitelist admin in the Hydro contract
+//     Register the venue contract for swapping offer_denom into ask_denom, replacing any previous one
+fn register_swap_venue(
+    deps: DepsMut,
+    info: MessageInfo,
+    offer_denom: String,
+    ask_denom: String,
+    venue: String,
+) -> Result<Response, ContractError> {
+    validate_sender_is_hydro_whitelist_admin(&deps, &info)?;
+
+    if offer_denom == ask_denom {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Offer denom and ask denom must be different",
+        )));
+    }
+
+    let venue = deps.api.addr_validate(&venue)?;
+    SWAP_VENUES.save(
+        deps.storage,
+        (offer_denom.clone(), ask_denom.clone()),
+        &venue,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_swap_venue")
+        .add_attribute("sender", info.sender)
+        .add_attribute("offer_denom", offer_denom)
+        .add_attribute("ask_denom", ask_denom)
+        .add_attribute("swap_venue", venue))
+}
+
+// RemoveSwapVenue(offer_denom, ask_denom):
+//     Validate that the sender is a whitelist admin in the Hydro contract
+//     Remove the venue contract registered for swapping offer_denom into ask_denom
+fn remove_swap_venue(
+    deps: DepsMut,
+    info: MessageInfo,
+    offer_denom: String,
+    ask_denom: String,
+) -> Result<Response, ContractError> {
+    validate_sender_is_hydro_whitelist_admin(&deps, &info)?;
+
+    let key = (offer_denom.clone(), ask_denom.clone());
+    if !SWAP_VENUES.has(deps.storage, key.clone()) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "No swap venue registered for swapping {} into {}",
+            offer_denom, ask_denom
+        ))));
+    }
+
+    SWAP_VENUES.remove(deps.storage, key);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_swap_venue")
+        .add_attribute("sender", info.sender)
+        .add_attribute("offer_denom", offer_denom)
+        .add_attribute("ask_denom", ask_denom))
+}
+
+// SetDisputeWindow(tranche_id, duration):
+//     Validate that the sender is a whitelist admin in the Hydro contract
+//     Set the length of the dispute window for the tranche, or remove it if the duration is 0
+fn set_dispute_window(
+    deps: DepsMut,
+    info: MessageInfo,
+    tranche_id: u64,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    validate_sender_is_hydro_whitelist_admin(&deps, &info)?;
+
+    match duration {
+        0 => DISPUTE_WINDOWS.remove(deps.storage, tranche_id),
+        duration => DISPUTE_WINDOWS.save(deps.storage, tranche_id, &duration)?,
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_dispute_window")
+        .add_attribute("sender", info.sender)
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("duration", duration.to_string()))
+}
+
+// ResolveTributeDispute(tribute_id, resolution):
+//     Validate that the sender is a whitelist admin in the Hydro contract
+//     Validate that the tribute wasn't refunded and that its dispute wasn't already resolved
+//     Validate that the round has ended and that the dispute window for the round and tranche is still open
+//     Refund the tribute to its depositor, or move it to another proposal of the same round and tranche
+//     Record the resolution of the dispute
+fn resolve_tribute_dispute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tribute_id: u64,
+    resolution: DisputeResolution,
+) -> Result<Response, ContractError> {
+    validate_sender_is_hydro_whitelist_admin(&deps, &info)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+    let (round_id, tranche_id) = (tribute.round_id, tribute.tranche_id);
+
+    if tribute.refunded {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Tribute {} has already been refunded",
+            tribute_id
+        ))));
+    }
+
+    if TRIBUTE_DISPUTE_RESOLUTIONS.has(deps.storage, (round_id, tranche_id, tribute_id)) {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Dispute for tribute {} has already been resolved",
+            tribute_id
+        ))));
+    }
+
+    if round_id >= query_current_round_id(&deps, &config.hydro_contract)? {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Round has not ended yet",
+        )));
+    }
+
+    match get_dispute_window_end(&deps.as_ref(), &config, round_id, tranche_id)? {
+        None => {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "No dispute window is set for tranche {}",
+                tranche_id
+            ))))
+        }
+        Some(dispute_window_end) if env.block.time >= dispute_window_end => {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Dispute window for round {} and tranche {} ended at: {}",
+                round_id, tranche_id, dispute_window_end
+            ))))
+        }
+        Some(_) => {}
+    }
+
+    let previous_proposal_id = tribute.proposal_id;
+    let mut response = Response::new()
+        .add_attribute("action", "resolve_tribute_dispute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("tribute_id", tribute_id.to_string());
+
+    match resolution {
+        DisputeResolution::Refund {} => {
+            tribute.refunded = true;
+
+            response = response
+                .add_attribute("resolution", "refund")
+                .add_attribute("refunded_amount", tribute.funds.to_string())
+                .add_message(BankMsg::Send {
+                    to_address: tribute.depositor.to_string(),
+                    amount: vec![tribute.funds.clone()],
+                });
+        }
+        DisputeResolution::Retarget { proposal_id } => {
+            if proposal_id == previous_proposal_id {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Tribute {} already belongs to proposal {}",
+                    tribute_id, proposal_id
+                ))));
+            }
+
+            // Check that the new proposal exists in the same round and tranche
+            query_proposal(
+                &deps,
+                &config.hydro_contract,
+                round_id,
+                tranche_id,
+                proposal_id,
+            )?;
+
+            TRIBUTE_MAP.remove(deps.storage, (round_id, previous_proposal_id, tribute_id));
+            TRIBUTE_MAP.save(
+                deps.storage,
+                (round_id, proposal_id, tribute_id),
+                &tribute_id,
+            )?;
+            tribute.proposal_id = proposal_id;
+
+            response = response
+                .add_attribute("resolution", "retarget")
+                .add_attribute("previous_proposal_id", previous_proposal_id.to_string())
+                .add_attribute("proposal_id", proposal_id.to_string());
+        }
+    }
+
+    ID_TO_TRIBUTE_MAP.save(deps.storage, tribute_id, &tribute)?;
+    TRIBUTE_DISPUTE_RESOLUTIONS.save(
+        deps.storage,
+        (round_id, tranche_id, tribute_id),
+        &TributeDisputeResolution {
+            tribute_id,
+            previous_proposal_id,
+            resolution,
+            resolved_at: env.block.time,
+        },
+    )?;
+
+    Ok(response)
+}
+
+// SyncProposalOutcomes(round_id, tranche_id):
+//     Check that the round is ended, since proposals can only be invalidated during their round
+//     Load the proposals of the round and tranche that were invalidated in Hydro
+//     Void the proposals that weren't voided yet, which makes their tributes refundable instead of claimable
+fn sync_proposal_outcomes(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: u64,
+    tranche_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if round_id >= query_current_round_id(&deps, &config.hydro_contract)? {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Round has not ended yet",
+        )));
+    }
+
+    let invalidated_proposals: InvalidatedProposalsResponse = deps.querier.query_wasm_smart(
+        &config.hydro_contract,
+        &HydroQueryMsg::InvalidatedProposals {
+            round_id,
+            tranche_id,
+        },
+    )?;
+
+    let mut voided_proposal_ids = vec![];
+    for proposal in invalidated_proposals.proposals {
+        let key = (round_id, tranche_id, proposal.proposal_id);
+        if VOIDED_PROPOSALS.has(deps.storage, key) {
+            continue;
+        }
+
+        VOIDED_PROPOSALS.save(
+            deps.storage,
+            key,
+            &VoidedProposal {
+                reason: proposal.reason,
+                voided_at: env.block.time,
+            },
+        )?;
+        voided_proposal_ids.push(proposal.proposal_id.to_string());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "sync_proposal_outcomes")
+        .add_attribute("sender", info.sender)
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("tranche_id", tranche_id.to_string())
+        .add_attribute("voided_proposal_ids", voided_proposal_ids.join(",")))
+}
+
+// Returns the end of the dispute window for the tributes of the given round and tranche,
+// or None if no dispute window is set for the tranche. The dispute window starts at the end of the round.
+fn get_dispute_window_end(
+    deps: &Deps,
+    config: &Config,
+    round_id: u64,
+    tranche_id: u64,
+) -> Result<Option<Timestamp>, ContractError> {
+    let Some(dispute_window) = DISPUTE_WINDOWS.may_load(deps.storage, tranche_id)? else {
+        return Ok(None);
+    };
+
+    let round_cutoffs: RoundCutoffsResponse = deps.querier.query_wasm_smart(
+        &config.hydro_contract,
+        &HydroQueryMsg::RoundCutoffs { round_id },
+    )?;
+
+    Ok(Some(round_cutoffs.round_end.plus_nanos(dispute_window)))
+}
+
+// Returns the time until which the tribute funds are held in escrow: the end of the tribute's round,
+// extended by the dispute window for its tranche, if one is set.
+fn get_escrow_end(
+    deps: &Deps,
+    config: &Config,
+    tribute: &Tribute,
+) -> Result<Timestamp, ContractError> {
+    if let Some(dispute_window_end) =
+        get_dispute_window_end(deps, config, tribute.round_id, tribute.tranche_id)?
+    {
+        return Ok(dispute_window_end);
+    }
+
+    let round_cutoffs: RoundCutoffsResponse = deps.querier.query_wasm_smart(
+        &config.hydro_contract,
+        &HydroQueryMsg::RoundCutoffs {
+            round_id: tribute.round_id,
+        },
+    )?;
+
+    Ok(round_cutoffs.round_end)
+}
+
+// Tributes are held in escrow under the round and tranche they were added for,
+// so they can only be claimed or refunded by referring to that same round and tranche.
+fn validate_tribute_location(
+    tribute: &Tribute,
+    round_id: u64,
+    tranche_id: u64,
+) -> Result<(), ContractError> {
+    if tribute.round_id != round_id || tribute.tranche_id != tranche_id {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Tribute {} doesn't belong to round {} and tranche {}",
+            tribute.tribute_id, round_id, tranche_id
+        ))));
+    }
+
+    Ok(())
+}
+
+// Tributes can't be claimed or refunded until the dispute window for their round and tranche has passed.
+fn validate_distribution_not_frozen(
+    deps: &Deps,
+    env: &Env,
+    config: &Config,
+    round_id: u64,
+    tranche_id: u64,
+) -> Result<(), ContractError> {
+    if let Some(dispute_window_end) = get_dispute_window_end(deps, config, round_id, tranche_id)? {
+        if env.block.time < dispute_window_end {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Tribute distribution for round {} and tranche {} is frozen until the dispute window ends at: {}",
+                round_id, tranche_id, dispute_window_end
+            ))));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_sender_is_hydro_whitelist_admin(
+    deps: &DepsMut,
+    info: &MessageInfo,
+) -> Result<(), ContractError> {
+    let hydro_contract = CONFIG.load(deps.storage)?.hydro_contract;
+    let whitelist_admins: WhitelistAdminsResponse = deps
+        .querier
+        .query_wasm_smart(hydro_contract, &HydroQueryMsg::WhitelistAdmins {})?;
+
+    if !whitelist_admins.admins.contains(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+// Claim hooks are fire-and-forget: if a hook fails, the error is only recorded in the
+// response attributes, and the tribute claim still succeeds.
+// If the swap of a tribute claimed through ClaimTributeAs fails, the claimed funds are still in
+// this contract, and they are sent to the claimant in their original denom instead.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SWAP_REPLY_ID => {
+            let fallback: SwapFallback = from_json(&msg.payload)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "claim_swap_failed")
+                .add_attribute("tribute_receiver", fallback.recipient.clone())
+                .add_attribute("tribute_amount", fallback.funds.to_string())
+                .add_attribute("error", msg.result.into_result().err().unwrap_or_default())
+                .add_message(BankMsg::Send {
+                    to_address: fallback.recipient,
+                    amount: vec![fallback.funds],
+                }))
+        }
+        CLAIM_HOOK_REPLY_ID => Ok(Response::new()
+            .add_attribute("action", "claim_hook_failed")
+            .add_attribute(
+                "claim_hook",
+                String::from_utf8_lossy(msg.payload.as_slice()),
+            )
+            .add_attribute("error", msg.result.into_result().err().unwrap_or_default())),
+        _ => Err(ContractError::Std(StdError::generic_err(format!(
+            "Unexpected reply id: {}",
+            msg.id
+        )))),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::ProposalTributes {
+            round_id,
+            proposal_id,
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_proposal_tributes(
+            deps,
+            round_id,
+            proposal_id,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::HistoricalTributeClaims {
+            user_address,
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_historical_tribute_claims(
+            &deps,
+            user_address,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::RoundTributes {
+            round_id,
+            start_from,
+            start_after,
+            limit,
+        } => to_json_binary(&query_round_tributes(
+            &deps,
+            round_id,
+            start_from,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::OutstandingTributeClaims {
+            user_address,
+            round_id,
             tranche_id,
             start_from,
             limit,
@@ -441,6 +1607,52 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_from,
             limit,
         )?),
+        QueryMsg::AllowedTributeDenoms {} => to_json_binary(&query_allowed_tribute_denoms(deps)?),
+        QueryMsg::SwapVenues {} => to_json_binary(&query_swap_venues(deps)?),
+        QueryMsg::ClaimHooks {} => to_json_binary(&query_claim_hooks(deps)?),
+        QueryMsg::DisputeStatus {
+            round_id,
+            tranche_id,
+        } => to_json_binary(&query_dispute_status(deps, env, round_id, tranche_id)?),
+        QueryMsg::TributeEscrow { tribute_id } => {
+            to_json_binary(&query_tribute_escrow(deps, env, tribute_id)?)
+        }
+        QueryMsg::TributeOutcome { tribute_id } => {
+            to_json_binary(&query_tribute_outcome(deps, tribute_id)?)
+        }
+        QueryMsg::TopTributeEarners {
+            round_id,
+            tranche_id,
+            denom,
+            limit,
+        } => to_json_binary(&query_top_tribute_earners(
+            deps, round_id, tranche_id, denom, limit,
+        )?),
+        QueryMsg::LifetimeTributeClaims { address } => {
+            to_json_binary(&query_lifetime_tribute_claims(deps, address)?)
+        }
+        QueryMsg::TributeVestingStatus {
+            tribute_id,
+            user_address,
+        } => to_json_binary(&query_tribute_vesting_status(
+            &deps,
+            tribute_id,
+            user_address,
+        )?),
+        QueryMsg::TributeClaimsReport {
+            user_address,
+            start_round_id,
+            end_round_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_tribute_claims_report(
+            &deps,
+            user_address,
+            start_round_id,
+            end_round_id,
+            start_after,
+            limit,
+        )?),
     }
 }
 
@@ -450,23 +1662,136 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     })
 }
 
+pub fn query_claim_hooks(deps: Deps) -> StdResult<ClaimHooksResponse> {
+    Ok(ClaimHooksResponse {
+        hooks: CLAIM_HOOKS.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+pub fn query_dispute_status(
+    deps: Deps,
+    env: Env,
+    round_id: u64,
+    tranche_id: u64,
+) -> StdResult<DisputeStatusResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let dispute_window_end = get_dispute_window_end(&deps, &config, round_id, tranche_id)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    let resolutions = TRIBUTE_DISPUTE_RESOLUTIONS
+        .prefix((round_id, tranche_id))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(_, resolution)| resolution))
+        .collect::<StdResult<Vec<TributeDisputeResolution>>>()?;
+
+    Ok(DisputeStatusResponse {
+        dispute_window_end,
+        distribution_frozen: dispute_window_end
+            .is_some_and(|dispute_window_end| env.block.time < dispute_window_end),
+        resolutions,
+    })
+}
+
+pub fn query_tribute_escrow(
+    deps: Deps,
+    env: Env,
+    tribute_id: u64,
+) -> StdResult<TributeEscrowResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+    let escrowed_until = get_escrow_end(&deps, &config, &tribute)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(TributeEscrowResponse {
+        escrowed: !tribute.refunded && env.block.time < escrowed_until,
+        escrowed_until,
+        tribute,
+    })
+}
+
+pub fn query_tribute_outcome(deps: Deps, tribute_id: u64) -> StdResult<TributeOutcomeResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+    let key = (tribute.round_id, tribute.tranche_id, tribute.proposal_id);
+
+    let outcome = if tribute.refunded {
+        TributeOutcome::Refunded {}
+    } else if let Some(voided_proposal) = VOIDED_PROPOSALS.may_load(deps.storage, key)? {
+        TributeOutcome::Voided {
+            reason: voided_proposal.reason,
+        }
+    } else {
+        let info = get_proposal_tributes_info(
+            &deps,
+            &config,
+            tribute.round_id,
+            tribute.tranche_id,
+            tribute.proposal_id,
+        )
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+        let current_round_id = query_current_round_id_with_deps(&deps, &config.hydro_contract)?;
+
+        match (info.had_deployment_entered, info.received_nonzero_funds) {
+            (false, _) if is_delivery_missed(&tribute, &info, current_round_id) => {
+                TributeOutcome::DeliveryMissed {}
+            }
+            (false, _) => TributeOutcome::Pending {},
+            (true, true) => TributeOutcome::Claimable {},
+            (true, false) => TributeOutcome::Refundable {},
+        }
+    };
+
+    Ok(TributeOutcomeResponse {
+        tribute_id,
+        outcome,
+    })
+}
+
+pub fn query_swap_venues(deps: Deps) -> StdResult<SwapVenuesResponse> {
+    let venues = SWAP_VENUES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|venue| {
+            venue.map(|((offer_denom, ask_denom), venue)| SwapVenue {
+                offer_denom,
+                ask_denom,
+                venue,
+            })
+        })
+        .collect::<StdResult<Vec<SwapVenue>>>()?;
+
+    Ok(SwapVenuesResponse { venues })
+}
+
+pub fn query_allowed_tribute_denoms(deps: Deps) -> StdResult<AllowedTributeDenomsResponse> {
+    let denoms = ALLOWED_TRIBUTE_DENOMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|l| l.map(|(denom, min_amount)| AllowedTributeDenom { denom, min_amount }))
+        .collect::<StdResult<Vec<AllowedTributeDenom>>>()?;
+
+    Ok(AllowedTributeDenomsResponse { denoms })
+}
+
 pub fn query_proposal_tributes(
     deps: Deps,
     round_id: u64,
     proposal_id: u64,
     start_from: u32,
+    start_after: Option<u64>,
     limit: u32,
 ) -> StdResult<ProposalTributesResponse> {
-    let tributes = TRIBUTE_MAP
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
+    let tributes: Vec<Tribute> = TRIBUTE_MAP
         .prefix((round_id, proposal_id))
-        .range(deps.storage, None, None, Order::Ascending)
+        .range(deps.storage, start, None, Order::Ascending)
         .map(|l| l.unwrap().1)
-        .skip(start_from as usize)
+        .skip(skip)
         .take(limit as usize)
         .map(|tribute_id| ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id).unwrap())
         .collect();
+    let next_key = get_next_key(&tributes, limit as usize, |tribute| tribute.tribute_id);
 
-    Ok(ProposalTributesResponse { tributes })
+    Ok(ProposalTributesResponse { tributes, next_key })
 }
 
 fn query_current_round_id(deps: &DepsMut, hydro_contract: &Addr) -> Result<u64, ContractError> {
@@ -477,6 +1802,26 @@ fn query_current_round_id(deps: &DepsMut, hydro_contract: &Addr) -> Result<u64,
     Ok(current_round_resp.round_id)
 }
 
+fn query_current_round_id_with_deps(deps: &Deps, hydro_contract: &Addr) -> StdResult<u64> {
+    let current_round_resp: CurrentRoundResponse = deps
+        .querier
+        .query_wasm_smart(hydro_contract, &HydroQueryMsg::CurrentRound {})?;
+
+    Ok(current_round_resp.round_id)
+}
+
+fn query_round_cutoffs(
+    deps: &DepsMut,
+    hydro_contract: &Addr,
+    round_id: u64,
+) -> Result<RoundCutoffsResponse, ContractError> {
+    let round_cutoffs_resp: RoundCutoffsResponse = deps
+        .querier
+        .query_wasm_smart(hydro_contract, &HydroQueryMsg::RoundCutoffs { round_id })?;
+
+    Ok(round_cutoffs_resp)
+}
+
 fn query_proposal(
     deps: &DepsMut,
     hydro_contract: &Addr,
@@ -521,63 +1866,250 @@ pub fn query_historical_tribute_claims(
     deps: &Deps,
     address: String,
     start_from: u32,
+    start_after: Option<u64>,
     limit: u32,
 ) -> StdResult<HistoricalTributeClaimsResponse> {
     // go through all TRIBUTE_CLAIMS for the address
     let address = deps.api.addr_validate(&address)?;
-    Ok(HistoricalTributeClaimsResponse {
-        claims: TRIBUTE_CLAIMS
-            .prefix(address)
-            .range(deps.storage, None, None, Order::Ascending)
-            .skip(start_from as usize)
-            .take(limit as usize)
-            .filter_map(|l| {
-                if l.is_err() {
-                    // log an error and skip this entry
-                    deps.api.debug("Error reading tribute claim");
-                    return None;
-                }
-                let (tribute_id, amount) = l.unwrap();
-                let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id).unwrap();
-                Some(TributeClaim {
-                    round_id: tribute.round_id,
-                    tranche_id: tribute.tranche_id,
-                    proposal_id: tribute.proposal_id,
-                    tribute_id,
-                    amount,
-                })
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
+    let claims: Vec<TributeClaim> = TRIBUTE_CLAIMS
+        .prefix(address)
+        .range(deps.storage, start, None, Order::Ascending)
+        .skip(skip)
+        .take(limit as usize)
+        .filter_map(|l| {
+            if l.is_err() {
+                // log an error and skip this entry
+                deps.api.debug("Error reading tribute claim");
+                return None;
+            }
+            let (tribute_id, amount) = l.unwrap();
+            let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id).unwrap();
+            Some(TributeClaim {
+                round_id: tribute.round_id,
+                tranche_id: tribute.tranche_id,
+                proposal_id: tribute.proposal_id,
+                tribute_id,
+                amount,
             })
-            .collect(),
-    })
+        })
+        .collect();
+    let next_key = get_next_key(&claims, limit as usize, |claim| claim.tribute_id);
+
+    Ok(HistoricalTributeClaimsResponse { claims, next_key })
+}
+
+// Returns the addresses that claimed the most tributes in the given denom for the round and tranche.
+// Only the first `limit` entries of the CLAIMANTS_BY_AMOUNT index are read, regardless of the number of claimants.
+pub fn query_top_tribute_earners(
+    deps: Deps,
+    round_id: u64,
+    tranche_id: u64,
+    denom: String,
+    limit: u32,
+) -> StdResult<TopTributeEarnersResponse> {
+    let earners = CLAIMANTS_BY_AMOUNT
+        .sub_prefix((round_id, tranche_id, denom))
+        .keys(deps.storage, None, None, Order::Descending)
+        .take(limit as usize)
+        .map(|key| {
+            key.map(|(amount, address)| TributeEarner {
+                address,
+                amount: Uint128::new(amount),
+            })
+        })
+        .collect::<StdResult<Vec<TributeEarner>>>()?;
+
+    Ok(TopTributeEarnersResponse { earners })
+}
+
+pub fn query_lifetime_tribute_claims(
+    deps: Deps,
+    address: String,
+) -> StdResult<LifetimeTributeClaimsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let claimed = LIFETIME_CLAIMED_TOTALS
+        .prefix(address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<Coin>>>()?;
+
+    Ok(LifetimeTributeClaimsResponse { claimed })
 }
 
 pub fn query_round_tributes(
     deps: &Deps,
     round_id: u64,
     start_from: u32,
+    start_after: Option<(u64, u64)>,
     limit: u32,
 ) -> StdResult<RoundTributesResponse> {
-    Ok(RoundTributesResponse {
-        tributes: TRIBUTE_MAP
-            .sub_prefix(round_id)
-            .range(deps.storage, None, None, Order::Ascending)
-            .skip(start_from as usize)
-            .take(limit as usize)
-            .filter_map(|l| {
-                if l.is_err() {
-                    // log an error and skip this entry
-                    deps.api
-                        .debug(format!("Error reading tribute: {:?}", l).as_str());
-                    return None;
-                }
-                let (_, tribute_id) = l.unwrap();
-                let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id).unwrap();
-                Some(tribute)
-            })
-            .collect(),
+    let (start, skip) = get_pagination_start(start_after, start_from as usize);
+    let tributes: Vec<Tribute> = TRIBUTE_MAP
+        .sub_prefix(round_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .skip(skip)
+        .take(limit as usize)
+        .filter_map(|l| {
+            if l.is_err() {
+                // log an error and skip this entry
+                deps.api
+                    .debug(format!("Error reading tribute: {:?}", l).as_str());
+                return None;
+            }
+            let (_, tribute_id) = l.unwrap();
+            let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id).unwrap();
+            Some(tribute)
+        })
+        .collect();
+    let next_key = get_next_key(&tributes, limit as usize, |tribute| {
+        (tribute.proposal_id, tribute.tribute_id)
+    });
+
+    Ok(RoundTributesResponse { tributes, next_key })
+}
+
+// Returns the user's share of the given tribute, together with the parts of it that have vested and
+// that were claimed so far. The share is zero if the user didn't vote for the tribute's proposal, or
+// isn't eligible to claim the tribute.
+pub fn query_tribute_vesting_status(
+    deps: &Deps,
+    tribute_id: u64,
+    user_address: String,
+) -> StdResult<TributeVestingStatusResponse> {
+    let address = deps.api.addr_validate(&user_address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+    let current_round_id = query_current_round_id_with_deps(deps, &config.hydro_contract)?;
+
+    let zero_coin = || Coin {
+        denom: tribute.funds.denom.clone(),
+        amount: Uint128::zero(),
+    };
+
+    let user_vote = query_user_votes(
+        deps,
+        &config.hydro_contract,
+        tribute.round_id,
+        tribute.tranche_id,
+        address.to_string(),
+    )
+    .unwrap_or_default()
+    .into_iter()
+    .find(|vote| vote.prop_id == tribute.proposal_id);
+
+    let share = match user_vote {
+        Some(vote) if validate_voter_eligibility(&tribute, vote.power).is_ok() => {
+            let ProposalResponse { tribute_weight, .. } = get_proposal(
+                deps,
+                &config,
+                tribute.round_id,
+                tribute.tranche_id,
+                tribute.proposal_id,
+            )
+            .map_err(|err| StdError::generic_err(format!("Failed to get proposal: {}", err)))?;
+
+            calculate_voter_claim_amount(tribute.funds.clone(), vote.tribute_weight, tribute_weight)
+                .map_err(|err| StdError::generic_err(err.to_string()))?
+        }
+        _ => zero_coin(),
+    };
+
+    // nothing has vested before the round of the tribute ends
+    let vested = Coin {
+        denom: share.denom.clone(),
+        amount: if current_round_id > tribute.round_id {
+            get_vested_amount(&tribute, share.amount, current_round_id)
+        } else {
+            Uint128::zero()
+        },
+    };
+    let claimed = TRIBUTE_CLAIMS
+        .may_load(deps.storage, (address, tribute_id))?
+        .unwrap_or_else(zero_coin);
+    let claimable = Coin {
+        denom: share.denom.clone(),
+        amount: vested.amount.saturating_sub(claimed.amount),
+    };
+
+    Ok(TributeVestingStatusResponse {
+        tribute_id,
+        fully_vested_round: get_fully_vested_round(&tribute),
+        share,
+        vested,
+        claimed,
+        claimable,
     })
 }
 
+// Returns the tributes claimed by the user from the rounds in the given range, along with the end time of
+// each round, built from the user's TRIBUTE_CLAIMS entries. The end time of each round is queried from
+// Hydro only once per query.
+pub fn query_tribute_claims_report(
+    deps: &Deps,
+    user_address: String,
+    start_round_id: u64,
+    end_round_id: u64,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<TributeClaimsReportResponse> {
+    if start_round_id > end_round_id {
+        return Err(StdError::generic_err(
+            "Start round id must not be greater than end round id",
+        ));
+    }
+
+    let address = deps.api.addr_validate(&user_address)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut round_ends: BTreeMap<u64, Timestamp> = BTreeMap::new();
+    let mut entries = vec![];
+
+    for claim in TRIBUTE_CLAIMS.prefix(address).range(
+        deps.storage,
+        start_after.map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    ) {
+        if entries.len() >= limit as usize {
+            break;
+        }
+
+        let (tribute_id, amount) = claim?;
+        let tribute = ID_TO_TRIBUTE_MAP.load(deps.storage, tribute_id)?;
+        if tribute.round_id < start_round_id || tribute.round_id > end_round_id {
+            continue;
+        }
+
+        let round_end = match round_ends.get(&tribute.round_id) {
+            Some(round_end) => *round_end,
+            None => {
+                let round_cutoffs: RoundCutoffsResponse = deps.querier.query_wasm_smart(
+                    &config.hydro_contract,
+                    &HydroQueryMsg::RoundCutoffs {
+                        round_id: tribute.round_id,
+                    },
+                )?;
+                round_ends.insert(tribute.round_id, round_cutoffs.round_end);
+                round_cutoffs.round_end
+            }
+        };
+
+        entries.push(TributeClaimsReportEntry {
+            round_id: tribute.round_id,
+            round_end,
+            tranche_id: tribute.tranche_id,
+            proposal_id: tribute.proposal_id,
+            tribute_id,
+            amount,
+        });
+    }
+
+    let next_key = get_next_key(&entries, limit as usize, |entry| entry.tribute_id);
+
+    Ok(TributeClaimsReportResponse { entries, next_key })
+}
+
 // This goes through all the tributes for a certain round and tranche,
 // then checks whether the given user address can claim them.
 // If the user has not claimed the tribute yet, the amount that the user would receive when claiming is
@@ -603,6 +2135,7 @@ pub fn query_outstanding_tribute_claims(
     .map_err(|err| StdError::generic_err(format!("Failed to get user votes: {}", err)))?;
 
     let config = CONFIG.load(deps.storage)?;
+    let current_round_id = query_current_round_id_with_deps(deps, &config.hydro_contract)?;
     let mut claims = vec![];
 
     for user_vote in user_votes {
@@ -614,7 +2147,11 @@ pub fn query_outstanding_tribute_claims(
             continue;
         }
 
-        let proposal = get_proposal(deps, &config, round_id, tranche_id, user_vote.prop_id)
+        let ProposalResponse {
+            proposal,
+            tribute_weight,
+            ..
+        } = get_proposal(deps, &config, round_id, tranche_id, user_vote.prop_id)
             .map_err(|err| StdError::generic_err(format!("Failed to get proposal: {}", err)))?;
 
         // get all tributes for this proposal
@@ -628,43 +2165,58 @@ pub fn query_outstanding_tribute_claims(
                 }
                 l.is_ok()
             })
-            .filter_map(|l| {
-                if l.is_ok() {
-                    let (_, tribute_id) = l.unwrap();
-                    Some(tribute_id)
-                } else {
-                    None
-                }
-            })
-            .filter(
-                // make sure that the user has not claimed the tribute already
-                |tribute_id| !TRIBUTE_CLAIMS.has(deps.storage, (address.clone(), *tribute_id)),
-            )
-            .skip(start_from as usize)
-            .take(limit as usize)
+            .filter_map(|l| l.ok().map(|(_, tribute_id)| tribute_id))
             .filter_map(|tribute_id| {
                 ID_TO_TRIBUTE_MAP
                     .may_load(deps.storage, tribute_id)
                     .unwrap_or(None)
             })
+            .filter(|tribute| !tribute.refunded)
+            .filter(
+                // make sure that the user has not claimed the tribute already; vesting tributes
+                // are skipped below if there is nothing left to claim
+                |tribute| {
+                    tribute.vesting_rounds.is_some()
+                        || !TRIBUTE_CLAIMS.has(deps.storage, (address.clone(), tribute.tribute_id))
+                },
+            )
+            .skip(start_from as usize)
+            .take(limit as usize)
             .collect::<Vec<Tribute>>();
 
         // for each tribute, compute the amount that the user would receive when claiming
         tributes
             .iter()
+            .filter(|tribute| validate_voter_eligibility(tribute, user_vote.power).is_ok())
             .filter_map(|tribute| {
                 match calculate_voter_claim_amount(
                     tribute.funds.clone(),
-                    user_vote.power,
-                    proposal.power,
+                    user_vote.tribute_weight,
+                    tribute_weight,
                 ) {
-                    Ok(sent_coin) => Some(TributeClaim {
-                        round_id: tribute.round_id,
-                        tranche_id: tribute.tranche_id,
-                        proposal_id: tribute.proposal_id,
-                        tribute_id: tribute.tribute_id,
-                        amount: sent_coin,
-                    }),
+                    Ok(share) => {
+                        let claimed_amount = TRIBUTE_CLAIMS
+                            .may_load(deps.storage, (address.clone(), tribute.tribute_id))
+                            .unwrap_or(None)
+                            .map(|claim| claim.amount)
+                            .unwrap_or_default();
+                        let amount = get_vested_amount(tribute, share.amount, current_round_id)
+                            .saturating_sub(claimed_amount);
+                        if !claimed_amount.is_zero() && amount.is_zero() {
+                            return None;
+                        }
+
+                        Some(TributeClaim {
+                            round_id: tribute.round_id,
+                            tranche_id: tribute.tranche_id,
+                            proposal_id: tribute.proposal_id,
+                            tribute_id: tribute.tribute_id,
+                            amount: Coin {
+                                denom: share.denom,
+                                amount,
+                            },
+                        })
+                    }
                     Err(err) => {
                         // log an error and skip this entry
                         deps.api.debug(
@@ -686,17 +2238,15 @@ fn get_proposal(
     round_id: u64,
     tranche_id: u64,
     proposal_id: u64,
-) -> Result<Proposal, ContractError> {
-    let proposal_resp: ProposalResponse = deps.querier.query_wasm_smart(
+) -> Result<ProposalResponse, ContractError> {
+    Ok(deps.querier.query_wasm_smart(
         &config.hydro_contract,
         &HydroQueryMsg::Proposal {
             round_id,
             tranche_id,
             proposal_id,
         },
-    )?;
-
-    Ok(proposal_resp.proposal)
+    )?)
 }
 
 fn get_liquidity_deployment(
@@ -1,3 +1,5 @@
+use cosmwasm_std::{Coin, Uint128};
+use hydro::msg::LockVote;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -9,11 +11,19 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, cw_orch::ExecuteFns)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    // If min_vote_power is set, voters whose voting power for the proposal
+    // is below it are not eligible to claim the tribute.
     #[cw_orch(payable)]
     AddTribute {
         round_id: u64,
         tranche_id: u64,
         proposal_id: u64,
+        min_vote_power: Option<Uint128>,
+        // Can't be larger than the deployment duration of the proposal.
+        vesting_rounds: Option<u64>,
+        // If set, the tribute is paid on delivery: if no liquidity deployment is entered for the proposal
+        // within the given number of rounds after the tribute's round, the tribute can be refunded instead.
+        delivery_deadline_rounds: Option<u64>,
     },
     ClaimTribute {
         round_id: u64,
@@ -21,10 +31,106 @@ pub enum ExecuteMsg {
         tribute_id: u64,
         voter_address: String,
     },
+    // Claims the tribute for the sender and swaps it into ask_denom through the registered swap venue.
+    // If the swap fails, the tribute is sent to the sender in its original denom.
+    ClaimTributeAs {
+        round_id: u64,
+        tranche_id: u64,
+        tribute_id: u64,
+        ask_denom: String,
+        min_output: Uint128,
+    },
+    // Claims all outstanding tributes of the voter on the proposals that the given locks voted for.
+    // Only callable by the Hydro contract, which sends it when the voter unlocks tokens with claim_tributes set.
+    ClaimForLocks {
+        voter_address: String,
+        lock_votes: Vec<LockVote>,
+    },
     RefundTribute {
         round_id: u64,
         tranche_id: u64,
         proposal_id: u64,
         tribute_id: u64,
     },
+    // Refunds all the tributes on a proposal that received no votes to their depositors.
+    // Can be executed by anyone once the round has ended.
+    RefundUnclaimedTribute {
+        round_id: u64,
+        tranche_id: u64,
+        proposal_id: u64,
+    },
+    AddAllowedTributeDenom {
+        denom: String,
+        min_amount: Uint128,
+    },
+    RemoveAllowedTributeDenom {
+        denom: String,
+    },
+    AddClaimHook {
+        address: String,
+    },
+    RemoveClaimHook {
+        address: String,
+    },
+    RegisterSwapVenue {
+        offer_denom: String,
+        ask_denom: String,
+        venue: String,
+    },
+    RemoveSwapVenue {
+        offer_denom: String,
+        ask_denom: String,
+    },
+    // Sets the length of the dispute window for the tributes of the given tranche, in nanoseconds.
+    // Setting it to 0 removes the dispute window.
+    SetDisputeWindow {
+        tranche_id: u64,
+        duration: u64,
+    },
+    ResolveTributeDispute {
+        tribute_id: u64,
+        resolution: DisputeResolution,
+    },
+    // Voids the proposals of the given round and tranche that were invalidated in Hydro, so that their
+    // tributes become refundable to the depositors. Can be executed by anyone once the round has ended.
+    SyncProposalOutcomes {
+        round_id: u64,
+        tranche_id: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeResolution {
+    // Sends the tribute back to its depositor.
+    Refund {},
+    // Moves the tribute to another proposal of the same round and tranche.
+    Retarget { proposal_id: u64 },
+}
+
+// Message that is sent to each of the registered claim hook contracts whenever a tribute is claimed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimHookMsg {
+    TributeClaimed {
+        claimant: String,
+        round_id: u64,
+        tranche_id: u64,
+        proposal_id: u64,
+        tribute_id: u64,
+        amount: Coin,
+    },
+}
+
+// Message that is sent to a registered swap venue, together with the funds to be swapped.
+// The venue is expected to swap the funds into ask_denom and send the result to the recipient,
+// or to fail if less than min_output of ask_denom would be received.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapVenueMsg {
+    Swap {
+        ask_denom: String,
+        min_output: Uint128,
+        recipient: String,
+    },
 }
@@ -1,9 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::{Config, Tribute};
+use crate::state::{Config, Tribute, TributeDisputeResolution};
 
 #[derive(
     Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses, cw_orch::QueryFns,
@@ -13,11 +13,16 @@ pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
 
+    // Paginated queries return the entries after start_after, which is the key of the last entry of the
+    // previous page, as returned in the next_key of the response: the tribute id, or the (proposal_id,
+    // tribute_id) for RoundTributes. The next_key is None once a page holds fewer than limit entries.
+    // The start_from offset is deprecated and is ignored if start_after is set.
     #[returns(ProposalTributesResponse)]
     ProposalTributes {
         round_id: u64,
         proposal_id: u64,
         start_from: u32,
+        start_after: Option<u64>,
         limit: u32,
     },
     // Returns all the tributes a certain user address has claimed.
@@ -25,6 +30,7 @@ pub enum QueryMsg {
     HistoricalTributeClaims {
         user_address: String,
         start_from: u32,
+        start_after: Option<u64>,
         limit: u32,
     },
 
@@ -32,11 +38,14 @@ pub enum QueryMsg {
     RoundTributes {
         round_id: u64,
         start_from: u32,
+        start_after: Option<(u64, u64)>,
         limit: u32,
     },
 
     // Returns all tributes for a certain round and tranche
     //  that a certain user address is able to claim, but has not claimed yet.
+    // The tributes are paginated separately for each proposal the user voted for, so this query
+    // only supports the offset-based pagination.
     #[returns(OutstandingTributeClaimsResponse)]
     OutstandingTributeClaims {
         user_address: String,
@@ -45,6 +54,52 @@ pub enum QueryMsg {
         start_from: u32,
         limit: u32,
     },
+
+    #[returns(AllowedTributeDenomsResponse)]
+    AllowedTributeDenoms {},
+
+    #[returns(SwapVenuesResponse)]
+    SwapVenues {},
+
+    #[returns(ClaimHooksResponse)]
+    ClaimHooks {},
+
+    #[returns(DisputeStatusResponse)]
+    DisputeStatus { round_id: u64, tranche_id: u64 },
+
+    #[returns(TributeEscrowResponse)]
+    TributeEscrow { tribute_id: u64 },
+
+    #[returns(TributeOutcomeResponse)]
+    TributeOutcome { tribute_id: u64 },
+
+    #[returns(TopTributeEarnersResponse)]
+    TopTributeEarners {
+        round_id: u64,
+        tranche_id: u64,
+        denom: String,
+        limit: u32,
+    },
+
+    #[returns(LifetimeTributeClaimsResponse)]
+    LifetimeTributeClaims { address: String },
+
+    #[returns(TributeVestingStatusResponse)]
+    TributeVestingStatus {
+        tribute_id: u64,
+        user_address: String,
+    },
+
+    // Returns the tributes that the user claimed from the rounds in the given inclusive range, ordered by
+    // tribute id.
+    #[returns(TributeClaimsReportResponse)]
+    TributeClaimsReport {
+        user_address: String,
+        start_round_id: u64,
+        end_round_id: u64,
+        start_after: Option<u64>,
+        limit: u32,
+    },
 }
 
 #[cw_serde]
@@ -55,6 +110,7 @@ pub struct ConfigResponse {
 #[cw_serde]
 pub struct ProposalTributesResponse {
     pub tributes: Vec<Tribute>,
+    pub next_key: Option<u64>,
 }
 
 #[cw_serde]
@@ -69,14 +125,133 @@ pub struct TributeClaim {
 #[cw_serde]
 pub struct HistoricalTributeClaimsResponse {
     pub claims: Vec<TributeClaim>,
+    pub next_key: Option<u64>,
 }
 
 #[cw_serde]
 pub struct RoundTributesResponse {
     pub tributes: Vec<Tribute>,
+    pub next_key: Option<(u64, u64)>,
 }
 
 #[cw_serde]
 pub struct OutstandingTributeClaimsResponse {
     pub claims: Vec<TributeClaim>,
 }
+
+#[cw_serde]
+pub struct AllowedTributeDenom {
+    pub denom: String,
+    pub min_amount: Uint128,
+}
+
+// If the list of allowed denoms is empty, tributes can be added in any denom.
+#[cw_serde]
+pub struct AllowedTributeDenomsResponse {
+    pub denoms: Vec<AllowedTributeDenom>,
+}
+
+#[cw_serde]
+pub struct ClaimHooksResponse {
+    pub hooks: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct SwapVenue {
+    pub offer_denom: String,
+    pub ask_denom: String,
+    pub venue: Addr,
+}
+
+#[cw_serde]
+pub struct SwapVenuesResponse {
+    pub venues: Vec<SwapVenue>,
+}
+
+// The dispute_window_end is None if no dispute window is set for the tranche.
+// While the distribution is frozen, the tributes can't be claimed or refunded.
+#[cw_serde]
+pub struct DisputeStatusResponse {
+    pub dispute_window_end: Option<Timestamp>,
+    pub distribution_frozen: bool,
+    pub resolutions: Vec<TributeDisputeResolution>,
+}
+
+#[cw_serde]
+pub enum TributeOutcome {
+    // The round hasn't ended yet, or no liquidity deployment was entered for the proposal yet.
+    Pending {},
+    Claimable {},
+    Refundable {},
+    // The proposal was invalidated in Hydro, so the tribute can be refunded to its depositor.
+    Voided { reason: String },
+    // No liquidity deployment was entered for the proposal before the delivery deadline of the tribute,
+    // so the tribute can be refunded to its depositor.
+    DeliveryMissed {},
+    Refunded {},
+}
+
+#[cw_serde]
+pub struct TributeEarner {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+// Earners are sorted by the claimed amount, in descending order.
+#[cw_serde]
+pub struct TopTributeEarnersResponse {
+    pub earners: Vec<TributeEarner>,
+}
+
+#[cw_serde]
+pub struct LifetimeTributeClaimsResponse {
+    pub claimed: Vec<Coin>,
+}
+
+// The share is the part of the tribute that the user gets once the tribute fully vests,
+// and the claimable amount is the vested part of the share that the user hasn't claimed yet. Whether
+// the tribute can actually be claimed also depends on the outcome of its proposal.
+#[cw_serde]
+pub struct TributeVestingStatusResponse {
+    pub tribute_id: u64,
+    pub share: Coin,
+    pub vested: Coin,
+    pub claimed: Coin,
+    pub claimable: Coin,
+    pub fully_vested_round: u64,
+}
+
+// The amount is the total amount claimed from the tribute so far, which for vesting tributes
+// can be less than the user's share.
+#[cw_serde]
+pub struct TributeClaimsReportEntry {
+    pub round_id: u64,
+    pub round_end: Timestamp,
+    pub tranche_id: u64,
+    pub proposal_id: u64,
+    pub tribute_id: u64,
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct TributeClaimsReportResponse {
+    pub entries: Vec<TributeClaimsReportEntry>,
+    pub next_key: Option<u64>,
+}
+
+#[cw_serde]
+pub struct TributeOutcomeResponse {
+    pub tribute_id: u64,
+    pub outcome: TributeOutcome,
+}
+
+// Tribute funds are held in escrow until escrowed_until: the end of the tribute's round, extended by
+// the dispute window for its tranche, if one is set. While escrowed, the funds can't be withdrawn by the
+// depositor; after the round ends, only the Hydro whitelist admins can move or refund them through
+// the dispute flow.
+#[cw_serde]
+pub struct TributeEscrowResponse {
+    pub tribute: Tribute,
+    pub escrowed_until: Timestamp,
+    pub escrowed: bool,
+}
@@ -1,19 +1,58 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Timestamp};
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
+use crate::msg::DisputeResolution;
+
 pub const CONFIG: Item<Config> = Item::new("config");
 
+// Denoms that can be used to add tributes, along with the minimum tribute amount for each denom.
+// While this map is empty, tributes can be added in any denom. It is managed by the Hydro whitelist admins.
+// ALLOWED_TRIBUTE_DENOMS: key(denom) -> min_amount
+pub const ALLOWED_TRIBUTE_DENOMS: Map<String, Uint128> = Map::new("allowed_tribute_denoms");
+
+// Contracts that are notified whenever a tribute is claimed, e.g. to run loyalty programs.
+// Managed by the Hydro whitelist admins and limited to MAX_CLAIM_HOOKS contracts.
+pub const CLAIM_HOOKS: Item<Vec<Addr>> = Item::new("claim_hooks");
+
+// Contracts that can be used to swap claimed tributes from offer_denom into ask_denom via ClaimTributeAs.
+// Managed by the Hydro whitelist admins.
+// SWAP_VENUES: key(offer_denom, ask_denom) -> venue contract
+pub const SWAP_VENUES: Map<(String, String), Addr> = Map::new("swap_venues");
+
+// Length of the dispute window for the tributes of each tranche, in nanoseconds. The dispute window starts
+// at the end of the round, and until it passes, the tributes of that round and tranche can't be claimed or
+// refunded, while the Hydro whitelist admins can resolve disputes by refunding or re-targeting tributes.
+// DISPUTE_WINDOWS: key(tranche_id) -> dispute_window_duration
+pub const DISPUTE_WINDOWS: Map<u64, u64> = Map::new("dispute_windows");
+
+// Resolutions of the tribute disputes, kept for auditing. Each tribute dispute can be resolved only once.
+// TRIBUTE_DISPUTE_RESOLUTIONS: key(round_id, tranche_id, tribute_id) -> TributeDisputeResolution
+pub const TRIBUTE_DISPUTE_RESOLUTIONS: Map<(u64, u64, u64), TributeDisputeResolution> =
+    Map::new("tribute_dispute_resolutions");
+
 #[cw_serde]
-pub struct Config {
-    pub hydro_contract: Addr,
+pub struct TributeDisputeResolution {
+    pub tribute_id: u64,
+    pub previous_proposal_id: u64,
+    pub resolution: DisputeResolution,
+    pub resolved_at: Timestamp,
 }
 
-// Need to have old version of Config in order to migrate data that is already stored on the chain.
+// Proposals that were invalidated in Hydro, recorded once their round has ended. Tributes of voided
+// proposals can't be claimed, and can be refunded to their depositors instead.
+// VOIDED_PROPOSALS: key(round_id, tranche_id, proposal_id) -> VoidedProposal
+pub const VOIDED_PROPOSALS: Map<(u64, u64, u64), VoidedProposal> = Map::new("voided_proposals");
+
 #[cw_serde]
-pub struct ConfigV1 {
+pub struct VoidedProposal {
+    pub reason: String,
+    pub voided_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct Config {
     pub hydro_contract: Addr,
-    pub top_n_props_count: u64,
 }
 
 pub const TRIBUTE_ID: Item<u64> = Item::new("tribute_id");
@@ -32,6 +71,17 @@ pub struct Tribute {
     pub refunded: bool,
     pub creation_time: Timestamp,
     pub creation_round: u64,
+    // Minimum voting power for the proposal that a voter must have to be eligible to claim the tribute.
+    // The shares of the ineligible voters are not distributed. None for the tributes created before
+    // the threshold was introduced.
+    pub min_vote_power: Option<Uint128>,
+    // Number of rounds over which the tribute vests after its round ends. Each voter can claim 1/N
+    // of their share of the tribute per elapsed round. None if the tribute can be claimed at once.
+    pub vesting_rounds: Option<u64>,
+    // Number of rounds after the tribute's round within which a liquidity deployment must be entered for
+    // the proposal. Once it passes without a deployment, the tribute can be refunded to its depositor.
+    // None if the tribute waits for the deployment indefinitely.
+    pub delivery_deadline_rounds: Option<u64>,
 }
 
 // For ease of accessing, maps each tribute_id to its Tribute struct
@@ -40,5 +90,27 @@ pub struct Tribute {
 pub const ID_TO_TRIBUTE_MAP: Map<u64, Tribute> = Map::new("id_to_tribute_map");
 
 // Importantly, the TRIBUTE_CLAIMS for a voter_addr and tribute_id being present at all means the user has claimed that tribute.
+// For vesting tributes, it means that the user has claimed at least a part of the tribute, and the amount is
+// the total amount claimed so far.
 // TRIBUTE_CLAIMS: key(voter_addr, tribute_id) -> amount_claimed
 pub const TRIBUTE_CLAIMS: Map<(Addr, u64), Coin> = Map::new("tribute_claims");
+
+// (round_id, tranche_id, denom) prefix of the claimed tribute totals
+pub type ClaimedTotalsPrefix = (u64, u64, String);
+
+// Running totals of the tributes claimed by each address in a round and tranche, per denom. Updated
+// whenever a tribute is claimed, so that the leaderboards can be served without scanning TRIBUTE_CLAIMS.
+// Claims made before these totals were introduced are not included.
+// ROUND_CLAIMED_TOTALS: key((round_id, tranche_id, denom), claimant) -> claimed_amount
+pub const ROUND_CLAIMED_TOTALS: Map<(ClaimedTotalsPrefix, Addr), Uint128> =
+    Map::new("round_claimed_totals");
+
+// Index of ROUND_CLAIMED_TOTALS ordered by the claimed amount, used to return the top tribute earners.
+// CLAIMANTS_BY_AMOUNT: key((round_id, tranche_id, denom), claimed_amount, claimant) -> ()
+pub const CLAIMANTS_BY_AMOUNT: Map<(ClaimedTotalsPrefix, u128, Addr), ()> =
+    Map::new("claimants_by_amount");
+
+// Running totals of the tributes claimed by each address across all rounds and tranches, per denom.
+// LIFETIME_CLAIMED_TOTALS: key(claimant, denom) -> claimed_amount
+pub const LIFETIME_CLAIMED_TOTALS: Map<(Addr, String), Uint128> =
+    Map::new("lifetime_claimed_totals");
@@ -1,24 +1,35 @@
+use std::collections::HashMap;
+
 use crate::{
     contract::{
-        execute, instantiate, query_historical_tribute_claims, query_outstanding_tribute_claims,
-        query_proposal_tributes, query_round_tributes,
+        execute, instantiate, query_allowed_tribute_denoms, query_claim_hooks,
+        query_dispute_status, query_historical_tribute_claims, query_lifetime_tribute_claims,
+        query_outstanding_tribute_claims, query_proposal_tributes, query_round_tributes,
+        query_swap_venues, query_top_tribute_earners, query_tribute_claims_report,
+        query_tribute_escrow, query_tribute_outcome, query_tribute_vesting_status, reply,
+        CLAIM_HOOK_GAS_LIMIT, CLAIM_HOOK_REPLY_ID, MAX_CLAIM_HOOKS, SWAP_REPLY_ID,
+    },
+    msg::{ClaimHookMsg, DisputeResolution, ExecuteMsg, InstantiateMsg, SwapVenueMsg},
+    query::{
+        AllowedTributeDenom, SwapVenue, TributeClaim, TributeClaimsReportEntry, TributeEarner,
+        TributeOutcome,
     },
-    msg::{ExecuteMsg, InstantiateMsg},
-    query::TributeClaim,
     state::{Config, Tribute, CONFIG, ID_TO_TRIBUTE_MAP, TRIBUTE_CLAIMS, TRIBUTE_MAP},
 };
 use cosmwasm_std::{
-    coins, from_json,
+    attr, coins, from_json,
     testing::{mock_dependencies, mock_env, MockApi},
-    to_json_binary, Addr, Binary, ContractResult, Decimal, MessageInfo, QuerierResult, Response,
-    StdError, StdResult, SystemError, SystemResult, Timestamp, Uint128, WasmQuery,
+    to_json_binary, Addr, Binary, ContractResult, Decimal, MessageInfo, QuerierResult, Reply,
+    ReplyOn, Response, StdError, StdResult, SubMsgResult, SystemError, SystemResult, Timestamp,
+    Uint128, WasmMsg, WasmQuery,
 };
 use cosmwasm_std::{BankMsg, Coin, CosmosMsg};
 use hydro::{
-    msg::LiquidityDeployment,
+    msg::{LiquidityDeployment, LockVote},
     query::{
-        ConstantsResponse, CurrentRoundResponse, LiquidityDeploymentResponse, ProposalResponse,
-        QueryMsg as HydroQueryMsg, UserVotesResponse,
+        ConstantsResponse, CurrentRoundResponse, InvalidatedProposal, InvalidatedProposalsResponse,
+        LiquidityDeploymentResponse, ProposalResponse, QueryMsg as HydroQueryMsg,
+        RoundCutoffsResponse, UserVotesResponse, WhitelistAdminsResponse,
     },
     state::{Constants, Proposal, VoteWithPower},
 };
@@ -77,6 +88,10 @@ pub struct MockWasmQuerier {
     user_votes: Vec<UserVote>,
     liquidity_deployments: Vec<LiquidityDeployment>,
     hydro_constants: Option<Constants>,
+    tribute_cutoffs: HashMap<u64, Timestamp>,
+    whitelist_admins: Vec<Addr>,
+    proposal_tribute_weights: HashMap<u64, Decimal>,
+    invalidated_proposals: HashMap<(u64, u64), Vec<InvalidatedProposal>>,
 }
 
 impl MockWasmQuerier {
@@ -95,9 +110,54 @@ impl MockWasmQuerier {
             user_votes,
             liquidity_deployments,
             hydro_constants,
+            tribute_cutoffs: HashMap::new(),
+            whitelist_admins: vec![],
+            proposal_tribute_weights: HashMap::new(),
+            invalidated_proposals: HashMap::new(),
         }
     }
 
+    // Unless set explicitly, the tribute cutoff has already passed for the rounds before the
+    // current round, and is far in the future for the current and the following rounds.
+    pub fn with_tribute_cutoff(mut self, round_id: u64, tribute_cutoff: Timestamp) -> Self {
+        self.tribute_cutoffs.insert(round_id, tribute_cutoff);
+        self
+    }
+
+    // Unless set explicitly, the tribute weight of a proposal is equal to its power.
+    pub fn with_proposal_tribute_weight(
+        mut self,
+        proposal_id: u64,
+        tribute_weight: Decimal,
+    ) -> Self {
+        self.proposal_tribute_weights
+            .insert(proposal_id, tribute_weight);
+        self
+    }
+
+    pub fn with_whitelist_admins(mut self, whitelist_admins: Vec<Addr>) -> Self {
+        self.whitelist_admins = whitelist_admins;
+        self
+    }
+
+    pub fn with_invalidated_proposal(
+        mut self,
+        round_id: u64,
+        tranche_id: u64,
+        proposal_id: u64,
+        reason: &str,
+    ) -> Self {
+        self.invalidated_proposals
+            .entry((round_id, tranche_id))
+            .or_default()
+            .push(InvalidatedProposal {
+                proposal_id,
+                reason: reason.to_string(),
+                invalidated_at: Timestamp::from_seconds(1),
+            });
+        self
+    }
+
     pub fn handler(&self, query: &WasmQuery) -> QuerierResult {
         match query {
             WasmQuery::Smart { contract_addr, msg } => {
@@ -169,9 +229,36 @@ impl MockWasmQuerier {
                                 }
                             }
                     }),
+                    HydroQueryMsg::RoundCutoffs { round_id } => {
+                        let tribute_cutoff = match self.tribute_cutoffs.get(&round_id) {
+                            Some(tribute_cutoff) => *tribute_cutoff,
+                            None if round_id < self.current_round => Timestamp::from_seconds(1),
+                            None => Timestamp::from_nanos(u64::MAX),
+                        };
+
+                        to_json_binary(&RoundCutoffsResponse {
+                            round_id,
+                            tribute_cutoff,
+                            // use an arbitrary timestamp here
+                            round_end: tribute_cutoff,
+                        })
+                    }
                     HydroQueryMsg::Constants {} => to_json_binary(&ConstantsResponse {
                         constants: self.hydro_constants.clone().unwrap(),
                     }),
+                    HydroQueryMsg::WhitelistAdmins {} => to_json_binary(&WhitelistAdminsResponse {
+                        admins: self.whitelist_admins.clone(),
+                    }),
+                    HydroQueryMsg::InvalidatedProposals {
+                        round_id,
+                        tranche_id,
+                    } => to_json_binary(&InvalidatedProposalsResponse {
+                        proposals: self
+                            .invalidated_proposals
+                            .get(&(round_id, tranche_id))
+                            .cloned()
+                            .unwrap_or_default(),
+                    }),
 
                     _ => panic!("unsupported query"),
                 };
@@ -218,8 +305,15 @@ impl MockWasmQuerier {
                 && prop.tranche_id == tranche_id
                 && prop.proposal_id == proposal_id
             {
+                let tribute_weight = self
+                    .proposal_tribute_weights
+                    .get(&proposal_id)
+                    .cloned()
+                    .unwrap_or(Decimal::from_ratio(prop.power, Uint128::one()));
                 let res: StdResult<Binary> = to_json_binary(&ProposalResponse {
                     proposal: prop.clone(),
+                    tribute_weight,
+                    capped_power: prop.power,
                 });
                 return res;
             }
@@ -319,6 +413,8 @@ fn add_tribute_test() {
         percentage: Uint128::zero(),
         minimum_atom_liquidity_request: Uint128::zero(),
         deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
     };
 
     let test_cases: Vec<AddTributeTestCase> = vec![
@@ -357,12 +453,12 @@ fn add_tribute_test() {
             expected_error_msg: "Must send exactly one coin".to_string(),
         },
         AddTributeTestCase {
-            description: "add tribute to previous round".to_string(),
+            description: "try adding tribute to previous round".to_string(),
             tributes_to_add: vec![vec![Coin::new(1000u64, DEFAULT_DENOM)]],
             // proposal is in round 10, but we are trying to add tribute during round 11
             mock_data: (11, vec![mock_proposal.clone()]),
-            expected_success: true,
-            expected_error_msg: String::new(),
+            expected_success: false,
+            expected_error_msg: "Tributes can no longer be added for round 10".to_string(),
         },
     ];
 
@@ -396,6 +492,9 @@ fn add_tribute_test() {
                 tranche_id: mock_proposal.tranche_id,
                 round_id: mock_proposal.round_id,
                 proposal_id: mock_proposal.proposal_id,
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
             };
 
             let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
@@ -419,6 +518,7 @@ fn add_tribute_test() {
             mock_proposal.round_id,
             mock_proposal.proposal_id,
             0,
+            None,
             3000,
         )
         .unwrap()
@@ -434,6 +534,2003 @@ fn add_tribute_test() {
     }
 }
 
+#[test]
+fn add_tribute_cutoff_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: Uint128::zero(),
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+
+    let env = mock_env();
+
+    // (description, tribute_cutoff, expected_success)
+    let test_cases = vec![
+        (
+            "one nanosecond before the cutoff",
+            env.block.time.plus_nanos(1),
+            true,
+        ),
+        ("exactly at the cutoff", env.block.time, false),
+        ("after the cutoff", env.block.time.minus_nanos(1), false),
+    ];
+
+    for (description, tribute_cutoff, expected_success) in test_cases {
+        println!("running test case: {}", description);
+
+        let mut deps = mock_dependencies();
+        let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+
+        let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+        let mock_querier = MockWasmQuerier::new(
+            hydro_contract_address.clone(),
+            mock_proposal.round_id,
+            vec![mock_proposal.clone()],
+            vec![],
+            vec![],
+            None,
+        )
+        .with_tribute_cutoff(mock_proposal.round_id, tribute_cutoff);
+        deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+        let msg = get_instantiate_msg(hydro_contract_address);
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+        assert!(res.is_ok());
+
+        let info = get_message_info(
+            &deps.api,
+            USER_ADDRESS_1,
+            &[Coin::new(1000u64, DEFAULT_DENOM)],
+        );
+        let msg = ExecuteMsg::AddTribute {
+            tranche_id: mock_proposal.tranche_id,
+            round_id: mock_proposal.round_id,
+            proposal_id: mock_proposal.proposal_id,
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        if expected_success {
+            assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+        } else {
+            assert!(res
+                .unwrap_err()
+                .to_string()
+                .contains("Tributes can no longer be added for round 10"));
+        }
+    }
+}
+
+#[test]
+fn allowed_tribute_denoms_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: Uint128::zero(),
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+    const OTHER_DENOM: &str = "untrn";
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let admin_address = deps.api.addr_make(USER_ADDRESS_2);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        mock_proposal.round_id,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    )
+    .with_whitelist_admins(vec![admin_address.clone()]);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let add_tribute_msg = ExecuteMsg::AddTribute {
+        tranche_id: mock_proposal.tranche_id,
+        round_id: mock_proposal.round_id,
+        proposal_id: mock_proposal.proposal_id,
+        min_vote_power: None,
+        vesting_rounds: None,
+        delivery_deadline_rounds: None,
+    };
+
+    // while the allowlist is empty, tributes can be added in any denom
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[Coin::new(1u64, OTHER_DENOM)]);
+    let res = execute(deps.as_mut(), env.clone(), info, add_tribute_msg.clone());
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    // only the Hydro whitelist admins can manage the allowlist
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::AddAllowedTributeDenom {
+            denom: DEFAULT_DENOM.to_string(),
+            min_amount: Uint128::new(100),
+        },
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let admin_info = MessageInfo {
+        sender: admin_address.clone(),
+        funds: vec![],
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        ExecuteMsg::AddAllowedTributeDenom {
+            denom: DEFAULT_DENOM.to_string(),
+            min_amount: Uint128::new(100),
+        },
+    );
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    let allowed_denoms = query_allowed_tribute_denoms(deps.as_ref()).unwrap();
+    assert_eq!(
+        allowed_denoms.denoms,
+        vec![AllowedTributeDenom {
+            denom: DEFAULT_DENOM.to_string(),
+            min_amount: Uint128::new(100),
+        }]
+    );
+
+    // (description, tribute, expected_error)
+    let test_cases = vec![
+        (
+            "denom not in the allowlist",
+            Coin::new(1000u64, OTHER_DENOM),
+            Some("Denom untrn is not allowed to be used for tributes"),
+        ),
+        (
+            "amount below the minimum",
+            Coin::new(99u64, DEFAULT_DENOM),
+            Some("Tribute amount 99 is below the minimum amount of 100"),
+        ),
+        (
+            "amount equal to the minimum",
+            Coin::new(100u64, DEFAULT_DENOM),
+            None,
+        ),
+    ];
+
+    for (description, tribute, expected_error) in test_cases {
+        println!("running test case: {}", description);
+
+        let info = get_message_info(&deps.api, USER_ADDRESS_1, &[tribute]);
+        let res = execute(deps.as_mut(), env.clone(), info, add_tribute_msg.clone());
+        match expected_error {
+            None => assert!(res.is_ok(), "failed with: {}", res.unwrap_err()),
+            Some(error) => assert!(res.unwrap_err().to_string().contains(error)),
+        }
+    }
+
+    // after removing the only allowed denom, any denom can be used again
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        ExecuteMsg::RemoveAllowedTributeDenom {
+            denom: DEFAULT_DENOM.to_string(),
+        },
+    );
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    assert!(query_allowed_tribute_denoms(deps.as_ref())
+        .unwrap()
+        .denoms
+        .is_empty());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info,
+        ExecuteMsg::RemoveAllowedTributeDenom {
+            denom: DEFAULT_DENOM.to_string(),
+        },
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("is not in the list of allowed tribute denoms"));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[Coin::new(1u64, OTHER_DENOM)]);
+    let res = execute(deps.as_mut(), env, info, add_tribute_msg);
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+}
+
+#[test]
+fn claim_hooks_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let admin_address = deps.api.addr_make(USER_ADDRESS_2);
+    let admin_info = MessageInfo {
+        sender: admin_address.clone(),
+        funds: vec![],
+    };
+    let voter_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        mock_proposal.round_id,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    let info = get_message_info(
+        &deps.api,
+        USER_ADDRESS_1,
+        &[Coin::new(1000u64, DEFAULT_DENOM)],
+    );
+    let msg = ExecuteMsg::AddTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        min_vote_power: None,
+        vesting_rounds: None,
+        delivery_deadline_rounds: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    // move to the next round, in which the tribute can be claimed
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        mock_proposal.round_id + 1,
+        vec![mock_proposal.clone()],
+        vec![(
+            mock_proposal.round_id,
+            mock_proposal.tranche_id,
+            voter_address.clone(),
+            VoteWithPower {
+                prop_id: mock_proposal.proposal_id,
+                power: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+                tribute_weight: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+            },
+        )],
+        vec![get_nonzero_deployment_for_proposal(mock_proposal.clone())],
+        None,
+    )
+    .with_whitelist_admins(vec![admin_address]);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    // only the Hydro whitelist admins can manage the claim hooks
+    let hooks = (0..=MAX_CLAIM_HOOKS)
+        .map(|i| deps.api.addr_make(&format!("hook{}", i)))
+        .collect::<Vec<Addr>>();
+    let add_hook_msg = |hook: &Addr| ExecuteMsg::AddClaimHook {
+        address: hook.to_string(),
+    };
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, add_hook_msg(&hooks[0]));
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    for hook in hooks.iter().take(MAX_CLAIM_HOOKS) {
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            add_hook_msg(hook),
+        );
+        assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        add_hook_msg(&hooks[0]),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("is already registered"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        add_hook_msg(&hooks[MAX_CLAIM_HOOKS]),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Maximum number of claim hooks (5) reached"));
+
+    // keep only the first two hooks
+    for hook in hooks.iter().take(MAX_CLAIM_HOOKS).skip(2) {
+        let msg = ExecuteMsg::RemoveClaimHook {
+            address: hook.to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+        assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    }
+    assert_eq!(
+        hooks[0..2].to_vec(),
+        query_claim_hooks(deps.as_ref()).unwrap().hooks
+    );
+
+    // claiming the tribute notifies each of the claim hooks
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = ExecuteMsg::ClaimTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        tribute_id: 0,
+        voter_address: voter_address.clone(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(3, res.messages.len());
+
+    let expected_hook_msg = to_json_binary(&ClaimHookMsg::TributeClaimed {
+        claimant: voter_address,
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        tribute_id: 0,
+        amount: Coin::new(7u64, DEFAULT_DENOM),
+    })
+    .unwrap();
+    for (hook, submsg) in hooks.iter().zip(res.messages.iter().skip(1)) {
+        assert_eq!(CLAIM_HOOK_REPLY_ID, submsg.id);
+        assert_eq!(ReplyOn::Error, submsg.reply_on);
+        assert_eq!(Some(CLAIM_HOOK_GAS_LIMIT), submsg.gas_limit);
+        assert_eq!(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: hook.to_string(),
+                msg: expected_hook_msg.clone(),
+                funds: vec![],
+            }),
+            submsg.msg
+        );
+    }
+
+    // a failing claim hook doesn't revert the claim
+    let reply_msg = Reply {
+        id: CLAIM_HOOK_REPLY_ID,
+        payload: Binary::from(hooks[0].as_bytes()),
+        gas_used: 0,
+        result: SubMsgResult::Err("hook failed".to_string()),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("claim_hook", hooks[0].to_string())));
+    assert!(res.attributes.contains(&attr("error", "hook failed")));
+}
+
+#[test]
+fn claim_tribute_as_test() {
+    const ASK_DENOM: &str = "untrn";
+
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let admin_address = deps.api.addr_make(USER_ADDRESS_2);
+    let admin_info = MessageInfo {
+        sender: admin_address.clone(),
+        funds: vec![],
+    };
+    let voter_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+    let venue = deps.api.addr_make("swap_venue");
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        mock_proposal.round_id,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    let info = get_message_info(
+        &deps.api,
+        USER_ADDRESS_1,
+        &[Coin::new(1000u64, DEFAULT_DENOM)],
+    );
+    let msg = ExecuteMsg::AddTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        min_vote_power: None,
+        vesting_rounds: None,
+        delivery_deadline_rounds: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    // move to the next round, in which the tribute can be claimed
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        mock_proposal.round_id + 1,
+        vec![mock_proposal.clone()],
+        vec![(
+            mock_proposal.round_id,
+            mock_proposal.tranche_id,
+            voter_address.clone(),
+            VoteWithPower {
+                prop_id: mock_proposal.proposal_id,
+                power: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+                tribute_weight: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+            },
+        )],
+        vec![get_nonzero_deployment_for_proposal(mock_proposal.clone())],
+        None,
+    )
+    .with_whitelist_admins(vec![admin_address]);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let claim_as_msg = ExecuteMsg::ClaimTributeAs {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        tribute_id: 0,
+        ask_denom: ASK_DENOM.to_string(),
+        min_output: Uint128::new(20),
+    };
+
+    // the tribute can't be claimed as a denom for which no swap venue is registered
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        claim_as_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("No swap venue registered for swapping uatom into untrn"));
+
+    // only the Hydro whitelist admins can register swap venues
+    let register_msg = ExecuteMsg::RegisterSwapVenue {
+        offer_denom: DEFAULT_DENOM.to_string(),
+        ask_denom: ASK_DENOM.to_string(),
+        venue: venue.to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        register_msg.clone(),
+    );
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let res = execute(deps.as_mut(), env.clone(), admin_info, register_msg);
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    assert_eq!(
+        vec![SwapVenue {
+            offer_denom: DEFAULT_DENOM.to_string(),
+            ask_denom: ASK_DENOM.to_string(),
+            venue: venue.clone(),
+        }],
+        query_swap_venues(deps.as_ref()).unwrap().venues
+    );
+
+    // the claimed tribute is sent to the swap venue, which sends the swapped funds to the claimant
+    let res = execute(deps.as_mut(), env.clone(), info, claim_as_msg).unwrap();
+    assert_eq!(1, res.messages.len());
+
+    let claimed_funds = Coin::new(7u64, DEFAULT_DENOM);
+    let submsg = &res.messages[0];
+    assert_eq!(SWAP_REPLY_ID, submsg.id);
+    assert_eq!(ReplyOn::Error, submsg.reply_on);
+    assert_eq!(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: venue.to_string(),
+            msg: to_json_binary(&SwapVenueMsg::Swap {
+                ask_denom: ASK_DENOM.to_string(),
+                min_output: Uint128::new(20),
+                recipient: voter_address.clone(),
+            })
+            .unwrap(),
+            funds: vec![claimed_funds.clone()],
+        }),
+        submsg.msg
+    );
+
+    let claim = TRIBUTE_CLAIMS
+        .load(&deps.storage, (Addr::unchecked(voter_address.clone()), 0))
+        .unwrap();
+    assert_eq!(claimed_funds, claim);
+
+    // if the swap fails, the claimed funds are sent to the claimant in their original denom
+    let reply_msg = Reply {
+        id: SWAP_REPLY_ID,
+        payload: submsg.payload.clone(),
+        gas_used: 0,
+        result: SubMsgResult::Err("slippage exceeded".to_string()),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    assert!(res.attributes.contains(&attr("error", "slippage exceeded")));
+    assert_eq!(
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: voter_address,
+            amount: vec![claimed_funds],
+        }),
+        res.messages[0].msg
+    );
+}
+
+#[test]
+fn claim_tribute_with_tribute_weight_bonus_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let voter_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        mock_proposal.round_id,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    let info = get_message_info(
+        &deps.api,
+        USER_ADDRESS_1,
+        &[Coin::new(1000u64, DEFAULT_DENOM)],
+    );
+    let msg = ExecuteMsg::AddTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        min_vote_power: None,
+        vesting_rounds: None,
+        delivery_deadline_rounds: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    // the vote of the user qualified for a 2x tribute weight bonus, so the proposal
+    // tribute weight is its power plus the bonus of that vote
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        mock_proposal.round_id + 1,
+        vec![mock_proposal.clone()],
+        vec![(
+            mock_proposal.round_id,
+            mock_proposal.tranche_id,
+            voter_address.clone(),
+            VoteWithPower {
+                prop_id: mock_proposal.proposal_id,
+                power: Decimal::from_ratio(Uint128::new(2500), Uint128::one()),
+                tribute_weight: Decimal::from_ratio(Uint128::new(5000), Uint128::one()),
+            },
+        )],
+        vec![get_nonzero_deployment_for_proposal(mock_proposal.clone())],
+        None,
+    )
+    .with_proposal_tribute_weight(
+        mock_proposal.proposal_id,
+        Decimal::from_ratio(Uint128::new(12500), Uint128::one()),
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    // the user gets 5000/12500 of the tribute instead of 2500/10000
+    let info = get_message_info(&deps.api, USER_ADDRESS_2, &[]);
+    let msg = ExecuteMsg::ClaimTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        tribute_id: 0,
+        voter_address: voter_address.clone(),
+    };
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    verify_tokens_received(res, &voter_address, &DEFAULT_DENOM.to_string(), 400);
+}
+
+#[test]
+fn tribute_earners_leaderboard_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let voter_1 = get_address_as_str(&deps.api, USER_ADDRESS_1);
+    let voter_2 = get_address_as_str(&deps.api, USER_ADDRESS_2);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        mock_proposal.round_id,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    for _ in 0..2 {
+        let info = get_message_info(
+            &deps.api,
+            USER_ADDRESS_1,
+            &[Coin::new(1000u64, DEFAULT_DENOM)],
+        );
+        let msg = ExecuteMsg::AddTribute {
+            round_id: mock_proposal.round_id,
+            tranche_id: mock_proposal.tranche_id,
+            proposal_id: mock_proposal.proposal_id,
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    }
+
+    let vote = |power: u128| VoteWithPower {
+        prop_id: mock_proposal.proposal_id,
+        power: Decimal::from_ratio(power, 1u128),
+        tribute_weight: Decimal::from_ratio(power, 1u128),
+    };
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        mock_proposal.round_id + 1,
+        vec![mock_proposal.clone()],
+        vec![
+            (
+                mock_proposal.round_id,
+                mock_proposal.tranche_id,
+                voter_1.clone(),
+                vote(2500),
+            ),
+            (
+                mock_proposal.round_id,
+                mock_proposal.tranche_id,
+                voter_2.clone(),
+                vote(7500),
+            ),
+        ],
+        vec![get_nonzero_deployment_for_proposal(mock_proposal.clone())],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    // the first voter claims both tributes, while the second voter claims only the first one
+    for (voter, tribute_id) in [(&voter_1, 0), (&voter_1, 1), (&voter_2, 0)] {
+        let info = get_message_info(&deps.api, USER_ADDRESS_2, &[]);
+        let msg = ExecuteMsg::ClaimTribute {
+            round_id: mock_proposal.round_id,
+            tranche_id: mock_proposal.tranche_id,
+            tribute_id,
+            voter_address: voter.clone(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    }
+
+    let earners = query_top_tribute_earners(
+        deps.as_ref(),
+        mock_proposal.round_id,
+        mock_proposal.tranche_id,
+        DEFAULT_DENOM.to_string(),
+        10,
+    )
+    .unwrap()
+    .earners;
+    assert_eq!(
+        earners,
+        vec![
+            TributeEarner {
+                address: Addr::unchecked(voter_2.clone()),
+                amount: Uint128::new(750),
+            },
+            TributeEarner {
+                address: Addr::unchecked(voter_1.clone()),
+                amount: Uint128::new(500),
+            },
+        ]
+    );
+
+    // the limit bounds the number of returned earners
+    let earners = query_top_tribute_earners(
+        deps.as_ref(),
+        mock_proposal.round_id,
+        mock_proposal.tranche_id,
+        DEFAULT_DENOM.to_string(),
+        1,
+    )
+    .unwrap()
+    .earners;
+    assert_eq!(earners.len(), 1);
+    assert_eq!(earners[0].address.to_string(), voter_2);
+
+    // no earners for other denoms or tranches
+    let earners = query_top_tribute_earners(
+        deps.as_ref(),
+        mock_proposal.round_id,
+        mock_proposal.tranche_id + 1,
+        DEFAULT_DENOM.to_string(),
+        10,
+    )
+    .unwrap()
+    .earners;
+    assert!(earners.is_empty());
+
+    let claimed = query_lifetime_tribute_claims(deps.as_ref(), voter_1)
+        .unwrap()
+        .claimed;
+    assert_eq!(claimed, vec![Coin::new(500u64, DEFAULT_DENOM)]);
+}
+
+#[test]
+fn tribute_vesting_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 3,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let voter_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        mock_proposal.round_id,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    let add_tribute_msg = |vesting_rounds: Option<u64>| ExecuteMsg::AddTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        min_vote_power: None,
+        vesting_rounds,
+        delivery_deadline_rounds: None,
+    };
+    let info = get_message_info(
+        &deps.api,
+        USER_ADDRESS_1,
+        &[Coin::new(1000u64, DEFAULT_DENOM)],
+    );
+
+    // the tribute can't vest for longer than the proposal gets deployed
+    for vesting_rounds in [0, 4] {
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            add_tribute_msg(Some(vesting_rounds)),
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Vesting rounds must be between 1 and the proposal deployment duration"));
+    }
+
+    let res = execute(deps.as_mut(), env.clone(), info, add_tribute_msg(Some(3)));
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    let voter_vote = VoteWithPower {
+        prop_id: mock_proposal.proposal_id,
+        power: Decimal::from_ratio(Uint128::new(2500), Uint128::one()),
+        tribute_weight: Decimal::from_ratio(Uint128::new(2500), Uint128::one()),
+    };
+    let claim_msg = ExecuteMsg::ClaimTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        tribute_id: 0,
+        voter_address: voter_address.clone(),
+    };
+
+    // the voter's share of the tribute is 250 tokens, and a third of it vests in each round
+    for (rounds_passed, expected_claim) in [(1, 83), (2, 83), (5, 84)] {
+        let mock_querier = MockWasmQuerier::new(
+            hydro_contract_address.clone(),
+            mock_proposal.round_id + rounds_passed,
+            vec![mock_proposal.clone()],
+            vec![(
+                mock_proposal.round_id,
+                mock_proposal.tranche_id,
+                voter_address.clone(),
+                voter_vote.clone(),
+            )],
+            vec![get_nonzero_deployment_for_proposal(mock_proposal.clone())],
+            None,
+        );
+        deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+        let status =
+            query_tribute_vesting_status(&deps.as_ref(), 0, voter_address.clone()).unwrap();
+        assert_eq!(status.share.amount, Uint128::new(250));
+        assert_eq!(status.claimable.amount, Uint128::new(expected_claim));
+        assert_eq!(status.fully_vested_round, mock_proposal.round_id + 3);
+
+        let info = get_message_info(&deps.api, USER_ADDRESS_2, &[]);
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), claim_msg.clone()).unwrap();
+        verify_tokens_received(
+            res,
+            &voter_address,
+            &DEFAULT_DENOM.to_string(),
+            expected_claim,
+        );
+
+        // nothing more can be claimed until the next round
+        let res = execute(deps.as_mut(), env.clone(), info, claim_msg.clone());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("User has already claimed the vested portion of the tribute"));
+    }
+
+    let claims = TRIBUTE_CLAIMS
+        .load(&deps.storage, (Addr::unchecked(voter_address.clone()), 0))
+        .unwrap();
+    assert_eq!(claims.amount, Uint128::new(250));
+
+    let status = query_tribute_vesting_status(&deps.as_ref(), 0, voter_address).unwrap();
+    assert_eq!(status.vested.amount, Uint128::new(250));
+    assert!(status.claimable.amount.is_zero());
+}
+
+#[test]
+fn tribute_delivery_deadline_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let voter_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+    let voter_vote = VoteWithPower {
+        prop_id: mock_proposal.proposal_id,
+        power: Decimal::from_ratio(Uint128::new(2500), Uint128::one()),
+        tribute_weight: Decimal::from_ratio(Uint128::new(2500), Uint128::one()),
+    };
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let get_mock_querier = |current_round: u64, delivered: bool| {
+        let liquidity_deployments = if delivered {
+            vec![get_nonzero_deployment_for_proposal(mock_proposal.clone())]
+        } else {
+            vec![]
+        };
+
+        MockWasmQuerier::new(
+            hydro_contract_address.clone(),
+            current_round,
+            vec![mock_proposal.clone()],
+            vec![(
+                mock_proposal.round_id,
+                mock_proposal.tranche_id,
+                voter_address.clone(),
+                voter_vote.clone(),
+            )],
+            liquidity_deployments,
+            None,
+        )
+    };
+
+    let mock_querier = get_mock_querier(mock_proposal.round_id, false);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    let add_tribute_msg = |delivery_deadline_rounds: Option<u64>| ExecuteMsg::AddTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        min_vote_power: None,
+        vesting_rounds: None,
+        delivery_deadline_rounds,
+    };
+    let depositor_info = get_message_info(
+        &deps.api,
+        USER_ADDRESS_2,
+        &[Coin::new(1000u64, DEFAULT_DENOM)],
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        depositor_info.clone(),
+        add_tribute_msg(Some(0)),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Delivery deadline must be at least 1 round"));
+
+    // both tributes must be delivered within 2 rounds after the proposal's round
+    for _ in 0..2 {
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            depositor_info.clone(),
+            add_tribute_msg(Some(2)),
+        );
+        assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    }
+
+    let refund_msg = |tribute_id: u64| ExecuteMsg::RefundTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        tribute_id,
+    };
+    let claim_msg = |tribute_id: u64| ExecuteMsg::ClaimTribute {
+        round_id: mock_proposal.round_id,
+        tranche_id: mock_proposal.tranche_id,
+        tribute_id,
+        voter_address: voter_address.clone(),
+    };
+    let depositor_info = get_message_info(&deps.api, USER_ADDRESS_2, &[]);
+
+    // until the deadline passes, the tribute waits for the deployment
+    let mock_querier = get_mock_querier(mock_proposal.round_id + 2, false);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    assert_eq!(
+        TributeOutcome::Pending {},
+        query_tribute_outcome(deps.as_ref(), 0).unwrap().outcome
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        depositor_info.clone(),
+        refund_msg(0),
+    );
+    assert!(res.unwrap_err().to_string().contains(
+        "Can't refund tribute for proposal that didn't have a liquidity deployment entered"
+    ));
+
+    // once the deadline passes without a deployment, the tribute can be refunded
+    let mock_querier = get_mock_querier(mock_proposal.round_id + 3, false);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    assert_eq!(
+        TributeOutcome::DeliveryMissed {},
+        query_tribute_outcome(deps.as_ref(), 0).unwrap().outcome
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        depositor_info.clone(),
+        refund_msg(0),
+    )
+    .unwrap();
+    verify_tokens_received(
+        res,
+        &depositor_info.sender.to_string(),
+        &DEFAULT_DENOM.to_string(),
+        1000,
+    );
+
+    // a deployment entered late makes the remaining tribute claimable, but not the refunded one
+    let mock_querier = get_mock_querier(mock_proposal.round_id + 3, true);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), claim_msg(0));
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Tribute was refunded to its depositor"));
+
+    let res = execute(deps.as_mut(), env.clone(), depositor_info, refund_msg(1));
+    assert!(res.unwrap_err().to_string().contains(
+        "Can't refund tribute for proposal that received a non-zero liquidity deployment"
+    ));
+
+    let res = execute(deps.as_mut(), env.clone(), info, claim_msg(1)).unwrap();
+    verify_tokens_received(res, &voter_address, &DEFAULT_DENOM.to_string(), 250);
+}
+
+#[test]
+fn claim_for_locks_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let voter_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        mock_proposal.round_id,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    // add two tributes to the proposal
+    for _ in 0..2 {
+        let info = get_message_info(
+            &deps.api,
+            USER_ADDRESS_2,
+            &[Coin::new(1000u64, DEFAULT_DENOM)],
+        );
+        let msg = ExecuteMsg::AddTribute {
+            round_id: mock_proposal.round_id,
+            tranche_id: mock_proposal.tranche_id,
+            proposal_id: mock_proposal.proposal_id,
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    }
+
+    // move to the next round, in which the tributes can be claimed
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        mock_proposal.round_id + 1,
+        vec![mock_proposal.clone()],
+        vec![(
+            mock_proposal.round_id,
+            mock_proposal.tranche_id,
+            voter_address.clone(),
+            VoteWithPower {
+                prop_id: mock_proposal.proposal_id,
+                power: Decimal::from_ratio(Uint128::new(2000), Uint128::one()),
+                tribute_weight: Decimal::from_ratio(Uint128::new(2000), Uint128::one()),
+            },
+        )],
+        vec![get_nonzero_deployment_for_proposal(mock_proposal.clone())],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    // both locks voted for the same proposal, so its tributes are claimed only once
+    let lock_votes: Vec<LockVote> = (0..2)
+        .map(|lock_id| LockVote {
+            lock_id,
+            round_id: mock_proposal.round_id,
+            tranche_id: mock_proposal.tranche_id,
+            proposal_id: mock_proposal.proposal_id,
+        })
+        .collect();
+    let msg = ExecuteMsg::ClaimForLocks {
+        voter_address: voter_address.clone(),
+        lock_votes,
+    };
+
+    // only the Hydro contract can claim the tributes for the locks
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let hydro_info = MessageInfo {
+        sender: Addr::unchecked(hydro_contract_address),
+        funds: vec![],
+    };
+    let res = execute(deps.as_mut(), env.clone(), hydro_info.clone(), msg.clone()).unwrap();
+    verify_tokens_received(res, &voter_address, &DEFAULT_DENOM.to_string(), 400);
+
+    for tribute_id in 0..2 {
+        assert_eq!(
+            Coin::new(200u64, DEFAULT_DENOM),
+            TRIBUTE_CLAIMS
+                .load(
+                    &deps.storage,
+                    (Addr::unchecked(voter_address.clone()), tribute_id)
+                )
+                .unwrap()
+        );
+    }
+
+    // the already claimed tributes are skipped
+    let res = execute(deps.as_mut(), env, hydro_info, msg).unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn dispute_window_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+    let other_proposal = Proposal {
+        proposal_id: 6,
+        title: "proposal title 2".to_string(),
+        description: "proposal description 2".to_string(),
+        ..mock_proposal.clone()
+    };
+    let (round_id, tranche_id) = (mock_proposal.round_id, mock_proposal.tranche_id);
+    const DISPUTE_WINDOW: u64 = 86_400_000_000_000;
+
+    let (mut deps, mut env) = (mock_dependencies(), mock_env());
+    let admin_address = deps.api.addr_make(USER_ADDRESS_2);
+    let voter_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+    let depositor_address = get_address_as_str(&deps.api, USER_ADDRESS_2);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        round_id,
+        vec![mock_proposal.clone(), other_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    )
+    .with_whitelist_admins(vec![admin_address.clone()]);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    for _ in 0..2 {
+        let info = get_message_info(
+            &deps.api,
+            USER_ADDRESS_2,
+            &[Coin::new(1000u64, DEFAULT_DENOM)],
+        );
+        let msg = ExecuteMsg::AddTribute {
+            round_id,
+            tranche_id,
+            proposal_id: mock_proposal.proposal_id,
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg);
+        assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    }
+
+    // only the Hydro whitelist admins can set the dispute window
+    let msg = ExecuteMsg::SetDisputeWindow {
+        tranche_id,
+        duration: DISPUTE_WINDOW,
+    };
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    assert!(res.unwrap_err().to_string().contains("Unauthorized"));
+
+    let admin_info = get_message_info(&deps.api, USER_ADDRESS_2, &[]);
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    // disputes can't be resolved before the round ends
+    let retarget_msg = ExecuteMsg::ResolveTributeDispute {
+        tribute_id: 0,
+        resolution: DisputeResolution::Retarget {
+            proposal_id: other_proposal.proposal_id,
+        },
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        retarget_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Round has not ended yet"));
+
+    // the round ends now, and the voter voted for the other proposal
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        round_id + 1,
+        vec![mock_proposal.clone(), other_proposal.clone()],
+        vec![(
+            round_id,
+            tranche_id,
+            voter_address.clone(),
+            VoteWithPower {
+                prop_id: other_proposal.proposal_id,
+                power: Decimal::from_ratio(Uint128::new(10000), Uint128::one()),
+                tribute_weight: Decimal::from_ratio(Uint128::new(10000), Uint128::one()),
+            },
+        )],
+        vec![
+            get_nonzero_deployment_for_proposal(mock_proposal.clone()),
+            get_nonzero_deployment_for_proposal(other_proposal.clone()),
+        ],
+        None,
+    )
+    .with_tribute_cutoff(round_id, env.block.time)
+    .with_whitelist_admins(vec![admin_address]);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let res = query_dispute_status(deps.as_ref(), env.clone(), round_id, tranche_id).unwrap();
+    assert_eq!(
+        Some(env.block.time.plus_nanos(DISPUTE_WINDOW)),
+        res.dispute_window_end
+    );
+    assert!(res.distribution_frozen);
+
+    // tributes can't be claimed during the dispute window
+    let claim_msg = ExecuteMsg::ClaimTribute {
+        round_id,
+        tranche_id,
+        tribute_id: 0,
+        voter_address: voter_address.clone(),
+    };
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), claim_msg.clone());
+    assert!(res.unwrap_err().to_string().contains("is frozen until"));
+
+    // move the first tribute to the other proposal and refund the second one
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        retarget_msg.clone(),
+    );
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    assert_eq!(
+        other_proposal.proposal_id,
+        ID_TO_TRIBUTE_MAP
+            .load(&deps.storage, 0)
+            .unwrap()
+            .proposal_id
+    );
+    assert!(TRIBUTE_MAP.has(&deps.storage, (round_id, other_proposal.proposal_id, 0)));
+    assert!(!TRIBUTE_MAP.has(&deps.storage, (round_id, mock_proposal.proposal_id, 0)));
+
+    let refund_msg = ExecuteMsg::ResolveTributeDispute {
+        tribute_id: 1,
+        resolution: DisputeResolution::Refund {},
+    };
+    let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), refund_msg).unwrap();
+    verify_tokens_received(res, &depositor_address, &DEFAULT_DENOM.to_string(), 1000);
+    assert!(ID_TO_TRIBUTE_MAP.load(&deps.storage, 1).unwrap().refunded);
+
+    // each dispute can be resolved only once
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        admin_info.clone(),
+        retarget_msg.clone(),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Dispute for tribute 0 has already been resolved"));
+
+    // after the dispute window passes, the distribution is unlocked
+    env.block.time = env.block.time.plus_nanos(DISPUTE_WINDOW);
+
+    let res = query_dispute_status(deps.as_ref(), env.clone(), round_id, tranche_id).unwrap();
+    assert!(!res.distribution_frozen);
+    assert_eq!(2, res.resolutions.len());
+    assert_eq!(
+        mock_proposal.proposal_id,
+        res.resolutions[0].previous_proposal_id
+    );
+    assert_eq!(
+        DisputeResolution::Retarget {
+            proposal_id: other_proposal.proposal_id
+        },
+        res.resolutions[0].resolution
+    );
+    assert_eq!(DisputeResolution::Refund {}, res.resolutions[1].resolution);
+
+    let refund_msg = ExecuteMsg::ResolveTributeDispute {
+        tribute_id: 1,
+        resolution: DisputeResolution::Refund {},
+    };
+    let res = execute(deps.as_mut(), env.clone(), admin_info, refund_msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("already been refunded"));
+
+    let res = execute(deps.as_mut(), env, info, claim_msg).unwrap();
+    verify_tokens_received(res, &voter_address, &DEFAULT_DENOM.to_string(), 1000);
+}
+
+#[test]
+fn tribute_escrow_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+    let refundable_proposal = Proposal {
+        round_id: 9,
+        proposal_id: 4,
+        ..mock_proposal.clone()
+    };
+    let round_id = mock_proposal.round_id;
+    const DISPUTE_WINDOW: u64 = 86_400_000_000_000;
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let admin_address = deps.api.addr_make(USER_ADDRESS_1);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        round_id,
+        vec![mock_proposal.clone(), refundable_proposal.clone()],
+        vec![],
+        vec![get_zero_deployment_for_proposal(
+            refundable_proposal.clone(),
+        )],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    let depositor_info = get_message_info(
+        &deps.api,
+        USER_ADDRESS_2,
+        &[Coin::new(1000u64, DEFAULT_DENOM)],
+    );
+    let msg = ExecuteMsg::AddTribute {
+        round_id,
+        tranche_id: mock_proposal.tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        min_vote_power: None,
+        vesting_rounds: None,
+        delivery_deadline_rounds: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), depositor_info.clone(), msg);
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    let res = query_tribute_escrow(deps.as_ref(), env.clone(), 0).unwrap();
+    assert!(res.escrowed);
+    assert_eq!(Timestamp::from_nanos(u64::MAX), res.escrowed_until);
+
+    // the tribute can't be refunded by referring to a refundable proposal from an earlier round
+    let msg = ExecuteMsg::RefundTribute {
+        round_id: refundable_proposal.round_id,
+        tranche_id: refundable_proposal.tranche_id,
+        proposal_id: refundable_proposal.proposal_id,
+        tribute_id: 0,
+    };
+    let res = execute(deps.as_mut(), env.clone(), depositor_info.clone(), msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Tribute 0 doesn't belong to round 9 and tranche 0"));
+
+    // nor can it be claimed through an earlier round
+    let msg = ExecuteMsg::ClaimTribute {
+        round_id: refundable_proposal.round_id,
+        tranche_id: refundable_proposal.tranche_id,
+        tribute_id: 0,
+        voter_address: get_address_as_str(&deps.api, USER_ADDRESS_2),
+    };
+    let res = execute(deps.as_mut(), env.clone(), depositor_info, msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Tribute 0 doesn't belong to round 9 and tranche 0"));
+
+    // once the round ends, the tribute stays in escrow until the dispute window passes
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        round_id + 1,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    )
+    .with_tribute_cutoff(round_id, env.block.time)
+    .with_whitelist_admins(vec![admin_address]);
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let res = query_tribute_escrow(deps.as_ref(), env.clone(), 0).unwrap();
+    assert!(!res.escrowed);
+    assert_eq!(env.block.time, res.escrowed_until);
+
+    let admin_info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = ExecuteMsg::SetDisputeWindow {
+        tranche_id: mock_proposal.tranche_id,
+        duration: DISPUTE_WINDOW,
+    };
+    let res = execute(deps.as_mut(), env.clone(), admin_info, msg);
+    assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+
+    let res = query_tribute_escrow(deps.as_ref(), env.clone(), 0).unwrap();
+    assert!(res.escrowed);
+    assert_eq!(
+        env.block.time.plus_nanos(DISPUTE_WINDOW),
+        res.escrowed_until
+    );
+    assert_eq!(mock_proposal.proposal_id, res.tribute.proposal_id);
+}
+
+#[test]
+fn sync_proposal_outcomes_test() {
+    let invalidated_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+    let other_proposal = Proposal {
+        proposal_id: 6,
+        title: "proposal title 2".to_string(),
+        description: "proposal description 2".to_string(),
+        ..invalidated_proposal.clone()
+    };
+    let (round_id, tranche_id) = (
+        invalidated_proposal.round_id,
+        invalidated_proposal.tranche_id,
+    );
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let voter_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+    let depositor_address = get_address_as_str(&deps.api, USER_ADDRESS_2);
+
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        round_id,
+        vec![invalidated_proposal.clone(), other_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    let depositor_info = get_message_info(
+        &deps.api,
+        USER_ADDRESS_2,
+        &[Coin::new(1000u64, DEFAULT_DENOM)],
+    );
+    for proposal_id in [invalidated_proposal.proposal_id, other_proposal.proposal_id] {
+        let msg = ExecuteMsg::AddTribute {
+            round_id,
+            tranche_id,
+            proposal_id,
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), depositor_info.clone(), msg);
+        assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+    }
+
+    let res = query_tribute_outcome(deps.as_ref(), 0).unwrap();
+    assert_eq!(TributeOutcome::Pending {}, res.outcome);
+
+    let sync_msg = ExecuteMsg::SyncProposalOutcomes {
+        round_id,
+        tranche_id,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), sync_msg.clone());
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Round has not ended yet"));
+
+    // the round ends without a liquidity deployment being entered for the invalidated proposal
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        round_id + 1,
+        vec![invalidated_proposal.clone(), other_proposal.clone()],
+        vec![(
+            round_id,
+            tranche_id,
+            voter_address.clone(),
+            VoteWithPower {
+                prop_id: invalidated_proposal.proposal_id,
+                power: Decimal::from_ratio(Uint128::new(10000), Uint128::one()),
+                tribute_weight: Decimal::from_ratio(Uint128::new(10000), Uint128::one()),
+            },
+        )],
+        vec![get_nonzero_deployment_for_proposal(other_proposal.clone())],
+        None,
+    )
+    .with_invalidated_proposal(
+        round_id,
+        tranche_id,
+        invalidated_proposal.proposal_id,
+        "fraudulent proposal",
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let refund_msg = ExecuteMsg::RefundTribute {
+        round_id,
+        tranche_id,
+        proposal_id: invalidated_proposal.proposal_id,
+        tribute_id: 0,
+    };
+    let refund_info = get_message_info(&deps.api, USER_ADDRESS_2, &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        refund_info.clone(),
+        refund_msg.clone(),
+    );
+    assert!(res.is_err());
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), sync_msg.clone()).unwrap();
+    assert!(res.attributes.contains(&attr("voided_proposal_ids", "5")));
+
+    // syncing again doesn't void the proposal twice
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), sync_msg).unwrap();
+    assert!(res.attributes.contains(&attr("voided_proposal_ids", "")));
+
+    let res = query_tribute_outcome(deps.as_ref(), 0).unwrap();
+    assert_eq!(
+        TributeOutcome::Voided {
+            reason: "fraudulent proposal".to_string()
+        },
+        res.outcome
+    );
+    let res = query_tribute_outcome(deps.as_ref(), 1).unwrap();
+    assert_eq!(TributeOutcome::Claimable {}, res.outcome);
+
+    // the tribute of the voided proposal can't be claimed, but it can be refunded
+    let claim_msg = ExecuteMsg::ClaimTribute {
+        round_id,
+        tranche_id,
+        tribute_id: 0,
+        voter_address,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, claim_msg);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Proposal was invalidated in Hydro"));
+
+    let res = execute(deps.as_mut(), env.clone(), refund_info, refund_msg).unwrap();
+    verify_tokens_received(res, &depositor_address, &DEFAULT_DENOM.to_string(), 1000);
+
+    let res = query_tribute_outcome(deps.as_ref(), 0).unwrap();
+    assert_eq!(TributeOutcome::Refunded {}, res.outcome);
+}
+
+#[test]
+fn refund_unclaimed_tribute_test() {
+    let unvoted_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::zero(),
+        percentage: Uint128::zero(),
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+    let voted_proposal = Proposal {
+        proposal_id: 6,
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        ..unvoted_proposal.clone()
+    };
+    let (round_id, tranche_id) = (unvoted_proposal.round_id, unvoted_proposal.tranche_id);
+    let other_denom = "untrn";
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        round_id,
+        vec![unvoted_proposal.clone(), voted_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert!(res.is_ok());
+
+    // the first depositor adds tributes in two denoms, and the second one in a single denom
+    let tributes = [
+        (USER_ADDRESS_1, Coin::new(1000u64, DEFAULT_DENOM)),
+        (USER_ADDRESS_2, Coin::new(500u64, other_denom)),
+        (USER_ADDRESS_1, Coin::new(200u64, other_denom)),
+    ];
+    for (depositor, funds) in tributes.iter() {
+        for proposal_id in [unvoted_proposal.proposal_id, voted_proposal.proposal_id] {
+            let depositor_info =
+                get_message_info(&deps.api, depositor, std::slice::from_ref(funds));
+            let msg = ExecuteMsg::AddTribute {
+                round_id,
+                tranche_id,
+                proposal_id,
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
+            };
+            let res = execute(deps.as_mut(), env.clone(), depositor_info, msg);
+            assert!(res.is_ok(), "failed with: {}", res.unwrap_err());
+        }
+    }
+
+    let refund_msg = |proposal_id| ExecuteMsg::RefundUnclaimedTribute {
+        round_id,
+        tranche_id,
+        proposal_id,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        refund_msg(unvoted_proposal.proposal_id),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Round has not ended yet"));
+
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        round_id + 1,
+        vec![unvoted_proposal.clone(), voted_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        refund_msg(voted_proposal.proposal_id),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Can't refund unclaimed tributes for proposal that received votes"));
+
+    // anyone can refund the tributes, and each depositor receives all of their tributes at once
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        refund_msg(unvoted_proposal.proposal_id),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("refunded_tribute_ids", "0,2,4")));
+
+    let mut refunds = res
+        .messages
+        .iter()
+        .map(|msg| match &msg.msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                (to_address.clone(), amount.clone())
+            }
+            _ => panic!("expected BankMsg::Send message"),
+        })
+        .collect::<Vec<_>>();
+    refunds.sort_by(|first, second| first.0.cmp(&second.0));
+
+    let mut expected_refunds = vec![
+        (
+            get_address_as_str(&deps.api, USER_ADDRESS_1),
+            vec![
+                Coin::new(1000u64, DEFAULT_DENOM),
+                Coin::new(200u64, other_denom),
+            ],
+        ),
+        (
+            get_address_as_str(&deps.api, USER_ADDRESS_2),
+            vec![Coin::new(500u64, other_denom)],
+        ),
+    ];
+    expected_refunds.sort_by(|first, second| first.0.cmp(&second.0));
+    assert_eq!(expected_refunds, refunds);
+
+    let res = query_tribute_outcome(deps.as_ref(), 0).unwrap();
+    assert_eq!(TributeOutcome::Refunded {}, res.outcome);
+
+    // the tributes can't be refunded twice
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        refund_msg(unvoted_proposal.proposal_id),
+    );
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("No unclaimed tributes to refund for the proposal"));
+}
+
+#[test]
+fn tribute_min_vote_power_test() {
+    let mock_proposal = Proposal {
+        round_id: 10,
+        tranche_id: 0,
+        proposal_id: 5,
+        title: "proposal title 1".to_string(),
+        description: "proposal description 1".to_string(),
+        power: Uint128::new(10000),
+        percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
+        minimum_atom_liquidity_request: Uint128::zero(),
+        deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
+    };
+    let (round_id, tranche_id) = (mock_proposal.round_id, mock_proposal.tranche_id);
+
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let whale_address = get_address_as_str(&deps.api, USER_ADDRESS_1);
+    let dust_address = get_address_as_str(&deps.api, USER_ADDRESS_2);
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        round_id,
+        vec![mock_proposal.clone()],
+        vec![],
+        vec![],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address.clone());
+    let res = instantiate(deps.as_mut(), env.clone(), info, msg);
+    assert!(res.is_ok());
+
+    let depositor_info =
+        get_message_info(&deps.api, "addr0003", &[Coin::new(10000u64, DEFAULT_DENOM)]);
+    let msg = ExecuteMsg::AddTribute {
+        round_id,
+        tranche_id,
+        proposal_id: mock_proposal.proposal_id,
+        min_vote_power: Some(Uint128::new(100)),
+        vesting_rounds: None,
+        delivery_deadline_rounds: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), depositor_info, msg).unwrap();
+    assert!(res.attributes.contains(&attr("min_vote_power", "100")));
+
+    let res = query_proposal_tributes(
+        deps.as_ref(),
+        round_id,
+        mock_proposal.proposal_id,
+        0,
+        None,
+        10,
+    )
+    .unwrap();
+    assert_eq!(Some(Uint128::new(100)), res.tributes[0].min_vote_power);
+
+    let vote = |address: &String, power: u128| {
+        (
+            round_id,
+            tranche_id,
+            address.clone(),
+            VoteWithPower {
+                prop_id: mock_proposal.proposal_id,
+                power: Decimal::from_ratio(power, 1u128),
+                tribute_weight: Decimal::from_ratio(power, 1u128),
+            },
+        )
+    };
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address,
+        round_id + 1,
+        vec![mock_proposal.clone()],
+        vec![vote(&whale_address, 9950), vote(&dust_address, 50)],
+        vec![get_nonzero_deployment_for_proposal(mock_proposal.clone())],
+        None,
+    );
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    // the voter below the threshold has no outstanding claims and can't claim the tribute
+    let res = query_outstanding_tribute_claims(
+        &deps.as_ref(),
+        dust_address.clone(),
+        round_id,
+        tranche_id,
+        0,
+        10,
+    )
+    .unwrap();
+    assert!(res.claims.is_empty());
+
+    let claim_msg = |voter_address: &String| ExecuteMsg::ClaimTribute {
+        round_id,
+        tranche_id,
+        tribute_id: 0,
+        voter_address: voter_address.clone(),
+    };
+    let info = get_message_info(&deps.api, USER_ADDRESS_2, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, claim_msg(&dust_address));
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("below the minimum of 100 required to claim tribute 0"));
+
+    let res = query_outstanding_tribute_claims(
+        &deps.as_ref(),
+        whale_address.clone(),
+        round_id,
+        tranche_id,
+        0,
+        10,
+    )
+    .unwrap();
+    assert_eq!(1, res.claims.len());
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, claim_msg(&whale_address)).unwrap();
+    verify_tokens_received(res, &whale_address, &DEFAULT_DENOM.to_string(), 9950);
+}
+
 #[test]
 fn claim_tribute_test() {
     let mock_proposal1 = Proposal {
@@ -446,6 +2543,8 @@ fn claim_tribute_test() {
         percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
         minimum_atom_liquidity_request: Uint128::zero(),
         deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
     };
     let mock_proposal2 = Proposal {
         round_id: 10,
@@ -457,6 +2556,8 @@ fn claim_tribute_test() {
         percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
         minimum_atom_liquidity_request: Uint128::zero(),
         deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
     };
     let mock_proposal3 = Proposal {
         round_id: 10,
@@ -468,6 +2569,8 @@ fn claim_tribute_test() {
         percentage: MIN_PROP_PERCENT_FOR_CLAIMABLE_TRIBUTES,
         minimum_atom_liquidity_request: Uint128::zero(),
         deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
     };
 
     let mock_proposals = vec![
@@ -537,6 +2640,7 @@ fn claim_tribute_test() {
                         VoteWithPower {
                             prop_id: 5,
                             power: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+                            tribute_weight: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
                         },
                     ),
                     (
@@ -546,6 +2650,7 @@ fn claim_tribute_test() {
                         VoteWithPower {
                             prop_id: 6,
                             power: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+                            tribute_weight: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
                         },
                     ),
                 ],
@@ -627,6 +2732,7 @@ fn claim_tribute_test() {
                     VoteWithPower {
                         prop_id: 5,
                         power: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+                        tribute_weight: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
                     },
                 )],
                 deployments_for_all_proposals.clone(),
@@ -662,6 +2768,7 @@ fn claim_tribute_test() {
                     VoteWithPower {
                         prop_id: 6,
                         power: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+                        tribute_weight: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
                     },
                 )],
                 deployments_for_all_proposals.clone(),
@@ -695,6 +2802,7 @@ fn claim_tribute_test() {
                     VoteWithPower {
                         prop_id: 5,
                         power: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+                        tribute_weight: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
                     },
                 )],
                 vec![],
@@ -728,6 +2836,7 @@ fn claim_tribute_test() {
                     VoteWithPower {
                         prop_id: 5,
                         power: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
+                        tribute_weight: Decimal::from_ratio(Uint128::new(70), Uint128::one()),
                     },
                 )],
                 zero_deployments_for_all_proposals,
@@ -758,11 +2867,18 @@ fn claim_tribute_test() {
 
         let tribute_payer = USER_ADDRESS_1;
         for tribute_to_add in test.tributes_to_add.iter() {
-            let info = get_message_info(&deps.api, tribute_payer, &[tribute_to_add.token.clone()]);
+            let info = get_message_info(
+                &deps.api,
+                tribute_payer,
+                std::slice::from_ref(&tribute_to_add.token),
+            );
             let msg = ExecuteMsg::AddTribute {
                 tranche_id: tribute_to_add.tranche_id,
                 round_id: tribute_to_add.round_id,
                 proposal_id: tribute_to_add.proposal_id,
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
             };
 
             let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
@@ -835,6 +2951,8 @@ fn refund_tribute_test() {
         percentage: Uint128::zero(),
         minimum_atom_liquidity_request: Uint128::zero(),
         deployment_duration: 1,
+        description_hash: None,
+        max_vote_percentage: None,
     };
 
     let mock_proposals = vec![mock_proposal.clone()];
@@ -965,6 +3083,9 @@ fn refund_tribute_test() {
             tranche_id: test.tribute_info.1,
             round_id: test.tribute_info.0,
             proposal_id: test.tribute_info.2,
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
         };
 
         let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
@@ -1100,7 +3221,7 @@ fn test_query_historical_tribute_claims() {
         let (mut deps, _env) = (mock_dependencies(), mock_env());
 
         // Mock the database
-        let tributes = vec![
+        let tributes = [
             Tribute {
                 tribute_id: 0,
                 round_id: 1,
@@ -1111,6 +3232,9 @@ fn test_query_historical_tribute_claims() {
                 refunded: false,
                 creation_round: 1,
                 creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
             },
             Tribute {
                 tribute_id: 1,
@@ -1122,6 +3246,9 @@ fn test_query_historical_tribute_claims() {
                 refunded: false,
                 creation_round: 1,
                 creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
             },
         ];
 
@@ -1143,6 +3270,7 @@ fn test_query_historical_tribute_claims() {
             &deps.as_ref(),
             test_case.user_address.to_string(),
             test_case.start_from,
+            None,
             test_case.limit,
         );
 
@@ -1157,6 +3285,103 @@ fn test_query_historical_tribute_claims() {
     }
 }
 
+#[test]
+fn test_query_tribute_claims_report() {
+    let (mut deps, env) = (mock_dependencies(), mock_env());
+    let hydro_contract_address = get_address_as_str(&deps.api, HYDRO_CONTRACT_ADDRESS);
+    let round_end = |round_id: u64| Timestamp::from_seconds(1000 * round_id);
+    let mock_querier = MockWasmQuerier::new(
+        hydro_contract_address.clone(),
+        4,
+        vec![],
+        vec![],
+        vec![],
+        None,
+    )
+    .with_tribute_cutoff(1, round_end(1))
+    .with_tribute_cutoff(2, round_end(2))
+    .with_tribute_cutoff(3, round_end(3));
+    deps.querier.update_wasm(move |q| mock_querier.handler(q));
+
+    let info = get_message_info(&deps.api, USER_ADDRESS_1, &[]);
+    let msg = get_instantiate_msg(hydro_contract_address);
+    let res = instantiate(deps.as_mut(), env, info, msg);
+    assert!(res.is_ok());
+
+    let user_address = deps.api.addr_make("user1");
+    let tribute_rounds = [1, 1, 2, 3];
+    for (tribute_id, round_id) in tribute_rounds.iter().enumerate() {
+        let tribute = Tribute {
+            tribute_id: tribute_id as u64,
+            round_id: *round_id,
+            tranche_id: 1,
+            proposal_id: tribute_id as u64,
+            depositor: Addr::unchecked("depositor"),
+            funds: Coin::new(Uint128::new(1000), format!("token{}", tribute_id)),
+            refunded: false,
+            creation_round: *round_id,
+            creation_time: Timestamp::from_seconds(1),
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
+        };
+        ID_TO_TRIBUTE_MAP
+            .save(&mut deps.storage, tribute.tribute_id, &tribute)
+            .unwrap();
+        TRIBUTE_CLAIMS
+            .save(
+                &mut deps.storage,
+                (user_address.clone(), tribute.tribute_id),
+                &Coin::new(Uint128::new(100), tribute.funds.denom.clone()),
+            )
+            .unwrap();
+    }
+
+    let expected_entry = |tribute_id: u64| TributeClaimsReportEntry {
+        round_id: tribute_rounds[tribute_id as usize],
+        round_end: round_end(tribute_rounds[tribute_id as usize]),
+        tranche_id: 1,
+        proposal_id: tribute_id,
+        tribute_id,
+        amount: Coin::new(Uint128::new(100), format!("token{}", tribute_id)),
+    };
+
+    // claims from the rounds outside of the range are left out
+    let res = query_tribute_claims_report(&deps.as_ref(), user_address.to_string(), 1, 2, None, 10)
+        .unwrap();
+    assert_eq!(
+        vec![expected_entry(0), expected_entry(1), expected_entry(2)],
+        res.entries
+    );
+
+    // the report can be paginated
+    let res = query_tribute_claims_report(&deps.as_ref(), user_address.to_string(), 1, 3, None, 2)
+        .unwrap();
+    assert_eq!(vec![expected_entry(0), expected_entry(1)], res.entries);
+
+    let res =
+        query_tribute_claims_report(&deps.as_ref(), user_address.to_string(), 1, 3, Some(1), 2)
+            .unwrap();
+    assert_eq!(vec![expected_entry(2), expected_entry(3)], res.entries);
+
+    let res = query_tribute_claims_report(
+        &deps.as_ref(),
+        deps.api.addr_make("user2").to_string(),
+        1,
+        3,
+        None,
+        10,
+    )
+    .unwrap();
+    assert!(res.entries.is_empty());
+
+    let res = query_tribute_claims_report(&deps.as_ref(), user_address.to_string(), 3, 1, None, 10);
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Start round id must not be greater than end round id"));
+}
+
 struct RoundTributesTestCase {
     description: String,
     round_id: u64,
@@ -1169,7 +3394,7 @@ struct RoundTributesTestCase {
 #[test]
 fn test_query_round_tributes() {
     // Mock the database
-    let tributes = vec![
+    let tributes = [
         Tribute {
             tribute_id: 1,
             round_id: 1,
@@ -1180,6 +3405,9 @@ fn test_query_round_tributes() {
             refunded: false,
             creation_round: 1,
             creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
         },
         Tribute {
             tribute_id: 2,
@@ -1191,6 +3419,9 @@ fn test_query_round_tributes() {
             refunded: false,
             creation_round: 1,
             creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
         },
         Tribute {
             tribute_id: 3,
@@ -1202,6 +3433,9 @@ fn test_query_round_tributes() {
             refunded: false,
             creation_round: 1,
             creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
         },
         Tribute {
             tribute_id: 4,
@@ -1213,6 +3447,9 @@ fn test_query_round_tributes() {
             refunded: false,
             creation_round: 1,
             creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
         },
         Tribute {
             tribute_id: 5,
@@ -1224,6 +3461,9 @@ fn test_query_round_tributes() {
             refunded: false,
             creation_round: 1,
             creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+            min_vote_power: None,
+            vesting_rounds: None,
+            delivery_deadline_rounds: None,
         },
     ];
 
@@ -1285,12 +3525,34 @@ fn test_query_round_tributes() {
             &deps.as_ref(),
             test_case.round_id,
             test_case.start_from,
+            None,
             test_case.limit,
         );
 
         match result {
             Ok(tributes) => {
                 assert_eq!(tributes.tributes, test_case.expected_tributes);
+
+                // The next page queried through the cursor must match the next page queried by offset
+                if let Some(next_key) = tributes.next_key {
+                    let by_cursor = query_round_tributes(
+                        &deps.as_ref(),
+                        test_case.round_id,
+                        0,
+                        Some(next_key),
+                        test_case.limit,
+                    )
+                    .unwrap();
+                    let by_offset = query_round_tributes(
+                        &deps.as_ref(),
+                        test_case.round_id,
+                        test_case.start_from + test_case.limit,
+                        None,
+                        test_case.limit,
+                    )
+                    .unwrap();
+                    assert_eq!(by_cursor.tributes, by_offset.tributes);
+                }
             }
             Err(err) => {
                 assert_eq!(Some(err), test_case.expected_error);
@@ -1360,7 +3622,7 @@ fn test_query_outstanding_tribute_claims() {
         let (mut deps, _env) = (mock_dependencies(), mock_env());
 
         // Mock the database
-        let tributes = vec![
+        let tributes = [
             Tribute {
                 // this tribute will be marked as already claimed by user1
                 tribute_id: 1,
@@ -1372,6 +3634,9 @@ fn test_query_outstanding_tribute_claims() {
                 refunded: false,
                 creation_round: 1,
                 creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
             },
             Tribute {
                 tribute_id: 2,
@@ -1383,6 +3648,9 @@ fn test_query_outstanding_tribute_claims() {
                 refunded: false,
                 creation_round: 1,
                 creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
             },
             Tribute {
                 tribute_id: 3,
@@ -1394,6 +3662,9 @@ fn test_query_outstanding_tribute_claims() {
                 refunded: false,
                 creation_round: 1,
                 creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
             },
             Tribute {
                 tribute_id: 4,
@@ -1405,6 +3676,9 @@ fn test_query_outstanding_tribute_claims() {
                 refunded: false,
                 creation_round: 1,
                 creation_time: cosmwasm_std::Timestamp::from_seconds(1),
+                min_vote_power: None,
+                vesting_rounds: None,
+                delivery_deadline_rounds: None,
             },
         ];
 
@@ -1460,6 +3734,8 @@ fn test_query_outstanding_tribute_claims() {
                 percentage: Uint128::new(7),
                 minimum_atom_liquidity_request: Uint128::zero(),
                 deployment_duration: 1,
+                description_hash: None,
+                max_vote_percentage: None,
             },
             Proposal {
                 round_id: 1,
@@ -1471,6 +3747,8 @@ fn test_query_outstanding_tribute_claims() {
                 percentage: Uint128::new(7),
                 minimum_atom_liquidity_request: Uint128::zero(),
                 deployment_duration: 1,
+                description_hash: None,
+                max_vote_percentage: None,
             },
         ];
 
@@ -1483,6 +3761,7 @@ fn test_query_outstanding_tribute_claims() {
         let user_vote = VoteWithPower {
             prop_id: 1,
             power: Decimal::from_ratio(Uint128::new(500), Uint128::one()),
+            tribute_weight: Decimal::from_ratio(Uint128::new(500), Uint128::one()),
         };
 
         // print this
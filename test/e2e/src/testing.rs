@@ -81,6 +81,8 @@ pub fn e2e_basic_test() -> anyhow::Result<()> {
             icq_managers: vec![],
             max_deployment_duration: 12,
             round_lock_power_schedule: get_default_power_schedule_vec(),
+            interpolate_lock_power: false,
+            allow_pre_launch_locks: false,
         },
         Some(&Addr::unchecked(whitelist_admin_address.clone())),
         &[],
@@ -225,6 +225,8 @@ func (s *HydroSuite) InstantiateHydroContract(
 		"icq_managers":                       []string{adminAddr},
 		"max_deployment_duration":            12,
 		"round_lock_power_schedule":          [][]interface{}{{1, "1"}, {2, "1.25"}, {3, "1.5"}, {6, "2"}, {12, "4"}},
+		"interpolate_lock_power":             false,
+		"allow_pre_launch_locks":             false,
 	}
 	initHydroJson, err := json.Marshal(initHydro)
 	s.Require().NoError(err)